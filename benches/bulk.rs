@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use filetime_type::FileTime;
+
+fn sample() -> Vec<u8> {
+    (0..100_000i64)
+        .flat_map(|i| (132601876135897932 + i * 1_000_003).to_le_bytes().to_vec())
+        .collect()
+}
+
+fn naive(bytes: &[u8]) -> Vec<FileTime> {
+    let mut out = Vec::new();
+    for chunk in bytes.chunks(8) {
+        let mut raw = [0u8; 8];
+        raw.copy_from_slice(chunk);
+        out.push(FileTime::from(raw));
+    }
+    out
+}
+
+fn bulk(c: &mut Criterion) {
+    let bytes = sample();
+    let mut group = c.benchmark_group("100k little-endian FILETIMEs");
+    group.bench_function("naive loop", |b| b.iter(|| naive(black_box(&bytes))));
+    group.bench_function("from_le_slice_bulk", |b| {
+        b.iter(|| FileTime::from_le_slice_bulk(black_box(&bytes)).unwrap())
+    });
+    #[cfg(feature = "rayon")]
+    group.bench_function("par_from_le_slice_bulk", |b| {
+        b.iter(|| FileTime::par_from_le_slice_bulk(black_box(&bytes)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bulk);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use filetime_type::FileTime;
+
+fn now(c: &mut Criterion) {
+    let mut group = c.benchmark_group("now");
+    group.bench_function("now", |b| b.iter(|| black_box(FileTime::now())));
+    // Start the updater outside of the measurement
+    FileTime::now_coarse();
+    group.bench_function("now_coarse", |b| {
+        b.iter(|| black_box(FileTime::now_coarse()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, now);
+criterion_main!(benches);
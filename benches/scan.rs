@@ -0,0 +1,52 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use filetime_type::{Endianness, FileTime, FileTimeRange};
+
+/// 1 MiB of xorshift noise
+fn sample() -> Vec<u8> {
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    (0..131_072)
+        .flat_map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state.to_le_bytes()
+        })
+        .collect()
+}
+
+/// Decode every window to a DateTime and compare that
+fn naive(buf: &[u8], window: FileTimeRange) -> usize {
+    let (start, end) = (window.start().to_datetime(), window.end().to_datetime());
+    buf.windows(8)
+        .filter(|chunk| {
+            let mut raw = [0u8; 8];
+            raw.copy_from_slice(chunk);
+            FileTime::from_i64_checked(i64::from_le_bytes(raw))
+                .map(|ft| (start..end).contains(&ft.to_datetime()))
+                .unwrap_or(false)
+        })
+        .count()
+}
+
+fn scan(c: &mut Criterion) {
+    let buf = sample();
+    let window = FileTimeRange::new(
+        FileTime::from_ymd_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+        FileTime::from_ymd_hms(2030, 1, 1, 0, 0, 0).unwrap(),
+    )
+    .unwrap();
+    let mut group = c.benchmark_group("scan 1 MiB of noise");
+    group.bench_function("naive via DateTime", |b| {
+        b.iter(|| naive(black_box(&buf), window))
+    });
+    group.bench_function("scan, alignment 1", |b| {
+        b.iter(|| FileTime::scan(black_box(&buf), window, 1, Endianness::Little).count())
+    });
+    group.bench_function("scan, alignment 8", |b| {
+        b.iter(|| FileTime::scan(black_box(&buf), window, 8, Endianness::Little).count())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, scan);
+criterion_main!(benches);
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Duration, Utc};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use filetime_type::FileTime;
+
+fn sample() -> Vec<FileTime> {
+    (0..100_000i64)
+        .map(|i| FileTime::from_i64(132601876135897932 + i * 1_000_003))
+        .collect()
+}
+
+/// The calendar based conversion `to_datetime` used to perform
+fn calendar(ft: FileTime) -> DateTime<Utc> {
+    FileTime::filetime_epoch()
+        + Duration::seconds(ft.seconds())
+        + Duration::nanoseconds(ft.nanoseconds())
+}
+
+fn to_datetime(c: &mut Criterion) {
+    let times = sample();
+    let mut group = c.benchmark_group("100k FILETIMEs to DateTime");
+    group.bench_function("epoch plus durations", |b| {
+        b.iter(|| {
+            black_box(&times)
+                .iter()
+                .map(|ft| calendar(*ft))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("to_datetime", |b| {
+        b.iter(|| {
+            black_box(&times)
+                .iter()
+                .map(FileTime::to_datetime)
+                .collect::<Vec<_>>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, to_datetime);
+criterion_main!(benches);
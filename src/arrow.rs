@@ -0,0 +1,250 @@
+//! Conversion from/to Apache Arrow timestamp arrays
+//!
+//! Arrow timestamps count seconds, milliseconds, microseconds or nanoseconds
+//! since the Unix-Epoch in an i64. Converting to a coarser unit truncates
+//! towards the earlier instant. Nanosecond arrays only cover 1677-09-21 to
+//! 2262-04-11, seconds to microseconds hold every FileTime.
+//!
+//! Arrow stores timestamps with a timezone annotation as UTC instants and
+//! those without as wall clock times; both are read as UTC.
+use crate::{FileTime, FileTimeError};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    ArrowTimestampType, TimestampMicrosecondType, TimestampMillisecondType,
+    TimestampNanosecondType, TimestampSecondType,
+};
+use arrow_array::{Array, ArrayRef, PrimitiveArray};
+use arrow_schema::{DataType, TimeUnit};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+fn to_unit(ft: FileTime, unit: TimeUnit) -> Option<i64> {
+    let ticks = ft.unix_ticks();
+    let value = match unit {
+        TimeUnit::Second => ticks.div_euclid(i128::from(FileTime::HUNDREDS_OF_NANOSECONDS)),
+        TimeUnit::Millisecond => ticks.div_euclid(i128::from(FileTime::TICKS_PER_MILLISECOND)),
+        TimeUnit::Microsecond => ticks.div_euclid(10),
+        TimeUnit::Nanosecond => ticks * 100,
+    };
+    i64::try_from(value).ok()
+}
+
+fn from_unit(value: i64, unit: TimeUnit) -> Result<FileTime, FileTimeError> {
+    let value = i128::from(value);
+    let ticks = match unit {
+        TimeUnit::Second => value * i128::from(FileTime::HUNDREDS_OF_NANOSECONDS),
+        TimeUnit::Millisecond => value * i128::from(FileTime::TICKS_PER_MILLISECOND),
+        TimeUnit::Microsecond => value * 10,
+        TimeUnit::Nanosecond => value.div_euclid(100),
+    };
+    FileTime::from_ticks_i128(ticks + i128::from(FileTime::EPOCH_AS_FILETIME))
+}
+
+fn build<T: ArrowTimestampType>(values: Vec<Option<i64>>) -> ArrayRef {
+    Arc::new(
+        values
+            .into_iter()
+            .collect::<PrimitiveArray<T>>()
+            .with_timezone_utc(),
+    )
+}
+
+/// Build a UTC annotated timestamp array of the given unit
+///
+/// Elements whose `nulls` entry is `true` become null, e.g. to mask sentinel
+/// values such as zero. Fails with [`FileTimeError::InvalidElement`] for
+/// FileTimes a nanosecond array cannot hold.
+///
+/// # Panics
+///
+/// If `nulls` is given and its length differs from `times`.
+/// ```
+/// use arrow_array::{cast::AsArray, Array};
+/// use arrow_array::types::TimestampMillisecondType;
+/// use arrow_schema::TimeUnit;
+/// use filetime_type::{arrow::to_timestamp_array, FileTime};
+///
+/// let times = [FileTime::from_i64(130139712831482830), FileTime::MIN];
+/// let array = to_timestamp_array(&times, TimeUnit::Millisecond, Some(&[false, true])).unwrap();
+/// let array = array.as_primitive::<TimestampMillisecondType>();
+/// assert_eq!(array.value(0), 1369497683148);
+/// assert!(array.is_null(1));
+/// ```
+pub fn to_timestamp_array(
+    times: &[FileTime],
+    unit: TimeUnit,
+    nulls: Option<&[bool]>,
+) -> Result<ArrayRef, FileTimeError> {
+    if let Some(nulls) = nulls {
+        assert_eq!(nulls.len(), times.len(), "null mask length mismatch");
+    }
+    let values = times
+        .iter()
+        .enumerate()
+        .map(|(index, &ft)| {
+            if nulls.is_some_and(|nulls| nulls[index]) {
+                return Ok(None);
+            }
+            to_unit(ft, unit)
+                .map(Some)
+                .ok_or(FileTimeError::InvalidElement {
+                    index,
+                    reason: "outside of the nanosecond timestamp range",
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(match unit {
+        TimeUnit::Second => build::<TimestampSecondType>(values),
+        TimeUnit::Millisecond => build::<TimestampMillisecondType>(values),
+        TimeUnit::Microsecond => build::<TimestampMicrosecondType>(values),
+        TimeUnit::Nanosecond => build::<TimestampNanosecondType>(values),
+    })
+}
+
+/// Read a timestamp array of any unit, nulls become `None`
+///
+/// Values before 1601 or after [`FileTime::MAX`] fail with
+/// [`FileTimeError::InvalidElement`], or become `None` with
+/// `out_of_range_as_null`. Nanoseconds are truncated to ticks towards the
+/// earlier instant. Other array types fail with [`FileTimeError::InvalidFormat`].
+pub fn from_timestamp_array(
+    array: &dyn Array,
+    out_of_range_as_null: bool,
+) -> Result<Vec<Option<FileTime>>, FileTimeError> {
+    let unit = match array.data_type() {
+        DataType::Timestamp(unit, _) => *unit,
+        _ => {
+            return Err(FileTimeError::InvalidFormat {
+                format: "Arrow timestamp array",
+                reason: "not a timestamp array",
+            })
+        }
+    };
+    let values: Box<dyn Iterator<Item = Option<i64>>> = match unit {
+        TimeUnit::Second => Box::new(array.as_primitive::<TimestampSecondType>().iter()),
+        TimeUnit::Millisecond => Box::new(array.as_primitive::<TimestampMillisecondType>().iter()),
+        TimeUnit::Microsecond => Box::new(array.as_primitive::<TimestampMicrosecondType>().iter()),
+        TimeUnit::Nanosecond => Box::new(array.as_primitive::<TimestampNanosecondType>().iter()),
+    };
+    values
+        .enumerate()
+        .map(|(index, value)| match value.map(|v| from_unit(v, unit)) {
+            None => Ok(None),
+            Some(Ok(ft)) => Ok(Some(ft)),
+            Some(Err(_)) if out_of_range_as_null => Ok(None),
+            Some(Err(err)) => Err(FileTimeError::InvalidElement {
+                index,
+                reason: match err {
+                    FileTimeError::BeforeEpoch => "before the FILETIME epoch",
+                    _ => "beyond the maximum FILETIME",
+                },
+            }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow_array::{
+        Int64Array, TimestampMicrosecondArray, TimestampNanosecondArray, TimestampSecondArray,
+    };
+
+    const UNITS: [TimeUnit; 4] = [
+        TimeUnit::Second,
+        TimeUnit::Millisecond,
+        TimeUnit::Microsecond,
+        TimeUnit::Nanosecond,
+    ];
+
+    #[test]
+    fn roundtrip() {
+        let times = [
+            FileTime::from_i64(130139712831482830),
+            FileTime::from_unix_seconds(0).unwrap(),
+            FileTime::from_unix_seconds(-1).unwrap(),
+            FileTime::from_i64(130139712831482830),
+        ];
+        let nulls = [false, false, false, true];
+        for &unit in &UNITS {
+            let array = to_timestamp_array(&times, unit, Some(&nulls)).unwrap();
+            assert_eq!(
+                array.data_type(),
+                &DataType::Timestamp(unit, Some("+00:00".into()))
+            );
+            assert_eq!(array.null_count(), 1);
+            let back = from_timestamp_array(array.as_ref(), false).unwrap();
+            assert_eq!(back[1..3], [Some(times[1]), Some(times[2])], "{:?}", unit);
+            assert_eq!(back[3], None);
+            let truncated = match unit {
+                TimeUnit::Second => 130139712830000000,
+                TimeUnit::Millisecond => 130139712831480000,
+                TimeUnit::Microsecond | TimeUnit::Nanosecond => 130139712831482830,
+            };
+            assert_eq!(back[0], Some(FileTime::from_i64(truncated)));
+        }
+
+        let all = to_timestamp_array(&[FileTime::MIN, FileTime::MAX], TimeUnit::Microsecond, None)
+            .unwrap();
+        assert_eq!(
+            from_timestamp_array(all.as_ref(), false),
+            Ok(vec![
+                Some(FileTime::MIN),
+                Some(FileTime::from_i64(i64::MAX - 7))
+            ])
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        // Nanosecond arrays start in 1677, their whole range is valid
+        let array = TimestampNanosecondArray::from(vec![i64::MIN, i64::MAX]);
+        assert_eq!(
+            from_timestamp_array(&array, false),
+            Ok(vec![
+                FileTime::from_ymd_hms_nano(1677, 9, 21, 0, 12, 43, 145_224_100).ok(),
+                FileTime::from_ymd_hms_nano(2262, 4, 11, 23, 47, 16, 854_775_800).ok(),
+            ])
+        );
+
+        // 1600-12-31T23:59:59.999999Z, 1 µs before the FILETIME-Epoch
+        let before = -11644473600 * 1_000_000 - 1;
+        let array = TimestampMicrosecondArray::from(vec![Some(0), Some(before), None]);
+        assert_eq!(
+            from_timestamp_array(&array, false),
+            Err(FileTimeError::InvalidElement {
+                index: 1,
+                reason: "before the FILETIME epoch",
+            })
+        );
+        assert_eq!(
+            from_timestamp_array(&array, true),
+            Ok(vec![FileTime::from_unix_seconds(0).ok(), None, None])
+        );
+
+        let array = TimestampSecondArray::from(vec![i64::MAX]).with_timezone("UTC");
+        assert_eq!(
+            from_timestamp_array(&array, false),
+            Err(FileTimeError::InvalidElement {
+                index: 0,
+                reason: "beyond the maximum FILETIME",
+            })
+        );
+        assert_eq!(from_timestamp_array(&array, true), Ok(vec![None]));
+
+        assert_eq!(
+            to_timestamp_array(&[FileTime::MIN], TimeUnit::Nanosecond, None).unwrap_err(),
+            FileTimeError::InvalidElement {
+                index: 0,
+                reason: "outside of the nanosecond timestamp range",
+            }
+        );
+        assert_eq!(
+            from_timestamp_array(&Int64Array::from(vec![0]), false),
+            Err(FileTimeError::InvalidFormat {
+                format: "Arrow timestamp array",
+                reason: "not a timestamp array",
+            })
+        );
+    }
+}
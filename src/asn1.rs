@@ -0,0 +1,341 @@
+//! ASN.1 UTCTime and GeneralizedTime, as used in X.509 validity periods and Kerberos tickets
+//!
+//! The string functions implement the DER profile of RFC 5280 and work
+//! without extra dependencies. With the `der` feature, conversions from/to
+//! `der::asn1::UtcTime` and `der::asn1::GeneralizedTime` are available as well.
+use crate::parse;
+use crate::{FileTime, FileTimeError};
+use chrono::Datelike;
+
+const UTC_TIME: &str = "UTCTime";
+const GENERALIZED_TIME: &str = "GeneralizedTime";
+
+impl FileTime {
+    /// Parse an ASN.1 UTCTime in DER form `YYMMDDHHMMSSZ`
+    ///
+    /// Two-digit years follow the RFC 5280 pivot: 50–99 map to 19xx, 00–49 to 20xx.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let not_before = FileTime::parse_asn1_utc_time("150604110438Z").unwrap();
+    /// assert_eq!(not_before.to_datetime().to_rfc3339(), "2015-06-04T11:04:38+00:00");
+    /// ```
+    pub fn parse_asn1_utc_time(s: &str) -> Result<Self, FileTimeError> {
+        let b = s.as_bytes();
+        if b.len() != 13 || b[12] != b'Z' {
+            return Err(FileTimeError::InvalidFormat {
+                format: UTC_TIME,
+                reason: "expected YYMMDDHHMMSSZ",
+            });
+        }
+        let yy = Self::asn1_field(&b[0..2], UTC_TIME)? as i32;
+        let year = if yy >= 50 { 1900 + yy } else { 2000 + yy };
+        Self::asn1_datetime(year, &b[2..12], 0, UTC_TIME)
+    }
+
+    /// Format as ASN.1 UTCTime `YYMMDDHHMMSSZ`, sub-second ticks are truncated
+    ///
+    /// UTCTime only covers the years 1950 to 2049, other instants fail
+    /// with [`FileTimeError::OutOfRange`].
+    pub fn to_asn1_utc_time(&self) -> Result<String, FileTimeError> {
+        let dt = self
+            .to_datetime_checked()
+            .map_err(|_| FileTimeError::OutOfRange(UTC_TIME))?;
+        if !(1950..2050).contains(&dt.year()) {
+            return Err(FileTimeError::OutOfRange(UTC_TIME));
+        }
+        Ok(dt.format("%y%m%d%H%M%SZ").to_string())
+    }
+
+    /// Parse an ASN.1 GeneralizedTime in DER form `YYYYMMDDHHMMSS[.f+]Z`
+    ///
+    /// Fractional seconds beyond tick precision (100ns) are truncated.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// // RFC 5280 "no well-defined expiration date"
+    /// let not_after = FileTime::parse_asn1_generalized_time("99991231235959Z").unwrap();
+    /// assert_eq!(not_after.to_datetime().to_rfc3339(), "9999-12-31T23:59:59+00:00");
+    /// ```
+    pub fn parse_asn1_generalized_time(s: &str) -> Result<Self, FileTimeError> {
+        let b = s.as_bytes();
+        if b.len() < 15 || b[b.len() - 1] != b'Z' {
+            return Err(FileTimeError::InvalidFormat {
+                format: GENERALIZED_TIME,
+                reason: "expected YYYYMMDDHHMMSS[.f+]Z",
+            });
+        }
+        let nanos = match &b[14..b.len() - 1] {
+            [] => 0,
+            [b'.', fraction @ ..] => {
+                parse::fraction_nanos(fraction).ok_or(FileTimeError::InvalidFormat {
+                    format: GENERALIZED_TIME,
+                    reason: "invalid fractional seconds",
+                })?
+            }
+            _ => {
+                return Err(FileTimeError::InvalidFormat {
+                    format: GENERALIZED_TIME,
+                    reason: "expected YYYYMMDDHHMMSS[.f+]Z",
+                })
+            }
+        };
+        let year = Self::asn1_field(&b[0..4], GENERALIZED_TIME)? as i32;
+        Self::asn1_datetime(year, &b[4..14], nanos, GENERALIZED_TIME)
+    }
+
+    /// Format as ASN.1 GeneralizedTime `YYYYMMDDHHMMSSZ`, sub-second ticks are truncated
+    ///
+    /// Instants after the year 9999 fail with [`FileTimeError::OutOfRange`].
+    pub fn to_asn1_generalized_time(&self) -> Result<String, FileTimeError> {
+        let dt = self
+            .to_datetime_checked()
+            .map_err(|_| FileTimeError::OutOfRange(GENERALIZED_TIME))?;
+        if dt.year() > 9999 {
+            return Err(FileTimeError::OutOfRange(GENERALIZED_TIME));
+        }
+        Ok(dt.format("%Y%m%d%H%M%SZ").to_string())
+    }
+
+    fn asn1_field(b: &[u8], format: &'static str) -> Result<u32, FileTimeError> {
+        parse::digits(b).ok_or(FileTimeError::InvalidFormat {
+            format,
+            reason: "expected digits",
+        })
+    }
+
+    /// Shared tail of both formats: `MMDDHHMMSS`
+    fn asn1_datetime(
+        year: i32,
+        b: &[u8],
+        nanos: u32,
+        format: &'static str,
+    ) -> Result<Self, FileTimeError> {
+        let month = Self::asn1_field(&b[0..2], format)?;
+        let day = Self::asn1_field(&b[2..4], format)?;
+        let hour = Self::asn1_field(&b[4..6], format)?;
+        let min = Self::asn1_field(&b[6..8], format)?;
+        let sec = Self::asn1_field(&b[8..10], format)?;
+        let dt = parse::datetime(year, month, day, hour, min, sec, nanos).ok_or(
+            FileTimeError::InvalidFormat {
+                format,
+                reason: "invalid date or time",
+            },
+        )?;
+        Self::from_datetime_checked(dt)
+    }
+}
+
+#[cfg(feature = "der")]
+mod der_impl {
+    use super::*;
+    use der::asn1::{GeneralizedTime, UtcTime};
+    use std::convert::TryFrom;
+    use std::time::Duration;
+
+    impl FileTime {
+        fn from_der_unix_duration(d: Duration) -> Result<Self, FileTimeError> {
+            Self::from_ticks_i128(
+                i128::from(Self::EPOCH_AS_FILETIME)
+                    + i128::from(d.as_secs()) * i128::from(Self::HUNDREDS_OF_NANOSECONDS),
+            )
+        }
+
+        /// Whole seconds since the Unix-Epoch, `None` before 1970
+        fn to_der_unix_duration(self) -> Option<Duration> {
+            let since_unix = self.unix_ticks();
+            if since_unix < 0 {
+                return None;
+            }
+            Some(Duration::from_secs(
+                (since_unix / i128::from(Self::HUNDREDS_OF_NANOSECONDS)) as u64,
+            ))
+        }
+    }
+
+    impl TryFrom<UtcTime> for FileTime {
+        type Error = FileTimeError;
+
+        fn try_from(t: UtcTime) -> Result<Self, Self::Error> {
+            Self::from_der_unix_duration(t.to_unix_duration())
+        }
+    }
+
+    /// Sub-second ticks are truncated, instants outside of 1970–2049 fail
+    impl TryFrom<FileTime> for UtcTime {
+        type Error = FileTimeError;
+
+        fn try_from(ft: FileTime) -> Result<Self, Self::Error> {
+            ft.to_der_unix_duration()
+                .and_then(|d| UtcTime::from_unix_duration(d).ok())
+                .ok_or(FileTimeError::OutOfRange(UTC_TIME))
+        }
+    }
+
+    impl TryFrom<GeneralizedTime> for FileTime {
+        type Error = FileTimeError;
+
+        fn try_from(t: GeneralizedTime) -> Result<Self, Self::Error> {
+            Self::from_der_unix_duration(t.to_unix_duration())
+        }
+    }
+
+    /// Sub-second ticks are truncated, instants outside of 1970–9999 fail
+    impl TryFrom<FileTime> for GeneralizedTime {
+        type Error = FileTimeError;
+
+        fn try_from(ft: FileTime) -> Result<Self, Self::Error> {
+            ft.to_der_unix_duration()
+                .and_then(|d| GeneralizedTime::from_unix_duration(d).ok())
+                .ok_or(FileTimeError::OutOfRange(GENERALIZED_TIME))
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use der::Decode;
+
+        #[test]
+        fn utc_time_der() {
+            // notBefore of ISRG Root X1
+            let der = b"\x17\x0d150604110438Z";
+            let t = UtcTime::from_der(der).unwrap();
+            let ft = FileTime::try_from(t).unwrap();
+            assert_eq!(ft, FileTime::parse_asn1_utc_time("150604110438Z").unwrap());
+            assert_eq!(UtcTime::try_from(ft).unwrap(), t);
+        }
+
+        #[test]
+        fn generalized_time_der() {
+            let der = b"\x18\x0f99991231235959Z";
+            let t = GeneralizedTime::from_der(der).unwrap();
+            let ft = FileTime::try_from(t).unwrap();
+            assert_eq!(
+                ft,
+                FileTime::parse_asn1_generalized_time("99991231235959Z").unwrap()
+            );
+            assert_eq!(GeneralizedTime::try_from(ft).unwrap(), t);
+        }
+
+        #[test]
+        fn der_range() {
+            let ft = FileTime::parse_asn1_generalized_time("20500101000000Z").unwrap();
+            assert_eq!(
+                UtcTime::try_from(ft),
+                Err(FileTimeError::OutOfRange(UTC_TIME))
+            );
+            // der::DateTime starts at the Unix-Epoch
+            let ft = FileTime::parse_asn1_generalized_time("19691231235959Z").unwrap();
+            assert_eq!(
+                GeneralizedTime::try_from(ft),
+                Err(FileTimeError::OutOfRange(GENERALIZED_TIME))
+            );
+            let ft = FileTime::from_i64(i64::MAX);
+            assert_eq!(
+                GeneralizedTime::try_from(ft),
+                Err(FileTimeError::OutOfRange(GENERALIZED_TIME))
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn utc_time() {
+        // ISRG Root X1 validity
+        let not_before = FileTime::parse_asn1_utc_time("150604110438Z").unwrap();
+        let not_after = FileTime::parse_asn1_utc_time("350604110438Z").unwrap();
+        assert_eq!(
+            not_before.to_datetime().to_rfc3339(),
+            "2015-06-04T11:04:38+00:00"
+        );
+        assert_eq!(
+            not_after.to_datetime().to_rfc3339(),
+            "2035-06-04T11:04:38+00:00"
+        );
+        assert_eq!(not_before.to_asn1_utc_time().unwrap(), "150604110438Z");
+        assert_eq!(not_after.to_asn1_utc_time().unwrap(), "350604110438Z");
+    }
+
+    #[test]
+    fn utc_time_pivot() {
+        // Thawte Premium Server CA notBefore
+        let ft = FileTime::parse_asn1_utc_time("960801000000Z").unwrap();
+        assert_eq!(ft.to_datetime().to_rfc3339(), "1996-08-01T00:00:00+00:00");
+
+        let ft = FileTime::parse_asn1_utc_time("500101000000Z").unwrap();
+        assert_eq!(ft.to_datetime().to_rfc3339(), "1950-01-01T00:00:00+00:00");
+        let ft = FileTime::parse_asn1_utc_time("491231235959Z").unwrap();
+        assert_eq!(ft.to_datetime().to_rfc3339(), "2049-12-31T23:59:59+00:00");
+    }
+
+    #[test]
+    fn utc_time_range() {
+        let ft = FileTime::parse_asn1_generalized_time("20500101000000Z").unwrap();
+        assert_eq!(
+            ft.to_asn1_utc_time(),
+            Err(FileTimeError::OutOfRange(UTC_TIME))
+        );
+        let ft = FileTime::parse_asn1_generalized_time("19491231235959Z").unwrap();
+        assert_eq!(
+            ft.to_asn1_utc_time(),
+            Err(FileTimeError::OutOfRange(UTC_TIME))
+        );
+        assert_eq!(
+            FileTime::MAX_RAW.to_asn1_utc_time(),
+            Err(FileTimeError::OutOfRange(UTC_TIME))
+        );
+    }
+
+    #[test]
+    fn generalized_time() {
+        let ft = FileTime::parse_asn1_generalized_time("20491231235959Z").unwrap();
+        assert_eq!(ft.to_asn1_generalized_time().unwrap(), "20491231235959Z");
+
+        let ft = FileTime::parse_asn1_generalized_time("20090725230000.0001Z").unwrap();
+        assert_eq!(ft, FileTime::from_i64(128930364000001000));
+        assert_eq!(ft.to_asn1_generalized_time().unwrap(), "20090725230000Z");
+
+        for ft in [FileTime::MAX, FileTime::MAX_RAW] {
+            assert_eq!(
+                ft.to_asn1_generalized_time(),
+                Err(FileTimeError::OutOfRange(GENERALIZED_TIME))
+            );
+        }
+        assert_eq!(
+            FileTime::parse_asn1_generalized_time("15000101000000Z"),
+            Err(FileTimeError::BeforeEpoch)
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for s in [
+            "150604110438",
+            "1506041104380Z",
+            "15060411043AZ",
+            "151304110438Z",
+        ] {
+            assert!(matches!(
+                FileTime::parse_asn1_utc_time(s),
+                Err(FileTimeError::InvalidFormat {
+                    format: UTC_TIME,
+                    ..
+                })
+            ));
+        }
+        for s in ["20150604110438", "20150604110438.Z", "20150604110438+0100"] {
+            assert!(matches!(
+                FileTime::parse_asn1_generalized_time(s),
+                Err(FileTimeError::InvalidFormat {
+                    format: GENERALIZED_TIME,
+                    ..
+                })
+            ));
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! async-graphql `FileTime` scalar
+//!
+//! Output is an RFC 3339 string with 7 fraction digits, as produced by
+//! [`FileTime::display`]. Input accepts that form (any string [`FileTime`]'s
+//! `FromStr` understands) or an integer holding raw ticks.
+use crate::FileTime;
+use ::async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+/// Windows FILETIME as RFC 3339 timestamp with 7 fraction digits, e.g.
+/// "2013-05-25T16:01:23.1482830Z". Accepts such a string or an integer
+/// counting 100-nanosecond intervals since 1601-01-01T00:00:00Z.
+#[Scalar(name = "FileTime")]
+impl ScalarType for FileTime {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => s.parse().map_err(|err| {
+                InputValueError::custom(format_args!("invalid FileTime {:?}: {}", s, err))
+            }),
+            Value::Number(n) => n
+                .as_i64()
+                .and_then(|ticks| FileTime::from_i64_checked(ticks).ok())
+                .ok_or_else(|| {
+                    InputValueError::custom(format_args!(
+                        "invalid FileTime {}: ticks must be an integer from 0 to {}",
+                        n,
+                        i64::MAX
+                    ))
+                }),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.display().to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn echo(&self, time: FileTime) -> FileTime {
+            time
+        }
+    }
+
+    fn run(query: &str) -> Result<String, String> {
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        let response = futures_executor::block_on(schema.execute(query));
+        match response.errors.first() {
+            Some(err) => Err(err.message.clone()),
+            None => Ok(response.data.into_json().unwrap()["echo"]
+                .as_str()
+                .unwrap()
+                .to_owned()),
+        }
+    }
+
+    #[test]
+    fn input_forms() {
+        let expected = Ok("2013-05-25T16:01:23.1482830Z".to_owned());
+        assert_eq!(run("{ echo(time: 130139712831482830) }"), expected);
+        assert_eq!(
+            run(r#"{ echo(time: "2013-05-25T18:01:23.148283+02:00") }"#),
+            expected
+        );
+        assert_eq!(
+            run(r#"{ echo(time: "2013-05-25T16:01:23.1482830Z") }"#),
+            expected
+        );
+        assert_eq!(
+            run("{ echo(time: 0) }"),
+            Ok("1601-01-01T00:00:00.0000000Z".to_owned())
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            run("{ echo(time: -1) }"),
+            Err(r#"Failed to parse "FileTime": invalid FileTime -1: ticks must be an integer from 0 to 9223372036854775807"#.to_owned())
+        );
+        assert_eq!(
+            run(r#"{ echo(time: "yesterday") }"#),
+            Err(r#"Failed to parse "FileTime": invalid FileTime "yesterday": invalid FILETIME: expected decimal ticks, 0x-prefixed hex ticks or an RFC 3339 timestamp"#.to_owned())
+        );
+        assert!(run("{ echo(time: 1.5) }").is_err());
+        assert_eq!(
+            run("{ echo(time: true) }"),
+            Err(r#"Expected input type "FileTime", found true."#.to_owned())
+        );
+    }
+
+    #[test]
+    fn sdl() {
+        let sdl = Schema::new(Query, EmptyMutation, EmptySubscription).sdl();
+        assert!(sdl.contains("scalar FileTime"));
+        assert!(sdl.contains("Windows FILETIME as RFC 3339 timestamp"));
+    }
+}
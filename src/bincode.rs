@@ -0,0 +1,116 @@
+//! bincode 2 `Encode`/`Decode` support
+//!
+//! FileTime is written as its raw ticks in 8 little-endian bytes, the
+//! FILETIME layout on disk, whatever the configuration's int encoding and
+//! endianness say. The format thus stays the same across configurations.
+//! Every 8 byte value is a FileTime (see [`FileTime::MAX_RAW`]), so only
+//! truncated input fails to decode.
+use crate::FileTime;
+use ::bincode::de::{Decode, Decoder};
+use ::bincode::enc::{Encode, Encoder};
+use ::bincode::error::{DecodeError, EncodeError};
+
+impl Encode for FileTime {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.as_u64().to_le_bytes().encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for FileTime {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        <[u8; 8]>::decode(decoder).map(FileTime::from)
+    }
+}
+
+::bincode::impl_borrow_decode!(FileTime);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::bincode::config::{self, Config};
+
+    #[derive(Debug, PartialEq, ::bincode::Encode, ::bincode::Decode)]
+    struct Record {
+        id: u32,
+        modified: FileTime,
+    }
+
+    fn roundtrip<C: Config>(config: C) {
+        let record = Record {
+            id: 7,
+            modified: FileTime::from_i64(130139712831482830),
+        };
+        let bytes = ::bincode::encode_to_vec(&record, config).unwrap();
+        // The standard config encodes the u32 as 1 byte varint, legacy as 4 bytes
+        let ticks = &bytes[bytes.len() - 8..];
+        assert_eq!(ticks, [0xCE, 0xEB, 0x7D, 0x1A, 0x61, 0x59, 0xCE, 0x01]);
+        let (back, len): (Record, usize) = ::bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!((back, len), (record, bytes.len()));
+        let (borrowed, _): (Record, usize) =
+            ::bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(borrowed.modified, FileTime::from_i64(130139712831482830));
+
+        for ft in [FileTime::MIN, FileTime::MAX, FileTime::MAX_RAW] {
+            let bytes = ::bincode::encode_to_vec(ft, config).unwrap();
+            assert_eq!(bytes, ft.as_u64().to_le_bytes());
+            assert_eq!(
+                ::bincode::decode_from_slice(&bytes, config).unwrap(),
+                (ft, 8)
+            );
+        }
+    }
+
+    #[test]
+    fn standard() {
+        roundtrip(config::standard());
+        roundtrip(config::standard().with_big_endian());
+        let pair = (7u32, FileTime::MIN);
+        assert_eq!(
+            ::bincode::encode_to_vec(pair, config::standard())
+                .unwrap()
+                .len(),
+            9
+        );
+    }
+
+    #[test]
+    fn legacy() {
+        roundtrip(config::legacy());
+        let pair = (7u32, FileTime::MIN);
+        assert_eq!(
+            ::bincode::encode_to_vec(pair, config::legacy())
+                .unwrap()
+                .len(),
+            12
+        );
+    }
+
+    #[test]
+    fn corrupted() {
+        let bytes = ::bincode::encode_to_vec(FileTime::MAX, config::standard()).unwrap();
+        for len in 0..8 {
+            let err =
+                ::bincode::decode_from_slice::<FileTime, _>(&bytes[..len], config::standard())
+                    .unwrap_err();
+            assert!(
+                matches!(err, DecodeError::UnexpectedEnd { additional } if additional == 8 - len),
+                "{}: {:?}",
+                len,
+                err
+            );
+        }
+        // Flipped bits still decode, there is no invalid tick count
+        let mut flipped = bytes.clone();
+        flipped[7] ^= 0x80;
+        assert_eq!(
+            ::bincode::decode_from_slice(&flipped, config::standard()).unwrap(),
+            (FileTime::MAX_RAW, 8)
+        );
+        // A limit below 8 bytes rejects a FileTime
+        let limited = config::standard().with_limit::<4>();
+        assert!(matches!(
+            ::bincode::decode_from_slice::<FileTime, _>(&bytes, limited),
+            Err(DecodeError::LimitExceeded)
+        ));
+    }
+}
@@ -0,0 +1,67 @@
+//! Conversion from/to `bson::DateTime`
+//!
+//! BSON datetimes count milliseconds since the Unix-Epoch in an i64, which
+//! covers every FileTime. Sub-millisecond ticks are truncated towards the
+//! earlier instant when converting to BSON. BSON datetimes before the
+//! FILETIME-Epoch fail with [`FileTimeError::BeforeEpoch`], those after
+//! [`FileTime::MAX`] with [`FileTimeError::Overflow`].
+//!
+//! FileTime itself serializes as integer; for BSON datetime elements use the
+//! `filetime_type::serde::bson_datetime` with-module (`serde` feature).
+use crate::{FileTime, FileTimeError};
+use std::convert::TryFrom;
+
+impl TryFrom<bson::DateTime> for FileTime {
+    type Error = FileTimeError;
+
+    fn try_from(dt: bson::DateTime) -> Result<Self, Self::Error> {
+        FileTime::from_unix_millis(dt.timestamp_millis())
+    }
+}
+
+impl From<FileTime> for bson::DateTime {
+    fn from(ft: FileTime) -> Self {
+        bson::DateTime::from_millis(ft.to_unix_millis())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let ft = FileTime::from_i64(130139712831482830);
+        let dt = bson::DateTime::from(ft);
+        assert_eq!(dt.timestamp_millis(), 1369497683148);
+        assert_eq!(
+            FileTime::try_from(dt),
+            Ok(FileTime::from_i64(130139712831480000))
+        );
+
+        let dt = bson::DateTime::from(FileTime::MIN);
+        assert_eq!(dt.timestamp_millis(), -11644473600000);
+        assert_eq!(FileTime::try_from(dt), Ok(FileTime::MIN));
+        let max = FileTime::from_i64(i64::MAX - i64::MAX % 10_000);
+        assert_eq!(
+            FileTime::try_from(bson::DateTime::from(FileTime::MAX)),
+            Ok(max)
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(
+            FileTime::try_from(bson::DateTime::from_millis(-11644473600001)),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::try_from(bson::DateTime::MAX),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!(
+            FileTime::try_from(bson::DateTime::MIN),
+            Err(FileTimeError::BeforeEpoch)
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Field by field construction, returned by [`FileTime::builder`]
+use crate::{FileTime, FileTimeError};
+use chrono::{Datelike, Timelike};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Base {
+    Raw(i64),
+    UnixSeconds(i64),
+}
+
+/// Builder for a FileTime, returned by [`FileTime::builder`]
+///
+/// Without a base, year, month and day are required and the time of day
+/// defaults to midnight. With [`raw`](FileTimeBuilder::raw) or
+/// [`unix_seconds`](FileTimeBuilder::unix_seconds) as base, every field
+/// defaults to the one of the base and the setters replace single fields.
+/// ```
+/// use filetime_type::FileTime;
+///
+/// let ft = FileTime::builder()
+///     .year(2023)
+///     .month(5)
+///     .day(17)
+///     .hour(9)
+///     .ticks_subsec(1234567)
+///     .build()
+///     .unwrap();
+/// assert_eq!(ft, FileTime::from_ymd_hms_nano(2023, 5, 17, 9, 0, 0, 123456700).unwrap());
+///
+/// // Same instant one year later
+/// let later = FileTime::builder().raw(ft.filetime()).year(2024).build().unwrap();
+/// assert_eq!(later, FileTime::from_ymd_hms_nano(2024, 5, 17, 9, 0, 0, 123456700).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileTimeBuilder {
+    base: Option<Base>,
+    conflicting_bases: bool,
+    year: Option<i32>,
+    month: Option<u32>,
+    day: Option<u32>,
+    hour: Option<u32>,
+    minute: Option<u32>,
+    second: Option<u32>,
+    ticks_subsec: Option<u32>,
+}
+
+impl FileTimeBuilder {
+    fn set_base(mut self, base: Base) -> Self {
+        self.conflicting_bases |= self.base.is_some_and(|b| b != base);
+        self.base = Some(base);
+        self
+    }
+
+    /// Start from a raw tick count
+    pub fn raw(self, ticks: i64) -> Self {
+        self.set_base(Base::Raw(ticks))
+    }
+
+    /// Start from seconds since the Unix-Epoch
+    pub fn unix_seconds(self, secs: i64) -> Self {
+        self.set_base(Base::UnixSeconds(secs))
+    }
+
+    /// Calendar year
+    pub fn year(mut self, year: i32) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    /// Month, 1 to 12
+    pub fn month(mut self, month: u32) -> Self {
+        self.month = Some(month);
+        self
+    }
+
+    /// Day of the month, starting at 1
+    pub fn day(mut self, day: u32) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// Hour, 0 to 23
+    pub fn hour(mut self, hour: u32) -> Self {
+        self.hour = Some(hour);
+        self
+    }
+
+    /// Minute, 0 to 59
+    pub fn minute(mut self, minute: u32) -> Self {
+        self.minute = Some(minute);
+        self
+    }
+
+    /// Second, 0 to 59
+    pub fn second(mut self, second: u32) -> Self {
+        self.second = Some(second);
+        self
+    }
+
+    /// 100ns ticks within the second, below 10_000_000
+    pub fn ticks_subsec(mut self, ticks: u32) -> Self {
+        self.ticks_subsec = Some(ticks);
+        self
+    }
+
+    /// Combine the fields
+    ///
+    /// Fails with [`FileTimeError::InvalidField`] naming the first missing or
+    /// invalid field (`"base"` if both bases were given), or with the range
+    /// errors of the base and the result.
+    pub fn build(&self) -> Result<FileTime, FileTimeError> {
+        let invalid = |field, reason| FileTimeError::InvalidField { field, reason };
+        if self.conflicting_bases {
+            return Err(invalid(
+                "base",
+                "raw and unix_seconds are mutually exclusive",
+            ));
+        }
+        let base = match self.base {
+            Some(Base::Raw(ticks)) => Some(FileTime::from_i64_checked(ticks)?.to_datetime()),
+            Some(Base::UnixSeconds(secs)) => Some(FileTime::from_unix_seconds(secs)?.to_datetime()),
+            None => None,
+        };
+        let required =
+            |field, value: Option<u32>, from_base: fn(&chrono::DateTime<chrono::Utc>) -> u32| {
+                value
+                    .or_else(|| base.as_ref().map(from_base))
+                    .ok_or_else(|| invalid(field, "not set"))
+            };
+        let optional =
+            |value: Option<u32>, from_base: fn(&chrono::DateTime<chrono::Utc>) -> u32| {
+                value.or_else(|| base.as_ref().map(from_base)).unwrap_or(0)
+            };
+
+        let year = self
+            .year
+            .or_else(|| base.as_ref().map(|dt| dt.year()))
+            .ok_or_else(|| invalid("year", "not set"))?;
+        let month = required("month", self.month, |dt| dt.month())?;
+        let day = required("day", self.day, |dt| dt.day())?;
+        let ticks = optional(self.ticks_subsec, |dt| dt.nanosecond() / 100);
+        if ticks >= 10_000_000 {
+            return Err(invalid("ticks_subsec", "must be below 10000000"));
+        }
+        FileTime::from_ymd_hms_nano(
+            year,
+            month,
+            day,
+            optional(self.hour, |dt| dt.hour()),
+            optional(self.minute, |dt| dt.minute()),
+            optional(self.second, |dt| dt.second()),
+            ticks * 100,
+        )
+    }
+}
+
+impl FileTime {
+    /// Builder to set the date and time field by field, see [`FileTimeBuilder`]
+    pub fn builder() -> FileTimeBuilder {
+        FileTimeBuilder::default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn invalid(field: &'static str, reason: &'static str) -> Result<FileTime, FileTimeError> {
+        Err(FileTimeError::InvalidField { field, reason })
+    }
+
+    #[test]
+    fn defaults() {
+        let ft = FileTime::builder().year(2023).month(5).day(17).build();
+        assert_eq!(ft, FileTime::from_ymd_hms(2023, 5, 17, 0, 0, 0));
+        assert_eq!(
+            FileTime::builder().month(5).day(17).build(),
+            invalid("year", "not set")
+        );
+        assert_eq!(
+            FileTime::builder().year(2023).day(17).build(),
+            invalid("month", "not set")
+        );
+        assert_eq!(
+            FileTime::builder().year(2023).month(5).build(),
+            invalid("day", "not set")
+        );
+    }
+
+    #[test]
+    fn fully_specified() {
+        let ft = FileTime::builder()
+            .year(2013)
+            .month(5)
+            .day(25)
+            .hour(16)
+            .minute(1)
+            .second(23)
+            .ticks_subsec(1482830)
+            .build();
+        assert_eq!(
+            ft,
+            FileTime::from_ymd_hms_nano(2013, 5, 25, 16, 1, 23, 148283000)
+        );
+        assert_eq!(ft, Ok(FileTime::from_i64(130139712831482830)));
+    }
+
+    #[test]
+    fn bases() {
+        let ft = FileTime::from_i64(130139712831482830);
+        assert_eq!(FileTime::builder().raw(ft.filetime()).build(), Ok(ft));
+        assert_eq!(
+            FileTime::builder()
+                .raw(ft.filetime())
+                .hour(0)
+                .ticks_subsec(0)
+                .build(),
+            FileTime::from_ymd_hms(2013, 5, 25, 0, 1, 23)
+        );
+        assert_eq!(
+            FileTime::builder().unix_seconds(0).day(2).build(),
+            FileTime::from_ymd_hms(1970, 1, 2, 0, 0, 0)
+        );
+        // Moving Feb 29 into a common year
+        assert_eq!(
+            FileTime::builder()
+                .raw(133536816000000000)
+                .year(2023)
+                .build(),
+            invalid("day", "does not exist in the given month")
+        );
+        assert_eq!(
+            FileTime::builder().raw(-1).build(),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::builder().unix_seconds(i64::MAX).build(),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!(
+            FileTime::builder().raw(0).raw(1).build(),
+            invalid("base", "raw and unix_seconds are mutually exclusive")
+        );
+        assert_eq!(
+            FileTime::builder().raw(0).unix_seconds(0).build(),
+            invalid("base", "raw and unix_seconds are mutually exclusive")
+        );
+        assert_eq!(
+            FileTime::builder().unix_seconds(5).unix_seconds(5).build(),
+            FileTime::from_unix_seconds(5)
+        );
+    }
+
+    #[test]
+    fn invalid_fields() {
+        let base = || FileTime::builder().year(2023).month(5).day(17);
+        let field = |builder: FileTimeBuilder| match builder.build() {
+            Err(FileTimeError::InvalidField { field, .. }) => field,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(field(base().month(13)), "month");
+        assert_eq!(field(base().day(32)), "day");
+        assert_eq!(field(base().hour(24)), "hour");
+        assert_eq!(field(base().minute(60)), "minute");
+        assert_eq!(field(base().second(60)), "second");
+        assert_eq!(field(base().ticks_subsec(10_000_000)), "ticks_subsec");
+        assert_eq!(base().year(1600).build(), Err(FileTimeError::BeforeEpoch));
+    }
+}
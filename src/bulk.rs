@@ -0,0 +1,216 @@
+//! Bulk conversion of FILETIME arrays
+use crate::{FileTime, FileTimeError};
+
+const FORMAT: &str = "FILETIME array";
+
+/// Error for the first negative value, found only once the batch failed validation
+fn first_negative(values: &[i64]) -> FileTimeError {
+    FileTimeError::InvalidElement {
+        index: values.iter().position(|v| *v < 0).unwrap_or(0),
+        reason: "negative FILETIME",
+    }
+}
+
+/// Whether all values are non-negative, checked without a branch per element
+fn all_non_negative(values: &[i64]) -> bool {
+    values.iter().fold(0, |acc, v| acc | v) >= 0
+}
+
+fn decode_le(bytes: &[u8]) -> Result<Vec<u64>, FileTimeError> {
+    if !bytes.len().is_multiple_of(8) {
+        return Err(FileTimeError::Malformed {
+            format: FORMAT,
+            offset: bytes.len() - bytes.len() % 8,
+            reason: "trailing partial FILETIME",
+        });
+    }
+    Ok(bytes
+        .chunks_exact(8)
+        .map(|chunk| {
+            let mut raw = [0u8; 8];
+            raw.copy_from_slice(chunk);
+            u64::from_le_bytes(raw)
+        })
+        .collect())
+}
+
+impl FileTime {
+    /// Convert a slice of signed FILETIME values
+    ///
+    /// Fails with [`FileTimeError::InvalidElement`] holding the index of the
+    /// first negative value. The whole slice is validated before any
+    /// conversion happens. Use [`convert_u64_slice`](Self::convert_u64_slice)
+    /// for values above [`FileTime::MAX`].
+    /// ```
+    /// use filetime_type::{FileTime, FileTimeError};
+    ///
+    /// let times = FileTime::convert_i64_slice(&[0, 128930364000000010]).unwrap();
+    /// assert_eq!(times[1].filetime(), 128930364000000010);
+    ///
+    /// assert_eq!(
+    ///     FileTime::convert_i64_slice(&[0, 1, -1, -2]),
+    ///     Err(FileTimeError::InvalidElement { index: 2, reason: "negative FILETIME" })
+    /// );
+    /// ```
+    pub fn convert_i64_slice(values: &[i64]) -> Result<Vec<FileTime>, FileTimeError> {
+        if !all_non_negative(values) {
+            return Err(first_negative(values));
+        }
+        Ok(values.iter().map(|v| FileTime::from_i64(*v)).collect())
+    }
+
+    /// Convert a slice of unsigned FILETIME values, covering the full tick range
+    pub fn convert_u64_slice(values: &[u64]) -> Vec<FileTime> {
+        values.iter().map(|v| FileTime::from_u64(*v)).collect()
+    }
+
+    /// Convert consecutive little-endian FILETIMEs
+    ///
+    /// Records are unsigned, so every 8 bytes are a valid FILETIME, up to
+    /// [`FileTime::MAX_RAW`]. A length that is not a multiple of 8 fails with
+    /// [`FileTimeError::Malformed`] pointing at the partial record.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let times = FileTime::from_le_slice_bulk(&[0xff; 16]).unwrap();
+    /// assert_eq!(times, vec![FileTime::MAX_RAW; 2]);
+    /// ```
+    pub fn from_le_slice_bulk(bytes: &[u8]) -> Result<Vec<FileTime>, FileTimeError> {
+        Ok(Self::convert_u64_slice(&decode_le(bytes)?))
+    }
+
+    /// Parallel [`convert_i64_slice`](Self::convert_i64_slice), worth it for millions of values
+    #[cfg(feature = "rayon")]
+    pub fn par_convert_i64_slice(values: &[i64]) -> Result<Vec<FileTime>, FileTimeError> {
+        use rayon::prelude::*;
+
+        let valid = values.par_chunks(64 * 1024).all(all_non_negative);
+        if !valid {
+            return Err(first_negative(values));
+        }
+        Ok(values.par_iter().map(|v| FileTime::from_i64(*v)).collect())
+    }
+
+    /// Parallel [`from_le_slice_bulk`](Self::from_le_slice_bulk)
+    #[cfg(feature = "rayon")]
+    pub fn par_from_le_slice_bulk(bytes: &[u8]) -> Result<Vec<FileTime>, FileTimeError> {
+        use rayon::prelude::*;
+
+        let values = decode_le(bytes)?;
+        Ok(values.par_iter().map(|v| FileTime::from_u64(*v)).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample() -> Vec<i64> {
+        (0..1000)
+            .map(|i: i64| i * 1_234_567_891_011 + i % 7)
+            .chain(vec![0, i64::MAX])
+            .collect()
+    }
+
+    fn element_wise(values: &[i64]) -> Result<Vec<FileTime>, FileTimeError> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, v)| {
+                FileTime::from_i64_checked(*v).map_err(|_| FileTimeError::InvalidElement {
+                    index,
+                    reason: "negative FILETIME",
+                })
+            })
+            .collect()
+    }
+
+    fn to_le(values: &[i64]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|v| v.to_le_bytes().to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn matches_element_wise() {
+        let values = sample();
+        let bulk = FileTime::convert_i64_slice(&values).unwrap();
+        assert_eq!(Ok(bulk.clone()), element_wise(&values));
+        assert_eq!(FileTime::from_le_slice_bulk(&to_le(&values)), Ok(bulk));
+        assert_eq!(FileTime::convert_i64_slice(&[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn first_invalid_position() {
+        let mut values = sample();
+        values[417] = -1;
+        values[900] = i64::MIN;
+        let expected = Err(FileTimeError::InvalidElement {
+            index: 417,
+            reason: "negative FILETIME",
+        });
+        assert_eq!(element_wise(&values), expected);
+        assert_eq!(FileTime::convert_i64_slice(&values), expected);
+    }
+
+    #[test]
+    fn unsigned_records() {
+        // The same bit patterns are valid unsigned records
+        let values = [1i64, -1, i64::MIN];
+        assert_eq!(
+            FileTime::from_le_slice_bulk(&to_le(&values)),
+            Ok(vec![
+                FileTime::from_u64(1),
+                FileTime::MAX_RAW,
+                FileTime::from_u64(1 << 63),
+            ])
+        );
+        let raw: Vec<u64> = values.iter().map(|v| *v as u64).collect();
+        assert_eq!(
+            FileTime::convert_u64_slice(&raw),
+            FileTime::from_le_slice_bulk(&to_le(&values)).unwrap()
+        );
+    }
+
+    #[test]
+    fn partial_record() {
+        let mut bytes = to_le(&[1, 2, 3]);
+        bytes.extend_from_slice(&[0, 0, 0]);
+        assert_eq!(
+            FileTime::from_le_slice_bulk(&bytes),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 24,
+                reason: "trailing partial FILETIME",
+            })
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel() {
+        let mut values: Vec<i64> = (0..300_000).map(|i| i * 987_654_321).collect();
+        assert_eq!(
+            FileTime::par_convert_i64_slice(&values),
+            FileTime::convert_i64_slice(&values)
+        );
+        assert_eq!(
+            FileTime::par_from_le_slice_bulk(&to_le(&values)),
+            FileTime::convert_i64_slice(&values)
+        );
+        values[200_001] = -5;
+        values[250_000] = -6;
+        assert_eq!(
+            FileTime::par_from_le_slice_bulk(&to_le(&values)),
+            FileTime::from_le_slice_bulk(&to_le(&values))
+        );
+        assert_eq!(
+            FileTime::par_convert_i64_slice(&values),
+            Err(FileTimeError::InvalidElement {
+                index: 200_001,
+                reason: "negative FILETIME",
+            })
+        );
+    }
+}
@@ -0,0 +1,1098 @@
+//! Calendar fields: construction without going through chrono, date and time of day
+use crate::{FileTime, FileTimeError};
+use chrono::{
+    DateTime, Datelike, Days, IsoWeek, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike,
+    Utc, Weekday,
+};
+use core::convert::TryFrom;
+
+const TICKS_PER_SECOND: i128 = FileTime::HUNDREDS_OF_NANOSECONDS as i128;
+const TICKS_PER_MINUTE: i128 = 60 * TICKS_PER_SECOND;
+const TICKS_PER_HOUR: i128 = 60 * TICKS_PER_MINUTE;
+const TICKS_PER_DAY: i128 = 24 * TICKS_PER_HOUR;
+/// Days from 0000-03-01 (the start of the proleptic Gregorian era) to 1601-01-01
+const DAYS_TO_EPOCH: i64 = 584_694;
+
+pub(crate) const fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+pub(crate) const fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1601-01-01 of a valid date, after Howard Hinnant's `days_from_civil`
+pub(crate) const fn days_since_epoch(year: i32, month: u32, day: u32) -> i64 {
+    let year = year as i64 - if month <= 2 { 1 } else { 0 };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - DAYS_TO_EPOCH
+}
+
+const fn invalid(field: &'static str, reason: &'static str) -> FileTimeError {
+    FileTimeError::InvalidField { field, reason }
+}
+
+impl FileTime {
+    /// Construct FileTime from a UTC calendar date and time of day
+    ///
+    /// Fails with [`FileTimeError::InvalidField`] naming the first invalid
+    /// component, or [`FileTimeError::BeforeEpoch`] / [`FileTimeError::Overflow`]
+    /// for dates outside of the FILETIME range. Usable in const contexts.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// const RELEASE: FileTime = match FileTime::from_ymd_hms(2009, 7, 25, 23, 0, 0) {
+    ///     Ok(ft) => ft,
+    ///     Err(_) => panic!("invalid date"),
+    /// };
+    /// assert_eq!(RELEASE.filetime(), 128930364000000000);
+    /// ```
+    pub const fn from_ymd_hms(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        min: u32,
+        sec: u32,
+    ) -> Result<FileTime, FileTimeError> {
+        Self::from_ymd_hms_nano(year, month, day, hour, min, sec, 0)
+    }
+
+    /// Like [`FileTime::from_ymd_hms`], with nanoseconds of the second
+    ///
+    /// Nanoseconds below the 100ns tick precision are truncated.
+    pub const fn from_ymd_hms_nano(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        min: u32,
+        sec: u32,
+        nanos: u32,
+    ) -> Result<FileTime, FileTimeError> {
+        if month < 1 || month > 12 {
+            return Err(invalid("month", "must be within 1 to 12"));
+        }
+        if day < 1 || day > days_in_month(year, month) {
+            return Err(invalid("day", "does not exist in the given month"));
+        }
+        if hour > 23 {
+            return Err(invalid("hour", "must be below 24"));
+        }
+        if min > 59 {
+            return Err(invalid("minute", "must be below 60"));
+        }
+        if sec > 59 {
+            return Err(invalid("second", "must be below 60"));
+        }
+        if nanos > 999_999_999 {
+            return Err(invalid("nanosecond", "must be below 1000000000"));
+        }
+        let days = days_since_epoch(year, month, day) as i128;
+        let secs = days * 86_400 + (hour * 3600 + min * 60 + sec) as i128;
+        let ticks = secs * TICKS_PER_SECOND + (nanos / 100) as i128;
+        if ticks < 0 {
+            Err(FileTimeError::BeforeEpoch)
+        } else if ticks > i64::MAX as i128 {
+            Err(FileTimeError::Overflow)
+        } else {
+            Ok(FileTime {
+                ticks: ticks as u64,
+            })
+        }
+    }
+}
+
+impl FileTime {
+    /// Date and time in UTC over the whole raw range
+    ///
+    /// Above [`FileTime::MAX`] this continues the proleptic Gregorian
+    /// calendar up to 60056-05-28, which chrono's `NaiveDateTime` still holds.
+    fn naive_utc(&self) -> NaiveDateTime {
+        let days = (self.ticks as i128 / TICKS_PER_DAY) as u64;
+        let ticks = (self.ticks as i128 % TICKS_PER_DAY) as u64;
+        let secs = ticks / TICKS_PER_SECOND as u64;
+        let nanos = ticks % TICKS_PER_SECOND as u64 * 100;
+        NaiveDate::from_ymd_opt(1601, 1, 1)
+            .and_then(|epoch| epoch.checked_add_days(Days::new(days)))
+            .zip(NaiveTime::from_num_seconds_from_midnight_opt(
+                secs as u32,
+                nanos as u32,
+            ))
+            .map(|(date, time)| date.and_time(time))
+            .expect("every u64 tick count lies within chrono's range")
+    }
+
+    /// Calendar date in UTC
+    ///
+    /// Raw values above [`FileTime::MAX`] continue the proleptic Gregorian
+    /// calendar, [`FileTime::MAX_RAW`] lies on 60056-05-28.
+    pub fn date(&self) -> NaiveDate {
+        self.naive_utc().date()
+    }
+
+    /// Time of day in UTC, with the full tick precision
+    pub fn time(&self) -> NaiveTime {
+        self.naive_utc().time()
+    }
+
+    /// Midnight UTC at the start of `date`
+    ///
+    /// Fails with [`FileTimeError::BeforeEpoch`] for dates before 1601-01-01 and
+    /// [`FileTimeError::Overflow`] after 30828-09-14, the date of [`FileTime::MAX`].
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use filetime_type::FileTime;
+    ///
+    /// let cutoff = FileTime::from_date(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()).unwrap();
+    /// assert_eq!(cutoff, FileTime::from_ymd_hms(2020, 1, 1, 0, 0, 0).unwrap());
+    /// ```
+    pub fn from_date(date: NaiveDate) -> Result<FileTime, FileTimeError> {
+        Self::from_datetime_checked(Utc.from_utc_datetime(&date.and_time(NaiveTime::MIN)))
+    }
+
+    /// Last tick of `date` in UTC, 23:59:59.9999999, for building inclusive ranges
+    ///
+    /// Fails like [`FileTime::from_date`], and with [`FileTimeError::Overflow`]
+    /// for 30828-09-14 whose last tick lies beyond [`FileTime::MAX`].
+    /// See [`FileTime::end_of_day`] for the day of a given FileTime.
+    pub fn end_of_date(date: NaiveDate) -> Result<FileTime, FileTimeError> {
+        Self::from_date(date)?;
+        let next = date.succ_opt().ok_or(FileTimeError::Overflow)?;
+        Self::from_date(next).map(|midnight| FileTime::from_u64(midnight.ticks - 1))
+    }
+
+    /// Same time of day on another date
+    ///
+    /// Fails with [`FileTimeError::BeforeEpoch`] / [`FileTimeError::Overflow`]
+    /// if the result lies outside of the FILETIME range.
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_ymd_hms_nano(2009, 7, 25, 23, 0, 0, 100).unwrap();
+    /// let moved = ft.with_date(NaiveDate::from_ymd_opt(2010, 1, 1).unwrap()).unwrap();
+    /// assert_eq!(moved, FileTime::from_ymd_hms_nano(2010, 1, 1, 23, 0, 0, 100).unwrap());
+    /// ```
+    pub fn with_date(&self, date: NaiveDate) -> Result<FileTime, FileTimeError> {
+        Self::from_datetime_checked(Utc.from_utc_datetime(&date.and_time(self.time())))
+    }
+
+    /// Another time of day on the same date
+    ///
+    /// Nanoseconds below the tick precision are truncated. Leap seconds
+    /// (nanoseconds of 1_000_000_000 and above) are rejected as FILETIME has none.
+    pub fn with_time(&self, time: NaiveTime) -> Result<FileTime, FileTimeError> {
+        if time.nanosecond() >= 1_000_000_000 {
+            return Err(invalid("second", "leap seconds are not representable"));
+        }
+        Self::from_datetime_checked(Utc.from_utc_datetime(&self.date().and_time(time)))
+    }
+}
+
+/// Calendar arithmetic in UTC, e.g. for retention schedules
+///
+/// The date moves by whole days on the calendar while the time of day stays
+/// the same down to the tick. Results outside of the FILETIME range fail with
+/// [`FileTimeError::BeforeEpoch`] / [`FileTimeError::Overflow`], raw values
+/// above [`FileTime::MAX`] with [`FileTimeError::OutOfRange`] as they have no date.
+impl FileTime {
+    /// Move by `days` calendar days, negative ones going back
+    ///
+    /// FILETIME has no leap seconds, so this is the same as adding
+    /// `days * 86400` seconds.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_ymd_hms_nano(2024, 2, 28, 13, 37, 0, 100).unwrap();
+    /// assert_eq!(ft.add_days(2), FileTime::from_ymd_hms_nano(2024, 3, 1, 13, 37, 0, 100));
+    /// assert_eq!(ft.add_days(-59), FileTime::from_ymd_hms_nano(2023, 12, 31, 13, 37, 0, 100));
+    /// ```
+    pub fn add_days(&self, days: i32) -> Result<FileTime, FileTimeError> {
+        self.to_datetime_checked()?;
+        Self::from_ticks_i128(self.ticks as i128 + i128::from(days) * TICKS_PER_DAY)
+    }
+
+    /// Move by `months` calendar months, clamping the day to the end of the month
+    ///
+    /// January 31 plus one month is February 28, or 29 in leap years, and
+    /// March 31 minus one month the same.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_ymd_hms(2023, 1, 31, 8, 0, 0).unwrap();
+    /// assert_eq!(ft.add_months(1), FileTime::from_ymd_hms(2023, 2, 28, 8, 0, 0));
+    /// assert_eq!(ft.add_months(13), FileTime::from_ymd_hms(2024, 2, 29, 8, 0, 0));
+    /// assert_eq!(ft.add_months(-2), FileTime::from_ymd_hms(2022, 11, 30, 8, 0, 0));
+    /// ```
+    pub fn add_months(&self, months: i32) -> Result<FileTime, FileTimeError> {
+        self.shift_months(i64::from(months))
+    }
+
+    /// Move by `years` calendar years, February 29 becomes February 28 outside of leap years
+    pub fn add_years(&self, years: i32) -> Result<FileTime, FileTimeError> {
+        self.shift_months(i64::from(years) * 12)
+    }
+
+    fn shift_months(&self, months: i64) -> Result<FileTime, FileTimeError> {
+        let dt = self.to_datetime_checked()?;
+        let (year, month, day) = (dt.year(), dt.month(), dt.day());
+        let index = i64::from(year) * 12 + i64::from(month - 1) + months;
+        // Years far outside of 1601..=30828 fail below, keep them out of i32
+        let target_year = index.div_euclid(12).clamp(0, 100_000) as i32;
+        let target_month = index.rem_euclid(12) as u32 + 1;
+        let target_day = day.min(days_in_month(target_year, target_month));
+        let days = days_since_epoch(target_year, target_month, target_day)
+            - days_since_epoch(year, month, day);
+        Self::from_ticks_i128(self.ticks as i128 + i128::from(days) * TICKS_PER_DAY)
+    }
+}
+
+/// Calendar truncation in UTC, e.g. for bucketing events into histograms
+///
+/// `start_of_*` returns the first tick of the period containing the value,
+/// `end_of_*` the last one, so `start <= ft <= end` always holds. FILETIME
+/// has no leap seconds and its epoch falls on the start of a year, so every
+/// start is representable; an end beyond [`FileTime::MAX`], in the periods
+/// containing it, is clamped to `MAX`. Raw values above `MAX` never panic:
+/// all periods follow the calendar continued by [`FileTime::date`], and
+/// their ends are clamped to [`FileTime::MAX_RAW`] instead.
+impl FileTime {
+    /// `ticks` rounded down to a multiple of `unit`, valid as the epoch is aligned to every unit
+    const fn floor_to(&self, unit: i128) -> FileTime {
+        let ticks = self.ticks as i128;
+        FileTime {
+            ticks: (ticks - ticks % unit) as u64,
+        }
+    }
+
+    /// The tick before `next_start`, clamped to [`FileTime::MAX`], or to
+    /// [`FileTime::MAX_RAW`] for values above `MAX`
+    const fn last_tick_before(&self, next_start: i128) -> FileTime {
+        let limit = if self.ticks > FileTime::MAX.ticks {
+            u64::MAX
+        } else {
+            FileTime::MAX.ticks
+        };
+        FileTime {
+            ticks: if next_start > limit as i128 {
+                limit
+            } else {
+                (next_start - 1) as u64
+            },
+        }
+    }
+
+    /// Midnight UTC of `year`-`month`-01, `month` may be 13 for January of the next year
+    fn month_start_ticks(year: i32, month: u32) -> i128 {
+        let (year, month) = if month > 12 {
+            (year + 1, 1)
+        } else {
+            (year, month)
+        };
+        days_since_epoch(year, month, 1) as i128 * TICKS_PER_DAY
+    }
+
+    /// First tick of the minute
+    pub const fn start_of_minute(&self) -> FileTime {
+        self.floor_to(TICKS_PER_MINUTE)
+    }
+
+    /// First tick of the hour
+    pub const fn start_of_hour(&self) -> FileTime {
+        self.floor_to(TICKS_PER_HOUR)
+    }
+
+    /// Midnight at the start of the day
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_ymd_hms(2024, 2, 29, 13, 37, 0).unwrap();
+    /// assert_eq!(ft.start_of_day(), FileTime::from_ymd_hms(2024, 2, 29, 0, 0, 0).unwrap());
+    /// assert_eq!(ft.end_of_day(), FileTime::from_ymd_hms_nano(2024, 2, 29, 23, 59, 59, 999_999_900).unwrap());
+    /// ```
+    pub const fn start_of_day(&self) -> FileTime {
+        self.floor_to(TICKS_PER_DAY)
+    }
+
+    /// Midnight at the start of the first day of the month
+    pub fn start_of_month(&self) -> FileTime {
+        let date = self.date();
+        FileTime {
+            ticks: Self::month_start_ticks(date.year(), date.month()) as u64,
+        }
+    }
+
+    /// Midnight at the start of January 1
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_ymd_hms(1601, 7, 1, 12, 0, 0).unwrap();
+    /// assert_eq!(ft.start_of_year(), FileTime::MIN);
+    /// ```
+    pub fn start_of_year(&self) -> FileTime {
+        FileTime {
+            ticks: Self::month_start_ticks(self.date().year(), 1) as u64,
+        }
+    }
+
+    /// Last tick of the minute, `:59.9999999`
+    pub const fn end_of_minute(&self) -> FileTime {
+        self.last_tick_before(self.start_of_minute().ticks as i128 + TICKS_PER_MINUTE)
+    }
+
+    /// Last tick of the hour, `:59:59.9999999`
+    pub const fn end_of_hour(&self) -> FileTime {
+        self.last_tick_before(self.start_of_hour().ticks as i128 + TICKS_PER_HOUR)
+    }
+
+    /// Last tick of the day, 23:59:59.9999999
+    ///
+    /// See [`FileTime::end_of_date`] for a given [`NaiveDate`].
+    pub const fn end_of_day(&self) -> FileTime {
+        self.last_tick_before(self.start_of_day().ticks as i128 + TICKS_PER_DAY)
+    }
+
+    /// Last tick of the last day of the month
+    pub fn end_of_month(&self) -> FileTime {
+        let date = self.date();
+        self.last_tick_before(Self::month_start_ticks(date.year(), date.month() + 1))
+    }
+
+    /// Last tick of December 31
+    pub fn end_of_year(&self) -> FileTime {
+        self.last_tick_before(Self::month_start_ticks(self.date().year(), 13))
+    }
+}
+
+impl FileTime {
+    /// Apply a chrono setter in UTC, keeping the result only if it is a FileTime
+    fn map_datetime<F>(&self, f: F) -> Option<FileTime>
+    where
+        F: FnOnce(DateTime<Utc>) -> Option<DateTime<Utc>>,
+    {
+        f(Utc.from_utc_datetime(&self.naive_utc()))
+            .and_then(|dt| Self::from_datetime_checked(dt).ok())
+    }
+}
+
+/// Date fields in UTC, the same as those of [`FileTime::to_datetime`]
+///
+/// Like [`FileTime::date`], the getters continue the calendar above
+/// [`FileTime::MAX`]. The `with_*` setters return `None` for dates that do not exist, like
+/// February 30, and for results outside of the FILETIME range.
+/// ```
+/// use chrono::{Datelike, Weekday};
+/// use filetime_type::FileTime;
+///
+/// let ft = FileTime::from_ymd_hms(2024, 2, 29, 13, 37, 0).unwrap();
+/// assert_eq!((ft.year(), ft.month(), ft.day()), (2024, 2, 29));
+/// assert_eq!(ft.weekday(), Weekday::Thu);
+/// assert_eq!(ft.with_year(2023), None);
+/// assert_eq!(ft.with_year(1600), None);
+/// ```
+impl Datelike for FileTime {
+    fn year(&self) -> i32 {
+        self.naive_utc().year()
+    }
+
+    fn month(&self) -> u32 {
+        self.naive_utc().month()
+    }
+
+    fn month0(&self) -> u32 {
+        self.naive_utc().month0()
+    }
+
+    fn day(&self) -> u32 {
+        self.naive_utc().day()
+    }
+
+    fn day0(&self) -> u32 {
+        self.naive_utc().day0()
+    }
+
+    fn ordinal(&self) -> u32 {
+        self.naive_utc().ordinal()
+    }
+
+    fn ordinal0(&self) -> u32 {
+        self.naive_utc().ordinal0()
+    }
+
+    fn weekday(&self) -> Weekday {
+        self.naive_utc().weekday()
+    }
+
+    fn iso_week(&self) -> IsoWeek {
+        self.naive_utc().iso_week()
+    }
+
+    fn with_year(&self, year: i32) -> Option<Self> {
+        self.map_datetime(|dt| dt.with_year(year))
+    }
+
+    fn with_month(&self, month: u32) -> Option<Self> {
+        self.map_datetime(|dt| dt.with_month(month))
+    }
+
+    fn with_month0(&self, month0: u32) -> Option<Self> {
+        self.map_datetime(|dt| dt.with_month0(month0))
+    }
+
+    fn with_day(&self, day: u32) -> Option<Self> {
+        self.map_datetime(|dt| dt.with_day(day))
+    }
+
+    fn with_day0(&self, day0: u32) -> Option<Self> {
+        self.map_datetime(|dt| dt.with_day0(day0))
+    }
+
+    fn with_ordinal(&self, ordinal: u32) -> Option<Self> {
+        self.map_datetime(|dt| dt.with_ordinal(ordinal))
+    }
+
+    fn with_ordinal0(&self, ordinal0: u32) -> Option<Self> {
+        self.map_datetime(|dt| dt.with_ordinal0(ordinal0))
+    }
+}
+
+/// Time of day fields in UTC, the same as those of [`FileTime::to_datetime`]
+///
+/// `nanosecond()` is in nanoseconds as the trait requires, always a multiple
+/// of 100. `with_nanosecond` truncates to the tick and, as FILETIME has no
+/// leap seconds, returns `None` from 1_000_000_000 on.
+/// ```
+/// use chrono::Timelike;
+/// use filetime_type::FileTime;
+///
+/// let ft = FileTime::from_i64(130139712831482830);
+/// assert_eq!((ft.hour(), ft.minute(), ft.second()), (16, 1, 23));
+/// assert_eq!(ft.nanosecond(), 148_283_000);
+/// assert_eq!(ft.with_nanosecond(999), Some(FileTime::from_i64(130139712830000009)));
+/// ```
+impl Timelike for FileTime {
+    fn hour(&self) -> u32 {
+        self.naive_utc().hour()
+    }
+
+    fn minute(&self) -> u32 {
+        self.naive_utc().minute()
+    }
+
+    fn second(&self) -> u32 {
+        self.naive_utc().second()
+    }
+
+    fn nanosecond(&self) -> u32 {
+        self.naive_utc().nanosecond()
+    }
+
+    fn with_hour(&self, hour: u32) -> Option<Self> {
+        self.map_datetime(|dt| dt.with_hour(hour))
+    }
+
+    fn with_minute(&self, min: u32) -> Option<Self> {
+        self.map_datetime(|dt| dt.with_minute(min))
+    }
+
+    fn with_second(&self, sec: u32) -> Option<Self> {
+        self.map_datetime(|dt| dt.with_second(sec))
+    }
+
+    fn with_nanosecond(&self, nano: u32) -> Option<Self> {
+        if nano >= 1_000_000_000 {
+            return None;
+        }
+        self.map_datetime(|dt| dt.with_nanosecond(nano))
+    }
+}
+
+/// Midnight UTC, see [`FileTime::from_date`]
+impl TryFrom<NaiveDate> for FileTime {
+    type Error = FileTimeError;
+
+    fn try_from(date: NaiveDate) -> Result<Self, Self::Error> {
+        Self::from_date(date)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn epoch_and_max() {
+        assert_eq!(
+            FileTime::from_ymd_hms(1601, 1, 1, 0, 0, 0),
+            Ok(FileTime::MIN)
+        );
+        assert_eq!(
+            FileTime::from_ymd_hms(1600, 12, 31, 23, 59, 59),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::from_ymd_hms_nano(30828, 9, 14, 2, 48, 5, 477_580_799),
+            Ok(FileTime::MAX)
+        );
+        assert_eq!(
+            FileTime::from_ymd_hms_nano(30828, 9, 14, 2, 48, 5, 477_580_800),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!(
+            FileTime::from_ymd_hms(i32::MAX, 12, 31, 23, 59, 59),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!(
+            FileTime::from_ymd_hms(i32::MIN, 1, 1, 0, 0, 0),
+            Err(FileTimeError::BeforeEpoch)
+        );
+    }
+
+    #[test]
+    fn leap_days() {
+        assert_eq!(
+            FileTime::from_ymd_hms(2024, 2, 29, 12, 0, 0),
+            Ok(FileTime::from_i64(133536816000000000))
+        );
+        assert_eq!(
+            FileTime::from_ymd_hms(2000, 2, 29, 0, 0, 0).map(|ft| ft.to_datetime()),
+            Ok(Utc.with_ymd_and_hms(2000, 2, 29, 0, 0, 0).unwrap())
+        );
+        for year in [1900, 2023, 2100] {
+            assert_eq!(
+                FileTime::from_ymd_hms(year, 2, 29, 0, 0, 0),
+                Err(FileTimeError::InvalidField {
+                    field: "day",
+                    reason: "does not exist in the given month",
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_fields() {
+        let field = |result: Result<FileTime, FileTimeError>| match result {
+            Err(FileTimeError::InvalidField { field, .. }) => field,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(field(FileTime::from_ymd_hms(2023, 0, 1, 0, 0, 0)), "month");
+        assert_eq!(field(FileTime::from_ymd_hms(2023, 13, 1, 0, 0, 0)), "month");
+        assert_eq!(field(FileTime::from_ymd_hms(2023, 4, 31, 0, 0, 0)), "day");
+        assert_eq!(field(FileTime::from_ymd_hms(2023, 4, 0, 0, 0, 0)), "day");
+        assert_eq!(field(FileTime::from_ymd_hms(2023, 4, 1, 24, 0, 0)), "hour");
+        assert_eq!(
+            field(FileTime::from_ymd_hms(2023, 4, 1, 0, 60, 0)),
+            "minute"
+        );
+        assert_eq!(
+            field(FileTime::from_ymd_hms(2023, 4, 1, 0, 0, 60)),
+            "second"
+        );
+        assert_eq!(
+            field(FileTime::from_ymd_hms_nano(
+                2023,
+                4,
+                1,
+                0,
+                0,
+                0,
+                1_000_000_000
+            )),
+            "nanosecond"
+        );
+        assert_eq!(
+            FileTime::from_ymd_hms(2023, 13, 1, 0, 0, 0)
+                .unwrap_err()
+                .to_string(),
+            "invalid month: must be within 1 to 12"
+        );
+    }
+
+    #[test]
+    fn date_and_time() {
+        let last = FileTime::from_ymd_hms_nano(2023, 12, 31, 23, 59, 59, 999_999_900).unwrap();
+        assert_eq!(last.date(), NaiveDate::from_ymd_opt(2023, 12, 31).unwrap());
+        assert_eq!(
+            last.time(),
+            NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_900).unwrap()
+        );
+        let next = FileTime::from_i64(last.filetime() + 1);
+        assert_eq!(next.date(), NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+        assert_eq!(next.time(), NaiveTime::MIN);
+        assert_eq!(
+            FileTime::MIN.date(),
+            NaiveDate::from_ymd_opt(1601, 1, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn above_max() {
+        let max = FileTime::MAX_RAW;
+        assert_eq!(max.date(), NaiveDate::from_ymd_opt(60056, 5, 28).unwrap());
+        assert_eq!(
+            max.time(),
+            NaiveTime::from_hms_nano_opt(5, 36, 10, 955_161_500).unwrap()
+        );
+        assert_eq!((max.year(), max.month(), max.day()), (60056, 5, 28));
+        assert_eq!((max.hour(), max.minute(), max.second()), (5, 36, 10));
+        assert_eq!(max.with_day(1), None);
+        assert_eq!(
+            max.start_of_month().date(),
+            NaiveDate::from_ymd_opt(60056, 5, 1).unwrap()
+        );
+        assert_eq!(max.end_of_year(), max);
+        // The first raw value above MAX follows it on the same day
+        let next = FileTime::from_u64(FileTime::MAX.as_u64() + 1);
+        assert_eq!(next.date(), FileTime::MAX.date());
+        assert_eq!(next.nanosecond(), 477_580_800);
+    }
+
+    #[test]
+    fn with_date() {
+        let ft = FileTime::from_ymd_hms_nano(2023, 12, 31, 23, 59, 59, 999_999_900).unwrap();
+        let date = NaiveDate::from_ymd_opt(1601, 1, 1).unwrap();
+        assert_eq!(
+            ft.with_date(date),
+            FileTime::from_ymd_hms_nano(1601, 1, 1, 23, 59, 59, 999_999_900)
+        );
+        assert_eq!(
+            FileTime::MIN.with_date(NaiveDate::from_ymd_opt(1600, 12, 31).unwrap()),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            ft.with_date(NaiveDate::from_ymd_opt(30828, 9, 14).unwrap()),
+            Err(FileTimeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn from_date() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(FileTime::from_date(date(1601, 1, 1)), Ok(FileTime::MIN));
+        assert_eq!(FileTime::try_from(date(1601, 1, 1)), Ok(FileTime::MIN));
+        assert_eq!(
+            FileTime::end_of_date(date(1601, 1, 1)),
+            Ok(FileTime::from_i64(864_000_000_000 - 1))
+        );
+        assert_eq!(
+            FileTime::from_date(date(2024, 2, 29)),
+            FileTime::from_ymd_hms(2024, 2, 29, 0, 0, 0)
+        );
+        assert_eq!(
+            FileTime::end_of_date(date(2024, 2, 29)),
+            FileTime::from_ymd_hms_nano(2024, 2, 29, 23, 59, 59, 999_999_900)
+        );
+
+        assert_eq!(
+            FileTime::from_date(date(1600, 12, 31)),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::end_of_date(date(1600, 12, 31)),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::from_date(date(30828, 9, 14)),
+            FileTime::from_ymd_hms(30828, 9, 14, 0, 0, 0)
+        );
+        assert_eq!(
+            FileTime::end_of_date(date(30828, 9, 14)),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!(
+            FileTime::try_from(date(30828, 9, 15)),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!(
+            FileTime::end_of_date(NaiveDate::MAX),
+            Err(FileTimeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn with_time() {
+        let ft = FileTime::from_ymd_hms_nano(2023, 12, 31, 23, 59, 59, 999_999_900).unwrap();
+        assert_eq!(
+            ft.with_time(NaiveTime::MIN),
+            FileTime::from_ymd_hms(2023, 12, 31, 0, 0, 0)
+        );
+        assert_eq!(
+            ft.with_time(NaiveTime::from_hms_nano_opt(12, 0, 0, 123_456_789).unwrap()),
+            FileTime::from_ymd_hms_nano(2023, 12, 31, 12, 0, 0, 123_456_700)
+        );
+        assert_eq!(
+            ft.with_time(NaiveTime::from_hms_nano_opt(23, 59, 59, 1_500_000_000).unwrap()),
+            Err(FileTimeError::InvalidField {
+                field: "second",
+                reason: "leap seconds are not representable",
+            })
+        );
+        assert_eq!(
+            FileTime::MAX.with_time(NaiveTime::from_hms_opt(3, 0, 0).unwrap()),
+            Err(FileTimeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn add_months_clamps() {
+        let at = |y, m, d| FileTime::from_ymd_hms_nano(y, m, d, 23, 59, 59, 999_999_900).unwrap();
+        // Forward into shorter months
+        assert_eq!(at(2023, 1, 31).add_months(1), Ok(at(2023, 2, 28)));
+        assert_eq!(at(2024, 1, 31).add_months(1), Ok(at(2024, 2, 29)));
+        assert_eq!(at(2024, 1, 31).add_months(3), Ok(at(2024, 4, 30)));
+        // Backward into shorter months
+        assert_eq!(at(2023, 3, 31).add_months(-1), Ok(at(2023, 2, 28)));
+        assert_eq!(at(2024, 3, 31).add_months(-1), Ok(at(2024, 2, 29)));
+        assert_eq!(at(2024, 7, 31).add_months(-1), Ok(at(2024, 6, 30)));
+        // Across year boundaries, clamping does not stick
+        assert_eq!(at(2023, 11, 30).add_months(2), Ok(at(2024, 1, 30)));
+        assert_eq!(at(2024, 1, 15).add_months(-13), Ok(at(2022, 12, 15)));
+        assert_eq!(at(2024, 5, 31).add_months(0), Ok(at(2024, 5, 31)));
+    }
+
+    #[test]
+    fn add_years_leap_day() {
+        let leap = FileTime::from_ymd_hms_nano(2024, 2, 29, 6, 30, 0, 1_234_500).unwrap();
+        let on = |y, m, d| FileTime::from_ymd_hms_nano(y, m, d, 6, 30, 0, 1_234_500).unwrap();
+        assert_eq!(leap.add_years(1), Ok(on(2025, 2, 28)));
+        assert_eq!(leap.add_years(4), Ok(on(2028, 2, 29)));
+        assert_eq!(leap.add_years(-100), Ok(on(1924, 2, 29)));
+        // 1900 and 2100 are not leap years, 2000 is
+        assert_eq!(leap.add_years(-124), Ok(on(1900, 2, 28)));
+        assert_eq!(leap.add_years(76), Ok(on(2100, 2, 28)));
+        assert_eq!(leap.add_years(-24), Ok(on(2000, 2, 29)));
+        // Sub-second ticks survive the round trip
+        assert_eq!(leap.add_years(4).unwrap().add_years(-4), Ok(leap));
+    }
+
+    #[test]
+    fn add_days() {
+        let ft = FileTime::from_i64(130139712831482830);
+        assert_eq!(ft.add_days(0), Ok(ft));
+        assert_eq!(
+            ft.add_days(-1).unwrap().filetime(),
+            ft.filetime() - 864_000_000_000
+        );
+        assert_eq!(ft.add_days(365).unwrap().time(), ft.time());
+        assert_eq!(ft.add_days(i32::MAX), Err(FileTimeError::Overflow));
+        assert_eq!(ft.add_days(i32::MIN), Err(FileTimeError::BeforeEpoch));
+    }
+
+    #[test]
+    fn add_range_bounds() {
+        let start = FileTime::from_ymd_hms(1601, 1, 31, 12, 0, 0).unwrap();
+        assert_eq!(
+            start.add_days(-30),
+            FileTime::from_ymd_hms(1601, 1, 1, 12, 0, 0)
+        );
+        assert_eq!(start.add_days(-31), Err(FileTimeError::BeforeEpoch));
+        assert_eq!(start.add_months(-1), Err(FileTimeError::BeforeEpoch));
+        assert_eq!(start.add_years(-1), Err(FileTimeError::BeforeEpoch));
+        assert_eq!(
+            start.add_months(1),
+            FileTime::from_ymd_hms(1601, 2, 28, 12, 0, 0)
+        );
+        assert_eq!(start.add_years(i32::MIN), Err(FileTimeError::BeforeEpoch));
+
+        // The day of FileTime::MAX, 30828-09-14, ends before 02:48:05.4775808
+        let late = FileTime::from_ymd_hms(30828, 8, 14, 2, 0, 0).unwrap();
+        assert_eq!(
+            late.add_months(1),
+            FileTime::from_ymd_hms(30828, 9, 14, 2, 0, 0)
+        );
+        let later = FileTime::from_ymd_hms(30828, 8, 14, 3, 0, 0).unwrap();
+        assert_eq!(later.add_months(1), Err(FileTimeError::Overflow));
+        assert_eq!(later.add_years(1), Err(FileTimeError::Overflow));
+        assert_eq!(later.add_years(i32::MAX), Err(FileTimeError::Overflow));
+        assert!(FileTime::MAX.add_days(-1).is_ok());
+        assert_eq!(
+            FileTime::MAX_RAW.add_days(-1),
+            Err(FileTimeError::OutOfRange("chrono::DateTime"))
+        );
+    }
+
+    #[test]
+    fn truncation_leap_day() {
+        let ft = FileTime::from_ymd_hms_nano(2024, 2, 29, 13, 37, 42, 123_456_700).unwrap();
+        let at = |y, mo, d, h, mi, s| FileTime::from_ymd_hms(y, mo, d, h, mi, s).unwrap();
+        let last = |y, mo, d, h, mi, s| {
+            FileTime::from_ymd_hms_nano(y, mo, d, h, mi, s, 999_999_900).unwrap()
+        };
+        assert_eq!(ft.start_of_minute(), at(2024, 2, 29, 13, 37, 0));
+        assert_eq!(ft.start_of_hour(), at(2024, 2, 29, 13, 0, 0));
+        assert_eq!(ft.start_of_day(), at(2024, 2, 29, 0, 0, 0));
+        assert_eq!(ft.start_of_month(), at(2024, 2, 1, 0, 0, 0));
+        assert_eq!(ft.start_of_year(), at(2024, 1, 1, 0, 0, 0));
+        assert_eq!(ft.end_of_minute(), last(2024, 2, 29, 13, 37, 59));
+        assert_eq!(ft.end_of_hour(), last(2024, 2, 29, 13, 59, 59));
+        assert_eq!(ft.end_of_day(), last(2024, 2, 29, 23, 59, 59));
+        assert_eq!(ft.end_of_month(), last(2024, 2, 29, 23, 59, 59));
+        assert_eq!(ft.end_of_year(), last(2024, 12, 31, 23, 59, 59));
+
+        let ft = FileTime::from_ymd_hms(2023, 2, 10, 0, 0, 0).unwrap();
+        assert_eq!(ft.end_of_month(), last(2023, 2, 28, 23, 59, 59));
+        let ft = FileTime::from_ymd_hms(1900, 2, 10, 0, 0, 0).unwrap();
+        assert_eq!(ft.end_of_month(), last(1900, 2, 28, 23, 59, 59));
+        let ft = FileTime::from_ymd_hms(2023, 12, 31, 12, 0, 0).unwrap();
+        assert_eq!(ft.end_of_month(), last(2023, 12, 31, 23, 59, 59));
+    }
+
+    #[test]
+    fn truncation_midnight() {
+        let before = FileTime::from_ymd_hms_nano(2023, 12, 31, 23, 59, 59, 999_999_900).unwrap();
+        let midnight = FileTime::from_i64(before.filetime() + 1);
+        assert_eq!(before.end_of_day(), before);
+        assert_eq!(before.end_of_year(), before);
+        assert_eq!(
+            before.start_of_day(),
+            FileTime::from_ymd_hms(2023, 12, 31, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            before.start_of_year(),
+            FileTime::from_ymd_hms(2023, 1, 1, 0, 0, 0).unwrap()
+        );
+        for start in [
+            midnight.start_of_minute(),
+            midnight.start_of_hour(),
+            midnight.start_of_day(),
+            midnight.start_of_month(),
+            midnight.start_of_year(),
+        ] {
+            assert_eq!(start, midnight);
+        }
+    }
+
+    #[test]
+    fn truncation_bounds() {
+        for ft in [FileTime::MIN, FileTime::from_i64(1)] {
+            assert_eq!(ft.start_of_year(), FileTime::MIN);
+            assert_eq!(ft.start_of_month(), FileTime::MIN);
+            assert_eq!(ft.start_of_minute(), FileTime::MIN);
+        }
+        assert_eq!(
+            FileTime::MIN.end_of_year(),
+            FileTime::from_ymd_hms_nano(1601, 12, 31, 23, 59, 59, 999_999_900).unwrap()
+        );
+        // 30828-09-14T02:48:05.4775807Z
+        let max = FileTime::MAX;
+        assert_eq!(
+            max.start_of_minute(),
+            FileTime::from_ymd_hms(30828, 9, 14, 2, 48, 0).unwrap()
+        );
+        assert_eq!(
+            max.start_of_year(),
+            FileTime::from_ymd_hms(30828, 1, 1, 0, 0, 0).unwrap()
+        );
+        for end in [
+            max.end_of_minute(),
+            max.end_of_hour(),
+            max.end_of_day(),
+            max.end_of_month(),
+            max.end_of_year(),
+        ] {
+            assert_eq!(end, max);
+        }
+    }
+
+    #[test]
+    fn truncation_above_max() {
+        let ymd = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let hms = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        // 60056-05-28T05:36:10.9551615
+        let max = FileTime::MAX_RAW;
+        let starts = [
+            max.start_of_minute(),
+            max.start_of_hour(),
+            max.start_of_day(),
+            max.start_of_month(),
+            max.start_of_year(),
+        ];
+        let expected = [
+            (ymd(60056, 5, 28), hms(5, 36)),
+            (ymd(60056, 5, 28), hms(5, 0)),
+            (ymd(60056, 5, 28), NaiveTime::MIN),
+            (ymd(60056, 5, 1), NaiveTime::MIN),
+            (ymd(60056, 1, 1), NaiveTime::MIN),
+        ];
+        for (start, (date, time)) in starts.iter().zip(expected.iter()) {
+            assert_eq!((start.date(), start.time()), (*date, *time));
+        }
+        for end in [
+            max.end_of_minute(),
+            max.end_of_hour(),
+            max.end_of_day(),
+            max.end_of_month(),
+            max.end_of_year(),
+        ] {
+            assert_eq!(end, max);
+        }
+
+        // Past MAX, but in the same periods as MAX: ends are no longer clamped to it
+        let next = FileTime::from_u64(FileTime::MAX.as_u64() + 1);
+        assert_eq!(next.start_of_minute(), FileTime::MAX.start_of_minute());
+        assert_eq!(next.start_of_year(), FileTime::MAX.start_of_year());
+        assert_eq!(
+            next.end_of_minute().time(),
+            NaiveTime::from_hms_nano_opt(2, 48, 59, 999_999_900).unwrap()
+        );
+        assert_eq!(next.end_of_year().date(), ymd(30828, 12, 31));
+    }
+
+    #[test]
+    fn truncation_invariants() {
+        type Bounds = fn(&FileTime) -> FileTime;
+        let periods: [(Bounds, Bounds); 5] = [
+            (FileTime::start_of_minute, FileTime::end_of_minute),
+            (FileTime::start_of_hour, FileTime::end_of_hour),
+            (FileTime::start_of_day, FileTime::end_of_day),
+            (FileTime::start_of_month, FileTime::end_of_month),
+            (FileTime::start_of_year, FileTime::end_of_year),
+        ];
+        // A pseudo-random walk over the whole range
+        let mut ticks: u64 = 1;
+        for _ in 0..2000 {
+            ticks = ticks
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let ft = FileTime::from_i64((ticks >> 1) as i64);
+            for (start_fn, end_fn) in &periods {
+                let (start, end) = (start_fn(&ft), end_fn(&ft));
+                assert!(start <= ft && ft <= end, "{:?}", ft);
+                // One tick after the end starts the next period
+                if end < FileTime::MAX {
+                    let next = FileTime::from_i64(end.filetime() + 1);
+                    assert_eq!(start_fn(&next), next, "{:?}", ft);
+                    assert_eq!(start_fn(&end), start, "{:?}", ft);
+                }
+            }
+            assert_eq!(ft.start_of_day().time(), NaiveTime::MIN);
+            assert_eq!(ft.start_of_month().date().day(), 1);
+            assert_eq!(ft.start_of_year().date().ordinal(), 1);
+        }
+    }
+
+    #[test]
+    fn datelike() {
+        for ft in [
+            FileTime::MIN,
+            FileTime::from_i64(130139712831482830),
+            FileTime::from_ymd_hms_nano(2024, 2, 29, 23, 59, 59, 999_999_900).unwrap(),
+            FileTime::from_ymd_hms(2021, 1, 3, 12, 0, 0).unwrap(),
+            FileTime::MAX,
+        ] {
+            let dt = ft.to_datetime();
+            assert_eq!(ft.year(), dt.year());
+            assert_eq!((ft.month(), ft.month0()), (dt.month(), dt.month0()));
+            assert_eq!((ft.day(), ft.day0()), (dt.day(), dt.day0()));
+            assert_eq!((ft.ordinal(), ft.ordinal0()), (dt.ordinal(), dt.ordinal0()));
+            assert_eq!(ft.weekday(), dt.weekday());
+            assert_eq!(ft.iso_week(), dt.iso_week());
+            assert_eq!(ft.num_days_from_ce(), dt.num_days_from_ce());
+        }
+        // Sunday, still in ISO week 53 of 2020
+        let ft = FileTime::from_ymd_hms(2021, 1, 3, 12, 0, 0).unwrap();
+        assert_eq!((ft.iso_week().year(), ft.iso_week().week()), (2020, 53));
+        assert_eq!(FileTime::MIN.weekday(), Weekday::Mon);
+    }
+
+    #[test]
+    fn datelike_setters() {
+        let ft = FileTime::from_ymd_hms_nano(2024, 1, 31, 13, 37, 42, 123_456_700).unwrap();
+        let at = |y, mo, d| FileTime::from_ymd_hms_nano(y, mo, d, 13, 37, 42, 123_456_700).ok();
+        assert_eq!(ft.with_year(2000), at(2000, 1, 31));
+        assert_eq!(ft.with_month(3), at(2024, 3, 31));
+        assert_eq!(ft.with_month0(2), at(2024, 3, 31));
+        assert_eq!(ft.with_day(1), at(2024, 1, 1));
+        assert_eq!(ft.with_day0(0), at(2024, 1, 1));
+        assert_eq!(ft.with_ordinal(366), at(2024, 12, 31));
+        assert_eq!(ft.with_ordinal0(59), at(2024, 2, 29));
+
+        // Invalid dates
+        assert_eq!(ft.with_month(2), None);
+        assert_eq!(ft.with_month(13), None);
+        assert_eq!(ft.with_day(32), None);
+        assert_eq!(ft.with_year(2023).unwrap().with_ordinal(366), None);
+        // Outside of the FILETIME range
+        assert_eq!(ft.with_year(1600), None);
+        assert_eq!(ft.with_year(30829), None);
+        assert_eq!(ft.with_year(30828).and_then(|ft| ft.with_month(10)), None);
+        assert_eq!(FileTime::MAX.with_day(15), None);
+        assert_eq!(FileTime::MIN.with_year(1601), Some(FileTime::MIN));
+    }
+
+    #[test]
+    fn timelike() {
+        for ft in [
+            FileTime::MIN,
+            FileTime::from_i64(130139712831482830),
+            FileTime::from_i64(130139712839999999),
+            FileTime::MAX,
+        ] {
+            let dt = ft.to_datetime();
+            assert_eq!(
+                (ft.hour(), ft.minute(), ft.second(), ft.nanosecond()),
+                (dt.hour(), dt.minute(), dt.second(), dt.nanosecond())
+            );
+            assert_eq!(
+                ft.num_seconds_from_midnight(),
+                dt.num_seconds_from_midnight()
+            );
+            assert_eq!(i64::from(ft.nanosecond()), ft.nanoseconds());
+        }
+        assert_eq!(
+            FileTime::from_i64(130139712839999999).nanosecond(),
+            999_999_900
+        );
+
+        let ft = FileTime::from_ymd_hms_nano(2023, 12, 31, 23, 59, 59, 999_999_900).unwrap();
+        let at = |h, mi, s, n| FileTime::from_ymd_hms_nano(2023, 12, 31, h, mi, s, n).ok();
+        assert_eq!(ft.with_hour(0), at(0, 59, 59, 999_999_900));
+        assert_eq!(ft.with_minute(0), at(23, 0, 59, 999_999_900));
+        assert_eq!(ft.with_second(0), at(23, 59, 0, 999_999_900));
+        assert_eq!(ft.with_nanosecond(123_456_789), at(23, 59, 59, 123_456_700));
+        assert_eq!(ft.with_hour(24), None);
+        assert_eq!(ft.with_minute(60), None);
+        assert_eq!(ft.with_second(60), None);
+        assert_eq!(ft.with_nanosecond(1_000_000_000), None);
+        assert_eq!(ft.with_nanosecond(1_999_999_999), None);
+        assert_eq!(FileTime::MAX.with_hour(3), None);
+        assert_eq!(
+            FileTime::MAX.with_nanosecond(0).map(|ft| ft.second()),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn matches_chrono() {
+        let ft = FileTime::from_ymd_hms_nano(2013, 5, 25, 16, 1, 23, 148_283_099).unwrap();
+        let dt = Utc
+            .with_ymd_and_hms(2013, 5, 25, 16, 1, 23)
+            .unwrap()
+            .with_nanosecond(148_283_099)
+            .unwrap();
+        assert_eq!(ft, FileTime::from_datetime(dt));
+        assert_eq!(ft.filetime(), 130139712831482830);
+
+        // Every first of the month over four centuries
+        for year in 1601..2001 {
+            for month in 1..=12 {
+                let dt = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).unwrap();
+                assert_eq!(
+                    FileTime::from_ymd_hms(year, month, 1, 0, 0, 0),
+                    Ok(FileTime::from_datetime(dt))
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,353 @@
+//! OLE compound file (CFBF) directory entry timestamps
+//!
+//! Legacy Office documents, MSI packages and `thumbs.db` files are compound
+//! files. Their directory stream is an array of 128 byte entries ([MS-CFB] 2.6):
+//!
+//! ```text
+//! 0x00  [u16; 32]  Name, UTF-16LE, null terminated
+//! 0x40  u16        Name length in bytes, including the terminator
+//! 0x42  u8         Object type: 0 unallocated, 1 storage, 2 stream, 5 root
+//! ...
+//! 0x64  FILETIME   Creation time
+//! 0x6C  FILETIME   Modified time
+//! ...
+//! ```
+//!
+//! Streams and often the root entry leave both timestamps zero.
+use crate::{FileTime, FileTimeError};
+use std::iter::FusedIterator;
+
+const FORMAT: &str = "CFBF directory entry";
+const CREATED: usize = 0x64;
+const MODIFIED: usize = 0x6C;
+
+/// Size of a directory entry
+pub const ENTRY_SIZE: usize = 128;
+
+fn read_time(entry: &[u8], offset: usize) -> Result<Option<FileTime>, FileTimeError> {
+    let mut raw = [0u8; 8];
+    raw.copy_from_slice(&entry[offset..offset + 8]);
+    match i64::from_le_bytes(raw) {
+        0 => Ok(None),
+        ticks => {
+            FileTime::from_i64_checked(ticks)
+                .map(Some)
+                .map_err(|_| FileTimeError::Malformed {
+                    format: FORMAT,
+                    offset,
+                    reason: "negative FILETIME",
+                })
+        }
+    }
+}
+
+/// Parse the creation and modified times of a directory entry, `None` where zero
+///
+/// Fails with [`FileTimeError::InvalidLength`] unless `entry` is exactly
+/// [`ENTRY_SIZE`] bytes and with [`FileTimeError::Malformed`] for negative values.
+/// ```
+/// use filetime_type::cfbf::parse_directory_entry_times;
+///
+/// let mut entry = [0u8; 128];
+/// entry[0x6C..0x74].copy_from_slice(&132601876135897932i64.to_le_bytes());
+/// let (created, modified) = parse_directory_entry_times(&entry).unwrap();
+/// assert_eq!(created, None);
+/// assert_eq!(modified.unwrap().filetime(), 132601876135897932);
+/// ```
+pub fn parse_directory_entry_times(
+    entry: &[u8],
+) -> Result<(Option<FileTime>, Option<FileTime>), FileTimeError> {
+    if entry.len() != ENTRY_SIZE {
+        return Err(FileTimeError::InvalidLength {
+            format: FORMAT,
+            expected: ENTRY_SIZE,
+            actual: entry.len(),
+        });
+    }
+    Ok((read_time(entry, CREATED)?, read_time(entry, MODIFIED)?))
+}
+
+fn parse_name(entry: &[u8]) -> Result<String, FileTimeError> {
+    let malformed = |offset, reason| FileTimeError::Malformed {
+        format: FORMAT,
+        offset,
+        reason,
+    };
+    let len = usize::from(u16::from_le_bytes([entry[0x40], entry[0x41]]));
+    if len > 64 || len % 2 != 0 {
+        return Err(malformed(0x40, "invalid name length"));
+    }
+    let units: Vec<u16> = entry[..len]
+        .chunks_exact(2)
+        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+        .collect();
+    let units = match units.split_last() {
+        Some((0, name)) => name,
+        Some(_) => return Err(malformed(0, "name is not null terminated")),
+        None => &[],
+    };
+    String::from_utf16(units).map_err(|_| malformed(0, "name is not valid UTF-16"))
+}
+
+/// Iterator over the allocated entries of a directory stream, see [`directory_times`]
+#[derive(Debug, Clone)]
+pub struct DirectoryTimes<'a> {
+    stream: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Iterator for DirectoryTimes<'a> {
+    type Item = Result<(String, Option<FileTime>, Option<FileTime>), FileTimeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.offset < self.stream.len() {
+            let base = self.offset;
+            let entry = match self.stream.get(base..base + ENTRY_SIZE) {
+                Some(entry) => entry,
+                None => {
+                    self.offset = self.stream.len();
+                    return Some(Err(FileTimeError::Malformed {
+                        format: FORMAT,
+                        offset: base,
+                        reason: "truncated directory entry",
+                    }));
+                }
+            };
+            self.offset += ENTRY_SIZE;
+            if entry[0x42] == 0 {
+                continue;
+            }
+            let parsed = parse_name(entry).and_then(|name| {
+                let (created, modified) = parse_directory_entry_times(entry)?;
+                Ok((name, created, modified))
+            });
+            if parsed.is_err() {
+                self.offset = self.stream.len();
+            }
+            return Some(parsed.map_err(|err| match err {
+                FileTimeError::Malformed {
+                    format,
+                    offset,
+                    reason,
+                } => FileTimeError::Malformed {
+                    format,
+                    offset: base + offset,
+                    reason,
+                },
+                err => err,
+            }));
+        }
+        None
+    }
+}
+
+impl FusedIterator for DirectoryTimes<'_> {}
+
+/// Walk a directory stream, yielding `(name, created, modified)` per allocated entry
+///
+/// Unallocated entries (object type 0) are skipped. Error offsets are
+/// relative to the start of `stream`, iteration stops after the first error.
+/// A trailing partial entry yields [`FileTimeError::Malformed`].
+/// ```
+/// use filetime_type::cfbf::directory_times;
+///
+/// let mut stream = vec![0u8; 256];
+/// for (i, unit) in "Root Entry\0".encode_utf16().enumerate() {
+///     stream[i * 2..i * 2 + 2].copy_from_slice(&unit.to_le_bytes());
+/// }
+/// stream[0x40] = 22;
+/// stream[0x42] = 5;
+/// let entries: Vec<_> = directory_times(&stream).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(entries, vec![("Root Entry".to_string(), None, None)]);
+/// ```
+pub fn directory_times(stream: &[u8]) -> DirectoryTimes<'_> {
+    DirectoryTimes { stream, offset: 0 }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The first five entries of the directory stream of `assets/sample.doc`
+    // from the ole crate 0.1.15 (sha256 2ebe9170...49e3b05a), a Word 97-2003
+    // document with an embedded Excel workbook saved 2003-02-10. Stream
+    // starts at sector 23, file offset 0x3000. The two storages have both
+    // times, the root entry only a modified time and streams none.
+    const DOC_DIRECTORY: [u8; 640] = [
+        0x52, 0x00, 0x6f, 0x00, 0x6f, 0x00, 0x74, 0x00, 0x20, 0x00, 0x45, 0x00, 0x6e, 0x00, 0x74,
+        0x00, 0x72, 0x00, 0x79, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x16, 0x00, 0x05, 0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0x0d, 0x00, 0x00, 0x00, 0x06, 0x09, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x46, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x60, 0x64, 0xda, 0x80, 0x45, 0xd1, 0xc2, 0x01, 0x1a, 0x00, 0x00, 0x00,
+        0xc0, 0x16, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x44, 0x00, 0x61, 0x00, 0x74, 0x00, 0x61,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0a, 0x00, 0x02,
+        0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x0e, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x57, 0x00, 0x6f, 0x00, 0x72, 0x00, 0x64, 0x00, 0x44, 0x00, 0x6f, 0x00, 0x63, 0x00,
+        0x75, 0x00, 0x6d, 0x00, 0x65, 0x00, 0x6e, 0x00, 0x74, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x1a, 0x00, 0x02, 0x01, 0x0c, 0x00, 0x00, 0x00, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x22, 0x1a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4f, 0x00, 0x62, 0x00, 0x6a, 0x00,
+        0x65, 0x00, 0x63, 0x00, 0x74, 0x00, 0x50, 0x00, 0x6f, 0x00, 0x6f, 0x00, 0x6c, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x16, 0x00,
+        0x01, 0x01, 0x0f, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x04, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x80, 0xfc, 0xa4, 0x80, 0x45, 0xd1, 0xc2, 0x01, 0x60, 0x64, 0xda,
+        0x80, 0x45, 0xd1, 0xc2, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x5f, 0x00, 0x31, 0x00, 0x31, 0x00, 0x30, 0x00, 0x36, 0x00, 0x33, 0x00, 0x39,
+        0x00, 0x36, 0x00, 0x34, 0x00, 0x37, 0x00, 0x31, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x18, 0x00, 0x01, 0x01, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0x06, 0x00, 0x00, 0x00, 0x20, 0x08, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46, 0x00, 0x00, 0x00, 0x00, 0x80, 0xfc, 0xa4,
+        0x80, 0x45, 0xd1, 0xc2, 0x01, 0xc0, 0x09, 0xa8, 0x80, 0x45, 0xd1, 0xc2, 0x01, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    // An unallocated entry of the directory stream of `assets/sample.ppt` from
+    // the same crate (sha256 7adabed3...8e358ef4), entry 6
+    const UNALLOCATED: [u8; 128] = [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    /// 2003-02-10T20:46:36.454Z
+    const ROOT_MODIFIED: i64 = 126893835964540000;
+    /// 2003-02-10T20:46:36.104Z
+    const OBJECT_POOL_CREATED: i64 = 126893835961040000;
+
+    fn doc_stream() -> Vec<u8> {
+        [&DOC_DIRECTORY[..], &UNALLOCATED, &UNALLOCATED, &UNALLOCATED].concat()
+    }
+
+    #[test]
+    fn entry_times() {
+        let entry = |i: usize| &DOC_DIRECTORY[i * ENTRY_SIZE..(i + 1) * ENTRY_SIZE];
+        assert_eq!(
+            parse_directory_entry_times(entry(0)),
+            Ok((None, Some(FileTime::from_i64(ROOT_MODIFIED))))
+        );
+        assert_eq!(
+            parse_directory_entry_times(entry(3)),
+            Ok((
+                Some(FileTime::from_i64(OBJECT_POOL_CREATED)),
+                Some(FileTime::from_i64(ROOT_MODIFIED))
+            ))
+        );
+        assert_eq!(parse_directory_entry_times(entry(1)), Ok((None, None)));
+        assert_eq!(parse_directory_entry_times(&UNALLOCATED), Ok((None, None)));
+        assert_eq!(
+            parse_directory_entry_times(&DOC_DIRECTORY[..ENTRY_SIZE - 1]),
+            Err(FileTimeError::InvalidLength {
+                format: FORMAT,
+                expected: ENTRY_SIZE,
+                actual: ENTRY_SIZE - 1,
+            })
+        );
+        let mut negative = entry(3).to_vec();
+        negative[CREATED + 7] |= 0x80;
+        assert_eq!(
+            parse_directory_entry_times(&negative),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: CREATED,
+                reason: "negative FILETIME",
+            })
+        );
+    }
+
+    #[test]
+    fn walk() {
+        let created = Some(FileTime::from_i64(OBJECT_POOL_CREATED));
+        let modified = Some(FileTime::from_i64(ROOT_MODIFIED));
+        let entries: Vec<_> = directory_times(&doc_stream())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                ("Root Entry".to_string(), None, modified),
+                ("Data".to_string(), None, None),
+                ("WordDocument".to_string(), None, None),
+                ("ObjectPool".to_string(), created, modified),
+                (
+                    "_1106396471".to_string(),
+                    created,
+                    // 2003-02-10T20:46:36.124Z
+                    Some(FileTime::from_i64(126893835961240000))
+                ),
+            ]
+        );
+        assert_eq!(directory_times(&[]).count(), 0);
+    }
+
+    #[test]
+    fn walk_errors() {
+        let malformed = |offset, reason| {
+            Some(Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset,
+                reason,
+            }))
+        };
+
+        let mut stream = doc_stream();
+        stream.truncate(ENTRY_SIZE * 8 - 1);
+        assert_eq!(directory_times(&stream).filter(Result::is_ok).count(), 5);
+        let mut iter = directory_times(&stream).skip(5);
+        assert_eq!(
+            iter.next(),
+            malformed(ENTRY_SIZE * 7, "truncated directory entry")
+        );
+        assert_eq!(iter.next(), None);
+
+        let mut stream = doc_stream();
+        stream[ENTRY_SIZE + MODIFIED + 7] = 0x80;
+        let mut iter = directory_times(&stream).skip(1);
+        assert_eq!(
+            iter.next(),
+            malformed(ENTRY_SIZE + MODIFIED, "negative FILETIME")
+        );
+        assert_eq!(iter.next(), None);
+
+        let mut stream = doc_stream();
+        stream[ENTRY_SIZE * 2 + 0x40] = 65;
+        assert_eq!(
+            directory_times(&stream).nth(2),
+            malformed(ENTRY_SIZE * 2 + 0x40, "invalid name length")
+        );
+        stream[ENTRY_SIZE * 2 + 0x40] = 4;
+        assert_eq!(
+            directory_times(&stream).nth(2),
+            malformed(ENTRY_SIZE * 2, "name is not null terminated")
+        );
+        // Unpaired surrogate
+        stream[ENTRY_SIZE * 2..ENTRY_SIZE * 2 + 4].copy_from_slice(&[0x00, 0xd8, 0x00, 0x00]);
+        assert_eq!(
+            directory_times(&stream).nth(2),
+            malformed(ENTRY_SIZE * 2, "name is not valid UTF-16")
+        );
+    }
+}
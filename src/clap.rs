@@ -0,0 +1,93 @@
+//! clap value parser, so `#[arg(long)] since: FileTime` works
+use crate::{FileTime, FileTimeError};
+use ::clap::builder::{TypedValueParser, ValueParserFactory};
+use std::ffi::OsStr;
+
+/// Parses command line arguments via [`FileTime`]'s `FromStr`
+///
+/// Accepts raw decimal ticks, `0x`-prefixed hex ticks and RFC 3339 timestamps.
+/// Rejected values produce clap's usual `invalid value` error listing these formats.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileTimeValueParser;
+
+impl TypedValueParser for FileTimeValueParser {
+    type Value = FileTime;
+
+    fn parse_ref(
+        &self,
+        cmd: &::clap::Command,
+        arg: Option<&::clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, ::clap::Error> {
+        let parse: fn(&str) -> Result<FileTime, FileTimeError> = str::parse;
+        parse.parse_ref(cmd, arg, value)
+    }
+}
+
+impl ValueParserFactory for FileTime {
+    type Parser = FileTimeValueParser;
+
+    fn value_parser() -> Self::Parser {
+        FileTimeValueParser
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::clap::Parser;
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[arg(long)]
+        since: FileTime,
+        #[arg(long)]
+        until: Option<FileTime>,
+    }
+
+    fn since(value: &str) -> Result<FileTime, ::clap::Error> {
+        Cli::try_parse_from(["tool", "--since", value]).map(|cli| cli.since)
+    }
+
+    #[test]
+    fn accepted() {
+        let ft = FileTime::from_i64(130139712831482830);
+        assert_eq!(since("130139712831482830").unwrap(), ft);
+        assert_eq!(since("0x01CE59611A7DEBCE").unwrap(), ft);
+        assert_eq!(since("2013-05-25T16:01:23.148283Z").unwrap(), ft);
+
+        let cli = Cli::try_parse_from(["tool", "--since", "0", "--until", "0x1"]).unwrap();
+        assert_eq!(cli.until, Some(FileTime::from_i64(1)));
+    }
+
+    #[test]
+    fn rejected() {
+        let err = since("2023-01-01").unwrap_err();
+        assert_eq!(err.kind(), ::clap::error::ErrorKind::ValueValidation);
+        let text = err.to_string();
+        assert!(
+            text.contains("invalid value '2023-01-01' for '--since <SINCE>'"),
+            "{}",
+            text
+        );
+        assert!(
+            text.contains("expected decimal ticks, 0x-prefixed hex ticks or an RFC 3339 timestamp"),
+            "{}",
+            text
+        );
+    }
+
+    #[test]
+    fn builder() {
+        let cmd = ::clap::Command::new("tool").arg(
+            ::clap::Arg::new("since")
+                .long("since")
+                .value_parser(::clap::value_parser!(FileTime)),
+        );
+        let matches = cmd.try_get_matches_from(["tool", "--since", "42"]).unwrap();
+        assert_eq!(
+            matches.get_one::<FileTime>("since"),
+            Some(&FileTime::from_i64(42))
+        );
+    }
+}
@@ -0,0 +1,436 @@
+//! Injectable time sources
+//!
+//! [`FileTime::now()`] always reads the system clock. Code that takes a
+//! [`Clock`] instead can be driven by a [`FixedClock`] or [`SteppingClock`]
+//! in tests.
+use crate::{FileTime, FileTimeDelta};
+use core::cell::Cell;
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+#[cfg(feature = "std")]
+use std::thread::{self, Thread};
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+/// Source of the current time
+pub trait Clock {
+    /// The current time according to this clock
+    fn now_filetime(&self) -> FileTime;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now_filetime(&self) -> FileTime {
+        (**self).now_filetime()
+    }
+}
+
+/// The system clock, as read by [`FileTime::now()`]
+#[cfg(feature = "std")]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now_filetime(&self) -> FileTime {
+        FileTime::now()
+    }
+}
+
+/// The cached system clock, as read by [`FileTime::now_coarse()`]
+#[cfg(feature = "std")]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Default)]
+pub struct CoarseClock;
+
+#[cfg(feature = "std")]
+impl Clock for CoarseClock {
+    fn now_filetime(&self) -> FileTime {
+        FileTime::now_coarse()
+    }
+}
+
+/// Ticks last stored by the coarse clock updater
+#[cfg(feature = "std")]
+static COARSE_TICKS: AtomicU64 = AtomicU64::new(0);
+/// Refresh interval of the updater in microseconds
+#[cfg(feature = "std")]
+static COARSE_INTERVAL_MICROS: AtomicU64 = AtomicU64::new(1000);
+/// The updater thread, `None` if it could not be started
+#[cfg(feature = "std")]
+static COARSE_UPDATER: OnceLock<Option<Thread>> = OnceLock::new();
+
+#[cfg(feature = "std")]
+fn coarse_updater() -> Option<&'static Thread> {
+    COARSE_UPDATER
+        .get_or_init(|| {
+            COARSE_TICKS.store(FileTime::now().as_u64(), Ordering::Relaxed);
+            thread::Builder::new()
+                .name("filetime-coarse-clock".into())
+                .spawn(|| loop {
+                    let micros = COARSE_INTERVAL_MICROS.load(Ordering::Relaxed);
+                    thread::park_timeout(Duration::from_micros(micros));
+                    COARSE_TICKS.store(FileTime::now().as_u64(), Ordering::Relaxed);
+                })
+                .ok()
+                .map(|handle| handle.thread().clone())
+        })
+        .as_ref()
+}
+
+/// Clock frozen at a single instant
+/// ```
+/// use filetime_type::{FileTime, FixedClock};
+///
+/// let clock = FixedClock(FileTime::from_i64(130139712831482830));
+/// assert_eq!(FileTime::now_with(&clock), FileTime::from_i64(130139712831482830));
+/// assert_eq!(FileTime::now_with(&clock), FileTime::from_i64(130139712831482830));
+/// ```
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct FixedClock(pub FileTime);
+
+impl Clock for FixedClock {
+    fn now_filetime(&self) -> FileTime {
+        self.0
+    }
+}
+
+/// Clock advancing by a fixed step on every reading
+///
+/// The first reading returns the start. Readings saturate at [`FileTime::MIN`]
+/// and [`FileTime::MAX`]. Not `Sync`, use one clock per thread.
+/// ```
+/// use filetime_type::{FileTime, FileTimeDelta, SteppingClock};
+///
+/// let clock = SteppingClock::new(FileTime::from_i64(1000), FileTimeDelta::from_ticks(10));
+/// assert_eq!(FileTime::now_with(&clock).filetime(), 1000);
+/// assert_eq!(FileTime::now_with(&clock).filetime(), 1010);
+/// assert_eq!(FileTime::now_with(&clock).filetime(), 1020);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SteppingClock {
+    next: Cell<FileTime>,
+    step: FileTimeDelta,
+}
+
+impl SteppingClock {
+    /// Clock starting at `start`, advancing by `step` per reading
+    pub fn new(start: FileTime, step: FileTimeDelta) -> Self {
+        Self {
+            next: Cell::new(start),
+            step,
+        }
+    }
+
+    /// The instant the next reading will return, without advancing
+    pub fn peek(&self) -> FileTime {
+        self.next.get()
+    }
+}
+
+impl Clock for SteppingClock {
+    fn now_filetime(&self) -> FileTime {
+        let now = self.next.get();
+        // Stay within the dated range even though arithmetic goes beyond it
+        let next = match now.checked_add(self.step) {
+            Some(next) if next <= FileTime::MAX || self.step.as_ticks() < 0 => next,
+            _ if self.step.as_ticks() < 0 => FileTime::MIN,
+            _ => FileTime::MAX,
+        };
+        self.next.set(next);
+        now
+    }
+}
+
+/// Clock wrapper whose readings strictly increase, even if the inner clock steps back
+///
+/// Every reading is the later of the inner clock's time and the previous
+/// reading plus one tick, so concurrent readers never see the same value
+/// twice. After the inner clock steps backwards (e.g. an NTP correction) the
+/// readings run ahead of it, one tick per reading, until it catches up. The
+/// guarantee only covers readings through the same `MonotonicClock`. Once
+/// a reading reaches [`FileTime::MAX_RAW`] the following ones stay there.
+/// ```
+/// use filetime_type::{Clock, FileTime, FileTimeDelta, MonotonicClock, SteppingClock};
+///
+/// // A clock going back 10 ticks per reading
+/// let inner = SteppingClock::new(FileTime::from_i64(1000), FileTimeDelta::from_ticks(-10));
+/// let clock = MonotonicClock::new(inner);
+/// assert_eq!(clock.now_filetime().filetime(), 1000);
+/// assert_eq!(clock.now_filetime().filetime(), 1001);
+/// assert_eq!(clock.now_filetime().filetime(), 1002);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct MonotonicClock<C> {
+    inner: C,
+    /// Lowest tick count of the next reading, one past the previous one
+    next: AtomicU64,
+}
+
+#[cfg(feature = "std")]
+impl<C> MonotonicClock<C> {
+    /// Wrap `inner`, the first reading is its time unchanged
+    pub const fn new(inner: C) -> Self {
+        Self {
+            inner,
+            next: AtomicU64::new(0),
+        }
+    }
+
+    /// The wrapped clock
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C: Clock> Clock for MonotonicClock<C> {
+    fn now_filetime(&self) -> FileTime {
+        let mut next = self.next.load(Ordering::Relaxed);
+        loop {
+            let reading = next.max(self.inner.now_filetime().as_u64());
+            match self.next.compare_exchange_weak(
+                next,
+                reading.saturating_add(1),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return FileTime::from_u64(reading),
+                Err(current) => next = current,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+static MONOTONIC: MonotonicClock<SystemClock> = MonotonicClock::new(SystemClock);
+
+impl FileTime {
+    /// The current system time, strictly later than any previous call in this process
+    ///
+    /// Suited for FILETIME based identifiers: values are unique across
+    /// threads and never go backwards, even if the system clock is stepped
+    /// back. The price is that after such a step the result runs ahead of the
+    /// wall clock until it catches up, see [`MonotonicClock`]. The guarantee
+    /// is process-local, other processes keep their own last value.
+    #[cfg(feature = "std")]
+    pub fn now_monotonic() -> Self {
+        MONOTONIC.now_filetime()
+    }
+
+    /// The current system time, cached and refreshed in the background
+    ///
+    /// For hot paths such as logging: a reading is a single atomic load. The
+    /// first call starts a thread that stores [`FileTime::now()`] every
+    /// [`coarse interval`](Self::set_coarse_interval), 1ms by default, so a
+    /// reading lags the system clock by at most the interval plus the time
+    /// the thread waits to be scheduled; under heavy load that can be longer.
+    /// Consecutive readings are often equal. Unless the system clock is set
+    /// back, they never run ahead of [`FileTime::now()`]. Where no thread can be started, e.g. on
+    /// `wasm32-unknown-unknown`, every call falls back to [`FileTime::now()`].
+    #[cfg(feature = "std")]
+    pub fn now_coarse() -> Self {
+        match coarse_updater() {
+            Some(_) => FileTime::from_u64(COARSE_TICKS.load(Ordering::Relaxed)),
+            None => FileTime::now(),
+        }
+    }
+
+    /// Set how often [`FileTime::now_coarse()`] is refreshed, at least every 100µs
+    ///
+    /// Applies to the whole process and takes effect at once, the cached
+    /// time is refreshed on the call. Shorter intervals mean fresher
+    /// readings but more wakeups of the updater thread.
+    #[cfg(feature = "std")]
+    pub fn set_coarse_interval(interval: Duration) {
+        let micros = interval.as_micros().clamp(100, u128::from(u64::MAX)) as u64;
+        COARSE_INTERVAL_MICROS.store(micros, Ordering::Relaxed);
+        if let Some(updater) = coarse_updater() {
+            updater.unpark();
+        }
+    }
+
+    /// The current time according to `clock`
+    pub fn now_with<C: Clock + ?Sized>(clock: &C) -> Self {
+        clock.now_filetime()
+    }
+
+    /// Time passed since this instant, negative if it lies in the future
+    #[cfg(feature = "std")]
+    pub fn elapsed(&self) -> FileTimeDelta {
+        self.elapsed_with(&SystemClock)
+    }
+
+    /// Time passed since this instant according to `clock`
+    ///
+    /// Saturates like [`FileTime::saturating_delta_since`] for raw values
+    /// above [`FileTime::MAX`].
+    /// ```
+    /// use filetime_type::{FileTime, FileTimeDelta, FixedClock};
+    ///
+    /// let clock = FixedClock(FileTime::from_i64(130139712831482830));
+    /// let ft = FileTime::from_i64(130139712831482830 - 50_000_000);
+    /// assert_eq!(ft.elapsed_with(&clock), FileTimeDelta::from_secs(5).unwrap());
+    /// ```
+    pub fn elapsed_with<C: Clock + ?Sized>(&self, clock: &C) -> FileTimeDelta {
+        clock.now_filetime().saturating_delta_since(*self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fixed() {
+        let ft = FileTime::from_i64(132601876135897932);
+        let clock = FixedClock(ft);
+        assert_eq!(FileTime::now_with(&clock), ft);
+        assert_eq!(ft.elapsed_with(&clock), FileTimeDelta::ZERO);
+        assert_eq!(
+            FileTime::MIN.elapsed_with(&clock),
+            FileTimeDelta::from_ticks(132601876135897932)
+        );
+        assert_eq!(
+            FileTime::MAX.elapsed_with(&clock),
+            FileTimeDelta::from_ticks(132601876135897932 - i64::MAX)
+        );
+        // Through a trait object
+        let dynamic: &dyn Clock = &clock;
+        assert_eq!(FileTime::now_with(dynamic), ft);
+    }
+
+    #[test]
+    fn stepping() {
+        let ft = FileTime::from_i64(132601876135897932);
+        let clock = SteppingClock::new(ft, FileTimeDelta::from_secs(1).unwrap());
+        let first = FileTime::now_with(&clock);
+        assert_eq!(first, ft);
+        assert_eq!(clock.peek(), FileTime::from_i64(132601876145897932));
+        assert_eq!(
+            first.elapsed_with(&clock),
+            FileTimeDelta::from_secs(1).unwrap()
+        );
+        assert_eq!(
+            first.elapsed_with(&clock),
+            FileTimeDelta::from_secs(2).unwrap()
+        );
+
+        let clock = SteppingClock::new(FileTime::from_i64(5), FileTimeDelta::from_ticks(-3));
+        let readings: [i64; 4] = core::array::from_fn(|_| clock.now_filetime().filetime());
+        assert_eq!(readings, [5, 2, 0, 0]);
+
+        let clock = SteppingClock::new(FileTime::MAX, FileTimeDelta::from_ticks(1));
+        assert_eq!(clock.now_filetime(), FileTime::MAX);
+        assert_eq!(clock.now_filetime(), FileTime::MAX);
+    }
+
+    /// Replays the given readings, then keeps returning the last one
+    #[cfg(feature = "std")]
+    struct ScriptedClock<'a>(Cell<&'a [i64]>);
+
+    #[cfg(feature = "std")]
+    impl Clock for ScriptedClock<'_> {
+        fn now_filetime(&self) -> FileTime {
+            let readings = self.0.get();
+            if readings.len() > 1 {
+                self.0.set(&readings[1..]);
+            }
+            FileTime::from_i64(readings[0])
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn monotonic_backwards_step() {
+        const S: i64 = 10_000_000;
+        let t = 132601876135897932;
+        // The system clock is set back an hour after the second reading
+        let system = [t, t + S, t + S - 3600 * S, t + S - 3599 * S, t + 2 * S];
+        let clock = MonotonicClock::new(ScriptedClock(Cell::new(&system)));
+        let readings: Vec<i64> = (0..5).map(|_| clock.now_filetime().filetime()).collect();
+        assert_eq!(readings, [t, t + S, t + S + 1, t + S + 2, t + 2 * S]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn monotonic_frozen() {
+        let clock = MonotonicClock::new(FixedClock(FileTime::from_i64(100)));
+        let readings: Vec<i64> = (0..3).map(|_| clock.now_filetime().filetime()).collect();
+        assert_eq!(readings, [100, 101, 102]);
+
+        let clock = MonotonicClock::new(FixedClock(FileTime::MIN));
+        assert_eq!(clock.now_filetime(), FileTime::MIN);
+        assert_eq!(clock.now_filetime(), FileTime::from_u64(1));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn monotonic_high_range() {
+        let clock = MonotonicClock::new(FixedClock(FileTime::MAX));
+        assert_eq!(clock.now_filetime(), FileTime::MAX);
+        assert_eq!(clock.now_filetime(), FileTime::from_u64(1 << 63));
+
+        let clock = MonotonicClock::new(FixedClock(FileTime::MAX_RAW));
+        assert_eq!(clock.now_filetime(), FileTime::MAX_RAW);
+        assert_eq!(clock.now_filetime(), FileTime::MAX_RAW);
+
+        let clock = MonotonicClock::new(FixedClock(FileTime::from_u64(u64::MAX - 1)));
+        let readings: Vec<u64> = (0..3).map(|_| clock.now_filetime().as_u64()).collect();
+        assert_eq!(readings, [u64::MAX - 1, u64::MAX, u64::MAX]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn monotonic_threads() {
+        use std::collections::HashSet;
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    let readings: Vec<FileTime> =
+                        (0..1000).map(|_| FileTime::now_monotonic()).collect();
+                    assert!(readings.windows(2).all(|pair| pair[0] < pair[1]));
+                    readings
+                })
+            })
+            .collect();
+        let mut seen = HashSet::new();
+        for handle in handles {
+            for ft in handle.join().unwrap() {
+                assert!(seen.insert(ft), "duplicate {:?}", ft);
+            }
+        }
+        assert_eq!(seen.len(), 8000);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn system() {
+        let before = FileTime::now();
+        let now = FileTime::now_with(&SystemClock);
+        assert!(before <= now && now <= FileTime::now());
+        assert!(now.elapsed() >= FileTimeDelta::ZERO);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn coarse_staleness() {
+        let interval = Duration::from_millis(2);
+        FileTime::set_coarse_interval(interval);
+        // 2ms plus 50ms for the updater waiting to be scheduled on a busy machine
+        let bound = FileTimeDelta::from_ticks(52 * 10_000);
+        let first = FileTime::now_coarse();
+        let mut latest = first;
+        for _ in 0..200 {
+            let coarse = FileTime::now_with(&CoarseClock);
+            let now = FileTime::now();
+            assert!(coarse <= now, "{:?} ahead of {:?}", coarse, now);
+            assert!(now - coarse <= bound, "stale by {}", now - coarse);
+            latest = coarse;
+            thread::sleep(Duration::from_micros(500));
+        }
+        assert!(latest > first, "cache never refreshed");
+    }
+}
@@ -0,0 +1,150 @@
+//! defmt `Format` for FileTime, for logging from embedded targets
+//!
+//! Logs as `FileTime(2013-05-25T16:01:23.1482830Z, raw=130139712831482830)`.
+//! The calendar fields are computed on the target with integer arithmetic
+//! only, neither chrono nor alloc is involved, so the host shows a readable
+//! instant without decoding the raw value by hand. Raw values above
+//! [`FileTime::MAX`] log as `FileTime(no date, raw=18446744073709551615)`.
+use crate::FileTime;
+
+const TICKS_PER_SECOND: u64 = FileTime::HUNDREDS_OF_NANOSECONDS as u64;
+const SECONDS_PER_DAY: u64 = 86_400;
+/// Days from 0000-03-01 (the start of the proleptic Gregorian era) to 1601-01-01
+const DAYS_TO_EPOCH: i64 = 584_694;
+
+/// UTC calendar fields of a FileTime
+#[derive(Debug, Eq, PartialEq)]
+struct Fields {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    /// Ticks within the second, 0 to 9_999_999
+    ticks: u32,
+}
+
+/// Date of the given day since 1601-01-01, after Howard Hinnant's `civil_from_days`
+const fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + DAYS_TO_EPOCH;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // Months counted from March
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month as u8, day as u8)
+}
+
+const fn fields(ft: FileTime) -> Fields {
+    let seconds = ft.ticks / TICKS_PER_SECOND;
+    let time = seconds % SECONDS_PER_DAY;
+    let (year, month, day) = civil_from_days((seconds / SECONDS_PER_DAY) as i64);
+    Fields {
+        // The last FileTime with a date is in 30828
+        year: year as u16,
+        month,
+        day,
+        hour: (time / 3600) as u8,
+        minute: (time / 60 % 60) as u8,
+        second: (time % 60) as u8,
+        ticks: (ft.ticks % TICKS_PER_SECOND) as u32,
+    }
+}
+
+impl defmt::Format for FileTime {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        if *self > FileTime::MAX {
+            return defmt::write!(f, "FileTime(no date, raw={=u64})", self.ticks);
+        }
+        let fields = fields(*self);
+        defmt::write!(
+            f,
+            "FileTime({=u16:04}-{=u8:02}-{=u8:02}T{=u8:02}:{=u8:02}:{=u8:02}.{=u32:07}Z, raw={=u64})",
+            fields.year,
+            fields.month,
+            fields.day,
+            fields.hour,
+            fields.minute,
+            fields.second,
+            fields.ticks,
+            self.ticks
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{Datelike, Timelike};
+
+    fn chrono_fields(ft: FileTime) -> Fields {
+        let dt = ft.to_datetime();
+        Fields {
+            year: dt.year() as u16,
+            month: dt.month() as u8,
+            day: dt.day() as u8,
+            hour: dt.hour() as u8,
+            minute: dt.minute() as u8,
+            second: dt.second() as u8,
+            ticks: dt.nanosecond() / 100,
+        }
+    }
+
+    #[test]
+    fn known_values() {
+        assert_eq!(
+            fields(FileTime::from_i64(130139712831482830)),
+            Fields {
+                year: 2013,
+                month: 5,
+                day: 25,
+                hour: 16,
+                minute: 1,
+                second: 23,
+                ticks: 1482830,
+            }
+        );
+        assert_eq!(civil_from_days(0), (1601, 1, 1));
+        assert_eq!(
+            fields(FileTime::MAX),
+            Fields {
+                year: 30828,
+                month: 9,
+                day: 14,
+                hour: 2,
+                minute: 48,
+                second: 5,
+                ticks: 4775807,
+            }
+        );
+    }
+
+    #[test]
+    fn matches_chrono() {
+        // Every day around the century and 400 year leap rules
+        for year in [1700, 1900, 2000, 2100, 2400] {
+            let start = FileTime::from_ymd_hms(year - 1, 12, 1, 0, 0, 0).unwrap();
+            for day in 0..500 {
+                let ft = FileTime::from_u64(
+                    start.as_u64() + day * SECONDS_PER_DAY * TICKS_PER_SECOND + 863_999_999_999,
+                );
+                assert_eq!(fields(ft), chrono_fields(ft), "{}", ft);
+            }
+        }
+        // Pseudo-random values over the whole date range
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        for _ in 0..100_000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let ft = FileTime::from_i64((state >> 1) as i64);
+            assert_eq!(fields(ft), chrono_fields(ft), "{}", ft.filetime());
+        }
+    }
+}
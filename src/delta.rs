@@ -0,0 +1,1300 @@
+//! Signed durations with FILETIME tick (100ns) resolution
+use crate::{FileTime, FileTimeError};
+use chrono::{DateTime, Utc};
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use core::str::FromStr;
+
+const TICKS_PER_MINUTE: i64 = 60 * FileTimeDelta::TICKS_PER_SECOND;
+const TICKS_PER_HOUR: i64 = 60 * TICKS_PER_MINUTE;
+const TICKS_PER_DAY: i64 = 24 * TICKS_PER_HOUR;
+
+/// Magnitude of [`FileTimeDelta::MIN`] in nanoseconds
+const MAX_MAGNITUDE_NANOS: i128 = (i64::MAX as i128 + 1) * 100;
+
+/// Units accepted by [`FileTimeDelta::parse`], largest first, as (suffix, nanoseconds)
+const UNITS: [(&str, i128); 8] = [
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("\u{b5}s", 1_000),
+    ("ns", 1),
+];
+
+/// How [`FileTimeDelta::parse_iso8601_with`] treats years and months
+///
+/// Neither has a fixed length, so there is no exact tick count for `P1Y`
+/// or `P2M` without a start date.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Default)]
+pub enum CalendarUnits {
+    /// Fail with [`FileTimeError::InvalidFormat`]
+    #[default]
+    Reject,
+    /// Count a year as 365 days and a month as 30 days
+    Approximate,
+}
+
+/// Designators of the date part of an ISO 8601 duration, as (designator, nanoseconds)
+const ISO_DATE_UNITS: [(u8, i128); 4] = [
+    (b'Y', 365 * 86_400_000_000_000),
+    (b'M', 30 * 86_400_000_000_000),
+    (b'W', 7 * 86_400_000_000_000),
+    (b'D', 86_400_000_000_000),
+];
+
+/// Designators of the time part of an ISO 8601 duration
+const ISO_TIME_UNITS: [(u8, i128); 3] = [
+    (b'H', 3_600_000_000_000),
+    (b'M', 60_000_000_000),
+    (b'S', 1_000_000_000),
+];
+
+/// Signed duration counted in 100ns ticks
+///
+/// Windows uses this representation for timer intervals and policy ages
+/// (e.g. `maxPwdAge`), negative values commonly denote relative intervals.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy, Default)]
+pub struct FileTimeDelta {
+    ticks: i64,
+}
+
+impl FileTimeDelta {
+    /// Empty duration
+    pub const ZERO: Self = Self { ticks: 0 };
+    /// Smallest (most negative) representable duration
+    pub const MIN: Self = Self { ticks: i64::MIN };
+    /// Largest representable duration
+    pub const MAX: Self = Self { ticks: i64::MAX };
+
+    const TICKS_PER_SECOND: i64 = 10_000_000;
+
+    /// Construct from a count of 100ns ticks
+    pub const fn from_ticks(ticks: i64) -> Self {
+        Self { ticks }
+    }
+
+    /// Construct from whole seconds, `None` on overflow
+    pub fn from_secs(secs: i64) -> Option<Self> {
+        secs.checked_mul(Self::TICKS_PER_SECOND)
+            .map(Self::from_ticks)
+    }
+
+    /// Construct from chrono::Duration, sub-tick nanoseconds are truncated towards zero
+    ///
+    /// Fails with [`FileTimeError::Overflow`] if the duration exceeds the tick range.
+    pub fn from_duration(d: chrono::Duration) -> Result<Self, FileTimeError> {
+        let secs = i128::from(d.num_seconds());
+        let subsec = i128::from(d.subsec_nanos() / 100);
+        let ticks = secs * i128::from(Self::TICKS_PER_SECOND) + subsec;
+        i64::try_from(ticks)
+            .map(Self::from_ticks)
+            .map_err(|_| FileTimeError::Overflow)
+    }
+
+    /// Return the count of 100ns ticks
+    pub const fn as_ticks(&self) -> i64 {
+        self.ticks
+    }
+
+    /// Return the duration in seconds as f64
+    pub fn as_secs_f64(&self) -> f64 {
+        self.ticks as f64 / Self::TICKS_PER_SECOND as f64
+    }
+
+    /// Return as chrono::Duration, which covers the whole tick range
+    pub fn to_duration(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.ticks / Self::TICKS_PER_SECOND)
+            + chrono::Duration::nanoseconds(self.ticks % Self::TICKS_PER_SECOND * 100)
+    }
+
+    /// Return as std::time::Duration
+    ///
+    /// Fails with [`FileTimeError::NegativeDuration`] for negative durations.
+    pub fn to_std_duration(&self) -> Result<core::time::Duration, FileTimeError> {
+        if self.ticks < 0 {
+            return Err(FileTimeError::NegativeDuration);
+        }
+        Ok(core::time::Duration::new(
+            (self.ticks / Self::TICKS_PER_SECOND) as u64,
+            (self.ticks % Self::TICKS_PER_SECOND * 100) as u32,
+        ))
+    }
+
+    /// Whether the duration is negative
+    pub const fn is_negative(&self) -> bool {
+        self.ticks < 0
+    }
+
+    /// Absolute value, `None` for [`FileTimeDelta::MIN`]
+    pub fn checked_abs(&self) -> Option<Self> {
+        self.ticks.checked_abs().map(Self::from_ticks)
+    }
+
+    /// Checked negation, `None` for [`FileTimeDelta::MIN`]
+    pub fn checked_neg(&self) -> Option<Self> {
+        self.ticks.checked_neg().map(Self::from_ticks)
+    }
+
+    /// Checked addition, `None` on overflow
+    pub fn checked_add(&self, rhs: Self) -> Option<Self> {
+        self.ticks.checked_add(rhs.ticks).map(Self::from_ticks)
+    }
+
+    /// Checked subtraction, `None` on overflow
+    pub fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        self.ticks.checked_sub(rhs.ticks).map(Self::from_ticks)
+    }
+
+    /// Total an iterator of deltas, `None` on overflow
+    ///
+    /// The [`Sum`] impl panics instead.
+    pub fn checked_sum<I: IntoIterator<Item = Self>>(iter: I) -> Option<Self> {
+        iter.into_iter()
+            .try_fold(Self::ZERO, |acc, delta| acc.checked_add(delta))
+    }
+
+    /// Parse a duration made of number/unit pairs, e.g. `90d`, `1h30m` or `2500ms`
+    ///
+    /// Supported units are `d`, `h`, `m`, `s`, `ms`, `us` (or `µs`) and `ns`.
+    /// Every number needs a unit, each unit may appear once and units must be
+    /// given largest first. Numbers may carry a fraction as long as the result
+    /// is a whole number of ticks. Components can be separated by whitespace
+    /// and an optional leading sign applies to the whole duration, so the
+    /// [`Display`](fmt::Display) output parses back to the same value.
+    /// ```
+    /// use filetime_type::FileTimeDelta;
+    ///
+    /// assert_eq!(FileTimeDelta::parse("1h30m"), Ok(FileTimeDelta::from_ticks(54_000_000_000)));
+    /// assert_eq!(FileTimeDelta::parse("2500ms").unwrap().as_ticks(), 25_000_000);
+    /// assert!(FileTimeDelta::parse("90").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, FileTimeError> {
+        let invalid = |reason| FileTimeError::InvalidFormat {
+            format: "duration",
+            reason,
+        };
+
+        let s = s.trim();
+        let (negative, mut rest) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if rest.is_empty() {
+            return Err(invalid("empty duration"));
+        }
+
+        // Nanoseconds, i128 keeps intermediate values of any i64 tick count exact
+        let mut total: i128 = 0;
+        let mut next_unit = 0;
+        while !rest.is_empty() {
+            let number_len = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(rest.len());
+            let (number, tail) = rest.split_at(number_len);
+            let unit_len = tail
+                .find(|c: char| c.is_ascii_digit() || c == '.' || c.is_whitespace())
+                .unwrap_or(tail.len());
+            let (unit, tail) = tail.split_at(unit_len);
+            rest = tail.trim_start();
+
+            if number.is_empty() {
+                return Err(invalid("expected a number"));
+            }
+            if unit.is_empty() {
+                return Err(invalid("number without unit"));
+            }
+            let index = UNITS
+                .iter()
+                .position(|(suffix, _)| *suffix == unit)
+                .ok_or_else(|| invalid("unknown unit"))?;
+            // us and µs share a rank
+            let rank = if index > 5 { index - 1 } else { index };
+            if rank < next_unit {
+                return Err(invalid("units repeated or out of order"));
+            }
+            next_unit = rank + 1;
+
+            let nanos = component_nanos(number, UNITS[index].1)
+                .ok_or_else(|| invalid("malformed number"))?;
+            total = total
+                .checked_add(nanos)
+                .filter(|t| *t <= MAX_MAGNITUDE_NANOS)
+                .ok_or(FileTimeError::Overflow)?;
+        }
+
+        Self::from_total_nanos(total, negative, "duration")
+    }
+
+    /// Parse an ISO 8601 duration such as `P90D`, `PT1H30M` or `P1W`, rejecting years and months
+    ///
+    /// Same as [`parse_iso8601_with`](Self::parse_iso8601_with) with [`CalendarUnits::Reject`].
+    /// ```
+    /// use filetime_type::FileTimeDelta;
+    ///
+    /// assert_eq!(FileTimeDelta::parse_iso8601("PT1H30M"), FileTimeDelta::parse("1h30m"));
+    /// assert_eq!(FileTimeDelta::parse_iso8601("PT0.0000001S").unwrap().as_ticks(), 1);
+    /// assert!(FileTimeDelta::parse_iso8601("P1Y").is_err());
+    /// ```
+    pub fn parse_iso8601(s: &str) -> Result<Self, FileTimeError> {
+        Self::parse_iso8601_with(s, CalendarUnits::Reject)
+    }
+
+    /// Parse an ISO 8601 duration, `[-]PnYnMnWnDTnHnMnS`
+    ///
+    /// Weeks, days, hours, minutes and seconds are exact. Years and months are
+    /// handled as `calendar` says. Components may be omitted but not
+    /// reordered, only the last one may carry a fraction (`.` or `,`) and the
+    /// result must be a whole number of ticks. A leading `-` negates the
+    /// duration, an extension found in Java and .NET.
+    pub fn parse_iso8601_with(s: &str, calendar: CalendarUnits) -> Result<Self, FileTimeError> {
+        let invalid = |reason| FileTimeError::InvalidFormat {
+            format: "ISO 8601 duration",
+            reason,
+        };
+
+        let (negative, rest) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let rest = rest
+            .strip_prefix('P')
+            .ok_or_else(|| invalid("expected 'P'"))?;
+        let (date, time) = match rest.find('T') {
+            Some(t) => (&rest[..t], Some(&rest[t + 1..])),
+            None => (rest, None),
+        };
+        if time == Some("") {
+            return Err(invalid("'T' without time components"));
+        }
+        if date.is_empty() && time.is_none() {
+            return Err(invalid("no components"));
+        }
+
+        let mut total: i128 = 0;
+        let mut fraction = false;
+        let parts = [
+            (date, &ISO_DATE_UNITS[..], true),
+            (time.unwrap_or(""), &ISO_TIME_UNITS[..], false),
+        ];
+        for (part, units, is_date) in parts {
+            let mut rest = part.as_bytes();
+            let mut next_unit = 0;
+            while !rest.is_empty() {
+                if fraction {
+                    return Err(invalid("fraction on a component other than the last"));
+                }
+                let number_len = rest
+                    .iter()
+                    .position(|b| !(b.is_ascii_digit() || *b == b'.' || *b == b','))
+                    .ok_or_else(|| invalid("number without designator"))?;
+                let (number, tail) = rest.split_at(number_len);
+                rest = &tail[1..];
+                if number.is_empty() {
+                    return Err(invalid("expected a number"));
+                }
+                let index = units
+                    .iter()
+                    .position(|(designator, _)| *designator == tail[0])
+                    .ok_or_else(|| invalid("unknown designator"))?;
+                if index < next_unit {
+                    return Err(invalid("designators repeated or out of order"));
+                }
+                next_unit = index + 1;
+                if is_date && index < 2 && calendar == CalendarUnits::Reject {
+                    return Err(invalid("years and months have no fixed length"));
+                }
+
+                // ASCII only, the split points are char boundaries
+                let number = core::str::from_utf8(number).unwrap();
+                let (int, frac) = match number.find(['.', ',']) {
+                    Some(sep) => (&number[..sep], &number[sep + 1..]),
+                    None => (number, ""),
+                };
+                fraction = number.len() != int.len();
+                let nanos = parts_nanos(int, frac, units[index].1)
+                    .ok_or_else(|| invalid("malformed number"))?;
+                total = total
+                    .checked_add(nanos)
+                    .filter(|t| *t <= MAX_MAGNITUDE_NANOS)
+                    .ok_or(FileTimeError::Overflow)?;
+            }
+        }
+
+        Self::from_total_nanos(total, negative, "ISO 8601 duration")
+    }
+
+    /// Format as ISO 8601 duration of days, hours, minutes and seconds
+    ///
+    /// Components that are zero are left out, zero itself is `PT0S`. Seconds
+    /// carry as many fraction digits as needed, down to 100ns, so the
+    /// result parses back exactly. Negative durations get a leading `-`.
+    /// ```
+    /// use filetime_type::FileTimeDelta;
+    ///
+    /// assert_eq!(FileTimeDelta::parse("90d").unwrap().to_iso8601(), "P90D");
+    /// assert_eq!(FileTimeDelta::parse("-1d 2h 0.25s").unwrap().to_iso8601(), "-P1DT2H0.25S");
+    /// assert_eq!(FileTimeDelta::ZERO.to_iso8601(), "PT0S");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_iso8601(&self) -> String {
+        use core::fmt::Write;
+
+        let ticks = self.ticks.unsigned_abs();
+        let days = ticks / TICKS_PER_DAY as u64;
+        let hours = ticks % TICKS_PER_DAY as u64 / TICKS_PER_HOUR as u64;
+        let minutes = ticks % TICKS_PER_HOUR as u64 / TICKS_PER_MINUTE as u64;
+        let secs = ticks % TICKS_PER_MINUTE as u64 / Self::TICKS_PER_SECOND as u64;
+        let frac = ticks % Self::TICKS_PER_SECOND as u64;
+
+        let mut s = String::from(if self.ticks < 0 { "-P" } else { "P" });
+        if days > 0 {
+            write!(s, "{}D", days).unwrap();
+        }
+        if days == 0 || hours > 0 || minutes > 0 || secs > 0 || frac > 0 {
+            s.push('T');
+        }
+        if hours > 0 {
+            write!(s, "{}H", hours).unwrap();
+        }
+        if minutes > 0 {
+            write!(s, "{}M", minutes).unwrap();
+        }
+        if secs > 0 || frac > 0 || ticks == 0 {
+            write!(s, "{}", secs).unwrap();
+            if frac > 0 {
+                let digits = format!("{:07}", frac);
+                write!(s, ".{}", digits.trim_end_matches('0')).unwrap();
+            }
+            s.push('S');
+        }
+        s
+    }
+
+    /// Apply the sign to a parsed magnitude in nanoseconds, which must be whole ticks
+    fn from_total_nanos(
+        total: i128,
+        negative: bool,
+        format: &'static str,
+    ) -> Result<Self, FileTimeError> {
+        if total % 100 != 0 {
+            return Err(FileTimeError::InvalidFormat {
+                format,
+                reason: "precision finer than 100ns",
+            });
+        }
+        let ticks = if negative { -total / 100 } else { total / 100 };
+        i64::try_from(ticks)
+            .map(Self::from_ticks)
+            .map_err(|_| FileTimeError::Overflow)
+    }
+}
+
+/// Nanoseconds in `number` (digits with an optional fraction) of a unit
+///
+/// Returns `None` for malformed numbers and for fractions finer than a nanosecond.
+fn component_nanos(number: &str, unit_nanos: i128) -> Option<i128> {
+    match number.find('.') {
+        Some(dot) => parts_nanos(&number[..dot], &number[dot + 1..], unit_nanos),
+        None => parts_nanos(number, "", unit_nanos),
+    }
+}
+
+/// Nanoseconds in a number split into integer and fraction digits, see [`component_nanos`]
+fn parts_nanos(int: &str, frac: &str, unit_nanos: i128) -> Option<i128> {
+    if int.is_empty() && frac.is_empty() || frac.contains('.') || frac.len() > 18 {
+        return None;
+    }
+    // Bounded so the multiplication below can't overflow i128
+    if int.len() > 20 {
+        return Some(i128::MAX);
+    }
+    let int: i128 = if int.is_empty() { 0 } else { int.parse().ok()? };
+    let mut nanos = int * unit_nanos;
+    if !frac.is_empty() {
+        let scale = 10i128.pow(frac.len() as u32);
+        let frac_nanos = frac.parse::<i128>().ok()? * unit_nanos;
+        if frac_nanos % scale != 0 {
+            return None;
+        }
+        nanos += frac_nanos / scale;
+    }
+    Some(nanos)
+}
+
+/// Formats as e.g. `-42d 3h 15m 2.5000000s`, starting at the largest non-zero unit
+impl fmt::Display for FileTimeDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ticks = self.ticks.unsigned_abs();
+        if self.ticks < 0 {
+            write!(f, "-")?;
+        }
+        let days = ticks / TICKS_PER_DAY as u64;
+        let hours = ticks % TICKS_PER_DAY as u64 / TICKS_PER_HOUR as u64;
+        let minutes = ticks % TICKS_PER_HOUR as u64 / TICKS_PER_MINUTE as u64;
+        let secs = ticks % TICKS_PER_MINUTE as u64 / Self::TICKS_PER_SECOND as u64;
+        let frac = ticks % Self::TICKS_PER_SECOND as u64;
+        if days > 0 {
+            write!(f, "{}d ", days)?;
+        }
+        if days > 0 || hours > 0 {
+            write!(f, "{}h ", hours)?;
+        }
+        if days > 0 || hours > 0 || minutes > 0 {
+            write!(f, "{}m ", minutes)?;
+        }
+        write!(f, "{}.{:07}s", secs, frac)
+    }
+}
+
+impl FromStr for FileTimeDelta {
+    type Err = FileTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Sum for FileTimeDelta {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self::checked_sum(iter).expect("overflow when summing FileTimeDelta")
+    }
+}
+
+impl<'a> Sum<&'a FileTimeDelta> for FileTimeDelta {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl From<FileTimeDelta> for chrono::Duration {
+    fn from(delta: FileTimeDelta) -> Self {
+        delta.to_duration()
+    }
+}
+
+impl TryFrom<chrono::Duration> for FileTimeDelta {
+    type Error = FileTimeError;
+
+    fn try_from(d: chrono::Duration) -> Result<Self, Self::Error> {
+        Self::from_duration(d)
+    }
+}
+
+impl Neg for FileTimeDelta {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg()
+            .expect("overflow when negating FileTimeDelta")
+    }
+}
+
+impl Add for FileTimeDelta {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.checked_add(rhs)
+            .expect("overflow when adding FileTimeDelta")
+    }
+}
+
+impl Sub for FileTimeDelta {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting FileTimeDelta")
+    }
+}
+
+impl AddAssign for FileTimeDelta {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for FileTimeDelta {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl FileTime {
+    /// Add a delta, `None` if the result leaves the raw `u64` range
+    ///
+    /// Values above [`FileTime::MAX`] take part like any other, so the sum
+    /// may lie beyond `MAX`.
+    /// ```
+    /// use filetime_type::{FileTime, FileTimeDelta};
+    ///
+    /// let ft = FileTime::from_i64(100);
+    /// assert_eq!(ft.checked_add(FileTimeDelta::from_ticks(-100)), Some(FileTime::from_i64(0)));
+    /// assert_eq!(ft.checked_add(FileTimeDelta::from_ticks(-101)), None);
+    /// assert_eq!(FileTime::MAX_RAW.checked_add(FileTimeDelta::from_ticks(1)), None);
+    /// ```
+    pub fn checked_add(&self, delta: FileTimeDelta) -> Option<FileTime> {
+        self.as_u64()
+            .checked_add_signed(delta.as_ticks())
+            .map(FileTime::from_u64)
+    }
+
+    /// Subtract a delta, `None` if the result leaves the raw `u64` range
+    pub fn checked_sub(&self, delta: FileTimeDelta) -> Option<FileTime> {
+        let ticks = delta.as_ticks();
+        if ticks < 0 {
+            self.as_u64().checked_add(ticks.unsigned_abs())
+        } else {
+            self.as_u64().checked_sub(ticks as u64)
+        }
+        .map(FileTime::from_u64)
+    }
+
+    /// Signed duration from `earlier` to `self`, the same as `self - earlier`
+    ///
+    /// Never overflows for values up to [`FileTime::MAX`].
+    ///
+    /// # Panics
+    ///
+    /// If the difference involves raw values above `MAX` and does not fit a
+    /// [`FileTimeDelta`]; see [`checked_delta_since`](Self::checked_delta_since)
+    /// and [`saturating_delta_since`](Self::saturating_delta_since).
+    pub fn delta_since(&self, earlier: FileTime) -> FileTimeDelta {
+        self.checked_delta_since(earlier)
+            .expect("FileTime - FileTime out of range")
+    }
+
+    /// Signed duration from `earlier` to `self`, `None` if it does not fit a [`FileTimeDelta`]
+    /// ```
+    /// use filetime_type::{FileTime, FileTimeDelta};
+    ///
+    /// let ft = FileTime::from_i64(100);
+    /// assert_eq!(ft.checked_delta_since(FileTime::MIN), Some(FileTimeDelta::from_ticks(100)));
+    /// assert_eq!(FileTime::MAX_RAW.checked_delta_since(FileTime::MIN), None);
+    /// ```
+    pub fn checked_delta_since(&self, earlier: FileTime) -> Option<FileTimeDelta> {
+        let ticks = i128::from(self.as_u64()) - i128::from(earlier.as_u64());
+        i64::try_from(ticks).ok().map(FileTimeDelta::from_ticks)
+    }
+
+    /// Signed duration from `earlier` to `self`, saturating at [`FileTimeDelta::MIN`] / [`FileTimeDelta::MAX`]
+    /// ```
+    /// use filetime_type::{FileTime, FileTimeDelta};
+    ///
+    /// assert_eq!(FileTime::MAX_RAW.saturating_delta_since(FileTime::MIN), FileTimeDelta::MAX);
+    /// assert_eq!(FileTime::MIN.saturating_delta_since(FileTime::MAX_RAW), FileTimeDelta::MIN);
+    /// ```
+    pub fn saturating_delta_since(&self, earlier: FileTime) -> FileTimeDelta {
+        let ticks = i128::from(self.as_u64()) - i128::from(earlier.as_u64());
+        FileTimeDelta::from_ticks(ticks.clamp(i128::from(i64::MIN), i128::from(i64::MAX)) as i64)
+    }
+
+    /// Add raw ticks, failing if the result leaves the raw `u64` range
+    ///
+    /// Fails with [`FileTimeError::BeforeEpoch`] below [`FileTime::MIN`] and
+    /// [`FileTimeError::Overflow`] above [`FileTime::MAX_RAW`].
+    /// ```
+    /// use filetime_type::{FileTime, FileTimeError};
+    ///
+    /// let ft = FileTime::from_i64(100);
+    /// assert_eq!(ft.add_ticks(-100), Ok(FileTime::MIN));
+    /// assert_eq!(ft.add_ticks(-101), Err(FileTimeError::BeforeEpoch));
+    /// assert_eq!(FileTime::MAX_RAW.add_ticks(1), Err(FileTimeError::Overflow));
+    /// ```
+    pub fn add_ticks(&self, ticks: i64) -> Result<FileTime, FileTimeError> {
+        self.checked_add_ticks(ticks).ok_or(if ticks < 0 {
+            FileTimeError::BeforeEpoch
+        } else {
+            FileTimeError::Overflow
+        })
+    }
+
+    /// Subtract raw ticks, failing if the result leaves the raw `u64` range
+    pub fn sub_ticks(&self, ticks: i64) -> Result<FileTime, FileTimeError> {
+        self.checked_sub_ticks(ticks).ok_or(if ticks > 0 {
+            FileTimeError::BeforeEpoch
+        } else {
+            FileTimeError::Overflow
+        })
+    }
+
+    /// Add raw ticks, `None` if the result leaves the raw `u64` range
+    pub fn checked_add_ticks(&self, ticks: i64) -> Option<FileTime> {
+        self.checked_add(FileTimeDelta::from_ticks(ticks))
+    }
+
+    /// Subtract raw ticks, `None` if the result leaves the raw `u64` range
+    pub fn checked_sub_ticks(&self, ticks: i64) -> Option<FileTime> {
+        self.checked_sub(FileTimeDelta::from_ticks(ticks))
+    }
+
+    /// Add raw ticks, ending up at [`FileTime::MIN`] / [`FileTime::MAX_RAW`] on overflow
+    pub fn saturating_add_ticks(&self, ticks: i64) -> FileTime {
+        self.checked_add_ticks(ticks).unwrap_or(if ticks < 0 {
+            FileTime::MIN
+        } else {
+            FileTime::MAX_RAW
+        })
+    }
+
+    /// Subtract raw ticks, ending up at [`FileTime::MIN`] / [`FileTime::MAX_RAW`] on overflow
+    pub fn saturating_sub_ticks(&self, ticks: i64) -> FileTime {
+        self.checked_sub_ticks(ticks).unwrap_or(if ticks > 0 {
+            FileTime::MIN
+        } else {
+            FileTime::MAX_RAW
+        })
+    }
+
+    /// One tick later, `None` at [`FileTime::MAX_RAW`]
+    ///
+    /// Turns an inclusive upper bound into the exclusive end of a range:
+    /// ```
+    /// use filetime_type::{FileTime, FileTimeRange};
+    ///
+    /// let first = FileTime::from_ymd_hms(2023, 5, 17, 0, 0, 0).unwrap();
+    /// let last = FileTime::from_i64(133288415999999999); // 2023-05-17T23:59:59.9999999Z
+    /// let day = FileTimeRange::new(first, last.next().unwrap()).unwrap();
+    /// assert!(day.contains(last));
+    /// assert!(!day.contains(last.next().unwrap()));
+    /// assert_eq!(day.end(), FileTime::from_ymd_hms(2023, 5, 18, 0, 0, 0).unwrap());
+    /// assert_eq!(last.next().unwrap().prev(), Some(last));
+    /// ```
+    pub fn next(&self) -> Option<FileTime> {
+        self.ticks.checked_add(1).map(FileTime::from_u64)
+    }
+
+    /// One tick earlier, `None` at [`FileTime::MIN`]
+    pub fn prev(&self) -> Option<FileTime> {
+        self.ticks.checked_sub(1).map(FileTime::from_u64)
+    }
+}
+
+impl Add<FileTimeDelta> for FileTime {
+    type Output = FileTime;
+
+    fn add(self, rhs: FileTimeDelta) -> FileTime {
+        self.checked_add(rhs)
+            .expect("FileTime + FileTimeDelta out of range")
+    }
+}
+
+impl Sub<FileTimeDelta> for FileTime {
+    type Output = FileTime;
+
+    fn sub(self, rhs: FileTimeDelta) -> FileTime {
+        self.checked_sub(rhs)
+            .expect("FileTime - FileTimeDelta out of range")
+    }
+}
+
+impl AddAssign<FileTimeDelta> for FileTime {
+    fn add_assign(&mut self, rhs: FileTimeDelta) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<FileTimeDelta> for FileTime {
+    fn sub_assign(&mut self, rhs: FileTimeDelta) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for FileTime {
+    type Output = FileTimeDelta;
+
+    fn sub(self, rhs: FileTime) -> FileTimeDelta {
+        self.delta_since(rhs)
+    }
+}
+
+impl FileTimeDelta {
+    /// `dt + self`, `None` if the result leaves the range of chrono
+    pub fn checked_add_to_datetime(&self, dt: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        dt.checked_add_signed(self.to_duration())
+    }
+
+    /// `dt - self`, `None` if the result leaves the range of chrono
+    pub fn checked_sub_from_datetime(&self, dt: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        dt.checked_sub_signed(self.to_duration())
+    }
+}
+
+impl FileTime {
+    /// `self - dt` exact to the nanosecond, `None` above [`FileTime::MAX`]
+    /// ```
+    /// use chrono::{DateTime, Duration, Utc};
+    /// use filetime_type::FileTime;
+    ///
+    /// let dt = DateTime::<Utc>::from_timestamp(0, 0).unwrap();
+    /// let ft = FileTime::from_datetime(dt + Duration::seconds(1));
+    /// assert_eq!(ft.checked_signed_duration_since(dt), Some(Duration::seconds(1)));
+    /// assert_eq!(FileTime::MAX_RAW.checked_signed_duration_since(dt), None);
+    /// ```
+    pub fn checked_signed_duration_since(&self, dt: DateTime<Utc>) -> Option<chrono::Duration> {
+        let own = self.to_datetime_checked().ok()?;
+        Some(own.signed_duration_since(dt))
+    }
+
+    /// `dt - self` exact to the nanosecond, `None` above [`FileTime::MAX`]
+    pub fn checked_signed_duration_until(&self, dt: DateTime<Utc>) -> Option<chrono::Duration> {
+        let own = self.to_datetime_checked().ok()?;
+        Some(dt.signed_duration_since(own))
+    }
+}
+
+/// Exact to the nanosecond, positive if `self` lies after `rhs`
+///
+/// Panics if `rhs` lies above [`FileTime::MAX`], see [`FileTime::checked_signed_duration_until`]
+impl Sub<FileTime> for DateTime<Utc> {
+    type Output = chrono::Duration;
+
+    fn sub(self, rhs: FileTime) -> chrono::Duration {
+        rhs.checked_signed_duration_until(self)
+            .expect("DateTime - FileTime out of range")
+    }
+}
+
+/// Exact to the nanosecond, positive if `self` lies after `rhs`
+///
+/// Panics if `self` lies above [`FileTime::MAX`], see [`FileTime::checked_signed_duration_since`]
+impl Sub<DateTime<Utc>> for FileTime {
+    type Output = chrono::Duration;
+
+    fn sub(self, rhs: DateTime<Utc>) -> chrono::Duration {
+        self.checked_signed_duration_since(rhs)
+            .expect("FileTime - DateTime out of range")
+    }
+}
+
+/// Panics if the result leaves the range of chrono, see [`FileTimeDelta::checked_add_to_datetime`]
+impl Add<FileTimeDelta> for DateTime<Utc> {
+    type Output = DateTime<Utc>;
+
+    fn add(self, rhs: FileTimeDelta) -> DateTime<Utc> {
+        rhs.checked_add_to_datetime(self)
+            .expect("DateTime + FileTimeDelta out of range")
+    }
+}
+
+/// Panics if the result leaves the range of chrono, see [`FileTimeDelta::checked_sub_from_datetime`]
+impl Sub<FileTimeDelta> for DateTime<Utc> {
+    type Output = DateTime<Utc>;
+
+    fn sub(self, rhs: FileTimeDelta) -> DateTime<Utc> {
+        rhs.checked_sub_from_datetime(self)
+            .expect("DateTime - FileTimeDelta out of range")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn constructors() {
+        assert_eq!(
+            FileTimeDelta::from_secs(-2).unwrap().as_ticks(),
+            -20_000_000
+        );
+        assert_eq!(FileTimeDelta::from_secs(i64::MAX / 1000), None);
+        assert_eq!(FileTimeDelta::from_ticks(-15_000_000).as_secs_f64(), -1.5);
+    }
+
+    #[test]
+    fn chrono_interop() {
+        let d = chrono::Duration::milliseconds(-1500) + chrono::Duration::nanoseconds(-250);
+        let delta = FileTimeDelta::from_duration(d).unwrap();
+        // Sub-tick nanoseconds are truncated towards zero
+        assert_eq!(delta.as_ticks(), -15_000_002);
+        assert_eq!(
+            delta.to_duration(),
+            chrono::Duration::nanoseconds(-1_500_000_200)
+        );
+
+        assert_eq!(
+            chrono::Duration::from(FileTimeDelta::MAX),
+            chrono::Duration::seconds(922337203685) + chrono::Duration::nanoseconds(477_580_700)
+        );
+        assert_eq!(
+            FileTimeDelta::try_from(chrono::Duration::MAX),
+            Err(FileTimeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn std_duration() {
+        let delta = FileTimeDelta::from_ticks(15_000_001);
+        assert_eq!(
+            delta.to_std_duration(),
+            Ok(std::time::Duration::new(1, 500_000_100))
+        );
+        assert_eq!(
+            (-delta).to_std_duration(),
+            Err(FileTimeError::NegativeDuration)
+        );
+    }
+
+    #[test]
+    fn delta_arithmetic() {
+        let a = FileTimeDelta::from_ticks(-5);
+        let b = FileTimeDelta::from_ticks(7);
+        assert_eq!(a + b, FileTimeDelta::from_ticks(2));
+        assert_eq!(a - b, FileTimeDelta::from_ticks(-12));
+        assert_eq!(-a, FileTimeDelta::from_ticks(5));
+        assert!(a.is_negative());
+        assert_eq!(a.checked_abs(), Some(FileTimeDelta::from_ticks(5)));
+
+        assert_eq!(FileTimeDelta::MAX.checked_add(b), None);
+        assert_eq!(FileTimeDelta::MIN.checked_sub(b), None);
+        assert_eq!(FileTimeDelta::MIN.checked_neg(), None);
+        assert_eq!(FileTimeDelta::MIN.checked_abs(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn delta_overflow() {
+        let _ = FileTimeDelta::MAX + FileTimeDelta::from_ticks(1);
+    }
+
+    #[test]
+    fn filetime_arithmetic() {
+        let ft = FileTime::from_i64(128930364000001000);
+        let delta = FileTimeDelta::from_secs(-3600).unwrap();
+        let earlier = ft + delta;
+        assert_eq!(earlier.filetime(), 128930328000001000);
+        assert_eq!(earlier - delta, ft);
+        assert_eq!(earlier - ft, delta);
+        assert_eq!(ft - earlier, -delta);
+
+        let mut moving = ft;
+        moving -= delta;
+        moving += delta;
+        assert_eq!(moving, ft);
+    }
+
+    #[test]
+    fn filetime_extremes() {
+        let zero = FileTime::from_i64(0);
+        let max = FileTime::from_i64(i64::MAX);
+        assert_eq!(max - zero, FileTimeDelta::MAX);
+        assert_eq!(zero - max, FileTimeDelta::from_ticks(-i64::MAX));
+        assert_eq!(zero.checked_sub(FileTimeDelta::from_ticks(1)), None);
+        assert_eq!(zero.checked_add(FileTimeDelta::MAX), Some(max));
+        // The raw range continues up to MAX_RAW
+        let raw_max = FileTime::MAX_RAW;
+        assert_eq!(
+            max.checked_add(FileTimeDelta::from_ticks(1)),
+            Some(FileTime::from_u64(1 << 63))
+        );
+        assert_eq!(raw_max.checked_add(FileTimeDelta::from_ticks(1)), None);
+        assert_eq!(max.checked_sub(FileTimeDelta::MIN), Some(raw_max));
+        assert_eq!(
+            raw_max.checked_sub(FileTimeDelta::MAX),
+            Some(FileTime::from_u64(1 << 63))
+        );
+        assert_eq!(raw_max - FileTime::from_u64(1 << 63), FileTimeDelta::MAX);
+        assert_eq!(zero.checked_add(FileTimeDelta::MIN), None);
+    }
+
+    #[test]
+    fn delta_since_above_max() {
+        let raw_max = FileTime::MAX_RAW;
+        let max = FileTime::MAX;
+        // 2^63 ticks apart, one more than FileTimeDelta::MAX
+        assert_eq!(raw_max.checked_delta_since(max), None);
+        assert_eq!(raw_max.saturating_delta_since(max), FileTimeDelta::MAX);
+        assert_eq!(max.saturating_delta_since(raw_max), FileTimeDelta::MIN);
+        assert_eq!(
+            max.checked_delta_since(raw_max),
+            Some(FileTimeDelta::from_ticks(i64::MIN))
+        );
+        assert_eq!(max - raw_max, FileTimeDelta::MIN);
+        assert_eq!(
+            raw_max.checked_delta_since(FileTime::from_u64(1 << 63)),
+            Some(FileTimeDelta::MAX)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "FileTime - FileTime out of range")]
+    fn sub_overflow() {
+        let _ = FileTime::MAX_RAW - FileTime::MAX;
+    }
+
+    #[test]
+    fn tick_arithmetic() {
+        let zero = FileTime::MIN;
+        let max = FileTime::MAX;
+        let raw_max = FileTime::MAX_RAW;
+        assert_eq!(zero.add_ticks(0), Ok(zero));
+        assert_eq!(zero.add_ticks(-1), Err(FileTimeError::BeforeEpoch));
+        assert_eq!(zero.sub_ticks(1), Err(FileTimeError::BeforeEpoch));
+        assert_eq!(zero.sub_ticks(i64::MIN), Ok(FileTime::from_u64(1 << 63)));
+        assert_eq!(zero.add_ticks(i64::MAX), Ok(max));
+        assert_eq!(max.add_ticks(1), Ok(FileTime::from_u64(1 << 63)));
+        assert_eq!(max.sub_ticks(i64::MAX), Ok(zero));
+        assert_eq!(raw_max.add_ticks(1), Err(FileTimeError::Overflow));
+        assert_eq!(raw_max.sub_ticks(-1), Err(FileTimeError::Overflow));
+        assert_eq!(raw_max.add_ticks(i64::MIN), Ok(max));
+        assert_eq!(raw_max.sub_ticks(i64::MAX), Ok(FileTime::from_u64(1 << 63)));
+
+        assert_eq!(zero.checked_add_ticks(-1), None);
+        assert_eq!(zero.checked_sub_ticks(-5), Some(FileTime::from_i64(5)));
+        assert_eq!(raw_max.checked_add_ticks(1), None);
+        assert_eq!(
+            raw_max.checked_sub_ticks(1),
+            Some(FileTime::from_u64(u64::MAX - 1))
+        );
+
+        assert_eq!(zero.saturating_add_ticks(-1), zero);
+        assert_eq!(zero.saturating_sub_ticks(i64::MAX), zero);
+        assert_eq!(raw_max.saturating_add_ticks(i64::MAX), raw_max);
+        assert_eq!(raw_max.saturating_sub_ticks(-1), raw_max);
+        assert_eq!(FileTime::from_i64(10).saturating_sub_ticks(11), zero);
+        assert_eq!(
+            FileTime::from_i64(10).saturating_add_ticks(-3),
+            FileTime::from_i64(7)
+        );
+
+        assert_eq!(zero.prev(), None);
+        assert_eq!(zero.next(), Some(FileTime::from_i64(1)));
+        assert_eq!(max.next(), Some(FileTime::from_u64(1 << 63)));
+        assert_eq!(raw_max.next(), None);
+        assert_eq!(raw_max.prev(), Some(FileTime::from_u64(u64::MAX - 1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn filetime_underflow() {
+        let _ = FileTime::from_i64(0) - FileTimeDelta::from_ticks(1);
+    }
+
+    #[test]
+    fn display() {
+        let delta = -FileTimeDelta::from_ticks(
+            42 * TICKS_PER_DAY + 3 * TICKS_PER_HOUR + 15 * TICKS_PER_MINUTE + 25_000_000,
+        );
+        assert_eq!(delta.to_string(), "-42d 3h 15m 2.5000000s");
+        assert_eq!(FileTimeDelta::ZERO.to_string(), "0.0000000s");
+        assert_eq!(
+            FileTimeDelta::from_ticks(TICKS_PER_HOUR + 1).to_string(),
+            "1h 0m 0.0000001s"
+        );
+        assert_eq!(
+            FileTimeDelta::MIN.to_string(),
+            "-10675199d 2h 48m 5.4775808s"
+        );
+    }
+
+    #[test]
+    fn parse() {
+        let parse = |s| FileTimeDelta::parse(s).map(|d| d.as_ticks());
+        assert_eq!(parse("90d"), Ok(90 * TICKS_PER_DAY));
+        assert_eq!(parse("1h30m"), Ok(90 * TICKS_PER_MINUTE));
+        assert_eq!(parse("2500ms"), Ok(25_000_000));
+        assert_eq!(parse("-1.5s"), Ok(-15_000_000));
+        assert_eq!(parse("+12us 300ns"), Ok(123));
+        assert_eq!(parse("7\u{b5}s"), Ok(70));
+        assert_eq!(parse(".25h"), Ok(15 * TICKS_PER_MINUTE));
+
+        let invalid = |reason| {
+            Err(FileTimeError::InvalidFormat {
+                format: "duration",
+                reason,
+            })
+        };
+        assert_eq!(parse(""), invalid("empty duration"));
+        assert_eq!(parse("-"), invalid("empty duration"));
+        assert_eq!(parse("90"), invalid("number without unit"));
+        assert_eq!(parse("1h30"), invalid("number without unit"));
+        assert_eq!(parse("5 s"), invalid("number without unit"));
+        assert_eq!(parse("h"), invalid("expected a number"));
+        assert_eq!(parse("3w"), invalid("unknown unit"));
+        assert_eq!(parse("1m1m"), invalid("units repeated or out of order"));
+        assert_eq!(parse("30m1h"), invalid("units repeated or out of order"));
+        assert_eq!(
+            parse("1us2\u{b5}s"),
+            invalid("units repeated or out of order")
+        );
+        assert_eq!(parse("1.2.3s"), invalid("malformed number"));
+        assert_eq!(parse(".s"), invalid("malformed number"));
+        assert_eq!(parse("50ns"), invalid("precision finer than 100ns"));
+
+        assert_eq!(parse("-10675199d 2h 48m 5.4775808s"), Ok(i64::MIN));
+        assert_eq!(
+            parse("10675199d 2h 48m 5.4775808s"),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!(
+            parse("99999999999999999999999999d"),
+            Err(FileTimeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn display_parse_roundtrip() {
+        for ticks in &[
+            0,
+            1,
+            -1,
+            9_999_999,
+            TICKS_PER_DAY,
+            -TICKS_PER_HOUR - 7,
+            i64::MAX,
+            i64::MIN,
+        ] {
+            let delta = FileTimeDelta::from_ticks(*ticks);
+            assert_eq!(delta.to_string().parse(), Ok(delta));
+        }
+    }
+
+    #[test]
+    fn parse_iso8601() {
+        let parse = |s| FileTimeDelta::parse_iso8601(s).map(|d| d.as_ticks());
+        assert_eq!(parse("PT0.0000001S"), Ok(1));
+        assert_eq!(parse("P1W"), Ok(7 * TICKS_PER_DAY));
+        assert_eq!(parse("P90D"), Ok(90 * TICKS_PER_DAY));
+        assert_eq!(parse("PT1H30M"), Ok(90 * TICKS_PER_MINUTE));
+        assert_eq!(
+            parse("P2W3DT4H5M6.789S"),
+            Ok(17 * TICKS_PER_DAY + 4 * TICKS_PER_HOUR + 5 * TICKS_PER_MINUTE + 67_890_000)
+        );
+        assert_eq!(parse("PT1,5S"), Ok(15_000_000));
+        assert_eq!(parse("PT0.5H"), Ok(30 * TICKS_PER_MINUTE));
+        assert_eq!(parse("-P1D"), Ok(-TICKS_PER_DAY));
+        assert_eq!(parse("PT0S"), Ok(0));
+        assert_eq!(parse("PT36H"), Ok(36 * TICKS_PER_HOUR));
+
+        let invalid = |reason| {
+            Err(FileTimeError::InvalidFormat {
+                format: "ISO 8601 duration",
+                reason,
+            })
+        };
+        assert_eq!(
+            parse("P1Y"),
+            invalid("years and months have no fixed length")
+        );
+        assert_eq!(
+            parse("P2M"),
+            invalid("years and months have no fixed length")
+        );
+        assert_eq!(parse("1D"), invalid("expected 'P'"));
+        assert_eq!(parse("P"), invalid("no components"));
+        assert_eq!(parse("PT"), invalid("'T' without time components"));
+        assert_eq!(parse("P5"), invalid("number without designator"));
+        assert_eq!(parse("PD"), invalid("expected a number"));
+        assert_eq!(parse("P1H"), invalid("unknown designator"));
+        assert_eq!(parse("PT1D"), invalid("unknown designator"));
+        assert_eq!(
+            parse("P1D1W"),
+            invalid("designators repeated or out of order")
+        );
+        assert_eq!(
+            parse("PT1M1M"),
+            invalid("designators repeated or out of order")
+        );
+        assert_eq!(
+            parse("PT1.5M30S"),
+            invalid("fraction on a component other than the last")
+        );
+        assert_eq!(
+            parse("P1.5DT1H"),
+            invalid("fraction on a component other than the last")
+        );
+        assert_eq!(parse("PT1.2.3S"), invalid("malformed number"));
+        assert_eq!(
+            parse("PT0.00000001S"),
+            invalid("precision finer than 100ns")
+        );
+        assert_eq!(parse("P10675200D"), Err(FileTimeError::Overflow));
+        assert_eq!(parse("-PT922337203685.4775808S"), Ok(i64::MIN));
+    }
+
+    #[test]
+    fn parse_iso8601_calendar_units() {
+        let approximate = |s| {
+            FileTimeDelta::parse_iso8601_with(s, CalendarUnits::Approximate).map(|d| d.as_ticks())
+        };
+        assert_eq!(approximate("P1Y"), Ok(365 * TICKS_PER_DAY));
+        assert_eq!(approximate("P1Y2M"), Ok(425 * TICKS_PER_DAY));
+        assert_eq!(
+            approximate("P1MT1M"),
+            Ok(30 * TICKS_PER_DAY + TICKS_PER_MINUTE)
+        );
+        assert_eq!(approximate("P0.5Y"), Ok(365 * TICKS_PER_DAY / 2));
+        assert_eq!(CalendarUnits::default(), CalendarUnits::Reject);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn to_iso8601() {
+        let cases: &[(i64, &str)] = &[
+            (0, "PT0S"),
+            (1, "PT0.0000001S"),
+            (-1, "-PT0.0000001S"),
+            (90 * TICKS_PER_DAY, "P90D"),
+            (7 * TICKS_PER_DAY, "P7D"),
+            (90 * TICKS_PER_MINUTE, "PT1H30M"),
+            (TICKS_PER_DAY + 5_000_000, "P1DT0.5S"),
+            (TICKS_PER_DAY + TICKS_PER_MINUTE, "P1DT1M"),
+            (i64::MIN, "-P10675199DT2H48M5.4775808S"),
+        ];
+        for (ticks, expected) in cases {
+            let delta = FileTimeDelta::from_ticks(*ticks);
+            assert_eq!(delta.to_iso8601(), *expected);
+            assert_eq!(FileTimeDelta::parse_iso8601(expected), Ok(delta));
+        }
+    }
+
+    #[test]
+    fn sum() {
+        let deltas = [
+            FileTimeDelta::from_ticks(5),
+            FileTimeDelta::from_ticks(-7),
+            FileTimeDelta::from_ticks(10),
+        ];
+        assert_eq!(
+            deltas.iter().sum::<FileTimeDelta>(),
+            FileTimeDelta::from_ticks(8)
+        );
+        assert_eq!(
+            FileTimeDelta::checked_sum(vec![FileTimeDelta::MAX, FileTimeDelta::from_ticks(1)]),
+            None
+        );
+        // Intermediate overflow is reported even if the total would fit
+        assert_eq!(
+            FileTimeDelta::checked_sum(vec![
+                FileTimeDelta::MAX,
+                FileTimeDelta::from_ticks(1),
+                FileTimeDelta::from_ticks(-1)
+            ]),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn sum_overflow() {
+        let _: FileTimeDelta = vec![FileTimeDelta::MIN, FileTimeDelta::from_ticks(-1)]
+            .into_iter()
+            .sum();
+    }
+
+    #[test]
+    fn datetime_arithmetic() {
+        use chrono::TimeZone;
+
+        let ft = FileTime::from_i64(130139712831482830);
+        let dt = Utc.with_ymd_and_hms(2013, 5, 25, 16, 1, 0).unwrap();
+        let later = ft.to_datetime() - dt;
+        assert_eq!(later, chrono::Duration::nanoseconds(23_148_283_000));
+        // lhs - rhs, positive when lhs is later, matching a common type
+        assert_eq!(ft - dt, later);
+        assert_eq!(dt - ft, -later);
+        assert_eq!((ft - FileTime::from_datetime(dt)).to_duration(), later);
+        assert_eq!(dt - ft.to_datetime(), dt - ft);
+
+        // Sub-tick nanoseconds of the DateTime are kept
+        let dt_nanos = dt + chrono::Duration::nanoseconds(1);
+        assert_eq!(ft - dt_nanos, later - chrono::Duration::nanoseconds(1));
+
+        let delta = FileTimeDelta::from_ticks(-15);
+        assert_eq!(dt + delta, dt - chrono::Duration::nanoseconds(1500));
+        assert_eq!(dt - delta, dt + chrono::Duration::nanoseconds(1500));
+        assert_eq!(
+            FileTime::from_datetime(dt) + delta,
+            FileTime::from_datetime(dt + delta)
+        );
+        assert_eq!(
+            FileTimeDelta::MAX.checked_add_to_datetime(DateTime::<Utc>::MAX_UTC),
+            None
+        );
+        assert_eq!(
+            FileTimeDelta::MAX.checked_sub_from_datetime(DateTime::<Utc>::MIN_UTC),
+            None
+        );
+        assert_eq!(
+            FileTimeDelta::ZERO.checked_add_to_datetime(DateTime::<Utc>::MAX_UTC),
+            Some(DateTime::<Utc>::MAX_UTC)
+        );
+    }
+
+    #[test]
+    fn datetime_difference_above_max() {
+        use chrono::TimeZone;
+
+        let dt = Utc.with_ymd_and_hms(2013, 5, 25, 16, 1, 0).unwrap();
+        let raw_max = FileTime::MAX_RAW;
+        assert_eq!(raw_max.checked_signed_duration_since(dt), None);
+        assert_eq!(raw_max.checked_signed_duration_until(dt), None);
+        let max = FileTime::MAX.to_datetime();
+        assert_eq!(
+            FileTime::MAX.checked_signed_duration_since(dt),
+            Some(max - dt)
+        );
+        assert_eq!(
+            FileTime::MAX.checked_signed_duration_until(dt),
+            Some(dt - max)
+        );
+        assert_eq!(FileTime::MAX - dt, max - dt);
+    }
+
+    #[test]
+    #[should_panic(expected = "FileTime - DateTime out of range")]
+    fn filetime_sub_datetime_above_max() {
+        let _ = FileTime::MAX_RAW - DateTime::<Utc>::MIN_UTC;
+    }
+
+    #[test]
+    #[should_panic(expected = "DateTime - FileTime out of range")]
+    fn datetime_sub_filetime_above_max() {
+        let _ = DateTime::<Utc>::MIN_UTC - FileTime::MAX_RAW;
+    }
+
+    #[test]
+    #[should_panic(expected = "DateTime + FileTimeDelta out of range")]
+    fn datetime_add_overflow() {
+        let _ = DateTime::<Utc>::MAX_UTC + FileTimeDelta::from_ticks(1);
+    }
+
+    mod proptests {
+        use super::*;
+        use ::proptest::prelude::*;
+
+        fn ticks_and_shuffled() -> impl Strategy<Value = (Vec<i64>, Vec<i64>)> {
+            // Bounded so that no ordering of up to 32 values can overflow
+            prop::collection::vec(-(1i64 << 56)..(1i64 << 56), 0..32)
+                .prop_flat_map(|v| (Just(v.clone()), Just(v).prop_shuffle()))
+        }
+
+        proptest! {
+            #[test]
+            fn sum_order_independent((ticks, shuffled) in ticks_and_shuffled()) {
+                let total: FileTimeDelta = ticks.into_iter().map(FileTimeDelta::from_ticks).sum();
+                let shuffled: FileTimeDelta = shuffled.into_iter().map(FileTimeDelta::from_ticks).sum();
+                prop_assert_eq!(total, shuffled);
+            }
+
+            #[test]
+            fn display_parse_roundtrip(ticks: i64) {
+                let delta = FileTimeDelta::from_ticks(ticks);
+                prop_assert_eq!(FileTimeDelta::parse(&delta.to_string()), Ok(delta));
+            }
+
+            #[test]
+            #[cfg(feature = "std")]
+            fn iso8601_roundtrip(ticks: i64) {
+                let delta = FileTimeDelta::from_ticks(ticks);
+                prop_assert_eq!(FileTimeDelta::parse_iso8601(&delta.to_iso8601()), Ok(delta));
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Diagnostic report showing one value in the common representations
+use crate::clock::{Clock, SystemClock};
+use crate::humanize::RelativeTime;
+use crate::{FileTime, FileTimeDelta};
+use core::fmt;
+
+const OUT_OF_RANGE: &str = "out of range";
+
+/// One FileTime in several representations, from [`FileTime::describe`]
+///
+/// Meant for triage of unknown 64 bit values: the `Display` impl prints one
+/// labelled line per field. Fields the value cannot be represented in are
+/// `None` and render as "out of range".
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct FileTimeReport {
+    /// Raw ticks, printed in decimal and hex
+    pub raw: u64,
+    /// The raw ticks as stored on disk, little-endian
+    pub le_bytes: [u8; 8],
+    /// RFC 3339 in UTC with 7 digits, `None` above [`FileTime::MAX`]
+    pub rfc3339: Option<String>,
+    /// Seconds since the Unix-Epoch, truncated towards the past
+    pub unix_seconds: i64,
+    /// Milliseconds since the Unix-Epoch, truncated towards the past
+    pub unix_millis: i64,
+    /// Microseconds since the Unix-Epoch, truncated towards the past
+    pub unix_micros: i64,
+    /// Microseconds since 1601-01-01 (WebKit / Chrome timestamp)
+    pub webkit_micros: i64,
+    /// Time from the value to the reference, negative for future values
+    ///
+    /// `None` if the distance does not fit a [`FileTimeDelta`].
+    pub age: Option<FileTimeDelta>,
+}
+
+impl FileTime {
+    /// Report of this value in the common representations, aged relative to now
+    pub fn describe(&self) -> FileTimeReport {
+        self.describe_with(&SystemClock)
+    }
+
+    /// Report of this value, aged relative to the current time of `clock`
+    /// ```
+    /// use filetime_type::{FileTime, FixedClock};
+    ///
+    /// let ft = FileTime::from_i64(130139712831482830);
+    /// let clock = FixedClock(FileTime::from_i64(130139712831482830 + 3 * 864_000_000_000));
+    /// let report = ft.describe_with(&clock);
+    /// assert_eq!(report.rfc3339.as_deref(), Some("2013-05-25T16:01:23.1482830Z"));
+    /// assert_eq!(report.unix_seconds, 1369497683);
+    /// assert!(report.to_string().contains("age:           3 days ago"));
+    /// ```
+    pub fn describe_with<C: Clock + ?Sized>(&self, clock: &C) -> FileTimeReport {
+        FileTimeReport {
+            raw: self.ticks,
+            le_bytes: self.ticks.to_le_bytes(),
+            rfc3339: self.to_rfc3339().ok(),
+            unix_seconds: self.to_unix_seconds(),
+            unix_millis: self.to_unix_millis(),
+            // Every u64 tick count divided by 10 fits into an i64
+            unix_micros: self.unix_ticks().div_euclid(10) as i64,
+            webkit_micros: self.to_webkit_micros(),
+            age: clock.now_filetime().checked_delta_since(*self),
+        }
+    }
+}
+
+impl fmt::Display for FileTimeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "raw:           {}", self.raw)?;
+        writeln!(f, "hex:           0x{:016X}", self.raw)?;
+        write!(f, "le bytes:     ")?;
+        for byte in &self.le_bytes {
+            write!(f, " {:02X}", byte)?;
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "rfc 3339:      {}",
+            self.rfc3339.as_deref().unwrap_or(OUT_OF_RANGE)
+        )?;
+        writeln!(f, "unix seconds:  {}", self.unix_seconds)?;
+        writeln!(f, "unix millis:   {}", self.unix_millis)?;
+        writeln!(f, "unix micros:   {}", self.unix_micros)?;
+        writeln!(f, "webkit micros: {}", self.webkit_micros)?;
+        match self.age {
+            Some(age) => write!(
+                f,
+                "age:           {} ({})",
+                RelativeTime::from_delta(-age),
+                age
+            ),
+            None => write!(f, "age:           {}", OUT_OF_RANGE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::clock::FixedClock;
+
+    const TICKS_PER_HOUR: i64 = 36_000_000_000;
+
+    #[test]
+    fn snapshot() {
+        let ft = FileTime::from_i64(130139712831482830);
+        let clock = FixedClock(FileTime::from_i64(130139712831482830 + 50 * TICKS_PER_HOUR));
+        assert_eq!(
+            ft.describe_with(&clock).to_string(),
+            "raw:           130139712831482830\n\
+             hex:           0x01CE59611A7DEBCE\n\
+             le bytes:      CE EB 7D 1A 61 59 CE 01\n\
+             rfc 3339:      2013-05-25T16:01:23.1482830Z\n\
+             unix seconds:  1369497683\n\
+             unix millis:   1369497683148\n\
+             unix micros:   1369497683148283\n\
+             webkit micros: 13013971283148283\n\
+             age:           2 days ago (2d 2h 0m 0.0000000s)"
+        );
+        let future = FixedClock(FileTime::from_i64(130139712831482830 - 2 * TICKS_PER_HOUR));
+        let report = ft.describe_with(&future);
+        assert_eq!(
+            report.age,
+            Some(FileTimeDelta::from_ticks(-2 * TICKS_PER_HOUR))
+        );
+        assert!(report
+            .to_string()
+            .ends_with("age:           in 2 hours (-2h 0m 0.0000000s)"));
+    }
+
+    #[test]
+    fn out_of_range() {
+        let clock = FixedClock(FileTime::from_i64(130139712831482830));
+        assert_eq!(
+            FileTime::MAX_RAW.describe_with(&clock).to_string(),
+            "raw:           18446744073709551615\n\
+             hex:           0xFFFFFFFFFFFFFFFF\n\
+             le bytes:      FF FF FF FF FF FF FF FF\n\
+             rfc 3339:      out of range\n\
+             unix seconds:  1833029933770\n\
+             unix millis:   1833029933770955\n\
+             unix micros:   1833029933770955161\n\
+             webkit micros: 1844674407370955161\n\
+             age:           out of range"
+        );
+        // MAX still has a date, and before 1970 the Unix values are negative
+        let report = FileTime::MAX.describe_with(&clock);
+        assert_eq!(
+            report.rfc3339.as_deref(),
+            Some("+30828-09-14T02:48:05.4775807Z")
+        );
+        let report = FileTime::MIN.describe_with(&clock);
+        assert_eq!(report.unix_seconds, -11644473600);
+        assert_eq!(report.unix_micros, -11644473600000000);
+        assert_eq!(report.webkit_micros, 0);
+        assert!(report.age.is_some());
+    }
+}
@@ -0,0 +1,186 @@
+//! Guessing the encoding of bare 64-bit timestamps
+//!
+//! Forensic artifacts often store timestamps as plain integers without saying
+//! which epoch and unit they use. [`FileTime::detect`] decodes a value in every
+//! supported encoding and keeps the interpretations that land in a
+//! plausibility window.
+use crate::FileTime;
+use chrono::{DateTime, TimeZone, Utc};
+
+/// Encoding of a bare integer timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TimestampFormat {
+    /// 100ns ticks since 1601-01-01T00:00:00Z
+    FileTime,
+    /// Seconds since the Unix-Epoch (1970-01-01T00:00:00Z)
+    UnixSeconds,
+    /// Milliseconds since the Unix-Epoch, as used by Java and JavaScript
+    UnixMillis,
+    /// Microseconds since the Unix-Epoch
+    UnixMicros,
+    /// Nanoseconds since the Unix-Epoch
+    UnixNanos,
+    /// Microseconds since 1601-01-01T00:00:00Z, as used by Chrome / WebKit
+    WebKitMicros,
+}
+
+impl TimestampFormat {
+    /// Every format tried by [`FileTime::detect`]
+    pub const ALL: [TimestampFormat; 6] = [
+        Self::FileTime,
+        Self::UnixSeconds,
+        Self::UnixMillis,
+        Self::UnixMicros,
+        Self::UnixNanos,
+        Self::WebKitMicros,
+    ];
+
+    /// Decode `value` in this format, `None` if it lies outside of the FILETIME range
+    pub fn decode(self, value: i64) -> Option<FileTime> {
+        let unix = |ticks: i128| ticks + i128::from(FileTime::EPOCH_AS_FILETIME);
+        let value = i128::from(value);
+        let ticks = match self {
+            Self::FileTime => value,
+            Self::UnixSeconds => unix(value * i128::from(FileTime::HUNDREDS_OF_NANOSECONDS)),
+            Self::UnixMillis => unix(value * i128::from(FileTime::TICKS_PER_MILLISECOND)),
+            Self::UnixMicros => unix(value * 10),
+            Self::UnixNanos => unix(value.div_euclid(100)),
+            Self::WebKitMicros => value * 10,
+        };
+        FileTime::from_ticks_i128(ticks).ok()
+    }
+}
+
+/// One interpretation found by [`FileTime::detect`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetectedFormat {
+    /// Assumed encoding of the input
+    pub format: TimestampFormat,
+    /// The input decoded in that encoding
+    pub filetime: FileTime,
+}
+
+impl FileTime {
+    /// Interpretations of a bare integer timestamp within a plausibility window
+    ///
+    /// `value` is decoded in each [`TimestampFormat`], candidates are kept if
+    /// they lie within `window` (both ends included), by default
+    /// 1990-01-01T00:00:00Z to 2040-01-01T00:00:00Z. The result is ordered by
+    /// plausibility: closest to the middle of the window first, ties in the
+    /// order of [`TimestampFormat::ALL`]. An empty result means no format fits.
+    /// ```
+    /// use filetime_type::{FileTime, TimestampFormat};
+    ///
+    /// let found = FileTime::detect(1688140800000, None);
+    /// assert_eq!(found.len(), 1);
+    /// assert_eq!(found[0].format, TimestampFormat::UnixMillis);
+    /// assert_eq!(found[0].filetime.to_datetime().to_rfc3339(), "2023-06-30T16:00:00+00:00");
+    /// ```
+    pub fn detect(
+        value: i64,
+        window: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    ) -> Vec<DetectedFormat> {
+        let (start, end) = window.unwrap_or_else(|| {
+            (
+                Utc.with_ymd_and_hms(1990, 1, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2040, 1, 1, 0, 0, 0).unwrap(),
+            )
+        });
+        let center = start + (end - start) / 2;
+        let mut found: Vec<_> = TimestampFormat::ALL
+            .iter()
+            .filter_map(|&format| {
+                let filetime = format.decode(value)?;
+                let dt = filetime.to_datetime_checked().ok()?;
+                if dt < start || dt > end {
+                    return None;
+                }
+                Some(DetectedFormat { format, filetime })
+            })
+            .collect();
+        // Stable sort keeps the ALL order for ties
+        found.sort_by_key(|candidate| (candidate.filetime.to_datetime() - center).abs());
+        found
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn formats(value: i64, window: Option<(DateTime<Utc>, DateTime<Utc>)>) -> Vec<TimestampFormat> {
+        FileTime::detect(value, window)
+            .into_iter()
+            .map(|candidate| candidate.format)
+            .collect()
+    }
+
+    #[test]
+    fn unambiguous() {
+        // All decode to 2013-05-25T16:01:23Z
+        let expected = FileTime::from_i64(130139712830000000);
+        for &(value, format) in &[
+            (130139712830000000, TimestampFormat::FileTime),
+            (1369497683, TimestampFormat::UnixSeconds),
+            (1369497683000, TimestampFormat::UnixMillis),
+            (1369497683000000, TimestampFormat::UnixMicros),
+            (1369497683000000000, TimestampFormat::UnixNanos),
+            (13013971283000000, TimestampFormat::WebKitMicros),
+        ] {
+            assert_eq!(
+                FileTime::detect(value, None),
+                vec![DetectedFormat {
+                    format,
+                    filetime: expected
+                }],
+                "{}",
+                value
+            );
+        }
+        assert_eq!(formats(0, None), []);
+        assert_eq!(formats(-1, None), []);
+        assert_eq!(formats(i64::MAX, None), []);
+    }
+
+    #[test]
+    fn ambiguous() {
+        let window = (
+            Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2100, 1, 1, 0, 0, 0).unwrap(),
+        );
+        // 2013-05-25 as FILETIME, 1974-02-15 as Unix nanoseconds
+        let found = FileTime::detect(130139712831482830, Some(window));
+        assert_eq!(
+            found.iter().map(|c| c.format).collect::<Vec<_>>(),
+            [TimestampFormat::FileTime, TimestampFormat::UnixNanos]
+        );
+        assert_eq!(found[0].filetime, FileTime::from_i64(130139712831482830));
+        assert_eq!(
+            found[1].filetime.to_datetime().date_naive().to_string(),
+            "1974-02-15"
+        );
+        // The default window excludes the 1974 reading
+        assert_eq!(
+            formats(130139712831482830, None),
+            [TimestampFormat::FileTime]
+        );
+    }
+
+    #[test]
+    fn window_bounds_inclusive() {
+        let start = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2001, 1, 1, 0, 0, 0).unwrap();
+        let secs = start.timestamp();
+        assert_eq!(
+            formats(secs, Some((start, end))),
+            [TimestampFormat::UnixSeconds]
+        );
+        assert_eq!(formats(secs - 1, Some((start, end))), []);
+        assert_eq!(
+            formats(end.timestamp(), Some((start, end))),
+            [TimestampFormat::UnixSeconds]
+        );
+        assert_eq!(formats(end.timestamp() + 1, Some((start, end))), []);
+    }
+}
@@ -0,0 +1,145 @@
+//! Diesel `ToSql`/`FromSql` support
+//!
+//! `AsExpression` and `FromSqlRow` are derived on [`FileTime`] itself. This
+//! module is not called `diesel` as those derives import the crate by name.
+//!
+//! - SQLite (`diesel-sqlite`): `BigInt` holding the raw FILETIME ticks,
+//!   negative values fail to deserialize, raw values above [`FileTime::MAX`]
+//!   to serialize.
+//! - PostgreSQL (`diesel-postgres`): `Timestamptz`, the last tick digit (100ns)
+//!   is truncated as PostgreSQL keeps microseconds only.
+use crate::FileTime;
+use ::diesel::deserialize::{self, FromSql};
+use ::diesel::serialize::{self, Output, ToSql};
+
+#[cfg(feature = "diesel-sqlite")]
+mod sqlite {
+    use super::*;
+    use ::diesel::serialize::IsNull;
+    use ::diesel::sql_types::BigInt;
+    use ::diesel::sqlite::{Sqlite, SqliteValue};
+
+    impl ToSql<BigInt, Sqlite> for FileTime {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+            out.set_value(self.to_i64_checked()?);
+            Ok(IsNull::No)
+        }
+    }
+
+    impl FromSql<BigInt, Sqlite> for FileTime {
+        fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+            let ticks = <i64 as FromSql<BigInt, Sqlite>>::from_sql(value)?;
+            Ok(FileTime::from_i64_checked(ticks)?)
+        }
+    }
+}
+
+#[cfg(feature = "diesel-postgres")]
+mod postgres {
+    use super::*;
+    use ::diesel::pg::data_types::PgTimestamp;
+    use ::diesel::pg::{Pg, PgValue};
+    use ::diesel::sql_types::Timestamptz;
+
+    impl ToSql<Timestamptz, Pg> for FileTime {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+            let micros = PgTimestamp(self.to_pg_micros());
+            <PgTimestamp as ToSql<Timestamptz, Pg>>::to_sql(&micros, &mut out.reborrow())
+        }
+    }
+
+    impl FromSql<Timestamptz, Pg> for FileTime {
+        fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+            let PgTimestamp(micros) = <PgTimestamp as FromSql<Timestamptz, Pg>>::from_sql(value)?;
+            Ok(FileTime::from_pg_micros(micros)?)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "diesel-sqlite"))]
+mod test {
+    use crate::{FileTime, FileTimeError};
+    use ::diesel::dsl::sql;
+    use ::diesel::prelude::*;
+    use ::diesel::result::{DeserializeFieldError, Error};
+    use ::diesel::sql_types::BigInt;
+
+    ::diesel::table! {
+        events (id) {
+            id -> BigInt,
+            at -> BigInt,
+        }
+    }
+
+    #[derive(Debug, PartialEq, Queryable, Insertable)]
+    #[diesel(table_name = events)]
+    struct Event {
+        id: i64,
+        at: FileTime,
+    }
+
+    fn connect() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        ::diesel::sql_query("CREATE TABLE events (id BIGINT PRIMARY KEY, at BIGINT NOT NULL)")
+            .execute(&mut conn)
+            .unwrap();
+        conn
+    }
+
+    #[test]
+    fn sqlite_roundtrip() {
+        let mut conn = connect();
+        let rows = vec![
+            Event {
+                id: 0,
+                at: FileTime::from_i64(0),
+            },
+            Event {
+                id: 1,
+                at: FileTime::from_i64(133326144001230000),
+            },
+        ];
+        for row in &rows {
+            ::diesel::insert_into(events::table)
+                .values(row)
+                .execute(&mut conn)
+                .unwrap();
+        }
+
+        let loaded: Vec<Event> = events::table.order(events::id).load(&mut conn).unwrap();
+        assert_eq!(loaded, rows);
+
+        let later: Vec<i64> = events::table
+            .filter(events::at.gt(FileTime::from_i64(1)))
+            .select(events::id)
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(later, vec![1]);
+
+        // The raw ticks are stored as-is
+        let raw: i64 = events::table
+            .filter(events::id.eq(1))
+            .select(events::at)
+            .first(&mut conn)
+            .unwrap();
+        assert_eq!(raw, 133326144001230000);
+    }
+
+    #[test]
+    fn sqlite_deserialize_negative() {
+        let mut conn = connect();
+        let err = ::diesel::select(sql::<BigInt>("-1"))
+            .get_result::<FileTime>(&mut conn)
+            .unwrap_err();
+        match err {
+            Error::DeserializationError(source) => {
+                let field = source.downcast_ref::<DeserializeFieldError>().unwrap();
+                assert_eq!(
+                    field.error.downcast_ref::<FileTimeError>(),
+                    Some(&FileTimeError::BeforeEpoch)
+                );
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+}
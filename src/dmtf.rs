@@ -0,0 +1,160 @@
+//! DMTF / CIM datetime strings, as returned by WMI
+//!
+//! Layout: `yyyymmddHHMMSS.mmmmmmsUUU`, 25 characters, local time with
+//! microsecond precision followed by the signed offset to UTC in minutes.
+use crate::parse;
+use crate::{FileTime, FileTimeError};
+use chrono::{Datelike, Duration};
+
+const DMTF: &str = "DMTF datetime";
+
+impl FileTime {
+    /// Parse a DMTF datetime string, e.g. `20230125093000.123456+060`
+    ///
+    /// The layout is validated strictly. Strings containing the `*` wildcard
+    /// and interval values (`ddddddddHHMMSS.mmmmmm:000`) are rejected, as they
+    /// don't describe a single instant.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::parse_dmtf("20230125093000.123456+060").unwrap();
+    /// assert_eq!(ft.to_datetime().to_rfc3339(), "2023-01-25T08:30:00.123456+00:00");
+    /// ```
+    pub fn parse_dmtf(s: &str) -> Result<Self, FileTimeError> {
+        let invalid = |reason| FileTimeError::InvalidFormat {
+            format: DMTF,
+            reason,
+        };
+        let b = s.as_bytes();
+        if b.len() != 25 {
+            return Err(invalid("expected 25 characters"));
+        }
+        if b.contains(&b'*') {
+            return Err(invalid("wildcard fields are not supported"));
+        }
+        if b[21] == b':' {
+            return Err(invalid("interval values are not supported"));
+        }
+        if b[14] != b'.' {
+            return Err(invalid("expected '.' at position 14"));
+        }
+        let sign = match b[21] {
+            b'+' => 1,
+            b'-' => -1,
+            _ => return Err(invalid("expected '+' or '-' at position 21")),
+        };
+        let field = |range: std::ops::Range<usize>| {
+            parse::digits(&b[range]).ok_or_else(|| invalid("expected digits"))
+        };
+        let offset = sign * field(22..25)? as i64;
+        let local = parse::datetime(
+            field(0..4)? as i32,
+            field(4..6)?,
+            field(6..8)?,
+            field(8..10)?,
+            field(10..12)?,
+            field(12..14)?,
+            field(15..21)? * 1000,
+        )
+        .ok_or_else(|| invalid("invalid date or time"))?;
+        Self::from_datetime_checked(local - Duration::minutes(offset))
+    }
+
+    /// Format as DMTF datetime string in the given offset to UTC
+    ///
+    /// Ticks below microsecond precision are truncated. Fails with
+    /// [`FileTimeError::OutOfRange`] if the offset is not within ±999 minutes
+    /// or the local year exceeds four digits.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_i64(128930364000001000);
+    /// assert_eq!(ft.to_dmtf(0).unwrap(), "20090725230000.000100+000");
+    /// assert_eq!(ft.to_dmtf(-300).unwrap(), "20090725180000.000100-300");
+    /// ```
+    pub fn to_dmtf(&self, utc_offset_minutes: i16) -> Result<String, FileTimeError> {
+        if !(-999..=999).contains(&utc_offset_minutes) {
+            return Err(FileTimeError::OutOfRange(DMTF));
+        }
+        let local = self
+            .to_datetime_checked()
+            .map_err(|_| FileTimeError::OutOfRange(DMTF))?
+            + Duration::minutes(i64::from(utc_offset_minutes));
+        if local.year() > 9999 {
+            return Err(FileTimeError::OutOfRange(DMTF));
+        }
+        Ok(format!(
+            "{}{}{:03}",
+            local.format("%Y%m%d%H%M%S%.6f"),
+            if utc_offset_minutes < 0 { '-' } else { '+' },
+            utc_offset_minutes.abs()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn utc() {
+        let ft = FileTime::parse_dmtf("20090725230000.000100+000").unwrap();
+        assert_eq!(ft, FileTime::from_i64(128930364000001000));
+        assert_eq!(ft.to_dmtf(0).unwrap(), "20090725230000.000100+000");
+    }
+
+    #[test]
+    fn offsets() {
+        let utc = FileTime::parse_dmtf("20230125083000.123456+000").unwrap();
+
+        let east = FileTime::parse_dmtf("20230125093000.123456+060").unwrap();
+        assert_eq!(east, utc);
+        assert_eq!(utc.to_dmtf(60).unwrap(), "20230125093000.123456+060");
+
+        let west = FileTime::parse_dmtf("20230124233000.123456-540").unwrap();
+        assert_eq!(west, utc);
+        assert_eq!(utc.to_dmtf(-540).unwrap(), "20230124233000.123456-540");
+
+        assert_eq!(utc.to_dmtf(1000), Err(FileTimeError::OutOfRange(DMTF)));
+        assert_eq!(
+            FileTime::MAX_RAW.to_dmtf(0),
+            Err(FileTimeError::OutOfRange(DMTF))
+        );
+    }
+
+    #[test]
+    fn truncation() {
+        let ft = FileTime::from_i64(128930364000001009);
+        assert_eq!(ft.to_dmtf(0).unwrap(), "20090725230000.000100+000");
+    }
+
+    #[test]
+    fn invalid() {
+        let reason = |s| match FileTime::parse_dmtf(s) {
+            Err(FileTimeError::InvalidFormat {
+                format: DMTF,
+                reason,
+            }) => reason,
+            other => panic!("unexpected result {:?}", other),
+        };
+        assert_eq!(
+            reason("2023012509****.******+060"),
+            "wildcard fields are not supported"
+        );
+        assert_eq!(
+            reason("00000001093000.000000:000"),
+            "interval values are not supported"
+        );
+        assert_eq!(reason("20230125093000.123456+60"), "expected 25 characters");
+        assert_eq!(
+            reason("20230125093000,123456+060"),
+            "expected '.' at position 14"
+        );
+        assert_eq!(
+            reason("20230125093000.123456 060"),
+            "expected '+' or '-' at position 21"
+        );
+        assert_eq!(reason("2023012509300a.123456+060"), "expected digits");
+        assert_eq!(reason("20230230093000.123456+060"), "invalid date or time");
+    }
+}
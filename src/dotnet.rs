@@ -0,0 +1,186 @@
+//! .NET `DateTime.ToBinary()` / `DateTime.FromBinary()` values
+//!
+//! An `i64` with the `DateTimeKind` in the top two bits and ticks since
+//! 0001-01-01 in the low 62:
+//!
+//! ```text
+//! 00  Unspecified  ticks of the wall clock time, zone unknown
+//! 01  Utc          ticks of the UTC time
+//! 1x  Local        ticks of the UTC time, converted with the writer's offset
+//! ```
+//!
+//! Local values store the instant, the wall clock time is lost: `ToBinary`
+//! subtracts the machine's UTC offset and `FromBinary` adds the reader's. A
+//! local time shortly after 0001-01-01 in a zone east of UTC gives negative
+//! UTC ticks, which are stored wrapped around 2^62.
+use crate::{FileTime, FileTimeError};
+
+/// Ticks from 0001-01-01 to the FILETIME-Epoch 1601-01-01
+const EPOCH_AS_DOTNET: i64 = 504_911_232_000_000_000;
+/// `DateTime.MaxValue.Ticks`, 9999-12-31T23:59:59.9999999
+const MAX_DOTNET_TICKS: i64 = 3_155_378_975_999_999_999;
+const TICKS_MASK: i64 = 0x3FFF_FFFF_FFFF_FFFF;
+const KIND_UTC: i64 = 0x4000_0000_0000_0000;
+const KIND_LOCAL: i64 = i64::MIN;
+/// Local values within a day below 2^62 are wrapped negative ticks
+const TICKS_CEILING: i64 = 0x4000_0000_0000_0000;
+const TICKS_PER_DAY: i64 = 864_000_000_000;
+
+/// `System.DateTimeKind` of a [`FileTime::from_dotnet_binary`] value
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum DotNetKind {
+    /// `DateTimeKind.Unspecified`, a wall clock time of unknown zone
+    Unspecified,
+    /// `DateTimeKind.Utc`
+    Utc,
+    /// `DateTimeKind.Local`, stored as UTC instant
+    Local,
+}
+
+impl FileTime {
+    /// Decode a `DateTime.ToBinary()` value into the instant and its kind
+    ///
+    /// Utc and Local values give the exact UTC instant; for Local, shift it
+    /// with [`to_local_filetime`](Self::to_local_filetime) to get a wall
+    /// clock time. Unspecified values give the wall clock ticks as if they
+    /// were UTC, use [`from_local_filetime`](Self::from_local_filetime) with
+    /// the writer's offset if known. Values before 1601-01-01 fail with
+    /// [`FileTimeError::BeforeEpoch`], ticks beyond `DateTime.MaxValue` with
+    /// [`FileTimeError::InvalidFormat`] as in .NET.
+    /// ```
+    /// use filetime_type::{DotNetKind, FileTime};
+    ///
+    /// // new DateTime(2000, 1, 1, 0, 0, 0, DateTimeKind.Utc).ToBinary()
+    /// let (ft, kind) = FileTime::from_dotnet_binary(5242508834427387904).unwrap();
+    /// assert_eq!(ft, FileTime::from_ymd_hms(2000, 1, 1, 0, 0, 0).unwrap());
+    /// assert_eq!(kind, DotNetKind::Utc);
+    /// ```
+    pub fn from_dotnet_binary(value: i64) -> Result<(FileTime, DotNetKind), FileTimeError> {
+        let mut ticks = value & TICKS_MASK;
+        let kind = if value & KIND_LOCAL != 0 {
+            if ticks > TICKS_CEILING - TICKS_PER_DAY {
+                ticks -= TICKS_CEILING;
+            }
+            DotNetKind::Local
+        } else if value & KIND_UTC != 0 {
+            DotNetKind::Utc
+        } else {
+            DotNetKind::Unspecified
+        };
+        if ticks > MAX_DOTNET_TICKS {
+            return Err(FileTimeError::InvalidFormat {
+                format: ".NET DateTime binary",
+                reason: "ticks beyond DateTime.MaxValue",
+            });
+        }
+        FileTime::from_i64_checked(ticks - EPOCH_AS_DOTNET).map(|ft| (ft, kind))
+    }
+
+    /// Encode as `DateTime.ToBinary()` value of the given kind
+    ///
+    /// For [`DotNetKind::Local`] `self` is the UTC instant, as stored by
+    /// .NET; `FromBinary` shows it in the reader's time zone. For
+    /// [`DotNetKind::Unspecified`] `self` is written as wall clock time.
+    ///
+    /// # Panics
+    ///
+    /// If `self` lies after 9999-12-31T23:59:59.9999999, `DateTime.MaxValue`.
+    /// ```
+    /// use filetime_type::{DotNetKind, FileTime};
+    ///
+    /// let ft = FileTime::from_ymd_hms(2000, 1, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(ft.to_dotnet_binary(DotNetKind::Unspecified), 630822816000000000);
+    /// assert_eq!(FileTime::from_dotnet_binary(ft.to_dotnet_binary(DotNetKind::Local)), Ok((ft, DotNetKind::Local)));
+    /// ```
+    pub fn to_dotnet_binary(&self, kind: DotNetKind) -> i64 {
+        let ticks = match self.to_i64_checked() {
+            Ok(ticks) if ticks <= MAX_DOTNET_TICKS - EPOCH_AS_DOTNET => ticks + EPOCH_AS_DOTNET,
+            _ => panic!("FileTime beyond DateTime.MaxValue"),
+        };
+        match kind {
+            DotNetKind::Unspecified => ticks,
+            DotNetKind::Utc => ticks | KIND_UTC,
+            DotNetKind::Local => ticks | KIND_LOCAL,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // No .NET runtime was at hand: the values are computed by the published
+    // ToBinary algorithm (ticks since 0001, kind in bits 62/63, local times
+    // stored as UTC), with the C# expressions they correspond to.
+    // new DateTime(2013, 5, 25, 16, 1, 23, DateTimeKind.Utc).AddTicks(1482830).ToBinary()
+    const UTC_2013: i64 = 5246736963258870734;
+    // The same with DateTimeKind.Unspecified, just the ticks
+    const UNSPECIFIED_2013: i64 = 635050944831482830;
+    // Local 18:01:23.1482830 written at UTC+02:00, stored as 16:01:23.1482830 UTC
+    const LOCAL_2013: i64 = -8588321092023292978;
+    // DateTime(1, 1, 1, 0, 0, 0, Local) written at UTC+01:00: -1 hour, wrapped around 2^62
+    const LOCAL_WRAPPED: i64 = -4611686054427387904;
+
+    #[test]
+    fn kinds() {
+        let ft = FileTime::from_i64(130139712831482830);
+        for &(value, kind) in &[
+            (UTC_2013, DotNetKind::Utc),
+            (UNSPECIFIED_2013, DotNetKind::Unspecified),
+            (LOCAL_2013, DotNetKind::Local),
+        ] {
+            assert_eq!(FileTime::from_dotnet_binary(value), Ok((ft, kind)));
+            assert_eq!(ft.to_dotnet_binary(kind), value, "{:?}", kind);
+        }
+        // Both kind bits set is local as well, .NET's ambiguous DST flag
+        assert_eq!(
+            FileTime::from_dotnet_binary(UNSPECIFIED_2013 | KIND_UTC | KIND_LOCAL),
+            Ok((ft, DotNetKind::Local))
+        );
+    }
+
+    #[test]
+    fn range() {
+        let epoch = EPOCH_AS_DOTNET;
+        assert_eq!(
+            FileTime::from_dotnet_binary(epoch | KIND_UTC),
+            Ok((FileTime::MIN, DotNetKind::Utc))
+        );
+        assert_eq!(
+            FileTime::from_dotnet_binary(epoch - 1),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        // DateTime.MinValue
+        assert_eq!(
+            FileTime::from_dotnet_binary(0),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::from_dotnet_binary(LOCAL_WRAPPED),
+            Err(FileTimeError::BeforeEpoch)
+        );
+
+        let max = FileTime::from_ymd_hms_nano(9999, 12, 31, 23, 59, 59, 999_999_900).unwrap();
+        assert_eq!(
+            max.to_dotnet_binary(DotNetKind::Unspecified),
+            MAX_DOTNET_TICKS
+        );
+        assert_eq!(
+            FileTime::from_dotnet_binary(MAX_DOTNET_TICKS),
+            Ok((max, DotNetKind::Unspecified))
+        );
+        assert_eq!(
+            FileTime::from_dotnet_binary(MAX_DOTNET_TICKS + 1),
+            Err(FileTimeError::InvalidFormat {
+                format: ".NET DateTime binary",
+                reason: "ticks beyond DateTime.MaxValue",
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "FileTime beyond DateTime.MaxValue")]
+    fn beyond_max_value() {
+        FileTime::MAX.to_dotnet_binary(DotNetKind::Utc);
+    }
+}
@@ -0,0 +1,99 @@
+use core::fmt;
+
+/// Errors returned by fallible FileTime conversions
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FileTimeError {
+    /// The instant lies before the FILETIME-Epoch (1601-01-01T00:00:00Z)
+    BeforeEpoch,
+    /// The instant lies beyond the largest representable FILETIME
+    Overflow,
+    /// A floating point input was NaN or infinite
+    NotFinite,
+    /// The value lies outside of the range supported by the named format
+    OutOfRange(&'static str),
+    /// The start of a range lies after its end
+    InvalidRange,
+    /// A negative duration was passed where only non-negative ones are valid
+    NegativeDuration,
+    /// The input holds the "not specified" encoding of its format
+    NotSpecified,
+    /// The two copies of the high part of a KSYSTEM_TIME disagree, it was read while being updated
+    TornRead,
+    /// The input does not match the layout of the named format
+    InvalidFormat {
+        /// Name of the expected format
+        format: &'static str,
+        /// Why the input was rejected
+        reason: &'static str,
+    },
+    /// A date, time or step component is missing or out of its valid range
+    InvalidField {
+        /// Name of the component, e.g. `"month"`
+        field: &'static str,
+        /// Why the value was rejected
+        reason: &'static str,
+    },
+    /// Binary input of the named format has the wrong size
+    InvalidLength {
+        /// Name of the expected format
+        format: &'static str,
+        /// Expected size in bytes
+        expected: usize,
+        /// Observed size in bytes
+        actual: usize,
+    },
+    /// An element of a bulk conversion is invalid
+    InvalidElement {
+        /// Zero-based index of the first offending element
+        index: usize,
+        /// Why the element was rejected
+        reason: &'static str,
+    },
+    /// Binary input of the named format is truncated or malformed
+    Malformed {
+        /// Name of the expected format
+        format: &'static str,
+        /// Byte offset of the offending field
+        offset: usize,
+        /// Why the input was rejected
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for FileTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BeforeEpoch => write!(f, "instant lies before the FILETIME epoch (1601-01-01)"),
+            Self::Overflow => write!(f, "instant exceeds the maximum FILETIME value"),
+            Self::NotFinite => write!(f, "floating point value is not finite"),
+            Self::OutOfRange(target) => write!(f, "value out of range for {}", target),
+            Self::InvalidRange => write!(f, "range start lies after its end"),
+            Self::NegativeDuration => write!(f, "duration is negative"),
+            Self::NotSpecified => write!(f, "timestamp is not specified"),
+            Self::TornRead => write!(f, "torn read: high parts of the value disagree"),
+            Self::InvalidFormat { format, reason } => write!(f, "invalid {}: {}", format, reason),
+            Self::InvalidField { field, reason } => write!(f, "invalid {}: {}", field, reason),
+            Self::InvalidLength {
+                format,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "invalid {} length: expected {} bytes, got {}",
+                format, expected, actual
+            ),
+            Self::InvalidElement { index, reason } => {
+                write!(f, "invalid element at index {}: {}", index, reason)
+            }
+            Self::Malformed {
+                format,
+                offset,
+                reason,
+            } => write!(f, "malformed {} at offset {}: {}", format, offset, reason),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FileTimeError {}
@@ -0,0 +1,190 @@
+//! Timestamps of ESE / JET Blue databases (Windows.edb, SRUM, WebCache)
+//!
+//! JET's `DateTime` column type stores an OLE Automation date: a little-endian
+//! f64 counting days since 1899-12-30T00:00:00Z. Many tables store raw
+//! FILETIMEs in 8-byte integer columns instead, and both look alike as bytes.
+//! [`detect_jet_timestamp`] tells them apart for plausible dates.
+use crate::{FileTime, FileTimeError, PlausibilityWindow};
+
+/// 1899-12-30T00:00:00Z, day zero of OLE Automation dates
+const OLE_EPOCH_AS_FILETIME: i64 = 94_353_120_000_000_000;
+const MILLISECONDS_PER_DAY: f64 = 86_400_000.0;
+
+/// Encoding of an 8-byte ESE timestamp, as guessed by [`detect_jet_timestamp`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum JetTimestampKind {
+    /// Little-endian f64 OLE Automation date, the JET `DateTime` column type
+    OleDate,
+    /// Little-endian FILETIME in an integer column
+    FileTime,
+    /// Neither reading lies within [`PlausibilityWindow::forensic()`]
+    Unknown,
+}
+
+/// Guess whether 8 bytes hold an OLE date or a FILETIME
+///
+/// Both readings are checked against [`PlausibilityWindow::forensic()`]
+/// (1970 to 2100). Within that window the encodings cannot collide: the bit
+/// patterns of plausible OLE dates read as FILETIMEs beyond the year 10000,
+/// and plausible FILETIMEs read as tiny subnormal-range doubles.
+/// ```
+/// use filetime_type::ese::{detect_jet_timestamp, JetTimestampKind};
+///
+/// let ole = 45000.5f64.to_le_bytes();
+/// assert_eq!(detect_jet_timestamp(ole), JetTimestampKind::OleDate);
+/// ```
+pub fn detect_jet_timestamp(bytes: [u8; 8]) -> JetTimestampKind {
+    let window = PlausibilityWindow::forensic();
+    let plausible =
+        |ft: Result<FileTime, FileTimeError>| matches!(ft, Ok(ft) if window.contains(ft));
+    if plausible(FileTime::from_jet_filetime_bytes(bytes)) {
+        JetTimestampKind::FileTime
+    } else if plausible(FileTime::from_jet_datetime_bytes(bytes)) {
+        JetTimestampKind::OleDate
+    } else {
+        JetTimestampKind::Unknown
+    }
+}
+
+impl FileTime {
+    /// Construct FileTime from an OLE Automation date, days since 1899-12-30
+    ///
+    /// Like .NET's `DateTime.FromOADate` the result is rounded to the nearest
+    /// millisecond, the resolution OLE dates are written with. For negative
+    /// values the fraction counts forward from midnight, e.g. -1.25 is
+    /// 1899-12-29T06:00:00Z.
+    pub(crate) fn from_ole_date(days: f64) -> Result<Self, FileTimeError> {
+        if !days.is_finite() {
+            return Err(FileTimeError::NotFinite);
+        }
+        let whole = days.trunc();
+        let fraction = (days - whole).abs();
+        let millis = ((whole + fraction) * MILLISECONDS_PER_DAY).round();
+        // Beyond ±2^63 milliseconds the result is far outside of FILETIME;
+        // the casts saturate and from_ticks_i128 rejects the value
+        let ticks = (millis as i64 as i128) * i128::from(FileTime::TICKS_PER_MILLISECOND)
+            + i128::from(OLE_EPOCH_AS_FILETIME);
+        if ticks < 0 {
+            return Err(FileTimeError::BeforeEpoch);
+        }
+        Self::from_ticks_i128(ticks)
+    }
+
+    /// Construct FileTime from the bytes of a JET `DateTime` column (LE f64 OLE date)
+    ///
+    /// Fails with [`FileTimeError::NotFinite`] for NaN or infinite values and
+    /// [`FileTimeError::BeforeEpoch`] for dates before 1601.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_jet_datetime_bytes(45000.5f64.to_le_bytes()).unwrap();
+    /// assert_eq!(ft.to_datetime().to_rfc3339(), "2023-03-15T12:00:00+00:00");
+    /// ```
+    pub fn from_jet_datetime_bytes(bytes: [u8; 8]) -> Result<Self, FileTimeError> {
+        Self::from_ole_date(f64::from_le_bytes(bytes))
+    }
+
+    /// Construct FileTime from the bytes of a FILETIME stored in an integer column
+    ///
+    /// Fails with [`FileTimeError::BeforeEpoch`] for negative values.
+    pub fn from_jet_filetime_bytes(bytes: [u8; 8]) -> Result<Self, FileTimeError> {
+        Self::from_i64_checked(i64::from_le_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::Datelike;
+
+    // Synthetic column values in the layouts SRUM uses: an OLE date as in the
+    // `TimeStamp` column and a FILETIME as in `ConnectStartTime`.
+    /// 45000.5, 2023-03-15T12:00:00Z
+    const OLE_BYTES: [u8; 8] = [0x00, 0x00, 0x00, 0x00, 0x10, 0xf9, 0xe5, 0x40];
+    /// 133287876001234567, 2023-05-17T09:00:00.1234567Z
+    const FILETIME_BYTES: [u8; 8] = [0x87, 0x7e, 0xeb, 0xf5, 0x9d, 0x88, 0xd9, 0x01];
+
+    #[test]
+    fn ole_epoch() {
+        assert_eq!(
+            FileTime::from_ymd_hms(1899, 12, 30, 0, 0, 0)
+                .unwrap()
+                .filetime(),
+            OLE_EPOCH_AS_FILETIME
+        );
+    }
+
+    #[test]
+    fn datetime_bytes() {
+        assert_eq!(
+            FileTime::from_jet_datetime_bytes(OLE_BYTES),
+            FileTime::from_ymd_hms(2023, 3, 15, 12, 0, 0)
+        );
+        assert_eq!(
+            FileTime::from_jet_datetime_bytes([0; 8])
+                .unwrap()
+                .filetime(),
+            OLE_EPOCH_AS_FILETIME
+        );
+        assert_eq!(
+            FileTime::from_jet_datetime_bytes((-1.25f64).to_le_bytes()),
+            FileTime::from_ymd_hms(1899, 12, 29, 6, 0, 0)
+        );
+        // 9999-12-31T23:59:59.0000054Z, rounded to the millisecond
+        assert_eq!(
+            FileTime::from_jet_datetime_bytes(2958465.999988426f64.to_le_bytes()),
+            FileTime::from_ymd_hms(9999, 12, 31, 23, 59, 59)
+        );
+        assert_eq!(
+            FileTime::from_jet_datetime_bytes((-200_000.0f64).to_le_bytes()),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::from_jet_datetime_bytes(1e300f64.to_le_bytes()),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!(
+            FileTime::from_jet_datetime_bytes(f64::NAN.to_le_bytes()),
+            Err(FileTimeError::NotFinite)
+        );
+    }
+
+    #[test]
+    fn filetime_bytes() {
+        assert_eq!(
+            FileTime::from_jet_filetime_bytes(FILETIME_BYTES),
+            Ok(FileTime::from_i64(133287876001234567))
+        );
+        assert_eq!(
+            FileTime::from_jet_filetime_bytes((-1i64).to_le_bytes()),
+            Err(FileTimeError::BeforeEpoch)
+        );
+    }
+
+    #[test]
+    fn detect() {
+        assert_eq!(detect_jet_timestamp(OLE_BYTES), JetTimestampKind::OleDate);
+        assert_eq!(
+            detect_jet_timestamp(FILETIME_BYTES),
+            JetTimestampKind::FileTime
+        );
+        assert_eq!(detect_jet_timestamp([0; 8]), JetTimestampKind::Unknown);
+        assert_eq!(detect_jet_timestamp([0xff; 8]), JetTimestampKind::Unknown);
+        // The other reading of each is far outside of the window
+        assert!(
+            FileTime::from_jet_filetime_bytes(OLE_BYTES)
+                .unwrap()
+                .to_datetime()
+                .year()
+                > 10000
+        );
+        assert!(
+            FileTime::from_jet_datetime_bytes(FILETIME_BYTES)
+                .unwrap()
+                .to_datetime()
+                .year()
+                < 1900
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! EXIF `DateTime*` tags with their `SubSecTime*` and `OffsetTime*` companions
+//!
+//! `DateTimeOriginal` holds `YYYY:MM:DD HH:MM:SS` in the camera's local time
+//! without timezone. EXIF 2.31 added `OffsetTimeOriginal` (`±HH:MM`) for the
+//! offset and `SubSecTimeOriginal` holds the fraction digits, any number of
+//! them. Older files lack the offset, see [`MissingTimezone`]. Unknown dates
+//! are written as blanks (`"    :  :     :  :  "`) or, by many cameras, as
+//! zeros.
+use crate::parse;
+use crate::{FileTime, FileTimeError, MissingTimezone};
+use chrono::{Duration, Timelike};
+
+const EXIF_DATETIME: &str = "EXIF DateTime";
+const EXIF_SUBSEC: &str = "EXIF SubSecTime";
+const EXIF_OFFSET: &str = "EXIF OffsetTime";
+
+/// Value of an ASCII tag without its NUL terminator and trailing blanks
+fn trim(s: &str) -> &str {
+    s.trim_end_matches(['\0', ' '])
+}
+
+/// Whether a tag holds the "unknown" encoding: only blanks and separators
+fn is_placeholder(s: &str) -> bool {
+    s.bytes().all(|c| matches!(c, b' ' | b':' | b'\0'))
+}
+
+impl FileTime {
+    /// Combine EXIF `DateTime`, `SubSecTime` and `OffsetTime` tag values
+    ///
+    /// A `subsec` of any length is read as fraction digits, digits beyond
+    /// tick precision are truncated. A missing, empty or blank `offset` is
+    /// handled as `missing` says. Trailing NULs and blanks are ignored in
+    /// all three.
+    ///
+    /// Fails with [`FileTimeError::NotSpecified`] for the blank or all-zero
+    /// "unknown" datetime, [`FileTimeError::InvalidFormat`] for malformed
+    /// tags.
+    /// ```
+    /// use filetime_type::{FileTime, MissingTimezone};
+    ///
+    /// let ft = FileTime::parse_exif(
+    ///     "2023:05:17 09:30:00",
+    ///     Some("25"),
+    ///     Some("+02:00"),
+    ///     MissingTimezone::Reject,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(ft, FileTime::from_ymd_hms_nano(2023, 5, 17, 7, 30, 0, 250_000_000).unwrap());
+    /// assert_eq!(ft.to_exif().unwrap(), ("2023:05:17 07:30:00".to_string(), "2500000".to_string()));
+    /// ```
+    pub fn parse_exif(
+        datetime: &str,
+        subsec: Option<&str>,
+        offset: Option<&str>,
+        missing: MissingTimezone,
+    ) -> Result<Self, FileTimeError> {
+        let datetime = trim(datetime);
+        if is_placeholder(datetime) || datetime == "0000:00:00 00:00:00" {
+            return Err(FileTimeError::NotSpecified);
+        }
+        let b = datetime.as_bytes();
+        if !parse::matches_layout(b, "dddd:dd:dd dd:dd:dd") {
+            return Err(FileTimeError::InvalidFormat {
+                format: EXIF_DATETIME,
+                reason: "expected YYYY:MM:DD HH:MM:SS",
+            });
+        }
+
+        let nanos = match subsec.map(trim) {
+            None | Some("") => 0,
+            Some(digits) => {
+                parse::fraction_nanos(digits.as_bytes()).ok_or(FileTimeError::InvalidFormat {
+                    format: EXIF_SUBSEC,
+                    reason: "expected fraction digits",
+                })?
+            }
+        };
+
+        let offset_minutes = match offset.map(trim) {
+            Some(offset) if !is_placeholder(offset) => {
+                let invalid = |reason| FileTimeError::InvalidFormat {
+                    format: EXIF_OFFSET,
+                    reason,
+                };
+                let tz = offset.as_bytes();
+                if !parse::matches_layout(&tz[1..], "dd:dd") {
+                    return Err(invalid("expected ±HH:MM"));
+                }
+                let hours = i64::from(parse::digits(&tz[1..3]).unwrap());
+                let minutes = i64::from(parse::digits(&tz[4..6]).unwrap());
+                if hours > 23 || minutes > 59 {
+                    return Err(invalid("offset beyond ±23:59"));
+                }
+                match tz[0] {
+                    b'+' => hours * 60 + minutes,
+                    b'-' => -(hours * 60 + minutes),
+                    _ => return Err(invalid("expected ±HH:MM")),
+                }
+            }
+            _ if missing == MissingTimezone::AssumeUtc => 0,
+            _ => {
+                return Err(FileTimeError::InvalidFormat {
+                    format: EXIF_OFFSET,
+                    reason: "missing timezone",
+                })
+            }
+        };
+
+        let field = |range: core::ops::Range<usize>| parse::digits(&b[range]).unwrap();
+        let local = parse::datetime(
+            field(0..4) as i32,
+            field(5..7),
+            field(8..10),
+            field(11..13),
+            field(14..16),
+            field(17..19),
+            nanos,
+        )
+        .ok_or(FileTimeError::InvalidFormat {
+            format: EXIF_DATETIME,
+            reason: "invalid date or time",
+        })?;
+        Self::from_datetime_checked(local - Duration::minutes(offset_minutes))
+    }
+
+    /// Format as EXIF `DateTime` and `SubSecTime` values in UTC
+    ///
+    /// The `SubSecTime` has all 7 tick digits; write `"+00:00"` as
+    /// `OffsetTime` along with them. Years after 9999 get five digits,
+    /// outside of the EXIF layout. Raw values above [`FileTime::MAX`] fail
+    /// with [`FileTimeError::OutOfRange`].
+    pub fn to_exif(&self) -> Result<(String, String), FileTimeError> {
+        let dt = self
+            .to_datetime_checked()
+            .map_err(|_| FileTimeError::OutOfRange(EXIF_DATETIME))?;
+        Ok((
+            dt.format("%Y:%m:%d %H:%M:%S").to_string(),
+            format!("{:07}", dt.nanosecond() / 100),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(
+        datetime: &str,
+        subsec: Option<&str>,
+        offset: Option<&str>,
+    ) -> Result<FileTime, FileTimeError> {
+        FileTime::parse_exif(datetime, subsec, offset, MissingTimezone::Reject)
+    }
+
+    #[test]
+    fn all_tags() {
+        let ft = parse("2013:05:25 18:01:23", Some("148283"), Some("+02:00"));
+        assert_eq!(ft, Ok(FileTime::from_i64(130139712831482830)));
+        let (datetime, subsec) = ft.unwrap().to_exif().unwrap();
+        assert_eq!(
+            (datetime.as_str(), subsec.as_str()),
+            ("2013:05:25 16:01:23", "1482830")
+        );
+        assert_eq!(
+            parse(&datetime, Some(&subsec), Some("+00:00")),
+            Ok(FileTime::from_i64(130139712831482830))
+        );
+        // As stored in the file, with NUL terminators and padding
+        assert_eq!(
+            parse(
+                "2013:05:25 18:01:23\0",
+                Some("148283  \0"),
+                Some("+02:00\0")
+            ),
+            Ok(FileTime::from_i64(130139712831482830))
+        );
+        assert_eq!(
+            FileTime::MIN.to_exif(),
+            Ok(("1601:01:01 00:00:00".to_string(), "0000000".to_string()))
+        );
+        assert_eq!(
+            FileTime::MAX_RAW.to_exif(),
+            Err(FileTimeError::OutOfRange(EXIF_DATETIME))
+        );
+    }
+
+    #[test]
+    fn subsec_lengths() {
+        let at = |subsec| parse("2023:05:17 09:30:00", Some(subsec), Some("+00:00"));
+        let nano = |nanos| FileTime::from_ymd_hms_nano(2023, 5, 17, 9, 30, 0, nanos);
+        assert_eq!(at("5"), nano(500_000_000));
+        assert_eq!(at("05"), nano(50_000_000));
+        assert_eq!(at("123"), nano(123_000_000));
+        assert_eq!(at("1234567"), nano(123_456_700));
+        assert_eq!(at("123456789"), nano(123_456_700));
+        assert_eq!(at("0000000000"), nano(0));
+        assert_eq!(at(""), nano(0));
+        assert_eq!(
+            at("12a"),
+            Err(FileTimeError::InvalidFormat {
+                format: EXIF_SUBSEC,
+                reason: "expected fraction digits",
+            })
+        );
+        assert_eq!(parse("2023:05:17 09:30:00", None, Some("+00:00")), nano(0));
+    }
+
+    #[test]
+    fn offsets() {
+        let utc = FileTime::from_ymd_hms(2023, 5, 17, 14, 0, 0);
+        assert_eq!(parse("2023:05:17 09:30:00", None, Some("-04:30")), utc);
+        assert_eq!(parse("2023:05:17 23:30:00", None, Some("+09:30")), utc);
+
+        let missing = Err(FileTimeError::InvalidFormat {
+            format: EXIF_OFFSET,
+            reason: "missing timezone",
+        });
+        assert_eq!(parse("2023:05:17 14:00:00", None, None), missing);
+        assert_eq!(parse("2023:05:17 14:00:00", None, Some("   :  ")), missing);
+        assert_eq!(
+            FileTime::parse_exif(
+                "2023:05:17 14:00:00",
+                None,
+                Some(""),
+                MissingTimezone::AssumeUtc
+            ),
+            utc
+        );
+        for offset in ["+0200", "02:00", "Z", "+2:00"] {
+            assert_eq!(
+                parse("2023:05:17 14:00:00", None, Some(offset)),
+                Err(FileTimeError::InvalidFormat {
+                    format: EXIF_OFFSET,
+                    reason: "expected ±HH:MM",
+                }),
+                "{}",
+                offset
+            );
+        }
+        assert_eq!(
+            parse("2023:05:17 14:00:00", None, Some("+24:00")),
+            Err(FileTimeError::InvalidFormat {
+                format: EXIF_OFFSET,
+                reason: "offset beyond ±23:59",
+            })
+        );
+    }
+
+    #[test]
+    fn placeholder() {
+        for datetime in [
+            "    :  :     :  :  ",
+            "                   ",
+            "0000:00:00 00:00:00",
+            "",
+            "\0",
+        ] {
+            assert_eq!(
+                parse(datetime, Some("00"), Some("+00:00")),
+                Err(FileTimeError::NotSpecified),
+                "{:?}",
+                datetime
+            );
+        }
+        let invalid = |reason| {
+            Err(FileTimeError::InvalidFormat {
+                format: EXIF_DATETIME,
+                reason,
+            })
+        };
+        assert_eq!(
+            parse("2023-05-17 09:30:00", None, Some("+00:00")),
+            invalid("expected YYYY:MM:DD HH:MM:SS")
+        );
+        assert_eq!(
+            parse("2023:02:30 09:30:00", None, Some("+00:00")),
+            invalid("invalid date or time")
+        );
+        assert_eq!(
+            parse("1601:01:01 00:30:00", None, Some("+01:00")),
+            Err(FileTimeError::BeforeEpoch)
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! fake support for generating fixture data
+//!
+//! `Faker` draws like fake does for `chrono::DateTime<Utc>`: a random `i64`
+//! of nanoseconds around the Unix-Epoch, i.e. an instant between 1677 and 2262.
+//! The fakers below restrict the window.
+//! ```
+//! use fake::{Fake, Faker};
+//! use filetime_type::fake::{FileTimeBetween, RecentFileTime};
+//! use filetime_type::FileTime;
+//!
+//! let any: FileTime = Faker.fake();
+//! let start = FileTime::from_i64(128930364000000000);
+//! let end = FileTime::from_i64(133535664000000000);
+//! let between: FileTime = FileTimeBetween(start, end).fake();
+//! assert!(start <= between && between <= end);
+//! let recent: FileTime = RecentFileTime(7).fake();
+//! ```
+use crate::FileTime;
+use ::fake::rand::RngExt;
+use ::fake::{Dummy, Faker};
+
+/// Between two instants, both included
+///
+/// Panics if the start lies after the end.
+#[derive(Debug, Clone, Copy)]
+pub struct FileTimeBetween(pub FileTime, pub FileTime);
+
+/// Within the given number of days before now
+#[derive(Debug, Clone, Copy)]
+pub struct RecentFileTime(pub u32);
+
+impl Dummy<Faker> for FileTime {
+    fn dummy_with_rng<R: RngExt + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        let nanos: i64 = rng.random();
+        // Every i64 of nanoseconds since 1970 lies after 1601
+        FileTime::from_i64(FileTime::EPOCH_AS_FILETIME + nanos.div_euclid(100))
+    }
+}
+
+impl Dummy<FileTimeBetween> for FileTime {
+    fn dummy_with_rng<R: RngExt + ?Sized>(c: &FileTimeBetween, rng: &mut R) -> Self {
+        FileTime::from_i64(rng.random_range(c.0.filetime()..=c.1.filetime()))
+    }
+}
+
+impl Dummy<RecentFileTime> for FileTime {
+    fn dummy_with_rng<R: RngExt + ?Sized>(c: &RecentFileTime, rng: &mut R) -> Self {
+        const TICKS_PER_DAY: i64 = 86_400 * FileTime::HUNDREDS_OF_NANOSECONDS;
+
+        let now = FileTime::now();
+        let start = now
+            .filetime()
+            .saturating_sub(i64::from(c.0).saturating_mul(TICKS_PER_DAY))
+            .max(0);
+        Self::dummy_with_rng(&FileTimeBetween(FileTime::from_i64(start), now), rng)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::fake::{Dummy, Fake};
+
+    const START: FileTime = FileTime::MIN;
+
+    #[derive(Debug, Dummy)]
+    struct Record {
+        any: FileTime,
+        #[dummy(
+            faker = "FileTimeBetween(FileTime::from_i64(128930364000000000), FileTime::from_i64(128930364000000010))"
+        )]
+        narrow: FileTime,
+        #[dummy(faker = "FileTimeBetween(START, FileTime::from_i64(0))")]
+        single: FileTime,
+        #[dummy(faker = "RecentFileTime(3)")]
+        recent: FileTime,
+    }
+
+    #[test]
+    fn derived_record() {
+        const DAY: i64 = 864_000_000_000;
+        let before = FileTime::now();
+        let records: Vec<Record> = (0..1000).map(|_| Faker.fake()).collect();
+        let after = FileTime::now();
+        for r in &records {
+            // 1677-09-21 and 2262-04-11
+            assert!(r.any.filetime() >= 21855139627914528);
+            assert!(r.any.filetime() <= 211678505236854775);
+            assert!(r.narrow.filetime() >= 128930364000000000);
+            assert!(r.narrow.filetime() <= 128930364000000010);
+            assert_eq!(r.single, START);
+            assert!(r.recent.filetime() >= before.filetime() - 3 * DAY);
+            assert!(r.recent <= after);
+        }
+        assert!(records
+            .iter()
+            .any(|r| r.narrow.filetime() == 128930364000000010));
+    }
+
+    #[test]
+    fn recent_clamps_to_epoch() {
+        let ft: FileTime = RecentFileTime(u32::MAX).fake();
+        assert!(ft <= FileTime::now());
+    }
+
+    #[test]
+    #[should_panic]
+    fn inverted_window() {
+        let _: FileTime = FileTimeBetween(FileTime::MAX, FileTime::MIN).fake();
+    }
+}
@@ -0,0 +1,686 @@
+//! strftime-style and allocation free fixed-layout formatting
+use crate::FileTime;
+#[cfg(feature = "std")]
+use crate::FileTimeError;
+use core::fmt::{self, Write};
+
+/// Formatted FileTime, returned by [`FileTime::format`]
+///
+/// Formatting fails with [`fmt::Error`] for invalid format strings and raw
+/// values above [`FileTime::MAX`], so
+/// `write!` returns an error while `to_string()` panics like it does for
+/// chrono's `DelayedFormat`.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct FileTimeFormat<'a> {
+    ft: FileTime,
+    fmt: &'a str,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for FileTimeFormat<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dt = self.ft.to_datetime_checked().map_err(|_| fmt::Error)?;
+        let ticks = self.ft.nanoseconds() / 100;
+        let bytes = self.fmt.as_bytes();
+
+        // Pass everything but the tick specifiers on to chrono, one segment at a time
+        let mut segment = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] != b'%' {
+                i += 1;
+                continue;
+            }
+            let (len, dot) = match &bytes[i + 1..] {
+                [b'7', b'f', ..] => (3, false),
+                [b'.', b'7', b'f', ..] => (4, true),
+                [b'%', ..] => {
+                    i += 2;
+                    continue;
+                }
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            };
+            write!(f, "{}", dt.format(&self.fmt[segment..i]))?;
+            if dot {
+                f.write_str(".")?;
+            }
+            write!(f, "{:07}", ticks)?;
+            i += len;
+            segment = i;
+        }
+        write!(f, "{}", dt.format(&self.fmt[segment..]))
+    }
+}
+
+/// Configurable ISO 8601 style display, returned by [`FileTime::display`]
+///
+/// Defaults to `2009-07-25T23:00:00.0001000Z`. Nothing is formatted or
+/// allocated until the adapter is written out. Values above
+/// [`FileTime::MAX`] have no date and show `none`, like `FileTime`'s `Display`.
+#[derive(Debug, Clone, Copy)]
+pub struct FileTimeDisplay {
+    ft: FileTime,
+    precision: u8,
+    separator: char,
+    suffix_z: bool,
+    raw: bool,
+}
+
+impl FileTimeDisplay {
+    /// Number of fractional second digits, clamped to 9
+    ///
+    /// Digits beyond the tick precision are zero, fewer digits truncate.
+    pub fn precision(mut self, digits: u8) -> Self {
+        self.precision = digits.min(9);
+        self
+    }
+
+    /// Separator between date and time, `T` by default
+    pub fn separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Whether to append `Z`, on by default
+    pub fn suffix_z(mut self, suffix_z: bool) -> Self {
+        self.suffix_z = suffix_z;
+        self
+    }
+
+    /// Whether to append the raw tick value in parentheses, off by default
+    pub fn raw(mut self, raw: bool) -> Self {
+        self.raw = raw;
+        self
+    }
+}
+
+/// Honors width, fill and alignment; a precision (`{:.3}`) overrides
+/// [`precision`](Self::precision)
+/// ```
+/// use filetime_type::FileTime;
+///
+/// let ft = FileTime::from_i64(128930364001234567);
+/// assert_eq!(format!("[{:>28.3}]", ft.display()), "[    2009-07-25T23:00:00.123Z]");
+/// ```
+impl fmt::Display for FileTimeDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().map_or(self.precision, |p| p.min(9) as u8);
+        write_padded(f, |w| {
+            if self.ft > FileTime::MAX {
+                w.write_str("none")?;
+            } else {
+                write_datetime(
+                    w,
+                    self.ft,
+                    Layout {
+                        separator: Some(self.separator),
+                        precision,
+                        suffix_z: self.suffix_z,
+                        signed_years: false,
+                    },
+                )?;
+            }
+            if self.raw {
+                w.write_str(" (")?;
+                write_digits(w, self.ft.as_u64(), 1)?;
+                w.write_str(")")?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Fixed output shapes for [`FileTime::format_into`]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub enum FormatStyle {
+    /// RFC 3339 in UTC with 7 fractional digits: `2009-07-25T23:00:00.0001000Z`,
+    /// same as [`FileTime::display`]
+    Rfc3339,
+    /// Digits only, sorting as text sorts by time: `200907252300000001000`
+    ///
+    /// Years beyond 9999 take 5 digits and no longer sort.
+    CompactSortable,
+    /// The raw tick count in decimal: `128930364000001000`
+    RawDecimal,
+}
+
+/// How [`write_datetime`] lays out the fields
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Layout {
+    /// Separator between date and time, `None` drops all separators and the decimal point
+    pub(crate) separator: Option<char>,
+    /// Number of fractional digits, at most 9
+    pub(crate) precision: u8,
+    pub(crate) suffix_z: bool,
+    /// Prefix years beyond 9999 with `+`, like chrono's `%Y`
+    pub(crate) signed_years: bool,
+}
+
+impl Layout {
+    pub(crate) const RFC3339: Layout = Layout {
+        separator: Some('T'),
+        precision: 7,
+        suffix_z: true,
+        signed_years: false,
+    };
+}
+
+/// Fixed capacity writer that fails once full, like `heapless::String`
+pub(crate) struct Fixed<const N: usize> {
+    data: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Fixed<N> {
+    pub(crate) fn new() -> Self {
+        Self {
+            data: [0; N],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.data[..self.len]).expect("only whole strs are written")
+    }
+}
+
+impl<const N: usize> Write for Fixed<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.data
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Write the output of `write` padded to the formatter's width, with its fill and alignment
+///
+/// Unlike [`fmt::Formatter::pad`] this leaves the precision alone, the
+/// datetime forms take it as the number of fractional digits. Left aligned
+/// by default, like strings.
+pub(crate) fn write_padded<F>(f: &mut fmt::Formatter<'_>, write: F) -> fmt::Result
+where
+    F: FnOnce(&mut Fixed<128>) -> fmt::Result,
+{
+    let mut buf = Fixed::new();
+    write(&mut buf)?;
+    let s = buf.as_str();
+    let padding = f.width().unwrap_or(0).saturating_sub(s.chars().count());
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str(s)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+/// Write `value` in decimal, zero padded to `width` digits
+pub(crate) fn write_digits<W: fmt::Write>(w: &mut W, mut value: u64, width: usize) -> fmt::Result {
+    let mut buf = [b'0'; 20];
+    let mut start = buf.len();
+    while value > 0 {
+        start -= 1;
+        buf[start] = b'0' + (value % 10) as u8;
+        value /= 10;
+    }
+    start = start.min(buf.len() - width.min(buf.len()));
+    // Only ASCII digits were written
+    w.write_str(core::str::from_utf8(&buf[start..]).map_err(|_| fmt::Error)?)
+}
+
+/// Write the date, time and fraction of `ft` in UTC, the backend of every formatter here
+pub(crate) fn write_datetime<W: fmt::Write>(
+    w: &mut W,
+    ft: FileTime,
+    layout: Layout,
+) -> fmt::Result {
+    use chrono::{Datelike, Timelike};
+
+    let dt = ft.to_datetime_checked().map_err(|_| fmt::Error)?;
+    // FILETIMEs start in 1601, the year is never negative
+    let year = dt.year() as u64;
+    if layout.signed_years && year > 9999 {
+        w.write_char('+')?;
+    }
+    let (date_sep, time_sep) = match layout.separator {
+        Some(_) => (Some('-'), Some(':')),
+        None => (None, None),
+    };
+    write_digits(w, year, 4)?;
+    for (sep, value) in [(date_sep, dt.month()), (date_sep, dt.day())] {
+        if let Some(sep) = sep {
+            w.write_char(sep)?;
+        }
+        write_digits(w, u64::from(value), 2)?;
+    }
+    if let Some(sep) = layout.separator {
+        w.write_char(sep)?;
+    }
+    write_digits(w, u64::from(dt.hour()), 2)?;
+    for value in [dt.minute(), dt.second()] {
+        if let Some(sep) = time_sep {
+            w.write_char(sep)?;
+        }
+        write_digits(w, u64::from(value), 2)?;
+    }
+    if layout.precision > 0 {
+        if layout.separator.is_some() {
+            w.write_char('.')?;
+        }
+        let precision = u32::from(layout.precision.min(9));
+        let nanos = ft.nanoseconds() as u64;
+        write_digits(w, nanos / 10u64.pow(9 - precision), precision as usize)?;
+    }
+    if layout.suffix_z {
+        w.write_char('Z')?;
+    }
+    Ok(())
+}
+
+/// Fractional second precision for [`FileTime::to_rfc3339_opts`]
+///
+/// Reducing the precision truncates towards the past, it never rounds, so the
+/// output always lies within the same second (millisecond, ...) as the value.
+#[cfg(feature = "std")]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum SecondsFormat7 {
+    /// No fraction: `23:00:00Z`
+    Secs,
+    /// 3 digits: `23:00:00.000Z`
+    Millis,
+    /// 6 digits: `23:00:00.000100Z`
+    Micros,
+    /// 7 digits, the full tick precision: `23:00:00.0001000Z`
+    Ticks,
+    /// 9 digits, the last two always zero: `23:00:00.000100000Z`
+    Nanos,
+    /// Up to 7 digits with trailing zeros removed, no fraction if it is zero: `23:00:00.0001Z`
+    Trimmed,
+}
+
+impl FileTime {
+    /// Builder for configurable ISO 8601 style output
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_i64(128930364000001000);
+    /// assert_eq!(ft.display().to_string(), "2009-07-25T23:00:00.0001000Z");
+    /// assert_eq!(
+    ///     ft.display().precision(0).separator(' ').suffix_z(false).to_string(),
+    ///     "2009-07-25 23:00:00"
+    /// );
+    /// ```
+    pub fn display(&self) -> FileTimeDisplay {
+        FileTimeDisplay {
+            ft: *self,
+            precision: 7,
+            separator: 'T',
+            suffix_z: true,
+            raw: false,
+        }
+    }
+
+    /// RFC 3339 / ISO 8601 string in UTC with the full 7 digit precision
+    ///
+    /// This is the shape of .NET's and PowerShell's round-trip (`"o"`) format.
+    /// Fails with [`FileTimeError::OutOfRange`] for raw values above
+    /// [`FileTime::MAX`], which have no date.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_i64(128930364000001000);
+    /// assert_eq!(ft.to_rfc3339().unwrap(), "2009-07-25T23:00:00.0001000Z");
+    /// assert!(FileTime::MAX_RAW.to_rfc3339().is_err());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_rfc3339(&self) -> Result<String, FileTimeError> {
+        self.to_rfc3339_opts(SecondsFormat7::Ticks)
+    }
+
+    /// RFC 3339 / ISO 8601 string in UTC with the given fractional second precision
+    ///
+    /// See [`SecondsFormat7`] for the truncation rules. Fails like
+    /// [`to_rfc3339`](Self::to_rfc3339).
+    #[cfg(feature = "std")]
+    pub fn to_rfc3339_opts(&self, precision: SecondsFormat7) -> Result<String, FileTimeError> {
+        let precision = match precision {
+            SecondsFormat7::Secs => 0,
+            SecondsFormat7::Millis => 3,
+            SecondsFormat7::Micros => 6,
+            SecondsFormat7::Ticks => 7,
+            SecondsFormat7::Nanos => 9,
+            SecondsFormat7::Trimmed => {
+                let mut ticks = self.nanoseconds() / 100;
+                let mut digits = 7;
+                while digits > 0 && ticks % 10 == 0 {
+                    ticks /= 10;
+                    digits -= 1;
+                }
+                digits
+            }
+        };
+        let mut s = String::with_capacity(32);
+        let layout = Layout {
+            separator: Some('T'),
+            precision,
+            suffix_z: true,
+            signed_years: true,
+        };
+        // Writing to a String only fails for values without a date
+        write_datetime(&mut s, *self, layout).map_err(|_| FileTimeError::OutOfRange("RFC 3339"))?;
+        Ok(s)
+    }
+
+    /// Write one of the fixed [`FormatStyle`]s without allocating
+    ///
+    /// Errors from the writer, such as a full fixed-size buffer, are passed on;
+    /// whatever was written up to that point stays in the writer. Date styles
+    /// fail for raw values above [`FileTime::MAX`], which have no date.
+    /// ```
+    /// use filetime_type::{FileTime, FormatStyle};
+    ///
+    /// let ft = FileTime::from_i64(128930364000001000);
+    /// let mut out = String::new();
+    /// ft.format_into(&mut out, FormatStyle::CompactSortable).unwrap();
+    /// assert_eq!(out, "200907252300000001000");
+    /// ```
+    pub fn format_into<W: fmt::Write>(&self, w: &mut W, style: FormatStyle) -> fmt::Result {
+        match style {
+            FormatStyle::Rfc3339 => write_datetime(w, *self, Layout::RFC3339),
+            FormatStyle::CompactSortable => write_datetime(
+                w,
+                *self,
+                Layout {
+                    separator: None,
+                    precision: 7,
+                    suffix_z: false,
+                    signed_years: false,
+                },
+            ),
+            FormatStyle::RawDecimal => write_digits(w, self.as_u64(), 1),
+        }
+    }
+
+    /// Format with chrono's strftime syntax, in UTC
+    ///
+    /// In addition to chrono's specifiers, `%7f` writes the 7 digit tick
+    /// fraction of the second and `%.7f` the same preceded by a dot.
+    /// chrono's `%f` / `%.9f` give 9 digits, the last two always zero.
+    ///
+    /// Windows Explorer style:
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_i64(128930364000001000);
+    /// assert_eq!(ft.format("%-m/%-d/%Y %-I:%M %p").to_string(), "7/25/2009 11:00 PM");
+    /// ```
+    ///
+    /// Compact and sortable, with full precision:
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_i64(128930364000001000);
+    /// assert_eq!(ft.format("%Y%m%d%H%M%S").to_string(), "20090725230000");
+    /// assert_eq!(ft.format("%Y-%m-%d %H:%M:%S%.7f").to_string(), "2009-07-25 23:00:00.0001000");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn format<'a>(&self, fmt: &'a str) -> FileTimeFormat<'a> {
+        FileTimeFormat { ft: *self, fmt }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn format_into_matches_allocating() {
+        let grid = [
+            FileTime::MIN,
+            FileTime::from_i64(1),
+            FileTime::from_i64(116444736000000000),
+            FileTime::from_i64(128930364001234567),
+            FileTime::from_i64(133535664000000010),
+            FileTime::MAX,
+        ];
+        for ft in grid {
+            let mut out = Fixed::<32>::new();
+            ft.format_into(&mut out, FormatStyle::Rfc3339).unwrap();
+            assert_eq!(out.as_str(), ft.display().to_string());
+            // The string based paths sign years beyond 9999 like chrono
+            assert_eq!(
+                out.as_str(),
+                ft.to_rfc3339().unwrap().trim_start_matches('+')
+            );
+
+            let mut out = Fixed::<32>::new();
+            ft.format_into(&mut out, FormatStyle::CompactSortable)
+                .unwrap();
+            let chrono = ft.format("%Y%m%d%H%M%S%7f").to_string();
+            assert_eq!(out.as_str(), chrono.trim_start_matches('+'));
+
+            let mut out = Fixed::<32>::new();
+            ft.format_into(&mut out, FormatStyle::RawDecimal).unwrap();
+            assert_eq!(out.as_str(), ft.filetime().to_string());
+        }
+    }
+
+    #[test]
+    fn format_into_styles() {
+        let ft = FileTime::from_i64(128930364001234567);
+        let cases = [
+            (FormatStyle::Rfc3339, "2009-07-25T23:00:00.1234567Z"),
+            (FormatStyle::CompactSortable, "200907252300001234567"),
+            (FormatStyle::RawDecimal, "128930364001234567"),
+        ];
+        for (style, expected) in cases {
+            let mut out = Fixed::<32>::new();
+            ft.format_into(&mut out, style).unwrap();
+            assert_eq!(out.as_str(), expected);
+        }
+        let mut out = Fixed::<32>::new();
+        FileTime::MIN
+            .format_into(&mut out, FormatStyle::RawDecimal)
+            .unwrap();
+        assert_eq!(out.as_str(), "0");
+    }
+
+    #[test]
+    fn format_into_full_writer() {
+        let ft = FileTime::from_i64(128930364001234567);
+        let styles = [
+            FormatStyle::Rfc3339,
+            FormatStyle::CompactSortable,
+            FormatStyle::RawDecimal,
+        ];
+        for style in styles {
+            let mut full = Fixed::<32>::new();
+            ft.format_into(&mut full, style).unwrap();
+            // Every shorter buffer fails, keeping what fit in whole writes
+            let mut small = Fixed::<17>::new();
+            assert_eq!(ft.format_into(&mut small, style), Err(fmt::Error));
+            assert!(full.as_str().starts_with(small.as_str()));
+            let mut empty = Fixed::<0>::new();
+            assert_eq!(ft.format_into(&mut empty, style), Err(fmt::Error));
+            assert_eq!(empty.as_str(), "");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn tick_fraction() {
+        // 1000 ticks: the trailing zeros must be kept
+        let ft = FileTime::from_i64(128930364000001000);
+        assert_eq!(ft.format("%.7f").to_string(), ".0001000");
+        assert_eq!(ft.format("%S%7f").to_string(), "000001000");
+        assert_eq!(ft.format("%.9f").to_string(), ".000100000");
+
+        let ft = FileTime::from_i64(128930364009999990);
+        assert_eq!(ft.format("%H:%M:%S%.7f").to_string(), "23:00:00.9999990");
+        assert_eq!(
+            FileTime::from_i64(0).format("%Y-%m-%d%.7f").to_string(),
+            "1601-01-01.0000000"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn escapes() {
+        let ft = FileTime::from_i64(128930364000001000);
+        assert_eq!(ft.format("%%7f %%.7f %%").to_string(), "%7f %.7f %");
+        assert_eq!(ft.format("100%% %7f").to_string(), "100% 0001000");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn rfc3339() {
+        // 2009-07-25T23:00:00.1234567Z
+        let ft = FileTime::from_i64(128930364001234567);
+        let format = |precision| ft.to_rfc3339_opts(precision).unwrap();
+        assert_eq!(ft.to_rfc3339().unwrap(), "2009-07-25T23:00:00.1234567Z");
+        assert_eq!(
+            FileTime::MAX_RAW.to_rfc3339_opts(SecondsFormat7::Secs),
+            Err(FileTimeError::OutOfRange("RFC 3339"))
+        );
+        assert_eq!(format(SecondsFormat7::Secs), "2009-07-25T23:00:00Z");
+        assert_eq!(format(SecondsFormat7::Millis), "2009-07-25T23:00:00.123Z");
+        assert_eq!(
+            format(SecondsFormat7::Micros),
+            "2009-07-25T23:00:00.123456Z"
+        );
+        assert_eq!(
+            format(SecondsFormat7::Ticks),
+            "2009-07-25T23:00:00.1234567Z"
+        );
+        assert_eq!(
+            format(SecondsFormat7::Nanos),
+            "2009-07-25T23:00:00.123456700Z"
+        );
+        assert_eq!(
+            format(SecondsFormat7::Trimmed),
+            "2009-07-25T23:00:00.1234567Z"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn rfc3339_truncates() {
+        // 2009-07-25T23:00:59.9999990Z
+        let ft = FileTime::from_i64(128930364599999990);
+        let format = |precision| ft.to_rfc3339_opts(precision).unwrap();
+        assert_eq!(format(SecondsFormat7::Secs), "2009-07-25T23:00:59Z");
+        assert_eq!(format(SecondsFormat7::Millis), "2009-07-25T23:00:59.999Z");
+        assert_eq!(
+            format(SecondsFormat7::Micros),
+            "2009-07-25T23:00:59.999999Z"
+        );
+        assert_eq!(
+            format(SecondsFormat7::Trimmed),
+            "2009-07-25T23:00:59.999999Z"
+        );
+
+        let ft = FileTime::from_i64(128930364000000000);
+        assert_eq!(
+            ft.to_rfc3339_opts(SecondsFormat7::Trimmed).unwrap(),
+            "2009-07-25T23:00:00Z"
+        );
+    }
+
+    #[test]
+    fn display_builder() {
+        let ft = FileTime::from_i64(128930364001234567);
+        assert_eq!(ft.display().to_string(), "2009-07-25T23:00:00.1234567Z");
+        assert_eq!(
+            ft.display().precision(3).to_string(),
+            "2009-07-25T23:00:00.123Z"
+        );
+        assert_eq!(
+            ft.display().precision(9).separator(' ').to_string(),
+            "2009-07-25 23:00:00.123456700Z"
+        );
+        assert_eq!(
+            ft.display().precision(12).suffix_z(false).to_string(),
+            "2009-07-25T23:00:00.123456700"
+        );
+        assert_eq!(
+            ft.display().precision(0).raw(true).to_string(),
+            "2009-07-25T23:00:00Z (128930364001234567)"
+        );
+        assert_eq!(
+            FileTime::from_i64(0)
+                .display()
+                .separator(' ')
+                .suffix_z(false)
+                .raw(true)
+                .to_string(),
+            "1601-01-01 00:00:00.0000000 (0)"
+        );
+        assert_eq!(
+            FileTime::from_i64(i64::MAX).display().to_string(),
+            "30828-09-14T02:48:05.4775807Z"
+        );
+        assert_eq!(FileTime::MAX_RAW.display().to_string(), "none");
+        assert_eq!(
+            format!("{:>30}", FileTime::MAX_RAW.display().raw(true)),
+            "   none (18446744073709551615)"
+        );
+    }
+
+    #[test]
+    fn display_flags() {
+        let ft = FileTime::from_i64(128930364001234567);
+        assert_eq!(
+            format!("|{:32}|", ft.display()),
+            "|2009-07-25T23:00:00.1234567Z    |"
+        );
+        assert_eq!(
+            format!("|{:>32}|", ft.display()),
+            "|    2009-07-25T23:00:00.1234567Z|"
+        );
+        assert_eq!(
+            format!("|{:_^31}|", ft.display()),
+            "|_2009-07-25T23:00:00.1234567Z__|"
+        );
+        // The flag precision overrides the builder's and truncates
+        assert_eq!(
+            format!("{:.3}", ft.display().precision(9)),
+            "2009-07-25T23:00:00.123Z"
+        );
+        assert_eq!(format!("{:.0}", ft.display()), "2009-07-25T23:00:00Z");
+        assert_eq!(
+            format!("{:.12}", ft.display()),
+            "2009-07-25T23:00:00.123456700Z"
+        );
+        // Padding counts chars, not bytes
+        assert_eq!(
+            format!("{:>1$.6}|", ft.display().separator('→').raw(true), 50),
+            "  2009-07-25→23:00:00.123456Z (128930364001234567)|"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn invalid() {
+        let ft = FileTime::from_i64(128930364000001000);
+        let mut s = String::new();
+        assert!(write!(s, "{}", ft.format("%Q")).is_err());
+        assert!(write!(s, "{}", ft.format("%7f %")).is_err());
+    }
+}
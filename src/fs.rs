@@ -0,0 +1,189 @@
+//! Setting file timestamps from FileTime values
+use crate::FileTime;
+use std::io;
+use std::path::Path;
+
+impl FileTime {
+    /// Seconds and nanoseconds relative to the Unix epoch, as used by the platform APIs
+    fn to_unix_parts(self) -> (i64, u32) {
+        let ticks = self.unix_ticks();
+        let unit = i128::from(Self::HUNDREDS_OF_NANOSECONDS);
+        (
+            ticks.div_euclid(unit) as i64,
+            (ticks.rem_euclid(unit) * 100) as u32,
+        )
+    }
+
+    fn to_filetime_crate(self) -> filetime::FileTime {
+        let (secs, nanos) = self.to_unix_parts();
+        filetime::FileTime::from_unix_time(secs, nanos)
+    }
+
+    /// Set the last modification time of the file at `path`
+    ///
+    /// The value is passed on with 100ns precision, the filesystem may store it coarser.
+    pub fn set_file_mtime<P: AsRef<Path>>(path: P, modified: FileTime) -> io::Result<()> {
+        filetime::set_file_mtime(path, modified.to_filetime_crate())
+    }
+
+    /// Set the last access time of the file at `path`
+    pub fn set_file_atime<P: AsRef<Path>>(path: P, accessed: FileTime) -> io::Result<()> {
+        filetime::set_file_atime(path, accessed.to_filetime_crate())
+    }
+
+    /// Set the last access and modification times of the file at `path`
+    pub fn set_file_times<P: AsRef<Path>>(
+        path: P,
+        accessed: FileTime,
+        modified: FileTime,
+    ) -> io::Result<()> {
+        filetime::set_file_times(
+            path,
+            accessed.to_filetime_crate(),
+            modified.to_filetime_crate(),
+        )
+    }
+
+    /// Set the creation time of the file or directory at `path`
+    ///
+    /// Only supported on Windows, other platforms fail with [`io::ErrorKind::Unsupported`].
+    #[cfg(windows)]
+    pub fn set_file_creation_time<P: AsRef<Path>>(path: P, created: FileTime) -> io::Result<()> {
+        use std::os::windows::fs::{FileTimesExt, OpenOptionsExt};
+        use std::time::{Duration, SystemTime};
+
+        const FILE_WRITE_ATTRIBUTES: u32 = 0x0100;
+        // Required to open directories
+        const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x0200_0000;
+
+        let (secs, nanos) = created.to_unix_parts();
+        let time = if secs >= 0 {
+            SystemTime::UNIX_EPOCH + Duration::new(secs as u64, nanos)
+        } else {
+            SystemTime::UNIX_EPOCH - Duration::from_secs(secs.unsigned_abs())
+                + Duration::from_nanos(u64::from(nanos))
+        };
+        let file = std::fs::OpenOptions::new()
+            .access_mode(FILE_WRITE_ATTRIBUTES)
+            .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+            .open(path)?;
+        file.set_times(std::fs::FileTimes::new().set_created(time))
+    }
+
+    /// Set the creation time of the file or directory at `path`
+    ///
+    /// Only supported on Windows, other platforms fail with [`io::ErrorKind::Unsupported`].
+    #[cfg(not(windows))]
+    pub fn set_file_creation_time<P: AsRef<Path>>(_path: P, _created: FileTime) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "setting the creation time is only supported on Windows",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("filetime_type-{}-{}", std::process::id(), name));
+            std::fs::write(&path, b"").unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    /// Assert that `actual` equals `expected` truncated to some filesystem precision up to 2s
+    fn assert_stored(actual: filetime::FileTime, expected: FileTime) {
+        let (secs, nanos) = expected.to_unix_parts();
+        let expected = i128::from(secs) * 1_000_000_000 + i128::from(nanos);
+        let actual =
+            i128::from(actual.unix_seconds()) * 1_000_000_000 + i128::from(actual.nanoseconds());
+        assert!(
+            actual <= expected && expected - actual < 2_000_000_000,
+            "stored {} expected {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn unix_parts() {
+        assert_eq!(
+            FileTime::from_i64(116444736000000001).to_unix_parts(),
+            (0, 100)
+        );
+        assert_eq!(
+            FileTime::from_i64(116444735999999999).to_unix_parts(),
+            (-1, 999_999_900)
+        );
+    }
+
+    #[test]
+    fn set_times() {
+        let file = TempFile::new("set_times");
+        // 2009-07-25T23:00:00.0000010Z and 2021-03-14T09:26:53.5897932Z
+        let accessed = FileTime::from_i64(128930364000000010);
+        let modified = FileTime::from_i64(132601876135897932);
+
+        FileTime::set_file_times(&file.0, accessed, modified).unwrap();
+        let meta = std::fs::metadata(&file.0).unwrap();
+        assert_stored(filetime::FileTime::from_last_access_time(&meta), accessed);
+        assert_stored(
+            filetime::FileTime::from_last_modification_time(&meta),
+            modified,
+        );
+
+        FileTime::set_file_mtime(&file.0, accessed).unwrap();
+        FileTime::set_file_atime(&file.0, modified).unwrap();
+        let meta = std::fs::metadata(&file.0).unwrap();
+        assert_stored(filetime::FileTime::from_last_access_time(&meta), modified);
+        assert_stored(
+            filetime::FileTime::from_last_modification_time(&meta),
+            accessed,
+        );
+    }
+
+    #[test]
+    fn set_before_unix_epoch() {
+        let file = TempFile::new("before_unix_epoch");
+        // 1969-12-31T23:59:59Z
+        let modified = FileTime::from_i64(116444735990000000);
+        FileTime::set_file_mtime(&file.0, modified).unwrap();
+        let meta = std::fs::metadata(&file.0).unwrap();
+        assert_stored(
+            filetime::FileTime::from_last_modification_time(&meta),
+            modified,
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn creation_time_unsupported() {
+        let file = TempFile::new("creation_time");
+        let err = FileTime::set_file_creation_time(&file.0, FileTime::from_i64(0)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn creation_time() {
+        let file = TempFile::new("creation_time");
+        let created = FileTime::from_i64(132601876135897932);
+        FileTime::set_file_creation_time(&file.0, created).unwrap();
+        let meta = std::fs::metadata(&file.0).unwrap();
+        let ft = filetime::FileTime::from_creation_time(&meta).unwrap();
+        assert_stored(ft, created);
+    }
+}
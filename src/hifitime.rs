@@ -0,0 +1,149 @@
+//! hifitime `Epoch` conversions
+//!
+//! Conversions go through integer nanoseconds since the Unix-Epoch in
+//! hifitime's UTC time scale. Like FILETIME, that scale has no leap seconds:
+//! every UTC day is 86400 seconds long and 2016-12-31T23:59:60 does not
+//! exist, so both directions are exact down to the tick and the leap second
+//! table plays no part. It does once an Epoch is converted to TAI or GPS
+//! time: those are ahead of UTC by the leap seconds accumulated up to the
+//! instant (37 s since 2017), and differences between TAI epochs count the
+//! leap seconds in between.
+use crate::{FileTime, FileTimeError};
+use ::hifitime::{Duration, Epoch};
+use std::convert::TryFrom;
+
+const NANOS_PER_TICK: i128 = 100;
+
+impl FileTime {
+    /// Convert from a hifitime Epoch in any time scale, truncated to the tick
+    ///
+    /// Epochs in TAI, GPS and other scales are converted to UTC first, which
+    /// takes off their leap seconds; hifitime puts the TAI seconds just before
+    /// a leap second takes effect one second early. Fails with [`FileTimeError::BeforeEpoch`]
+    /// / [`FileTimeError::Overflow`] outside of the FILETIME range.
+    /// ```
+    /// use filetime_type::FileTime;
+    /// use hifitime::Epoch;
+    ///
+    /// let epoch = Epoch::from_gregorian_utc(2013, 5, 25, 16, 1, 23, 148_283_099);
+    /// let ft = FileTime::from_hifitime(epoch).unwrap();
+    /// assert_eq!(ft, FileTime::from_i64(130139712831482830));
+    /// ```
+    pub fn from_hifitime(epoch: Epoch) -> Result<FileTime, FileTimeError> {
+        let nanos = epoch.to_unix_duration().total_nanoseconds();
+        Self::from_ticks_i128(
+            nanos.div_euclid(NANOS_PER_TICK) + i128::from(Self::EPOCH_AS_FILETIME),
+        )
+    }
+
+    /// Convert to a hifitime Epoch in the UTC time scale
+    ///
+    /// Call `to_time_scale(TimeScale::TAI)` on the result for leap second
+    /// aware arithmetic.
+    /// ```
+    /// use filetime_type::FileTime;
+    /// use hifitime::TimeScale;
+    ///
+    /// let ft = FileTime::from_ymd_hms(2017, 1, 1, 0, 0, 0).unwrap();
+    /// let tai = ft.to_hifitime().to_time_scale(TimeScale::TAI);
+    /// assert_eq!(tai.to_string(), "2017-01-01T00:00:37 TAI");
+    /// ```
+    pub fn to_hifitime(&self) -> Epoch {
+        Epoch::from_unix_duration(Duration::from_total_nanoseconds(
+            self.unix_ticks() * NANOS_PER_TICK,
+        ))
+    }
+}
+
+impl TryFrom<Epoch> for FileTime {
+    type Error = FileTimeError;
+
+    fn try_from(epoch: Epoch) -> Result<Self, Self::Error> {
+        Self::from_hifitime(epoch)
+    }
+}
+
+impl From<FileTime> for Epoch {
+    fn from(ft: FileTime) -> Self {
+        ft.to_hifitime()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::hifitime::{TimeScale, Unit};
+
+    #[test]
+    fn roundtrip() {
+        for ticks in [
+            130139712831482830,
+            133325856001234567,
+            FileTime::EPOCH_AS_FILETIME,
+            FileTime::EPOCH_AS_FILETIME - 1,
+            0,
+            i64::MAX,
+        ] {
+            let ft = FileTime::from_i64(ticks);
+            let epoch = ft.to_hifitime();
+            assert_eq!(epoch.time_scale, TimeScale::UTC);
+            assert_eq!(FileTime::from_hifitime(epoch), Ok(ft), "{}", ticks);
+        }
+        let ft = FileTime::from_i64(133325856001234567);
+        assert_eq!(
+            ft.to_hifitime(),
+            Epoch::from_gregorian_utc(2023, 6, 30, 8, 0, 0, 123_456_700)
+        );
+        assert_eq!(
+            FileTime::MIN.to_hifitime().to_string(),
+            "1601-01-01T00:00:00 UTC"
+        );
+    }
+
+    #[test]
+    fn truncation_and_range() {
+        let epoch = Epoch::from_gregorian_utc(2023, 7, 1, 0, 0, 0, 199);
+        assert_eq!(
+            FileTime::from_hifitime(epoch),
+            FileTime::from_ymd_hms_nano(2023, 7, 1, 0, 0, 0, 100)
+        );
+        // Before 1970 the truncation still goes towards the past
+        let epoch = Epoch::from_gregorian_utc(1969, 12, 31, 23, 59, 59, 999_999_950);
+        assert_eq!(
+            FileTime::from_hifitime(epoch),
+            Ok(FileTime::from_i64(FileTime::EPOCH_AS_FILETIME - 1))
+        );
+        let before = FileTime::MIN.to_hifitime() - Unit::Nanosecond * 1;
+        assert_eq!(
+            FileTime::from_hifitime(before),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        let after = FileTime::MAX.to_hifitime() + Unit::Nanosecond * 100;
+        assert_eq!(FileTime::from_hifitime(after), Err(FileTimeError::Overflow));
+    }
+
+    #[test]
+    fn leap_second_2016() {
+        let before = FileTime::from_ymd_hms(2016, 12, 31, 23, 59, 59).unwrap();
+        let after = FileTime::from_ymd_hms(2017, 1, 1, 0, 0, 0).unwrap();
+        // One second apart in FILETIME and in hifitime's UTC scale alike
+        assert_eq!(after.to_unix_seconds() - before.to_unix_seconds(), 1);
+        assert_eq!(after.to_hifitime() - before.to_hifitime(), Unit::Second * 1);
+        // Two in TAI, which counts the leap second 23:59:60
+        let tai = |ft: FileTime| ft.to_hifitime().to_time_scale(TimeScale::TAI);
+        assert_eq!(tai(after) - tai(before), Unit::Second * 2);
+        assert_eq!(tai(before).to_string(), "2017-01-01T00:00:35 TAI");
+        assert_eq!(tai(after).to_string(), "2017-01-01T00:00:37 TAI");
+
+        // TAI epochs come back as the same UTC instant, except for the
+        // seconds hifitime files under the new leap second count early
+        let minute_before = FileTime::from_ymd_hms(2016, 12, 31, 23, 59, 0).unwrap();
+        assert_eq!(
+            FileTime::from_hifitime(tai(minute_before)),
+            Ok(minute_before)
+        );
+        assert_eq!(FileTime::from_hifitime(tai(after)), Ok(after));
+        let gps = after.to_hifitime().to_time_scale(TimeScale::GPST);
+        assert_eq!(FileTime::from_hifitime(gps), Ok(after));
+    }
+}
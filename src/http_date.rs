@@ -0,0 +1,256 @@
+//! HTTP-date, as in `Last-Modified` and `If-Modified-Since` headers ([RFC 7231] 7.1.1.1)
+//!
+//! ```text
+//! IMF-fixdate  Sun, 06 Nov 1994 08:49:37 GMT
+//! RFC 850      Sunday, 06-Nov-94 08:49:37 GMT
+//! asctime      Sun Nov  6 08:49:37 1994
+//! ```
+//!
+//! All three are UTC with whole seconds. Only IMF-fixdate is generated.
+//!
+//! [RFC 7231]: https://www.rfc-editor.org/rfc/rfc7231#section-7.1.1.1
+use crate::parse;
+use crate::{FileTime, FileTimeError};
+
+const HTTP_DATE: &str = "HTTP-date";
+const DAY_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const LONG_DAY_NAMES: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+fn invalid(reason: &'static str) -> FileTimeError {
+    FileTimeError::InvalidFormat {
+        format: HTTP_DATE,
+        reason,
+    }
+}
+
+fn month(name: &[u8]) -> Result<u32, FileTimeError> {
+    MONTH_NAMES
+        .iter()
+        .position(|m| m.as_bytes() == name)
+        .map(|index| index as u32 + 1)
+        .ok_or_else(|| invalid("unknown month"))
+}
+
+fn number(b: &[u8]) -> u32 {
+    parse::digits(b).expect("layout checked")
+}
+
+impl FileTime {
+    /// Parse an HTTP-date in any of the three formats RFC 7231 requires recipients to accept
+    ///
+    /// The weekday must be spelled correctly but is not checked against the
+    /// date. Two digit RFC 850 years 00 to 69 are taken as 2000 to 2069,
+    /// 70 to 99 as 1970 to 1999; RFC 7231 instead asks for a window relative
+    /// to the current date, which would make parsing depend on the clock.
+    /// Fails with [`FileTimeError::InvalidFormat`] otherwise.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+    /// assert_eq!(ft.to_datetime().to_rfc3339(), "1994-11-06T08:49:37+00:00");
+    /// assert_eq!(FileTime::parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), Ok(ft));
+    /// assert_eq!(FileTime::parse_http_date("Sun Nov  6 08:49:37 1994"), Ok(ft));
+    /// ```
+    pub fn parse_http_date(s: &str) -> Result<Self, FileTimeError> {
+        let (year, month, day, time) = match s.find(',') {
+            Some(comma) if DAY_NAMES.contains(&&s[..comma]) => {
+                let b = &s.as_bytes()[comma + 1..];
+                if !parse::matches_layout(b, " dd aaa dddd dd:dd:dd GMT") {
+                    return Err(invalid("expected IMF-fixdate layout"));
+                }
+                (
+                    number(&b[8..12]),
+                    month(&b[4..7])?,
+                    number(&b[1..3]),
+                    &b[13..21],
+                )
+            }
+            Some(comma) if LONG_DAY_NAMES.contains(&&s[..comma]) => {
+                let b = &s.as_bytes()[comma + 1..];
+                if !parse::matches_layout(b, " dd-aaa-dd dd:dd:dd GMT") {
+                    return Err(invalid("expected RFC 850 layout"));
+                }
+                let yy = number(&b[8..10]);
+                let year = if yy < 70 { 2000 + yy } else { 1900 + yy };
+                (year, month(&b[4..7])?, number(&b[1..3]), &b[11..19])
+            }
+            None if s.get(..3).is_some_and(|name| DAY_NAMES.contains(&name)) => {
+                // Single digit days of month are space padded
+                let b = &s.as_bytes()[3..];
+                let padded = b.len() == 21 && b[5] == b' ';
+                let layout = if padded {
+                    " aaa  d dd:dd:dd dddd"
+                } else {
+                    " aaa dd dd:dd:dd dddd"
+                };
+                if !parse::matches_layout(b, layout) {
+                    return Err(invalid("expected asctime layout"));
+                }
+                (
+                    number(&b[17..21]),
+                    month(&b[1..4])?,
+                    number(b[5..7].trim_ascii_start()),
+                    &b[8..16],
+                )
+            }
+            _ => return Err(invalid("unknown weekday")),
+        };
+        let dt = parse::datetime(
+            year as i32,
+            month,
+            day,
+            number(&time[0..2]),
+            number(&time[3..5]),
+            number(&time[6..8]),
+            0,
+        )
+        .ok_or_else(|| invalid("invalid date or time"))?;
+        Self::from_datetime_checked(dt)
+    }
+
+    /// Format as IMF-fixdate, e.g. `Tue, 15 Nov 1994 08:12:31 GMT`
+    ///
+    /// Sub-second ticks are truncated. FileTimes after 9999 get a five digit
+    /// year, which HTTP-date does not allow. Raw values above [`FileTime::MAX`]
+    /// fail with [`FileTimeError::OutOfRange`].
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_i64(130139712831482830);
+    /// assert_eq!(ft.to_http_date().unwrap(), "Sat, 25 May 2013 16:01:23 GMT");
+    /// ```
+    pub fn to_http_date(&self) -> Result<String, FileTimeError> {
+        let dt = self
+            .to_datetime_checked()
+            .map_err(|_| FileTimeError::OutOfRange(HTTP_DATE))?;
+        Ok(dt.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn three_grammars() {
+        let expected = FileTime::from_ymd_hms(1994, 11, 6, 8, 49, 37).unwrap();
+        for s in &[
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+        ] {
+            assert_eq!(FileTime::parse_http_date(s), Ok(expected), "{}", s);
+        }
+        assert_eq!(
+            expected.to_http_date().unwrap(),
+            "Sun, 06 Nov 1994 08:49:37 GMT"
+        );
+
+        // Two digit days in asctime
+        let expected = FileTime::from_ymd_hms(2023, 6, 30, 16, 0, 0).unwrap();
+        assert_eq!(
+            FileTime::parse_http_date("Fri Jun 30 16:00:00 2023"),
+            Ok(expected)
+        );
+        assert_eq!(
+            FileTime::parse_http_date("Friday, 30-Jun-23 16:00:00 GMT"),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn weekdays() {
+        let cases = [
+            ((1601, 1, 1), "Mon, 01 Jan 1601 00:00:00 GMT"),
+            ((1970, 1, 1), "Thu, 01 Jan 1970 00:00:00 GMT"),
+            ((2000, 2, 29), "Tue, 29 Feb 2000 00:00:00 GMT"),
+            ((1994, 11, 15), "Tue, 15 Nov 1994 00:00:00 GMT"),
+            ((2024, 12, 31), "Tue, 31 Dec 2024 00:00:00 GMT"),
+        ];
+        for ((y, m, d), expected) in &cases {
+            let ft = FileTime::from_ymd_hms(*y, *m, *d, 0, 0, 0).unwrap();
+            assert_eq!(ft.to_http_date().unwrap(), *expected);
+            assert_eq!(FileTime::parse_http_date(expected), Ok(ft));
+        }
+    }
+
+    #[test]
+    fn truncation() {
+        let ft = FileTime::from_i64(130139712839999999);
+        assert_eq!(ft.to_http_date().unwrap(), "Sat, 25 May 2013 16:01:23 GMT");
+        assert_eq!(
+            FileTime::parse_http_date(&ft.to_http_date().unwrap()),
+            Ok(FileTime::from_i64(130139712830000000))
+        );
+        assert_eq!(
+            FileTime::MAX_RAW.to_http_date(),
+            Err(FileTimeError::OutOfRange(HTTP_DATE))
+        );
+    }
+
+    #[test]
+    fn two_digit_years() {
+        let year = |s| {
+            FileTime::parse_http_date(s)
+                .unwrap()
+                .to_http_date()
+                .unwrap()
+                .split(' ')
+                .nth(3)
+                .unwrap()
+                .to_owned()
+        };
+        assert_eq!(year("Monday, 01-Jan-00 00:00:00 GMT"), "2000");
+        assert_eq!(year("Monday, 01-Jan-69 00:00:00 GMT"), "2069");
+        assert_eq!(year("Monday, 01-Jan-70 00:00:00 GMT"), "1970");
+        assert_eq!(year("Monday, 01-Jan-99 00:00:00 GMT"), "1999");
+    }
+
+    #[test]
+    fn errors() {
+        let cases = [
+            ("", "unknown weekday"),
+            (
+                "Sun, 06 Nov 1994 08:49:37 UTC",
+                "expected IMF-fixdate layout",
+            ),
+            (
+                "Sun, 6 Nov 1994 08:49:37 GMT",
+                "expected IMF-fixdate layout",
+            ),
+            (
+                "Sun,06 Nov 1994 08:49:37 GMT",
+                "expected IMF-fixdate layout",
+            ),
+            ("sun, 06 Nov 1994 08:49:37 GMT", "unknown weekday"),
+            (
+                "Sunday, 06-Nov-1994 08:49:37 GMT",
+                "expected RFC 850 layout",
+            ),
+            ("Sun Nov 06 08:49:37 1994 GMT", "expected asctime layout"),
+            ("Sun Nov 6 08:49:37 1994", "expected asctime layout"),
+            ("Sun, 06 Noe 1994 08:49:37 GMT", "unknown month"),
+            ("Sun, 31 Nov 1994 08:49:37 GMT", "invalid date or time"),
+            ("Sun, 06 Nov 1994 24:00:00 GMT", "invalid date or time"),
+            ("1994-11-06T08:49:37Z", "unknown weekday"),
+        ];
+        for (s, reason) in &cases {
+            assert_eq!(FileTime::parse_http_date(s), Err(invalid(reason)), "{}", s);
+        }
+        assert_eq!(
+            FileTime::parse_http_date("Sun, 06 Nov 1600 08:49:37 GMT"),
+            Err(FileTimeError::BeforeEpoch)
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Relative, human readable time descriptions ("3 days ago", "in 2 hours")
+#[cfg(feature = "std")]
+use crate::clock::{Clock, SystemClock};
+use crate::{FileTime, FileTimeDelta};
+use core::fmt;
+
+const SECS_PER_MINUTE: u64 = 60;
+const SECS_PER_HOUR: u64 = 60 * SECS_PER_MINUTE;
+const SECS_PER_DAY: u64 = 24 * SECS_PER_HOUR;
+const SECS_PER_MONTH: u64 = 30 * SECS_PER_DAY;
+const SECS_PER_YEAR: u64 = 365 * SECS_PER_DAY;
+
+/// Largest unit chosen by [`RelativeTime`]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum RelativeUnit {
+    /// Less than 5 seconds apart, counts are always zero
+    JustNow,
+    /// Up to 90 seconds
+    Seconds,
+    /// Up to 90 minutes
+    Minutes,
+    /// Up to 36 hours
+    Hours,
+    /// Up to 45 days
+    Days,
+    /// 30 day months, up to 18 of them
+    Months,
+    /// 365 day years
+    Years,
+}
+
+/// Distance between two instants bucketed into a single unit
+///
+/// Counts are rounded down. The `Display` impl renders English text,
+/// other languages can be built on the public fields.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct RelativeTime {
+    /// Unit the distance is expressed in
+    pub unit: RelativeUnit,
+    /// Number of whole units
+    pub count: u64,
+    /// Whether the instant lies after the reference
+    pub future: bool,
+}
+
+impl RelativeTime {
+    /// Bucket the signed distance from a reference to an instant
+    pub fn from_delta(delta: FileTimeDelta) -> Self {
+        let secs = delta.as_ticks().unsigned_abs() / 10_000_000;
+        let (unit, count) = if secs < 5 {
+            (RelativeUnit::JustNow, 0)
+        } else if secs < 90 {
+            (RelativeUnit::Seconds, secs)
+        } else if secs < 90 * SECS_PER_MINUTE {
+            (RelativeUnit::Minutes, secs / SECS_PER_MINUTE)
+        } else if secs < 36 * SECS_PER_HOUR {
+            (RelativeUnit::Hours, secs / SECS_PER_HOUR)
+        } else if secs < 45 * SECS_PER_DAY {
+            (RelativeUnit::Days, secs / SECS_PER_DAY)
+        } else if secs < 18 * SECS_PER_MONTH {
+            (RelativeUnit::Months, secs / SECS_PER_MONTH)
+        } else {
+            (RelativeUnit::Years, secs / SECS_PER_YEAR)
+        };
+        Self {
+            unit,
+            count,
+            future: delta.as_ticks() > 0,
+        }
+    }
+}
+
+impl fmt::Display for RelativeTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.unit {
+            RelativeUnit::JustNow => return f.write_str("just now"),
+            RelativeUnit::Seconds => "second",
+            RelativeUnit::Minutes => "minute",
+            RelativeUnit::Hours => "hour",
+            RelativeUnit::Days => "day",
+            RelativeUnit::Months => "month",
+            RelativeUnit::Years => "year",
+        };
+        let plural = if self.count == 1 { "" } else { "s" };
+        if self.future {
+            write!(f, "in {} {}{}", self.count, name, plural)
+        } else {
+            write!(f, "{} {}{} ago", self.count, name, plural)
+        }
+    }
+}
+
+impl FileTime {
+    /// Describe this instant relative to now, e.g. "3 days ago" or "in 2 hours"
+    #[cfg(feature = "std")]
+    pub fn humanize(&self) -> String {
+        self.humanize_with(&SystemClock)
+    }
+
+    /// Describe this instant relative to the current time of `clock`
+    /// ```
+    /// use filetime_type::{FileTime, FixedClock};
+    ///
+    /// let clock = FixedClock(FileTime::from_i64(128930364000000000));
+    /// let earlier = FileTime::from_i64(128930364000000000 - 2 * 36_000_000_000);
+    /// assert_eq!(earlier.humanize_with(&clock), "2 hours ago");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn humanize_with<C: Clock + ?Sized>(&self, clock: &C) -> String {
+        self.humanize_relative_to(clock.now_filetime())
+    }
+
+    /// Describe this instant relative to `reference`
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let reference = FileTime::from_i64(128930364000000000);
+    /// let earlier = FileTime::from_i64(128930364000000000 - 3 * 864_000_000_000);
+    /// assert_eq!(earlier.humanize_relative_to(reference), "3 days ago");
+    /// assert_eq!(reference.humanize_relative_to(earlier), "in 3 days");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn humanize_relative_to(&self, reference: FileTime) -> String {
+        self.relative_to(reference).to_string()
+    }
+
+    /// Bucketed distance to `reference`, for custom rendering
+    ///
+    /// Distances beyond [`FileTimeDelta::MAX`], only possible for raw values
+    /// above [`FileTime::MAX`], saturate.
+    pub fn relative_to(&self, reference: FileTime) -> RelativeTime {
+        RelativeTime::from_delta(self.saturating_delta_since(reference))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn buckets() {
+        const S: i64 = 10_000_000;
+        const M: i64 = 60 * S;
+        const H: i64 = 60 * M;
+        const D: i64 = 24 * H;
+        let reference = FileTime::from_i64(132601876135897932);
+        let cases: &[(i64, &str)] = &[
+            (0, "just now"),
+            (-4 * S, "just now"),
+            (4 * S + 9_999_999, "just now"),
+            (-5 * S, "5 seconds ago"),
+            (5 * S, "in 5 seconds"),
+            (-89 * S, "89 seconds ago"),
+            (-90 * S, "1 minute ago"),
+            (119 * S, "in 1 minute"),
+            (-45 * M, "45 minutes ago"),
+            (89 * M + 59 * S, "in 89 minutes"),
+            (-90 * M, "1 hour ago"),
+            (2 * H, "in 2 hours"),
+            (-35 * H, "35 hours ago"),
+            (-36 * H, "1 day ago"),
+            (3 * D, "in 3 days"),
+            (-44 * D, "44 days ago"),
+            (-45 * D, "1 month ago"),
+            (100 * D, "in 3 months"),
+            (-539 * D, "17 months ago"),
+            (-540 * D, "1 year ago"),
+            (730 * D, "in 2 years"),
+            (-3650 * D, "10 years ago"),
+        ];
+        for (offset, expected) in cases {
+            let ft = FileTime::from_i64(reference.filetime() + offset);
+            assert_eq!(
+                ft.humanize_relative_to(reference),
+                *expected,
+                "offset {}",
+                offset
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn extremes() {
+        let min = FileTime::from_i64(0);
+        let max = FileTime::from_i64(i64::MAX);
+        assert_eq!(min.humanize_relative_to(max), "29247 years ago");
+        assert_eq!(max.humanize_relative_to(min), "in 29247 years");
+        // 2^64 - 1 ticks apart, saturated
+        let raw_max = FileTime::MAX_RAW;
+        assert_eq!(min.humanize_relative_to(raw_max), "29247 years ago");
+        assert_eq!(raw_max.humanize_relative_to(min), "in 29247 years");
+        assert_eq!(raw_max.relative_to(max), max.relative_to(min));
+        assert!(raw_max.humanize().starts_with("in "));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn with_clock() {
+        use crate::{FixedClock, SteppingClock};
+
+        let reference = FileTime::from_i64(132601876135897932);
+        let ft = FileTime::from_i64(reference.filetime() - 3 * 600_000_000);
+        assert_eq!(ft.humanize_with(&FixedClock(reference)), "3 minutes ago");
+
+        // Each reading moves the clock 30 seconds on
+        let clock = SteppingClock::new(ft, FileTimeDelta::from_secs(30).unwrap());
+        let seen: Vec<String> = (0..4).map(|_| ft.humanize_with(&clock)).collect();
+        assert_eq!(
+            seen,
+            [
+                "just now",
+                "30 seconds ago",
+                "60 seconds ago",
+                "1 minute ago"
+            ]
+        );
+    }
+
+    #[test]
+    fn relative_fields() {
+        let reference = FileTime::from_i64(132601876135897932);
+        let ft = FileTime::from_i64(reference.filetime() + 2 * 36_000_000_000);
+        assert_eq!(
+            ft.relative_to(reference),
+            RelativeTime {
+                unit: RelativeUnit::Hours,
+                count: 2,
+                future: true,
+            }
+        );
+    }
+}
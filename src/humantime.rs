@@ -0,0 +1,188 @@
+//! Interop with the `humantime` timestamp and duration formats
+//!
+//! humantime's RFC 3339 support covers 1970-01-01T00:00:00Z to
+//! 9999-12-31T23:59:59.999999999Z; FileTimes outside of it fail with
+//! [`FileTimeError::OutOfRange`]. Sub-tick nanoseconds (below 100ns) are truncated.
+use crate::{FileTime, FileTimeError};
+use humantime::{DurationError, TimestampError};
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const TIMESTAMP: &str = "humantime RFC 3339 timestamp";
+const DURATION: &str = "humantime duration";
+/// 10000-01-01T00:00:00Z, the first instant humantime cannot format
+const MAX_UNIX_SECONDS: u64 = 253_402_300_800;
+
+fn timestamp_error(err: TimestampError) -> FileTimeError {
+    let reason = match err {
+        TimestampError::OutOfRange => "numeric component out of range",
+        TimestampError::InvalidDigit => "bad character where a digit is expected",
+        TimestampError::InvalidFormat => "expected e.g. 2018-02-13T23:08:32Z",
+    };
+    FileTimeError::InvalidFormat {
+        format: TIMESTAMP,
+        reason,
+    }
+}
+
+fn duration_error(err: DurationError) -> FileTimeError {
+    let reason = match err {
+        DurationError::InvalidCharacter(_) => "invalid character",
+        DurationError::NumberExpected(_) => "expected a number before the unit",
+        DurationError::UnknownUnit { .. } => "unknown unit",
+        DurationError::NumberOverflow => "number too large or too precise",
+        DurationError::Empty => "empty input",
+    };
+    FileTimeError::InvalidFormat {
+        format: DURATION,
+        reason,
+    }
+}
+
+impl FileTime {
+    /// Parse an RFC 3339 timestamp with humantime's `parse_rfc3339`
+    ///
+    /// Accepts `Z`-suffixed UTC timestamps with up to 9 fraction digits,
+    /// digits beyond the tick precision are truncated. Fails with
+    /// [`FileTimeError::InvalidFormat`] carrying humantime's reason.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::parse_humantime_rfc3339("2018-02-13T23:08:32Z").unwrap();
+    /// assert_eq!(ft.to_unix_seconds(), 1518563312);
+    /// ```
+    pub fn parse_humantime_rfc3339(s: &str) -> Result<Self, FileTimeError> {
+        let since_unix = humantime::parse_rfc3339(s)
+            .map_err(timestamp_error)?
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| timestamp_error(TimestampError::OutOfRange))?;
+        Self::from_ticks_i128(
+            i128::from(Self::EPOCH_AS_FILETIME) + (since_unix.as_nanos() / 100) as i128,
+        )
+    }
+
+    /// Add a humantime duration such as `"2h 30m"` or `"15days 2min"`
+    ///
+    /// Fails with [`FileTimeError::InvalidFormat`] for unparsable input and
+    /// [`FileTimeError::Overflow`] if the sum exceeds [`FileTime::MAX`].
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::parse_humantime_rfc3339("2018-02-13T23:08:32Z").unwrap();
+    /// let later = ft.add_human("1h 30m").unwrap();
+    /// assert_eq!(later.format_humantime().unwrap(), "2018-02-14T00:38:32Z");
+    /// ```
+    pub fn add_human(&self, s: &str) -> Result<Self, FileTimeError> {
+        let duration = humantime::parse_duration(s).map_err(duration_error)?;
+        Self::from_ticks_i128(i128::from(self.as_u64()) + (duration.as_nanos() / 100) as i128)
+    }
+
+    /// Format as RFC 3339 with humantime's `format_rfc3339`
+    ///
+    /// Whole seconds print without a fraction, otherwise with 9 digits.
+    /// Fails with [`FileTimeError::OutOfRange`] before 1970 or after 9999.
+    pub fn format_humantime(&self) -> Result<String, FileTimeError> {
+        let since_unix = u64::try_from(self.unix_ticks())
+            .ok()
+            .map(|ticks| {
+                let per_second = FileTime::HUNDREDS_OF_NANOSECONDS as u64;
+                Duration::new(ticks / per_second, (ticks % per_second) as u32 * 100)
+            })
+            .filter(|d| d.as_secs() < MAX_UNIX_SECONDS)
+            .ok_or(FileTimeError::OutOfRange(TIMESTAMP))?;
+        let time: SystemTime = UNIX_EPOCH + since_unix;
+        Ok(humantime::format_rfc3339(time).to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_and_add() {
+        let ft = FileTime::parse_humantime_rfc3339("2018-02-13T23:08:32Z").unwrap();
+        assert_eq!(ft.to_datetime().to_rfc3339(), "2018-02-13T23:08:32+00:00");
+        let later = ft.add_human("15days 2min").unwrap();
+        assert_eq!(
+            later.filetime() - ft.filetime(),
+            (15 * 86400 + 2 * 60) * FileTime::HUNDREDS_OF_NANOSECONDS
+        );
+        assert_eq!(later.format_humantime().unwrap(), "2018-02-28T23:10:32Z");
+        assert_eq!(
+            FileTime::parse_humantime_rfc3339(&later.format_humantime().unwrap()),
+            Ok(later)
+        );
+    }
+
+    #[test]
+    fn sub_second() {
+        let ft = FileTime::parse_humantime_rfc3339("2013-05-25T16:01:23.148283099Z").unwrap();
+        assert_eq!(ft, FileTime::from_i64(130139712831482830));
+        assert_eq!(
+            ft.format_humantime().unwrap(),
+            "2013-05-25T16:01:23.148283000Z"
+        );
+        assert_eq!(ft.add_human("99ns"), Ok(ft));
+        assert_eq!(
+            ft.add_human("1us 99ns"),
+            Ok(FileTime::from_i64(130139712831482840))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            FileTime::parse_humantime_rfc3339("2018-02-13 23:08:32"),
+            Err(FileTimeError::InvalidFormat {
+                format: TIMESTAMP,
+                reason: "expected e.g. 2018-02-13T23:08:32Z"
+            })
+        );
+        assert_eq!(
+            FileTime::parse_humantime_rfc3339("2018-13-13T23:08:32Z"),
+            Err(FileTimeError::InvalidFormat {
+                format: TIMESTAMP,
+                reason: "numeric component out of range"
+            })
+        );
+        assert_eq!(
+            FileTime::MIN.add_human("2 fortnights"),
+            Err(FileTimeError::InvalidFormat {
+                format: DURATION,
+                reason: "unknown unit"
+            })
+        );
+        assert_eq!(
+            FileTime::MIN.add_human(""),
+            Err(FileTimeError::InvalidFormat {
+                format: DURATION,
+                reason: "empty input"
+            })
+        );
+        assert_eq!(FileTime::MAX.add_human("1s"), Err(FileTimeError::Overflow));
+    }
+
+    #[test]
+    fn format_range() {
+        let unix_epoch = FileTime::from_unix_seconds(0).unwrap();
+        assert_eq!(
+            unix_epoch.format_humantime().unwrap(),
+            "1970-01-01T00:00:00Z"
+        );
+        let out_of_range = Err(FileTimeError::OutOfRange(TIMESTAMP));
+        assert_eq!(
+            FileTime::from_i64(unix_epoch.filetime() - 1).format_humantime(),
+            out_of_range
+        );
+        let last = FileTime::from_unix_seconds(MAX_UNIX_SECONDS as i64).unwrap();
+        assert_eq!(
+            FileTime::from_i64(last.filetime() - 1)
+                .format_humantime()
+                .unwrap(),
+            "9999-12-31T23:59:59.999999900Z"
+        );
+        assert_eq!(last.format_humantime(), out_of_range);
+        assert_eq!(FileTime::MAX.format_humantime(), out_of_range);
+    }
+}
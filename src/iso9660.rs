@@ -0,0 +1,210 @@
+//! ISO 9660 (ECMA-119) timestamps of optical media images
+//!
+//! Both forms store local time plus the offset to GMT as signed number of
+//! 15 minute intervals (-48 to +52).
+use crate::parse;
+use crate::{FileTime, FileTimeError};
+use chrono::{Datelike, Duration, Timelike};
+
+const DIR_RECORD: &str = "ISO 9660 directory record time";
+const VOLUME: &str = "ISO 9660 volume descriptor time";
+
+fn gmt_offset(quarters: i8, format: &'static str) -> Result<Duration, FileTimeError> {
+    if !(-48..=52).contains(&quarters) {
+        return Err(FileTimeError::OutOfRange(format));
+    }
+    Ok(Duration::minutes(i64::from(quarters) * 15))
+}
+
+impl FileTime {
+    /// Parse the 7 byte recording date and time of a directory record (ECMA-119 9.1.5)
+    ///
+    /// Layout: years since 1900, month, day, hour, minute, second, GMT offset.
+    /// The all-zero encoding fails with [`FileTimeError::NotSpecified`].
+    pub fn from_iso9660_dir_record(raw: &[u8; 7]) -> Result<Self, FileTimeError> {
+        if raw == &[0; 7] {
+            return Err(FileTimeError::NotSpecified);
+        }
+        let local = parse::datetime(
+            1900 + i32::from(raw[0]),
+            u32::from(raw[1]),
+            u32::from(raw[2]),
+            u32::from(raw[3]),
+            u32::from(raw[4]),
+            u32::from(raw[5]),
+            0,
+        )
+        .ok_or(FileTimeError::InvalidFormat {
+            format: DIR_RECORD,
+            reason: "invalid date or time",
+        })?;
+        Self::from_datetime_checked(local - gmt_offset(raw[6] as i8, DIR_RECORD)?)
+    }
+
+    /// Build the 7 byte directory record form, in the given GMT offset (15 minute intervals)
+    ///
+    /// Sub-second ticks are truncated. Fails with [`FileTimeError::OutOfRange`]
+    /// for offsets outside of -48..=52 and local years outside of 1900..=2155.
+    pub fn to_iso9660_dir_record(&self, gmt_offset_quarters: i8) -> Result<[u8; 7], FileTimeError> {
+        let local = self
+            .to_datetime_checked()
+            .map_err(|_| FileTimeError::OutOfRange(DIR_RECORD))?
+            + gmt_offset(gmt_offset_quarters, DIR_RECORD)?;
+        if !(1900..=2155).contains(&local.year()) {
+            return Err(FileTimeError::OutOfRange(DIR_RECORD));
+        }
+        Ok([
+            (local.year() - 1900) as u8,
+            local.month() as u8,
+            local.day() as u8,
+            local.hour() as u8,
+            local.minute() as u8,
+            local.second() as u8,
+            gmt_offset_quarters as u8,
+        ])
+    }
+
+    /// Parse the 17 byte date and time of a volume descriptor (ECMA-119 8.4.26.1)
+    ///
+    /// Layout: `YYYYMMDDHHMMSSCC` as ASCII digits (CC = hundredths of a second)
+    /// followed by the GMT offset. The "not specified" encoding (all digits
+    /// zero, offset zero) fails with [`FileTimeError::NotSpecified`].
+    pub fn from_iso9660_volume(raw: &[u8; 17]) -> Result<Self, FileTimeError> {
+        if raw[..16].iter().all(|&b| b == b'0') && raw[16] == 0 {
+            return Err(FileTimeError::NotSpecified);
+        }
+        let field = |range: std::ops::Range<usize>| {
+            parse::digits(&raw[range]).ok_or(FileTimeError::InvalidFormat {
+                format: VOLUME,
+                reason: "expected digits",
+            })
+        };
+        let local = parse::datetime(
+            field(0..4)? as i32,
+            field(4..6)?,
+            field(6..8)?,
+            field(8..10)?,
+            field(10..12)?,
+            field(12..14)?,
+            field(14..16)? * 10_000_000,
+        )
+        .ok_or(FileTimeError::InvalidFormat {
+            format: VOLUME,
+            reason: "invalid date or time",
+        })?;
+        Self::from_datetime_checked(local - gmt_offset(raw[16] as i8, VOLUME)?)
+    }
+
+    /// Build the 17 byte volume descriptor form, in the given GMT offset (15 minute intervals)
+    ///
+    /// Ticks below hundredths of a second are truncated. Fails with
+    /// [`FileTimeError::OutOfRange`] for offsets outside of -48..=52 and
+    /// local years beyond 9999.
+    pub fn to_iso9660_volume(&self, gmt_offset_quarters: i8) -> Result<[u8; 17], FileTimeError> {
+        let local = self
+            .to_datetime_checked()
+            .map_err(|_| FileTimeError::OutOfRange(VOLUME))?
+            + gmt_offset(gmt_offset_quarters, VOLUME)?;
+        if local.year() > 9999 {
+            return Err(FileTimeError::OutOfRange(VOLUME));
+        }
+        let digits = format!(
+            "{}{:02}",
+            local.format("%Y%m%d%H%M%S"),
+            local.nanosecond() / 10_000_000
+        );
+        let mut raw = [0u8; 17];
+        raw[..16].copy_from_slice(digits.as_bytes());
+        raw[16] = gmt_offset_quarters as u8;
+        Ok(raw)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // From `images/rockridge.iso` of the cdfs 0.2.3 crate, written by mkisofs
+    // in UTC-7 (SHA-256 8f4438dc2575ea12a0e2be30650903877f4feb5699ecd80c96b7b4bef0d465fd)
+
+    /// Volume creation date, at byte 0x832D
+    const VOLUME_CREATION: [u8; 17] = *b"2023090306020024\xe4";
+    /// Volume expiration date, not set, at byte 0x834F
+    const VOLUME_EXPIRATION: [u8; 17] = *b"0000000000000000\x00";
+    /// Recording time of the root directory record, at byte 0x80AE
+    const DIR_RECORD_TIME: [u8; 7] = [123, 9, 3, 6, 2, 0, 228];
+
+    #[test]
+    fn volume() {
+        let ft = FileTime::from_iso9660_volume(&VOLUME_CREATION).unwrap();
+        assert_eq!(
+            ft.to_datetime().to_rfc3339(),
+            "2023-09-03T13:02:00.240+00:00"
+        );
+        assert_eq!(ft.to_iso9660_volume(-28).unwrap(), VOLUME_CREATION);
+        assert_eq!(&ft.to_iso9660_volume(0).unwrap(), b"2023090313020024\x00");
+    }
+
+    #[test]
+    fn dir_record() {
+        let ft = FileTime::from_iso9660_dir_record(&DIR_RECORD_TIME).unwrap();
+        assert_eq!(ft.to_datetime().to_rfc3339(), "2023-09-03T13:02:00+00:00");
+        assert_eq!(ft.to_iso9660_dir_record(-28).unwrap(), DIR_RECORD_TIME);
+
+        // East of GMT, +01:00
+        let raw = [123, 9, 3, 14, 2, 0, 4];
+        assert_eq!(FileTime::from_iso9660_dir_record(&raw), Ok(ft));
+        assert_eq!(ft.to_iso9660_dir_record(4).unwrap(), raw);
+    }
+
+    #[test]
+    fn not_specified() {
+        assert_eq!(
+            FileTime::from_iso9660_dir_record(&[0; 7]),
+            Err(FileTimeError::NotSpecified)
+        );
+        assert_eq!(
+            FileTime::from_iso9660_volume(&VOLUME_EXPIRATION),
+            Err(FileTimeError::NotSpecified)
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            FileTime::from_iso9660_dir_record(&[119, 13, 7, 12, 38, 46, 4]),
+            Err(FileTimeError::InvalidFormat {
+                format: DIR_RECORD,
+                reason: "invalid date or time"
+            })
+        );
+        assert_eq!(
+            FileTime::from_iso9660_dir_record(&[119, 12, 7, 12, 38, 46, 53]),
+            Err(FileTimeError::OutOfRange(DIR_RECORD))
+        );
+        assert_eq!(
+            FileTime::from_iso9660_volume(b"2019120712 84612\x04"),
+            Err(FileTimeError::InvalidFormat {
+                format: VOLUME,
+                reason: "expected digits"
+            })
+        );
+        let ft = FileTime::from_iso9660_volume(&VOLUME_CREATION).unwrap();
+        assert_eq!(
+            ft.to_iso9660_dir_record(-49),
+            Err(FileTimeError::OutOfRange(DIR_RECORD))
+        );
+        assert_eq!(
+            FileTime::from_i64(0).to_iso9660_dir_record(0),
+            Err(FileTimeError::OutOfRange(DIR_RECORD))
+        );
+        assert_eq!(
+            FileTime::MAX_RAW.to_iso9660_dir_record(0),
+            Err(FileTimeError::OutOfRange(DIR_RECORD))
+        );
+        assert_eq!(
+            FileTime::MAX_RAW.to_iso9660_volume(0),
+            Err(FileTimeError::OutOfRange(VOLUME))
+        );
+    }
+}
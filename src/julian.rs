@@ -0,0 +1,168 @@
+//! Fractional day counts: Julian Date (JD), Modified Julian Date (MJD) and SQLite's `julianday()`
+//!
+//! All conversions are UTC-based and, like FILETIME itself, ignore leap
+//! seconds: every day is 86400 seconds long.
+use crate::{FileTime, FileTimeError};
+
+/// Julian Day number of the FILETIME-Epoch (1601-01-01T00:00:00Z)
+const EPOCH_AS_JULIAN_DAY: f64 = 2305813.5;
+/// Modified Julian Date of the FILETIME-Epoch, MJD = JD - 2400000.5
+const EPOCH_AS_MJD: f64 = -94187.0;
+const TICKS_PER_DAY: f64 = 864_000_000_000.0;
+
+impl FileTime {
+    /// Construct FileTime from a fractional day count relative to a Julian Day number
+    fn from_julian_days(days: f64, epoch: f64) -> Result<Self, FileTimeError> {
+        if !days.is_finite() {
+            return Err(FileTimeError::NotFinite);
+        }
+        let ticks = ((days - epoch) * TICKS_PER_DAY).round();
+        if ticks < 0.0 {
+            return Err(FileTimeError::BeforeEpoch);
+        }
+        // i64::MAX as f64 rounds up to 2^63
+        if ticks >= i64::MAX as f64 {
+            return Err(FileTimeError::Overflow);
+        }
+        Ok(Self::from_i64(ticks as i64))
+    }
+
+    fn to_julian_days(self, epoch: f64) -> f64 {
+        self.as_u64() as f64 / TICKS_PER_DAY + epoch
+    }
+
+    /// Construct FileTime from a SQLite `julianday()` value
+    ///
+    /// The value is rounded to the nearest tick. Note that f64 only resolves
+    /// about 40µs at current dates, so julianday values are not suited for
+    /// sub-millisecond timestamps.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_sqlite_julian(2451544.5).unwrap();
+    /// assert_eq!(ft.to_datetime().to_rfc3339(), "2000-01-01T00:00:00+00:00");
+    /// ```
+    pub fn from_sqlite_julian(julian_day: f64) -> Result<Self, FileTimeError> {
+        Self::from_julian_days(julian_day, EPOCH_AS_JULIAN_DAY)
+    }
+
+    /// Return the SQLite `julianday()` value, see [`FileTime::from_sqlite_julian`] for precision
+    pub fn to_sqlite_julian(&self) -> f64 {
+        self.to_julian_days(EPOCH_AS_JULIAN_DAY)
+    }
+
+    /// Construct FileTime from a Julian Date
+    ///
+    /// Same as [`FileTime::from_sqlite_julian`], f64 resolves about 40µs at current dates.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_julian_date(2440587.5).unwrap();
+    /// assert_eq!(ft.to_datetime().to_rfc3339(), "1970-01-01T00:00:00+00:00");
+    /// ```
+    pub fn from_julian_date(jd: f64) -> Result<Self, FileTimeError> {
+        Self::from_julian_days(jd, EPOCH_AS_JULIAN_DAY)
+    }
+
+    /// Return the Julian Date
+    pub fn to_julian_date(&self) -> f64 {
+        self.to_julian_days(EPOCH_AS_JULIAN_DAY)
+    }
+
+    /// Construct FileTime from a Modified Julian Date (days since 1858-11-17T00:00:00Z)
+    ///
+    /// The smaller magnitude of MJD values gives a resolution of a few µs at current dates.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_mjd(0.0).unwrap();
+    /// assert_eq!(ft.to_datetime().to_rfc3339(), "1858-11-17T00:00:00+00:00");
+    /// ```
+    pub fn from_mjd(mjd: f64) -> Result<Self, FileTimeError> {
+        Self::from_julian_days(mjd, EPOCH_AS_MJD)
+    }
+
+    /// Return the Modified Julian Date
+    pub fn to_mjd(&self) -> f64 {
+        self.to_julian_days(EPOCH_AS_MJD)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sqlite_julian() {
+        // SQLite documentation: julianday('2000-01-01 00:00:00') = 2451544.5
+        let ft = FileTime::from_sqlite_julian(2451544.5).unwrap();
+        assert_eq!(ft.filetime(), 125911584000000000);
+        assert_eq!(ft.to_sqlite_julian(), 2451544.5);
+
+        assert_eq!(
+            FileTime::from_sqlite_julian(EPOCH_AS_JULIAN_DAY),
+            Ok(FileTime::from_i64(0))
+        );
+    }
+
+    #[test]
+    fn sqlite_julian_roundtrip() {
+        let ft = FileTime::from_i64(133326144001234567);
+        let back = FileTime::from_sqlite_julian(ft.to_sqlite_julian()).unwrap();
+        // Within 50µs
+        assert!((back.filetime() - ft.filetime()).abs() < 500);
+    }
+
+    #[test]
+    fn mjd() {
+        let ft = FileTime::from_mjd(0.0).unwrap();
+        assert_eq!(ft.to_datetime().to_rfc3339(), "1858-11-17T00:00:00+00:00");
+        assert_eq!(ft.to_julian_date(), 2400000.5);
+
+        let unix = FileTime::from_mjd(40587.0).unwrap();
+        assert_eq!(unix.filetime(), 116444736000000000);
+        assert_eq!(unix.to_mjd(), 40587.0);
+        assert_eq!(FileTime::from_julian_date(2440587.5), Ok(unix));
+    }
+
+    #[test]
+    fn mjd_roundtrip() {
+        let ft = FileTime::from_i64(133326144001234567);
+        let back = FileTime::from_mjd(ft.to_mjd()).unwrap();
+        assert!((back.filetime() - ft.filetime()).abs() < 20);
+        assert!((ft.to_julian_date() - ft.to_mjd() - 2400000.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn mjd_invalid() {
+        assert_eq!(FileTime::from_mjd(f64::NAN), Err(FileTimeError::NotFinite));
+        assert_eq!(
+            FileTime::from_julian_date(f64::NEG_INFINITY),
+            Err(FileTimeError::NotFinite)
+        );
+        assert_eq!(
+            FileTime::from_mjd(-94187.5),
+            Err(FileTimeError::BeforeEpoch)
+        );
+    }
+
+    #[test]
+    fn sqlite_julian_invalid() {
+        assert_eq!(
+            FileTime::from_sqlite_julian(f64::NAN),
+            Err(FileTimeError::NotFinite)
+        );
+        assert_eq!(
+            FileTime::from_sqlite_julian(f64::INFINITY),
+            Err(FileTimeError::NotFinite)
+        );
+        assert_eq!(
+            FileTime::from_sqlite_julian(2305813.4),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::from_sqlite_julian(1e9),
+            Err(FileTimeError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! `KSYSTEM_TIME`, as in the shared user data page (`KUSER_SHARED_DATA`)
+//!
+//! ```text
+//! 0x00  u32  LowPart
+//! 0x04  i32  High1Time
+//! 0x08  i32  High2Time
+//! ```
+//!
+//! The kernel writes `High2Time`, `LowPart`, then `High1Time`, and readers
+//! read in the opposite order and retry until both high parts agree. In a
+//! memory image there is no retry, a mismatch means the image was taken
+//! during an update. `KUSER_SHARED_DATA` (at `0x7FFE0000` in every process,
+//! `0xFFFFF78000000000` in the x64 kernel) holds `SystemTime` at offset 0x14,
+//! after `InterruptTime` at 0x08 which counts since boot rather than 1601.
+use crate::{FileTime, FileTimeError};
+
+impl FileTime {
+    /// Combine the parts of a `KSYSTEM_TIME`
+    ///
+    /// Fails with [`FileTimeError::TornRead`] if `high1` and `high2` differ,
+    /// and with [`FileTimeError::BeforeEpoch`] for a negative high part.
+    /// ```
+    /// use filetime_type::{FileTime, FileTimeError};
+    ///
+    /// let ft = FileTime::from_ksystem_time(0x2C1B_2B87, 0x01D9_AB45, 0x01D9_AB45).unwrap();
+    /// assert_eq!(ft.filetime(), 0x01D9_AB45_2C1B_2B87);
+    /// assert_eq!(
+    ///     FileTime::from_ksystem_time(0x2C1B_2B87, 0x01D9_AB45, 0x01D9_AB46),
+    ///     Err(FileTimeError::TornRead)
+    /// );
+    /// ```
+    pub const fn from_ksystem_time(
+        low: u32,
+        high1: i32,
+        high2: i32,
+    ) -> Result<FileTime, FileTimeError> {
+        if high1 != high2 {
+            return Err(FileTimeError::TornRead);
+        }
+        // Sign extended, so a negative high part makes the whole value negative
+        let ticks = (high1 as i64) << 32 | low as i64;
+        if ticks < 0 {
+            return Err(FileTimeError::BeforeEpoch);
+        }
+        Ok(FileTime {
+            ticks: ticks as u64,
+        })
+    }
+
+    /// Decode the 12 bytes of a little-endian `KSYSTEM_TIME`
+    ///
+    /// For the system time in a memory image, pass the 12 bytes at offset
+    /// 0x14 of `KUSER_SHARED_DATA`. Fails like [`FileTime::from_ksystem_time`].
+    pub const fn from_ksystem_time_bytes(bytes: &[u8; 12]) -> Result<FileTime, FileTimeError> {
+        let low = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let high1 = i32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        let high2 = i32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+        Self::from_ksystem_time(low, high1, high2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // 2023-06-30T08:00:00.1234567Z
+    const TICKS: i64 = 133325856001234567;
+
+    fn parts(ticks: i64) -> (u32, i32) {
+        (ticks as u32, (ticks >> 32) as i32)
+    }
+
+    #[test]
+    fn consistent() {
+        let (low, high) = parts(TICKS);
+        assert_eq!(
+            FileTime::from_ksystem_time(low, high, high),
+            Ok(FileTime::from_i64(TICKS))
+        );
+        // The low part is unsigned, its top bit must not leak into the high part
+        let ticks = 0x01D9_AB45_8000_0001;
+        let (low, high) = parts(ticks);
+        assert!(low > i32::MAX as u32);
+        assert_eq!(
+            FileTime::from_ksystem_time(low, high, high),
+            Ok(FileTime::from_i64(ticks))
+        );
+        assert_eq!(FileTime::from_ksystem_time(0, 0, 0), Ok(FileTime::MIN));
+        assert_eq!(
+            FileTime::from_ksystem_time(u32::MAX, i32::MAX, i32::MAX),
+            Ok(FileTime::MAX)
+        );
+    }
+
+    #[test]
+    fn torn() {
+        let (low, high) = parts(TICKS);
+        assert_eq!(
+            FileTime::from_ksystem_time(low, high, high + 1),
+            Err(FileTimeError::TornRead)
+        );
+        // Torn across a carry into the high part: new High2, old High1
+        assert_eq!(
+            FileTime::from_ksystem_time(0, high, high + 1),
+            Err(FileTimeError::TornRead)
+        );
+        assert_eq!(
+            FileTime::from_ksystem_time(0, -1, 0),
+            Err(FileTimeError::TornRead)
+        );
+        assert_eq!(
+            FileTime::from_ksystem_time(0, -1, -1),
+            Err(FileTimeError::BeforeEpoch)
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        // SystemTime as found at KUSER_SHARED_DATA + 0x14, the plain FILETIME
+        // layout followed by the second copy of the high part
+        let plain = TICKS.to_le_bytes();
+        let mut bytes = [0u8; 12];
+        bytes[..8].copy_from_slice(&plain);
+        bytes[8..].copy_from_slice(&plain[4..]);
+        assert_eq!(
+            FileTime::from_ksystem_time_bytes(&bytes),
+            Ok(FileTime::from_i64(TICKS))
+        );
+        assert_eq!(
+            FileTime::from_ksystem_time_bytes(&bytes)
+                .unwrap()
+                .to_datetime(),
+            FileTime::from_ymd_hms_nano(2023, 6, 30, 8, 0, 0, 123_456_700)
+                .unwrap()
+                .to_datetime()
+        );
+        bytes[11] ^= 0x01;
+        assert_eq!(
+            FileTime::from_ksystem_time_bytes(&bytes),
+            Err(FileTimeError::TornRead)
+        );
+    }
+}
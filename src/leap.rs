@@ -0,0 +1,355 @@
+//! Leap second aware conversion between FILETIME (UTC) and TAI
+//!
+//! Like POSIX time, FILETIME pretends every day has 86400 seconds. TAI counts
+//! every elapsed SI second, so it runs ahead of UTC by the accumulated leap
+//! seconds (TAI − UTC, 37 s since 2017-01-01). TAI ticks here count 100ns
+//! intervals since the FILETIME epoch read as TAI, i.e. FILETIME ticks plus
+//! TAI − UTC.
+//!
+//! Leap second policy: UTC cannot express 23:59:60, so TAI instants within an
+//! inserted leap second are clamped to 23:59:59.9999999 of that day.
+//! Before the first table entry (1972-01-01 for the built-in table) the first
+//! offset is used, the fractional offsets of the 1960s are not modelled.
+use crate::{FileTime, FileTimeError};
+
+const TICKS_PER_SECOND: i64 = FileTime::HUNDREDS_OF_NANOSECONDS;
+/// 1900-01-01T00:00:00Z, the NTP epoch used by `leap-seconds.list`
+const NTP_EPOCH_AS_FILETIME: i64 = 94_354_848_000_000_000;
+const IERS_LIST: &str = "leap-seconds.list";
+
+/// UTC start dates and the TAI − UTC offset in effect from then on, as published by the IERS
+const BUILTIN: [(i32, u32, i32); 28] = [
+    (1972, 1, 10),
+    (1972, 7, 11),
+    (1973, 1, 12),
+    (1974, 1, 13),
+    (1975, 1, 14),
+    (1976, 1, 15),
+    (1977, 1, 16),
+    (1978, 1, 17),
+    (1979, 1, 18),
+    (1980, 1, 19),
+    (1981, 7, 20),
+    (1982, 7, 21),
+    (1983, 7, 22),
+    (1985, 7, 23),
+    (1988, 1, 24),
+    (1990, 1, 25),
+    (1991, 1, 26),
+    (1992, 7, 27),
+    (1993, 7, 28),
+    (1994, 7, 29),
+    (1996, 1, 30),
+    (1997, 7, 31),
+    (1999, 1, 32),
+    (2006, 1, 33),
+    (2009, 1, 34),
+    (2012, 7, 35),
+    (2015, 7, 36),
+    (2017, 1, 37),
+];
+
+/// TAI − UTC offsets over time
+///
+/// [`LeapSecondTable::builtin()`] (also the `Default`) ships the IERS table up
+/// to the leap second at the end of 2016, the latest one as of this release.
+/// Newer tables can be loaded from the IERS `leap-seconds.list` file with
+/// [`LeapSecondTable::from_iers_list()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeapSecondTable {
+    /// UTC ticks the offset takes effect at and TAI − UTC in seconds, sorted
+    entries: Vec<(u64, i32)>,
+}
+
+impl LeapSecondTable {
+    /// The built-in table, 1972-01-01 (10 s) to 2017-01-01 (37 s)
+    pub fn builtin() -> Self {
+        let entries = BUILTIN
+            .iter()
+            .map(|&(year, month, offset)| {
+                let start = FileTime::from_ymd_hms(year, month, 1, 0, 0, 0)
+                    .expect("built-in leap second table is valid");
+                (start.as_u64(), offset)
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Table from `(start, TAI − UTC seconds)` pairs
+    ///
+    /// Fails with [`FileTimeError::InvalidElement`] for an empty list or start
+    /// instants that are not strictly increasing.
+    pub fn from_entries<I>(entries: I) -> Result<Self, FileTimeError>
+    where
+        I: IntoIterator<Item = (FileTime, i32)>,
+    {
+        let entries: Vec<_> = entries
+            .into_iter()
+            .map(|(start, offset)| (start.as_u64(), offset))
+            .collect();
+        if entries.is_empty() {
+            return Err(FileTimeError::InvalidElement {
+                index: 0,
+                reason: "leap second table is empty",
+            });
+        }
+        if let Some(index) = entries.windows(2).position(|pair| pair[0].0 >= pair[1].0) {
+            return Err(FileTimeError::InvalidElement {
+                index: index + 1,
+                reason: "start instants must be strictly increasing",
+            });
+        }
+        Ok(Self { entries })
+    }
+
+    /// Parse the IERS / IETF `leap-seconds.list` file
+    ///
+    /// Each data line holds NTP seconds since 1900-01-01 and TAI − UTC,
+    /// everything after `#` is a comment. Fails with
+    /// [`FileTimeError::Malformed`] naming the byte offset of a bad line.
+    /// ```
+    /// use filetime_type::{FileTime, LeapSecondTable};
+    ///
+    /// let list = "\
+    /// ## Updated through IERS Bulletin C
+    /// 2272060800\t10\t# 1 Jan 1972
+    /// 3692217600\t37\t# 1 Jan 2017
+    /// ";
+    /// let table = LeapSecondTable::from_iers_list(list).unwrap();
+    /// let ft = FileTime::from_ymd_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(table.offset_at(ft), 37);
+    /// ```
+    pub fn from_iers_list(list: &str) -> Result<Self, FileTimeError> {
+        let malformed = |offset, reason| FileTimeError::Malformed {
+            format: IERS_LIST,
+            offset,
+            reason,
+        };
+        let mut entries = Vec::new();
+        let mut offset = 0;
+        for line in list.split_inclusive('\n') {
+            let line_offset = offset;
+            offset += line.len();
+            let data = line.split('#').next().unwrap_or_default();
+            let mut fields = data.split_whitespace();
+            let ntp_secs = match fields.next() {
+                Some(field) => field,
+                None => continue,
+            };
+            let ntp_secs: i64 = ntp_secs
+                .parse()
+                .map_err(|_| malformed(line_offset, "expected NTP seconds"))?;
+            let tai_minus_utc: i32 = fields
+                .next()
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(|| malformed(line_offset, "expected TAI - UTC seconds"))?;
+            if fields.next().is_some() {
+                return Err(malformed(line_offset, "unexpected trailing field"));
+            }
+            let start = ntp_secs
+                .checked_mul(TICKS_PER_SECOND)
+                .and_then(|ticks| ticks.checked_add(NTP_EPOCH_AS_FILETIME))
+                .and_then(|ticks| FileTime::from_i64_checked(ticks).ok())
+                .ok_or_else(|| malformed(line_offset, "NTP seconds out of FILETIME range"))?;
+            if matches!(entries.last(), Some(&(last, _)) if last >= start.as_u64()) {
+                return Err(malformed(
+                    line_offset,
+                    "entries must be strictly increasing",
+                ));
+            }
+            entries.push((start.as_u64(), tai_minus_utc));
+        }
+        if entries.is_empty() {
+            return Err(malformed(0, "no entries"));
+        }
+        Ok(Self { entries })
+    }
+
+    /// TAI − UTC in seconds at the given UTC instant
+    pub fn offset_at(&self, ft: FileTime) -> i32 {
+        let index = self
+            .entries
+            .partition_point(|&(start, _)| start <= ft.as_u64());
+        self.entries[index.saturating_sub(1)].1
+    }
+}
+
+impl Default for LeapSecondTable {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl FileTime {
+    /// TAI ticks since 1601-01-01T00:00:00 TAI, saturating at the i64 range
+    /// ```
+    /// use filetime_type::{FileTime, LeapSecondTable};
+    ///
+    /// let table = LeapSecondTable::builtin();
+    /// let ft = FileTime::from_ymd_hms(2017, 1, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(ft.to_tai_ticks(&table), ft.filetime() + 37 * 10_000_000);
+    /// assert_eq!(FileTime::from_tai_ticks(ft.to_tai_ticks(&table), &table), Ok(ft));
+    /// ```
+    pub fn to_tai_ticks(&self, table: &LeapSecondTable) -> i64 {
+        self.filetime()
+            .saturating_add(i64::from(table.offset_at(*self)) * TICKS_PER_SECOND)
+    }
+
+    /// Construct FileTime from TAI ticks since 1601-01-01T00:00:00 TAI
+    ///
+    /// Instants within an inserted leap second are clamped to 23:59:59.9999999
+    /// UTC. Fails for results outside of the FILETIME range.
+    pub fn from_tai_ticks(tai_ticks: i64, table: &LeapSecondTable) -> Result<Self, FileTimeError> {
+        let tai_ticks = i128::from(tai_ticks);
+        let tai_start = |&(start, offset): &(u64, i32)| {
+            i128::from(start) + i128::from(offset) * i128::from(TICKS_PER_SECOND)
+        };
+        let index = table
+            .entries
+            .partition_point(|entry| tai_start(entry) <= tai_ticks)
+            .saturating_sub(1);
+        let offset = i128::from(table.entries[index].1) * i128::from(TICKS_PER_SECOND);
+        let mut utc = tai_ticks - offset;
+        if let Some(&(next_start, _)) = table.entries.get(index + 1) {
+            // Inside the leap second preceding the next entry
+            utc = utc.min(i128::from(next_start) - 1);
+        }
+        Self::from_ticks_i128(utc)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SECOND: i64 = TICKS_PER_SECOND;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> FileTime {
+        FileTime::from_ymd_hms(year, month, day, hour, min, sec).unwrap()
+    }
+
+    #[test]
+    fn leap_second_2016() {
+        let table = LeapSecondTable::builtin();
+        let before = utc(2016, 12, 31, 23, 59, 59);
+        let after = utc(2017, 1, 1, 0, 0, 0);
+        assert_eq!(table.offset_at(before), 36);
+        assert_eq!(table.offset_at(after), 37);
+        // One UTC second apart, but two SI seconds elapsed
+        assert_eq!(after.filetime() - before.filetime(), SECOND);
+        assert_eq!(
+            after.to_tai_ticks(&table) - before.to_tai_ticks(&table),
+            2 * SECOND
+        );
+
+        for ft in &[before, after, FileTime::from_i64(after.filetime() - 1)] {
+            assert_eq!(
+                FileTime::from_tai_ticks(ft.to_tai_ticks(&table), &table),
+                Ok(*ft)
+            );
+        }
+    }
+
+    #[test]
+    fn clamps_inside_leap_second() {
+        let table = LeapSecondTable::builtin();
+        let last_tick = FileTime::from_i64(utc(2017, 1, 1, 0, 0, 0).filetime() - 1);
+        let leap_start = utc(2016, 12, 31, 23, 59, 59).to_tai_ticks(&table) + SECOND;
+        for &tai in &[leap_start, leap_start + SECOND / 2, leap_start + SECOND - 1] {
+            assert_eq!(FileTime::from_tai_ticks(tai, &table), Ok(last_tick));
+        }
+        assert_eq!(
+            FileTime::from_tai_ticks(leap_start + SECOND, &table),
+            Ok(utc(2017, 1, 1, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn outside_of_table() {
+        let table = LeapSecondTable::builtin();
+        assert_eq!(table.offset_at(FileTime::MIN), 10);
+        assert_eq!(table.offset_at(FileTime::MAX), 37);
+        assert_eq!(FileTime::MIN.to_tai_ticks(&table), 10 * SECOND);
+        assert_eq!(FileTime::MAX.to_tai_ticks(&table), i64::MAX);
+        assert_eq!(
+            FileTime::from_tai_ticks(0, &table),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::from_tai_ticks(10 * SECOND, &table),
+            Ok(FileTime::MIN)
+        );
+    }
+
+    #[test]
+    fn iers_list() {
+        // Layout of the published file with header and hash lines, the
+        // data lines are generated from the built-in table
+        let mut list = String::from("#\tleap-seconds.list\n#$\t 3676924800\n#@\t3913697848\n\n");
+        for &(year, month, offset) in BUILTIN.iter() {
+            let ntp = (utc(year, month, 1, 0, 0, 0).filetime() - NTP_EPOCH_AS_FILETIME) / SECOND;
+            list.push_str(&format!("{}\t{}\t# {}-{:02}\n", ntp, offset, year, month));
+        }
+        list.push_str("#h\t16edd0f0 3666784f 37db6bdd e74ced87 59af48f1\n");
+        assert_eq!(
+            LeapSecondTable::from_iers_list(&list),
+            Ok(LeapSecondTable::builtin())
+        );
+        // Published NTP timestamp of 1972-01-01
+        assert!(list.contains("\n2272060800\t10\t"));
+
+        let err = |offset, reason| {
+            Err(FileTimeError::Malformed {
+                format: IERS_LIST,
+                offset,
+                reason,
+            })
+        };
+        assert_eq!(
+            LeapSecondTable::from_iers_list("# empty\n"),
+            err(0, "no entries")
+        );
+        assert_eq!(
+            LeapSecondTable::from_iers_list("2272060800\t10\nx\t11\n"),
+            err(14, "expected NTP seconds")
+        );
+        assert_eq!(
+            LeapSecondTable::from_iers_list("2272060800\n"),
+            err(0, "expected TAI - UTC seconds")
+        );
+        assert_eq!(
+            LeapSecondTable::from_iers_list("2272060800 10 5\n"),
+            err(0, "unexpected trailing field")
+        );
+        assert_eq!(
+            LeapSecondTable::from_iers_list("2287785600\t11\n2272060800\t10\n"),
+            err(14, "entries must be strictly increasing")
+        );
+    }
+
+    #[test]
+    fn from_entries() {
+        let entries = BUILTIN
+            .iter()
+            .map(|&(year, month, offset)| (utc(year, month, 1, 0, 0, 0), offset));
+        assert_eq!(
+            LeapSecondTable::from_entries(entries),
+            Ok(LeapSecondTable::default())
+        );
+        assert_eq!(
+            LeapSecondTable::from_entries(Vec::new()),
+            Err(FileTimeError::InvalidElement {
+                index: 0,
+                reason: "leap second table is empty"
+            })
+        );
+        let start = utc(2017, 1, 1, 0, 0, 0);
+        assert_eq!(
+            LeapSecondTable::from_entries(vec![(start, 37), (start, 38)]),
+            Err(FileTimeError::InvalidElement {
+                index: 1,
+                reason: "start instants must be strictly increasing"
+            })
+        );
+    }
+}
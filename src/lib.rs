@@ -28,8 +28,231 @@
 //! // Parsing from DateTime<Utc>
 //! let ft_dt = FileTime::from_datetime(Utc::now());
 //! ```
-use chrono::{prelude::*, Duration};
-use std::fmt;
+//!
+//! ## `no_std`
+//!
+//! Without the default `std` feature the crate is `#![no_std]` and needs no
+//! allocator. The type itself, the i64 / byte / `DateTime<Utc>` conversions,
+//! [`FileTimeDelta`] arithmetic, [`FileTimeRange`], the NTFS and LNK parsers
+//! and `Display` keep working; `now()`, strftime formatting, string output and
+//! the `io` based helpers require `std`.
+//!
+//! ## Optional features
+//!
+//! - `std` (default): Everything that needs the standard library, all other features imply it
+//! - `arbitrary`: `Arbitrary` for FileTime, FileTimeDelta and FileTimeRange, for structured fuzzing
+//! - `arrow`: Conversion from/to Arrow timestamp arrays of any unit via the `arrow` module
+//! - `async-graphql`: `FileTime` GraphQL scalar, RFC 3339 with 7 fraction digits on output, that or integer ticks on input
+//! - `bincode`: bincode 2 `Encode`/`Decode`, the raw ticks as 8 little-endian bytes in every configuration
+//! - `bson`: Conversion from/to `bson::DateTime`, with `serde` also the `serde::bson_datetime` with-module
+//! - `clap`: `ValueParserFactory` for FileTime, accepting decimal ticks, `0x` hex ticks and RFC 3339
+//! - `defmt`: `defmt::Format` for FileTime, logging the UTC datetime and raw ticks; no_std, no alloc
+//! - `der`: Conversion from/to `der::asn1::UtcTime` and `der::asn1::GeneralizedTime`
+//! - `diesel-postgres`: Diesel `ToSql`/`FromSql` for PostgreSQL `Timestamptz` columns
+//! - `diesel-sqlite`: Diesel `ToSql`/`FromSql` for SQLite `BigInt` columns holding raw FILETIME ticks
+//! - `fake`: fake `Dummy` for FileTime and the `FileTimeBetween` / `RecentFileTime` fakers in the `fake` module
+//! - `fs`: Setting file access/modification (and on Windows creation) times via `FileTime::set_file_times()` and friends
+//! - `hifitime`: Conversion from/to `hifitime::Epoch` in integer nanoseconds on the UTC scale
+//! - `humantime`: Parsing humantime RFC 3339 timestamps, `add_human("2h 30m")` and `format_humantime()`
+//! - `leap-seconds`: Conversion between FILETIME and TAI ticks via a `LeapSecondTable`, built-in or loaded from the IERS `leap-seconds.list`
+//! - `minicbor`: minicbor `Encode`/`Decode` as raw ticks, CBOR tag 1 via the `minicbor::epoch` with-module; no_std
+//! - `ntfs`: Conversion from/to the ntfs crate's `NtfsTime`, keeping every raw value
+//! - `num-traits`: `ToPrimitive`, `FromPrimitive` and `Bounded` over the raw tick count
+//! - `polars`: Conversion from/to polars `Datetime` series (µs, UTC) and `Int64` tick series via the `polars` module
+//! - `postgres`: `ToSql`/`FromSql` for PostgreSQL `timestamptz` / `timestamp` columns
+//! - `prost`: Conversion from/to `google.protobuf.Timestamp` (`prost_types::Timestamp`)
+//! - `proptest`: `Arbitrary` for FileTime and strategies in the `proptest` module
+//! - `pyo3`: Conversion from/to Python `datetime.datetime` objects
+//! - `quickcheck`: quickcheck `Arbitrary` for FileTime, weighted towards boundary values
+//! - `rand`: `rng.gen::<FileTime>()` and `rng.gen_range(start..end)` via `Standard` and `SampleUniform`
+//! - `rayon`: Parallel bulk conversion via `FileTime::par_convert_i64_slice()` / `FileTime::par_from_le_slice_bulk()`
+//! - `redis`: `ToRedisArgs`/`FromRedisValue` as integer ticks, plus `FileTime::to_redis_score()` for sorted sets
+//! - `rusqlite`: rusqlite `ToSql`/`FromSql` for `INTEGER` columns holding raw FILETIME ticks
+//! - `serde`: `Serialize`/`Deserialize` as raw `i64` ticks, lenient number / string / RFC 3339 input via `serde::lenient`
+//! - `specta`: specta `Type` for TypeScript bindings, `FileTime = number` like the serde representation
+//! - `sqlx-postgres`: sqlx `Type`/`Encode`/`Decode` for PostgreSQL `timestamptz` columns
+//! - `sqlx-sqlite`: sqlx `Type`/`Encode`/`Decode` for SQLite `INTEGER` columns holding raw FILETIME ticks
+//! - `toml`: Conversion from/to `toml::value::Datetime`, with `serde` also the `serde::toml_datetime` with-module
+//! - `utoipa`: OpenAPI `ToSchema` describing FileTime as `int64` ticks
+//! - `valuable`: `Valuable` for structured logging, a structable with `ticks` and `rfc3339` fields
+//! - `wasm`: Conversion from/to JavaScript `Date` via `js-sys`, `FileTime::now()` on wasm32-unknown-unknown
+//! - `winreg`: Windows registry values (`REG_BINARY`, `REG_QWORD`, `REG_DWORD` pairs) via the `registry` module, the data model is available on every platform
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+use chrono::prelude::*;
+use core::convert::TryFrom;
+use core::fmt;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "std")]
+mod asn1;
+#[cfg(feature = "async-graphql")]
+mod async_graphql;
+#[cfg(feature = "bincode")]
+mod bincode;
+#[cfg(feature = "bson")]
+mod bson;
+mod builder;
+#[cfg(feature = "std")]
+mod bulk;
+mod calendar;
+#[cfg(feature = "std")]
+pub mod cfbf;
+#[cfg(feature = "clap")]
+mod clap;
+mod clock;
+#[cfg(feature = "defmt")]
+mod defmt;
+mod delta;
+#[cfg(feature = "std")]
+mod describe;
+#[cfg(feature = "std")]
+mod detect;
+#[cfg(any(feature = "diesel-postgres", feature = "diesel-sqlite"))]
+mod diesel_types;
+#[cfg(feature = "std")]
+mod dmtf;
+mod dotnet;
+mod error;
+#[cfg(feature = "std")]
+pub mod ese;
+#[cfg(feature = "std")]
+mod exif;
+#[cfg(feature = "fake")]
+pub mod fake;
+mod format;
+#[cfg(feature = "fs")]
+mod fs;
+#[cfg(feature = "hifitime")]
+mod hifitime;
+#[cfg(feature = "std")]
+mod http_date;
+mod humanize;
+#[cfg(feature = "humantime")]
+mod humantime;
+#[cfg(feature = "std")]
+mod iso9660;
+#[cfg(feature = "std")]
+mod julian;
+mod ksystem;
+#[cfg(feature = "leap-seconds")]
+mod leap;
+pub mod lnk;
+#[doc(hidden)]
+pub mod macros;
+#[cfg(feature = "minicbor")]
+pub mod minicbor;
+#[cfg(feature = "std")]
+mod ms_json;
+pub mod ntfs;
+#[cfg(feature = "ntfs")]
+mod ntfs_time;
+#[cfg(feature = "num-traits")]
+mod num;
+#[cfg(feature = "std")]
+mod parse;
+#[cfg(feature = "std")]
+mod pdf_date;
+mod plausible;
+#[cfg(feature = "polars")]
+pub mod polars;
+#[cfg(feature = "postgres")]
+mod postgres;
+mod precision;
+#[cfg(feature = "std")]
+pub mod prefetch;
+pub mod propset;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "prost")]
+mod prost;
+#[cfg(feature = "pyo3")]
+mod python;
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+#[cfg(feature = "rand")]
+mod rand;
+mod range;
+#[cfg(feature = "std")]
+pub mod recyclebin;
+#[cfg(feature = "redis")]
+mod redis;
+#[cfg(feature = "winreg")]
+pub mod registry;
+#[cfg(feature = "std")]
+mod rfc2822;
+#[cfg(feature = "rusqlite")]
+mod rusqlite;
+mod scan;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "std")]
+mod sort_key;
+#[cfg(feature = "specta")]
+pub mod specta;
+#[cfg(feature = "std")]
+mod sqlserver;
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+mod sqlx;
+mod stats;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "std")]
+mod system_time;
+#[cfg(feature = "std")]
+pub mod timeline;
+#[cfg(feature = "toml")]
+mod toml;
+#[cfg(feature = "std")]
+mod touch;
+mod tzi;
+#[cfg(feature = "utoipa")]
+mod utoipa;
+#[cfg(feature = "valuable")]
+mod valuable;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(all(windows, feature = "std"))]
+mod windows;
+#[cfg(feature = "std")]
+mod xsd;
+#[cfg(feature = "std")]
+pub mod zip_ntfs;
+
+#[cfg(feature = "clap")]
+pub use crate::clap::FileTimeValueParser;
+#[cfg(feature = "rand")]
+pub use crate::rand::UniformFileTime;
+pub use builder::FileTimeBuilder;
+pub use clock::{Clock, FixedClock, SteppingClock};
+#[cfg(feature = "std")]
+pub use clock::{CoarseClock, MonotonicClock, SystemClock};
+pub use delta::{CalendarUnits, FileTimeDelta};
+#[cfg(feature = "std")]
+pub use describe::FileTimeReport;
+#[cfg(feature = "std")]
+pub use detect::{DetectedFormat, TimestampFormat};
+pub use dotnet::DotNetKind;
+pub use error::FileTimeError;
+pub use format::{FileTimeDisplay, FormatStyle};
+#[cfg(feature = "std")]
+pub use format::{FileTimeFormat, SecondsFormat7};
+pub use humanize::{RelativeTime, RelativeUnit};
+#[cfg(feature = "leap-seconds")]
+pub use leap::LeapSecondTable;
+pub use plausible::PlausibilityWindow;
+pub use precision::{second_aligned_fraction, PrecisionClass};
+pub use range::{FileTimeRange, FileTimeSteps};
+pub use scan::{Endianness, FileTimeScan};
+pub use stats::{filetime_mean, FileTimeStats};
+#[cfg(feature = "std")]
+pub use stream::{FileTimeStream, ParseManyError, ParseManyErrorKind};
+pub use tzi::{TimeZoneInformation, TziTransition};
+#[cfg(feature = "std")]
+pub use xsd::MissingTimezone;
 
 /// FILETIME type
 ///
@@ -37,12 +260,28 @@ use std::fmt;
 /// In contrary to unix, the FILETIME-Epoch is: 1601-01-01T00:00:00.000000000Z
 ///
 /// Allows conversion between:
-/// - Raw i64 value
+/// - Raw u64 / i64 value
 /// - DateTime UTC
-#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+///
+/// Like on Windows the raw value covers the whole `u64` range, so sentinels
+/// such as `accountExpires = 0xFFFFFFFFFFFFFFFF` and malformed samples are
+/// representable, compare and order correctly and round-trip through bytes.
+/// Only values up to [`FileTime::MAX`] have a calendar date, Windows'
+/// `FileTimeToSystemTime` rejects the ones above as well: the `_checked`
+/// datetime conversions fail for them, the others panic.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[cfg_attr(
+    any(feature = "diesel-postgres", feature = "diesel-sqlite"),
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "diesel-sqlite", diesel(sql_type = diesel::sql_types::BigInt))]
+#[cfg_attr(
+    feature = "diesel-postgres",
+    diesel(sql_type = diesel::sql_types::Timestamptz)
+)]
 pub struct FileTime {
-    secs: i64,
-    nsecs: i64,
+    /// 100ns ticks since the FILETIME-Epoch
+    ticks: u64,
 }
 
 impl FileTime {
@@ -50,38 +289,170 @@ impl FileTime {
     /// aka. 100 of nanoseconds since 1601-01-01T00:00:00.000000000Z
     const EPOCH_AS_FILETIME: i64 = 116444736000000000;
     const HUNDREDS_OF_NANOSECONDS: i64 = 10000000;
+    const TICKS_PER_MILLISECOND: i64 = 10000;
+    #[cfg(any(
+        feature = "diesel-postgres",
+        feature = "postgres",
+        feature = "sqlx-postgres"
+    ))]
+    const PG_EPOCH_AS_FILETIME: i64 = 125911584000000000;
+
+    /// Smallest FILETIME, the epoch 1601-01-01T00:00:00Z
+    pub const MIN: FileTime = FileTime { ticks: 0 };
+    /// Largest FILETIME with a calendar date (`i64::MAX` ticks), 30828-09-14T02:48:05.4775807Z
+    pub const MAX: FileTime = FileTime {
+        ticks: i64::MAX as u64,
+    };
+    /// Largest raw FILETIME (`u64::MAX` ticks), without a calendar date
+    pub const MAX_RAW: FileTime = FileTime { ticks: u64::MAX };
 
     /// Construct new FileTime by providing seconds and nanoseconds since 1601-01-01T00:00:00.000000000Z
+    ///
+    /// Nanoseconds are truncated to 100ns ticks. Panics if the value exceeds [`FileTime::MAX`].
     pub fn new(secs: i64, nsecs: i64) -> Self {
         assert!(secs > 0, "Positive seconds required");
         assert!(nsecs > 0, "Positive nanoseconds required");
 
-        Self { secs, nsecs }
+        let ticks = secs
+            .checked_mul(Self::HUNDREDS_OF_NANOSECONDS)
+            .and_then(|ticks| ticks.checked_add(nsecs / 100))
+            .expect("FILETIME overflow");
+        Self {
+            ticks: ticks as u64,
+        }
     }
 
     /// Creates a new timestamp representing the current system time
+    ///
+    /// On Windows, the time comes from `GetSystemTimePreciseAsFileTime` with
+    /// full 100ns resolution. With the `wasm` feature on wasm32-unknown-unknown,
+    /// the time is taken from `Date.now()`.
+    /// Code that needs a controllable time source should take a [`Clock`] and
+    /// call [`FileTime::now_with()`] instead.
+    #[cfg(feature = "std")]
     pub fn now() -> Self {
-        Utc::now().into()
+        #[cfg(windows)]
+        return windows::now();
+        #[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+        return wasm::now();
+        #[cfg(not(any(
+            windows,
+            all(feature = "wasm", target_arch = "wasm32", target_os = "unknown")
+        )))]
+        return Utc::now().into();
     }
 
     /// Seconds since FILETIME-Epoch
     pub fn seconds(&self) -> i64 {
-        self.secs
+        (self.ticks / Self::HUNDREDS_OF_NANOSECONDS as u64) as i64
     }
 
     /// Leap Nanoseconds since FILETIME-Epoch
     pub fn nanoseconds(&self) -> i64 {
-        self.nsecs
+        (self.ticks % Self::HUNDREDS_OF_NANOSECONDS as u64 * 100) as i64
     }
 
-    /// Return FILETIME as i64
+    /// Return FILETIME as i64, saturating at `i64::MAX` for raw values above [`FileTime::MAX`]
+    ///
+    /// Use [`FileTime::as_u64`] to get every raw value unchanged.
     /// ```
     /// use filetime_type::FileTime;
     ///
     /// let ft_i64 = FileTime::now().filetime();
+    /// assert_eq!(FileTime::MAX_RAW.filetime(), i64::MAX);
     /// ```
     pub fn filetime(&self) -> i64 {
-        (self.secs * Self::HUNDREDS_OF_NANOSECONDS) + self.nsecs / 100
+        if self.ticks > i64::MAX as u64 {
+            i64::MAX
+        } else {
+            self.ticks as i64
+        }
+    }
+
+    /// Return FILETIME as i64, failing for raw values above [`FileTime::MAX`] instead of saturating
+    ///
+    /// For storage that only has signed 64-bit integers, like SQLite `INTEGER`.
+    /// ```
+    /// use filetime_type::{FileTime, FileTimeError};
+    ///
+    /// assert_eq!(FileTime::MAX.to_i64_checked(), Ok(i64::MAX));
+    /// assert_eq!(FileTime::MAX_RAW.to_i64_checked(), Err(FileTimeError::OutOfRange("i64")));
+    /// ```
+    pub const fn to_i64_checked(&self) -> Result<i64, FileTimeError> {
+        if self.ticks > i64::MAX as u64 {
+            Err(FileTimeError::OutOfRange("i64"))
+        } else {
+            Ok(self.ticks as i64)
+        }
+    }
+
+    /// Construct FileTime from the raw unsigned tick count, as Windows stores it
+    ///
+    /// Every value is accepted, those above [`FileTime::MAX`] have no calendar date.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// // accountExpires of an account that never expires
+    /// let never = FileTime::from_u64(0xFFFF_FFFF_FFFF_FFFF);
+    /// assert!(never > FileTime::MAX);
+    /// assert_eq!(never.as_u64(), u64::MAX);
+    /// assert!(never.to_datetime_checked().is_err());
+    /// ```
+    pub const fn from_u64(ticks: u64) -> Self {
+        Self { ticks }
+    }
+
+    /// Return the raw unsigned tick count
+    pub const fn as_u64(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Combine the `dwLowDateTime` and `dwHighDateTime` halves of a Win32 `FILETIME`
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_parts(0x1A7D_EBCE, 0x01CE_5961);
+    /// assert_eq!(ft, FileTime::from_i64(130139712831482830));
+    /// assert_eq!(ft.to_parts(), (0x1A7D_EBCE, 0x01CE_5961));
+    /// ```
+    pub const fn from_parts(low: u32, high: u32) -> Self {
+        Self {
+            ticks: (high as u64) << 32 | low as u64,
+        }
+    }
+
+    /// Split into the `(dwLowDateTime, dwHighDateTime)` halves of a Win32 `FILETIME`
+    pub const fn to_parts(&self) -> (u32, u32) {
+        (self.ticks as u32, (self.ticks >> 32) as u32)
+    }
+
+    /// Whether both lie at most `tolerance_ticks` apart
+    ///
+    /// Useful when comparing timestamps from filesystems of different resolution.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// // FAT stores modification times with 2 second resolution
+    /// let ntfs = FileTime::from_i64(128930364019999999);
+    /// let fat = FileTime::from_i64(128930364000000000);
+    /// assert!(ntfs.approx_eq(fat, 20_000_000));
+    /// assert!(!ntfs.approx_eq(fat, 19_999_998));
+    /// ```
+    pub fn approx_eq(&self, other: FileTime, tolerance_ticks: u64) -> bool {
+        self.ticks.abs_diff(other.ticks) <= tolerance_ticks
+    }
+
+    /// Like [`FileTime::approx_eq`], with the tolerance as Duration
+    ///
+    /// Fractions of a tick in the tolerance are truncated, the ticks apart are always whole.
+    pub fn approx_eq_duration(&self, other: FileTime, tolerance: core::time::Duration) -> bool {
+        let ticks = tolerance.as_nanos() / 100;
+        self.approx_eq(other, u64::try_from(ticks).unwrap_or(u64::MAX))
+    }
+
+    /// Whether both lie within the same whole second
+    pub fn eq_ignoring_subsec(&self, other: FileTime) -> bool {
+        self.seconds() == other.seconds()
     }
 
     /// Return FILETIME epoch as DateTime<Utc>
@@ -99,10 +470,37 @@ impl FileTime {
     /// ```
     pub fn from_i64(filetime: i64) -> Self {
         assert!(filetime >= 0, "Only positive values allowed");
-        let secs: i64 = filetime / Self::HUNDREDS_OF_NANOSECONDS;
-        let nsecs: i64 = filetime % Self::HUNDREDS_OF_NANOSECONDS * 100;
 
-        Self { secs, nsecs }
+        Self {
+            ticks: filetime as u64,
+        }
+    }
+
+    /// Construct FileTime from i64, failing for negative values instead of panicking
+    /// ```
+    /// use filetime_type::{FileTime, FileTimeError};
+    ///
+    /// assert!(FileTime::from_i64_checked(128930364000001000).is_ok());
+    /// assert_eq!(FileTime::from_i64_checked(-1), Err(FileTimeError::BeforeEpoch));
+    /// ```
+    pub fn from_i64_checked(filetime: i64) -> Result<Self, FileTimeError> {
+        if filetime < 0 {
+            return Err(FileTimeError::BeforeEpoch);
+        }
+        Ok(Self::from_i64(filetime))
+    }
+
+    /// Ticks since the Unix-Epoch, widened so that every raw value fits
+    pub(crate) const fn unix_ticks(&self) -> i128 {
+        self.ticks as i128 - Self::EPOCH_AS_FILETIME as i128
+    }
+
+    /// Range-checked construction from a widened tick count
+    fn from_ticks_i128(ticks: i128) -> Result<Self, FileTimeError> {
+        if ticks > i128::from(i64::MAX) {
+            return Err(FileTimeError::Overflow);
+        }
+        Self::from_i64_checked(ticks.max(-1) as i64)
     }
 
     /// Example
@@ -119,6 +517,22 @@ impl FileTime {
         Self::from_i64(nsecs)
     }
 
+    /// Construct FileTime from DateTime<Utc>, failing for instants outside of the FILETIME range
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use filetime_type::{FileTime, FileTimeError};
+    ///
+    /// let dt = Utc.with_ymd_and_hms(1600, 12, 31, 23, 59, 59).unwrap();
+    /// assert_eq!(FileTime::from_datetime_checked(dt), Err(FileTimeError::BeforeEpoch));
+    /// ```
+    pub fn from_datetime_checked(dt: DateTime<Utc>) -> Result<Self, FileTimeError> {
+        Self::from_ticks_i128(
+            i128::from(Self::EPOCH_AS_FILETIME)
+                + i128::from(dt.timestamp()) * i128::from(Self::HUNDREDS_OF_NANOSECONDS)
+                + i128::from(dt.timestamp_subsec_nanos() / 100),
+        )
+    }
+
     /// Example
     /// ```
     /// use chrono::{DateTime, Utc};
@@ -127,19 +541,292 @@ impl FileTime {
     /// let ft_now: DateTime<Utc> = FileTime::now().to_datetime();
     /// ```
     pub fn to_datetime(&self) -> DateTime<Utc> {
-        Self::filetime_epoch() + Duration::seconds(self.secs) + Duration::nanoseconds(self.nsecs)
+        self.to_datetime_checked()
+            .expect("FILETIME above FileTime::MAX has no date")
+    }
+
+    /// Convert to DateTime<Utc> without panicking
+    ///
+    /// Fails with [`FileTimeError::OutOfRange`] for raw values above [`FileTime::MAX`].
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let dt = FileTime::MAX.to_datetime_checked().unwrap();
+    /// assert_eq!(dt.to_rfc3339(), "+30828-09-14T02:48:05.477580700+00:00");
+    /// ```
+    pub fn to_datetime_checked(&self) -> Result<DateTime<Utc>, FileTimeError> {
+        const OUT_OF_RANGE: FileTimeError = FileTimeError::OutOfRange("chrono::DateTime");
+        if *self > Self::MAX {
+            return Err(OUT_OF_RANGE);
+        }
+        let unix_ticks = self.ticks as i64 - Self::EPOCH_AS_FILETIME;
+        let secs = unix_ticks.div_euclid(Self::HUNDREDS_OF_NANOSECONDS);
+        let nsecs = unix_ticks.rem_euclid(Self::HUNDREDS_OF_NANOSECONDS) as u32 * 100;
+        Utc.timestamp_opt(secs, nsecs).single().ok_or(OUT_OF_RANGE)
+    }
+
+    /// Construct FileTime from seconds since the Unix-Epoch (1970-01-01T00:00:00Z)
+    ///
+    /// Fails for instants before the FILETIME-Epoch.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_unix_seconds(1248562800).unwrap();
+    /// assert_eq!(ft.filetime(), 128930364000000000);
+    /// ```
+    pub fn from_unix_seconds(secs: i64) -> Result<Self, FileTimeError> {
+        Self::from_ticks_i128(
+            i128::from(secs) * i128::from(Self::HUNDREDS_OF_NANOSECONDS)
+                + i128::from(Self::EPOCH_AS_FILETIME),
+        )
+    }
+
+    /// Return seconds since the Unix-Epoch (1970-01-01T00:00:00Z)
+    ///
+    /// Sub-second ticks are truncated towards the earlier instant,
+    /// also for timestamps before 1970.
+    pub fn to_unix_seconds(&self) -> i64 {
+        self.unix_ticks()
+            .div_euclid(i128::from(Self::HUNDREDS_OF_NANOSECONDS)) as i64
+    }
+
+    /// Construct FileTime from milliseconds since the Unix-Epoch (1970-01-01T00:00:00Z)
+    ///
+    /// Fails for instants before the FILETIME-Epoch.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_unix_millis(0).unwrap();
+    /// assert_eq!(ft.filetime(), 116444736000000000);
+    /// ```
+    pub fn from_unix_millis(millis: i64) -> Result<Self, FileTimeError> {
+        Self::from_ticks_i128(
+            i128::from(millis) * i128::from(Self::TICKS_PER_MILLISECOND)
+                + i128::from(Self::EPOCH_AS_FILETIME),
+        )
+    }
+
+    /// Return milliseconds since the Unix-Epoch (1970-01-01T00:00:00Z)
+    ///
+    /// Sub-millisecond ticks are truncated towards the earlier instant,
+    /// also for timestamps before 1970.
+    pub fn to_unix_millis(&self) -> i64 {
+        self.unix_ticks()
+            .div_euclid(i128::from(Self::TICKS_PER_MILLISECOND)) as i64
+    }
+
+    /// Construct FileTime from a Java epoch-millisecond value
+    ///
+    /// Accepts the values produced by `System.currentTimeMillis()` and
+    /// `Instant.toEpochMilli()`. Same as [`FileTime::from_unix_millis`],
+    /// fails for instants before the FILETIME-Epoch.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// // System.currentTimeMillis() at 2023-06-30T16:00:00Z
+    /// let java_millis: i64 = 1688140800000;
+    /// let ft = FileTime::from_java_millis(java_millis).unwrap();
+    /// assert_eq!(ft.to_datetime().to_rfc3339(), "2023-06-30T16:00:00+00:00");
+    /// assert_eq!(ft.to_java_millis(), java_millis);
+    /// ```
+    #[doc(alias = "currentTimeMillis")]
+    pub fn from_java_millis(millis: i64) -> Result<Self, FileTimeError> {
+        Self::from_unix_millis(millis)
+    }
+
+    /// Return the Java epoch-millisecond value, as used by `Instant.ofEpochMilli()`
+    ///
+    /// Same as [`FileTime::to_unix_millis`], sub-millisecond ticks are truncated.
+    #[doc(alias = "toEpochMilli")]
+    pub fn to_java_millis(&self) -> i64 {
+        self.to_unix_millis()
+    }
+
+    /// Construct FileTime from a WebKit / Chrome timestamp
+    ///
+    /// Microseconds since 1601-01-01T00:00:00Z, as found in Chrome's SQLite
+    /// databases (e.g. `urls.last_visit_time`).
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_webkit_micros(12893036400000100).unwrap();
+    /// assert_eq!(ft, FileTime::from_i64(128930364000001000));
+    /// ```
+    pub fn from_webkit_micros(micros: i64) -> Result<Self, FileTimeError> {
+        Self::from_ticks_i128(i128::from(micros) * 10)
+    }
+
+    /// Return microseconds since 1601-01-01T00:00:00Z (WebKit / Chrome timestamp)
+    ///
+    /// The last tick digit (100ns) is truncated.
+    pub fn to_webkit_micros(&self) -> i64 {
+        (self.ticks / 10) as i64
+    }
+
+    /// Time elapsed since the FILETIME-Epoch (1601-01-01T00:00:00Z), exact to the tick
+    /// ```
+    /// use core::time::Duration;
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_i64(130139712831482830);
+    /// assert_eq!(ft.to_duration_since_epoch(), Duration::new(13013971283, 148283000));
+    /// assert_eq!(FileTime::from_duration_since_epoch(ft.to_duration_since_epoch()), Ok(ft));
+    /// ```
+    pub fn to_duration_since_epoch(&self) -> core::time::Duration {
+        core::time::Duration::new(self.seconds() as u64, self.nanoseconds() as u32)
+    }
+
+    /// Construct FileTime from the time elapsed since the FILETIME-Epoch
+    ///
+    /// Sub-tick nanoseconds (below 100ns) are truncated. Fails with
+    /// [`FileTimeError::Overflow`] beyond [`FileTime::MAX`].
+    pub fn from_duration_since_epoch(d: core::time::Duration) -> Result<Self, FileTimeError> {
+        i128::try_from(d.as_nanos() / 100)
+            .map_err(|_| FileTimeError::Overflow)
+            .and_then(Self::from_ticks_i128)
+    }
+
+    /// Microseconds since the PostgreSQL epoch (2000-01-01T00:00:00Z), sub-microsecond ticks are truncated
+    #[cfg(any(
+        feature = "diesel-postgres",
+        feature = "postgres",
+        feature = "sqlx-postgres"
+    ))]
+    pub(crate) fn to_pg_micros(self) -> i64 {
+        (self.ticks as i128 - i128::from(Self::PG_EPOCH_AS_FILETIME)).div_euclid(10) as i64
+    }
+
+    /// Construct FileTime from microseconds since the PostgreSQL epoch
+    ///
+    /// The `infinity` / `-infinity` sentinels are rejected as
+    /// [`FileTimeError::Overflow`] / [`FileTimeError::BeforeEpoch`].
+    #[cfg(any(
+        feature = "diesel-postgres",
+        feature = "postgres",
+        feature = "sqlx-postgres"
+    ))]
+    pub(crate) fn from_pg_micros(micros: i64) -> Result<Self, FileTimeError> {
+        match micros {
+            i64::MAX => Err(FileTimeError::Overflow),
+            i64::MIN => Err(FileTimeError::BeforeEpoch),
+            _ => Self::from_ticks_i128(
+                i128::from(micros) * 10 + i128::from(Self::PG_EPOCH_AS_FILETIME),
+            ),
+        }
+    }
+}
+
+/// Compact form with the decoded datetime and the raw value in hex,
+/// e.g. `FileTime(2013-05-25T16:01:23.1482830Z, raw=0x01CE59611A7DEBCE)`
+///
+/// Values above [`FileTime::MAX`] read `FileTime(no date, raw=0xFFFFFFFFFFFFFFFF)`.
+/// The alternate form `{:#?}` prints the raw field instead.
+impl fmt::Debug for FileTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return f
+                .debug_struct("FileTime")
+                .field("ticks", &self.ticks)
+                .finish();
+        }
+        if *self > Self::MAX {
+            return write!(f, "FileTime(no date, raw={:#018X})", self.ticks);
+        }
+        write!(f, "FileTime({}, raw={:#018X})", self.display(), self.ticks)
     }
 }
 
+/// Shows the datetime with all 7 fractional digits, so distinct values never print alike
+///
+/// Width, fill and alignment pad the whole text. A precision sets the
+/// number of fractional digits of the datetime, truncating, up to 9.
+/// Values above [`FileTime::MAX`] show `DateTime=none`.
+/// ```
+/// use filetime_type::FileTime;
+///
+/// let ft = FileTime::from_i64(128930364000000010);
+/// assert_eq!(
+///     ft.to_string(),
+///     "DateTime=2009-07-25 23:00:00.0000010 UTC secs=12893036400 nsecs=1000"
+/// );
+/// assert_eq!(
+///     format!("{:.3}", ft),
+///     "DateTime=2009-07-25 23:00:00.000 UTC secs=12893036400 nsecs=1000"
+/// );
+/// ```
 impl fmt::Display for FileTime {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "DateTime={} secs={} nsecs={}",
-            self.to_datetime(),
-            self.secs,
-            self.nsecs
-        )
+        use fmt::Write;
+
+        let precision = f.precision().map_or(7, |p| p.min(9) as u8);
+        format::write_padded(f, |w| {
+            w.write_str("DateTime=")?;
+            if *self > Self::MAX {
+                w.write_str("none")?;
+            } else {
+                format::write_datetime(
+                    w,
+                    *self,
+                    format::Layout {
+                        separator: Some(' '),
+                        precision,
+                        suffix_z: false,
+                        signed_years: true,
+                    },
+                )?;
+            }
+            w.write_str(" UTC secs=")?;
+            format::write_digits(w, self.seconds() as u64, 1)?;
+            w.write_str(" nsecs=")?;
+            format::write_digits(w, self.nanoseconds() as u64, 1)
+        })
+    }
+}
+
+/// Parses raw decimal ticks, `0x`-prefixed hex ticks or an RFC 3339 timestamp
+///
+/// Raw ticks may use the full unsigned range up to [`FileTime::MAX_RAW`].
+/// RFC 3339 input with any offset is converted to UTC, digits beyond the
+/// tick precision are truncated.
+/// ```
+/// use filetime_type::FileTime;
+///
+/// let ft = FileTime::from_i64(130139712831482830);
+/// assert_eq!("130139712831482830".parse(), Ok(ft));
+/// assert_eq!("0x01CE59611A7DEBCE".parse(), Ok(ft));
+/// assert_eq!("2013-05-25T18:01:23.148283+02:00".parse(), Ok(ft));
+/// ```
+impl core::str::FromStr for FileTime {
+    type Err = FileTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const INVALID: FileTimeError = FileTimeError::InvalidFormat {
+            format: "FILETIME",
+            reason: "expected decimal ticks, 0x-prefixed hex ticks or an RFC 3339 timestamp",
+        };
+        let int_error = |err: core::num::ParseIntError| match err.kind() {
+            core::num::IntErrorKind::PosOverflow => FileTimeError::Overflow,
+            _ => INVALID,
+        };
+
+        if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(INVALID);
+            }
+            return u64::from_str_radix(hex, 16)
+                .map_err(int_error)
+                .map(Self::from_u64);
+        }
+        if let Some(digits) = s.strip_prefix('-') {
+            if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(FileTimeError::BeforeEpoch);
+            }
+        }
+        if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+            return s.parse::<u64>().map_err(int_error).map(Self::from_u64);
+        }
+        let dt = DateTime::parse_from_rfc3339(s).map_err(|_| INVALID)?;
+        Self::from_datetime_checked(dt.with_timezone(&Utc))
     }
 }
 
@@ -161,27 +848,43 @@ impl From<FileTime> for i64 {
     }
 }
 
-impl From<FileTime> for DateTime<Utc> {
+/// Fails with [`FileTimeError::OutOfRange`] for raw values above [`FileTime::MAX`]
+impl TryFrom<FileTime> for DateTime<Utc> {
+    type Error = FileTimeError;
+
+    fn try_from(ft: FileTime) -> Result<Self, Self::Error> {
+        ft.to_datetime_checked()
+    }
+}
+
+impl From<u64> for FileTime {
+    fn from(val: u64) -> Self {
+        Self::from_u64(val)
+    }
+}
+
+impl From<FileTime> for u64 {
     fn from(ft: FileTime) -> Self {
-        ft.to_datetime()
+        ft.as_u64()
     }
 }
 
 impl From<[u8; 8]> for FileTime {
     fn from(val: [u8; 8]) -> Self {
-        Self::from_i64(i64::from_le_bytes(val))
+        Self::from_u64(u64::from_le_bytes(val))
     }
 }
 
 impl From<FileTime> for [u8; 8] {
     fn from(ft: FileTime) -> Self {
-        ft.filetime().to_le_bytes()
+        ft.as_u64().to_le_bytes()
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use chrono::Duration;
 
     #[test]
     fn from_datetime() {
@@ -191,8 +894,12 @@ mod test {
                 .naive_utc(),
         );
         assert_eq!(
-            dt,
-            FileTime::from_i64(128930364000001000).into()
+            DateTime::<Utc>::try_from(FileTime::from_i64(128930364000001000)),
+            Ok(dt)
+        );
+        assert_eq!(
+            DateTime::<Utc>::try_from(FileTime::MAX_RAW),
+            Err(FileTimeError::OutOfRange("chrono::DateTime"))
         );
     }
 
@@ -203,8 +910,7 @@ mod test {
         assert_eq!(
             ft,
             FileTime {
-                secs: 13013971283,
-                nsecs: 148283000
+                ticks: 130139712831482830
             }
         );
     }
@@ -213,8 +919,7 @@ mod test {
     fn into_u8_array() {
         let bytes = [0xCE_u8, 0xEB, 0x7D, 0x1A, 0x61, 0x59, 0xCE, 0x01];
         let ft: [u8; 8] = FileTime {
-            secs: 13013971283,
-            nsecs: 148283000,
+            ticks: 130139712831482830,
         }
         .into();
         assert_eq!(ft, bytes);
@@ -223,7 +928,7 @@ mod test {
     #[test]
     fn from_u8_array_min() {
         let bytes = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
-        let ft: [u8; 8] = FileTime { secs: 0, nsecs: 0 }.into();
+        let ft: [u8; 8] = FileTime { ticks: 0 }.into();
         assert_eq!(ft, bytes);
     }
 
@@ -255,6 +960,281 @@ mod test {
         assert_eq!(ft.nanoseconds(), 100);
     }
 
+    /// The calendar based conversion `to_datetime` used before switching to
+    /// `timestamp_opt`, kept to check both agree
+    fn to_datetime_reference(ft: FileTime) -> DateTime<Utc> {
+        FileTime::filetime_epoch()
+            + Duration::seconds(ft.seconds())
+            + Duration::nanoseconds(ft.nanoseconds())
+    }
+
+    #[test]
+    fn to_datetime_matches_reference() {
+        let mut grid = vec![
+            0,
+            1,
+            9_999_999,
+            10_000_000,
+            FileTime::EPOCH_AS_FILETIME - 1,
+            FileTime::EPOCH_AS_FILETIME,
+            FileTime::EPOCH_AS_FILETIME + 1,
+            128930364000001000,
+            130139712831482830,
+            // 2024-02-29T12:00:00Z, 2024-03-31T01:00:00Z
+            133536816000000000,
+            133563204000000000,
+            i64::MAX - 1,
+            i64::MAX,
+        ];
+        grid.extend((0..1000i64).map(|i| i * (i64::MAX / 1000) + i * 7_777));
+        for ticks in grid {
+            let ft = FileTime::from_i64(ticks);
+            assert_eq!(ft.to_datetime(), to_datetime_reference(ft), "{}", ticks);
+            assert_eq!(ft.to_datetime_checked(), Ok(to_datetime_reference(ft)));
+        }
+    }
+
+    ::proptest::proptest! {
+        #[test]
+        fn to_datetime_matches_reference_prop(ticks in 0..=i64::MAX) {
+            let ft = FileTime::from_i64(ticks);
+            ::proptest::prop_assert_eq!(ft.to_datetime(), to_datetime_reference(ft));
+        }
+    }
+
+    #[test]
+    fn from_datetime_checked() {
+        let dt = Utc.with_ymd_and_hms(2009, 7, 25, 23, 0, 0).unwrap() + Duration::microseconds(100);
+        assert_eq!(
+            FileTime::from_datetime_checked(dt),
+            Ok(FileTime::from_i64(128930364000001000))
+        );
+        assert_eq!(
+            FileTime::from_datetime_checked(FileTime::filetime_epoch()),
+            Ok(FileTime::from_i64(0))
+        );
+        assert_eq!(
+            FileTime::from_datetime_checked(
+                FileTime::filetime_epoch() - Duration::nanoseconds(100)
+            ),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::from_datetime_checked(Utc.with_ymd_and_hms(30829, 1, 1, 0, 0, 0).unwrap()),
+            Err(FileTimeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn unix_seconds() {
+        let ft = FileTime::from_unix_seconds(1248562800).unwrap();
+        assert_eq!(ft, FileTime::from_i64(128930364000000000));
+        assert_eq!(
+            FileTime::from_i64(128930364009999999).to_unix_seconds(),
+            1248562800
+        );
+        assert_eq!(FileTime::from_i64(116444735999999999).to_unix_seconds(), -1);
+        assert_eq!(
+            FileTime::from_unix_seconds(-11644473601),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::from_unix_seconds(i64::MAX),
+            Err(FileTimeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn java_millis() {
+        let ft = FileTime::from_java_millis(1688140800123).unwrap();
+        assert_eq!(ft.filetime(), 133326144001230000);
+        assert_eq!(ft.to_java_millis(), 1688140800123);
+
+        // Sub-millisecond ticks are truncated
+        assert_eq!(
+            FileTime::from_i64(133326144001239999).to_java_millis(),
+            1688140800123
+        );
+        // .. towards the earlier instant before 1970
+        assert_eq!(FileTime::from_i64(116444735999999999).to_java_millis(), -1);
+    }
+
+    #[test]
+    fn java_millis_range() {
+        assert_eq!(
+            FileTime::from_java_millis(-11644473600000),
+            Ok(FileTime::from_i64(0))
+        );
+        assert_eq!(
+            FileTime::from_java_millis(-11644473600001),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::from_java_millis(i64::MAX),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!(
+            FileTime::from_java_millis(i64::MIN),
+            Err(FileTimeError::BeforeEpoch)
+        );
+    }
+
+    #[test]
+    fn webkit_micros() {
+        let ft = FileTime::from_webkit_micros(13326614400123456).unwrap();
+        assert_eq!(ft.filetime(), 133266144001234560);
+        assert_eq!(
+            FileTime::from_i64(133266144001234569).to_webkit_micros(),
+            13326614400123456
+        );
+        assert_eq!(
+            FileTime::from_webkit_micros(-1),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::from_webkit_micros(i64::MAX),
+            Err(FileTimeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let ft = FileTime::from_i64(130139712831482830);
+        assert_eq!(
+            format!("{:?}", ft),
+            "FileTime(2013-05-25T16:01:23.1482830Z, raw=0x01CE59611A7DEBCE)"
+        );
+        assert_eq!(
+            format!("{:#?}", ft),
+            "FileTime {\n    ticks: 130139712831482830,\n}"
+        );
+        assert_eq!(
+            format!("{:?}", FileTime::from_i64(i64::MAX)),
+            "FileTime(30828-09-14T02:48:05.4775807Z, raw=0x7FFFFFFFFFFFFFFF)"
+        );
+    }
+
+    #[test]
+    fn display_one_tick_apart() {
+        let a = FileTime::from_i64(128930364000000000);
+        let b = FileTime::from_i64(128930364000000001);
+        assert_ne!(a.to_string(), b.to_string());
+        assert_eq!(
+            b.to_string(),
+            "DateTime=2009-07-25 23:00:00.0000001 UTC secs=12893036400 nsecs=100"
+        );
+        assert_eq!(
+            FileTime::MAX.to_string(),
+            "DateTime=+30828-09-14 02:48:05.4775807 UTC secs=922337203685 nsecs=477580700"
+        );
+    }
+
+    #[test]
+    fn display_flags() {
+        let ft = FileTime::from_i64(128930364001234567);
+        let plain = "DateTime=2009-07-25 23:00:00.1234567 UTC secs=12893036400 nsecs=123456700";
+        assert_eq!(format!("{:80}|", ft), format!("{}       |", plain));
+        assert_eq!(format!("{:>80}", ft), format!("       {}", plain));
+        assert_eq!(format!("{:*^80}", ft), format!("***{}****", plain));
+        // Narrower widths never cut the text
+        assert_eq!(format!("{:10}", ft), plain);
+        assert_eq!(
+            format!("{:.0}", ft),
+            "DateTime=2009-07-25 23:00:00 UTC secs=12893036400 nsecs=123456700"
+        );
+        assert_eq!(
+            format!("{:.9}", ft),
+            "DateTime=2009-07-25 23:00:00.123456700 UTC secs=12893036400 nsecs=123456700"
+        );
+        assert_eq!(format!("{:.12}", ft), format!("{:.9}", ft));
+        assert_eq!(
+            format!("{:->73.3}", ft),
+            "----DateTime=2009-07-25 23:00:00.123 UTC secs=12893036400 nsecs=123456700"
+        );
+        assert_eq!(
+            format!("{:<1$.2$}|", ft, 70, 1),
+            "DateTime=2009-07-25 23:00:00.1 UTC secs=12893036400 nsecs=123456700   |"
+        );
+    }
+
+    #[test]
+    fn approx_eq() {
+        let a = FileTime::from_i64(128930364000000000);
+        let b = FileTime::from_i64(128930364000000100);
+        assert!(a.approx_eq(b, 100));
+        assert!(b.approx_eq(a, 100));
+        assert!(!a.approx_eq(b, 99));
+        assert!(!b.approx_eq(a, 99));
+        assert!(a.approx_eq(a, 0));
+
+        assert!(FileTime::MIN.approx_eq(FileTime::MAX, i64::MAX as u64));
+        assert!(!FileTime::MAX.approx_eq(FileTime::MIN, i64::MAX as u64 - 1));
+        assert!(FileTime::MAX.approx_eq(FileTime::MIN, u64::MAX));
+
+        let tolerance = core::time::Duration::from_nanos(10_099);
+        assert!(a.approx_eq_duration(b, tolerance));
+        assert!(!a.approx_eq_duration(b, core::time::Duration::from_nanos(9_999)));
+        assert!(FileTime::MIN.approx_eq_duration(FileTime::MAX, core::time::Duration::MAX));
+    }
+
+    #[test]
+    fn eq_ignoring_subsec() {
+        let a = FileTime::from_i64(128930364000000000);
+        assert!(a.eq_ignoring_subsec(FileTime::from_i64(128930364009999999)));
+        assert!(!a.eq_ignoring_subsec(FileTime::from_i64(128930364010000000)));
+        assert!(!a.eq_ignoring_subsec(FileTime::from_i64(128930363999999999)));
+        assert!(FileTime::MIN.eq_ignoring_subsec(FileTime::from_i64(1)));
+        assert!(FileTime::MAX.eq_ignoring_subsec(FileTime::from_i64(i64::MAX - 4_775_807)));
+    }
+
+    #[test]
+    fn from_str() {
+        let ft = FileTime::from_i64(130139712831482830);
+        let invalid = FileTimeError::InvalidFormat {
+            format: "FILETIME",
+            reason: "expected decimal ticks, 0x-prefixed hex ticks or an RFC 3339 timestamp",
+        };
+        assert_eq!("130139712831482830".parse(), Ok(ft));
+        assert_eq!("0x01ce59611a7debce".parse(), Ok(ft));
+        assert_eq!("0X01CE59611A7DEBCE".parse(), Ok(ft));
+        assert_eq!("2013-05-25T16:01:23.1482830Z".parse(), Ok(ft));
+        assert_eq!("2013-05-25T16:01:23.148283099Z".parse(), Ok(ft));
+        assert_eq!("0".parse(), Ok(FileTime::MIN));
+        assert_eq!("9223372036854775807".parse(), Ok(FileTime::MAX));
+        assert_eq!(
+            "0x8000000000000000".parse(),
+            Ok(FileTime::from_u64(1 << 63))
+        );
+        assert_eq!("18446744073709551615".parse(), Ok(FileTime::MAX_RAW));
+        assert_eq!("0xFFFFFFFFFFFFFFFF".parse(), Ok(FileTime::MAX_RAW));
+        assert_eq!(
+            "18446744073709551616".parse::<FileTime>(),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!(
+            "0x10000000000000000".parse::<FileTime>(),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!("-1".parse::<FileTime>(), Err(FileTimeError::BeforeEpoch));
+        assert_eq!(
+            "1600-12-31T23:59:59Z".parse::<FileTime>(),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        for s in [
+            "",
+            "0x",
+            "0x-1",
+            "+1",
+            " 1",
+            "1.5",
+            "-",
+            "2013-05-25",
+            "yesterday",
+        ] {
+            assert_eq!(s.parse::<FileTime>(), Err(invalid.clone()), "{:?}", s);
+        }
+    }
+
     #[test]
     #[should_panic]
     fn filetime_panic_invalid_i64() {
@@ -275,8 +1255,78 @@ mod test {
 
     #[test]
     #[should_panic]
-    fn filetime_panic_from_bytes() {
-        let val: i64 = -1;
-        let _ = FileTime::from(val.to_le_bytes());
+    fn filetime_panic_new_overflow() {
+        FileTime::new(i64::MAX, 1);
+    }
+
+    #[test]
+    fn seconds_nanoseconds() {
+        let ft = FileTime::new(13013971283, 148283099);
+        assert_eq!(ft.filetime(), 130139712831482830);
+        assert_eq!(ft.seconds(), 13013971283);
+        assert_eq!(ft.nanoseconds(), 148283000);
+    }
+
+    #[test]
+    fn duration_since_epoch() {
+        use core::time::Duration as StdDuration;
+
+        let unix_epoch = FileTime::from_unix_seconds(0).unwrap();
+        for &(ft, d) in &[
+            (FileTime::MIN, StdDuration::ZERO),
+            (unix_epoch, StdDuration::from_secs(11644473600)),
+            (FileTime::MAX, StdDuration::new(922337203685, 477580700)),
+        ] {
+            assert_eq!(ft.to_duration_since_epoch(), d);
+            assert_eq!(FileTime::from_duration_since_epoch(d), Ok(ft));
+        }
+        assert_eq!(
+            FileTime::from_duration_since_epoch(StdDuration::from_nanos(199)),
+            Ok(FileTime::from_i64(1))
+        );
+        assert_eq!(
+            FileTime::from_duration_since_epoch(StdDuration::from_secs(40_000 * 365 * 86_400)),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!(
+            FileTime::from_duration_since_epoch(StdDuration::MAX),
+            Err(FileTimeError::Overflow)
+        );
+    }
+
+    ::proptest::proptest! {
+        #[test]
+        fn i64_roundtrip(ticks in 0..=i64::MAX) {
+            ::proptest::prop_assert_eq!(FileTime::from_i64(ticks).filetime(), ticks);
+            let bytes: [u8; 8] = FileTime::from_i64(ticks).into();
+            ::proptest::prop_assert_eq!(FileTime::from(bytes).filetime(), ticks);
+        }
+
+        #[test]
+        fn ordering_matches_ticks(a in 0..=i64::MAX, b in 0..=i64::MAX) {
+            ::proptest::prop_assert_eq!(FileTime::from_i64(a).cmp(&FileTime::from_i64(b)), a.cmp(&b));
+        }
+    }
+
+    #[test]
+    fn raw_u64_range() {
+        let bytes = [0xFF; 8];
+        let ft = FileTime::from(bytes);
+        assert_eq!(ft, FileTime::MAX_RAW);
+        assert_eq!(<[u8; 8]>::from(ft), bytes);
+        assert_eq!(u64::from(ft), u64::MAX);
+        assert_eq!(FileTime::from(u64::MAX), ft);
+        assert_eq!(ft.filetime(), i64::MAX);
+        assert!(ft.to_datetime_checked().is_err());
+
+        // Ordering continues across the i64 boundary
+        let above = FileTime::from_u64(1 << 63);
+        assert!(FileTime::MAX < above && above < FileTime::MAX_RAW);
+        assert_eq!(
+            FileTime::MAX.checked_add(FileTimeDelta::from_ticks(1)),
+            Some(above)
+        );
+        assert_eq!(above.seconds(), 922_337_203_685);
+        assert_eq!(above.nanoseconds(), 477_580_800);
     }
 }
@@ -0,0 +1,173 @@
+//! Windows shortcut (.lnk) header timestamps
+//!
+//! Every shell link starts with a fixed size `ShellLinkHeader` ([MS-SHLLINK] 2.1):
+//!
+//! ```text
+//! 0x00  u32       HeaderSize, always 0x4C
+//! 0x04  [u8; 16]  LinkCLSID, always 00021401-0000-0000-C000-000000000046
+//! 0x14  u32       LinkFlags
+//! 0x18  u32       FileAttributes
+//! 0x1C  FILETIME  CreationTime
+//! 0x24  FILETIME  AccessTime
+//! 0x2C  FILETIME  WriteTime
+//! ...
+//! ```
+//!
+//! The timestamps describe the link target, a value of zero means it is not set.
+use crate::{FileTime, FileTimeError};
+
+const FORMAT: &str = "ShellLinkHeader";
+const HEADER_SIZE: usize = 0x4C;
+const LINK_CLSID: [u8; 16] = [
+    0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46,
+];
+
+/// Timestamps of a `ShellLinkHeader`, `None` where the field is zero
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct LnkTimes {
+    /// CreationTime of the link target
+    pub created: Option<FileTime>,
+    /// AccessTime of the link target
+    pub accessed: Option<FileTime>,
+    /// WriteTime of the link target
+    pub written: Option<FileTime>,
+}
+
+fn read_time(buf: &[u8], offset: usize) -> Result<Option<FileTime>, FileTimeError> {
+    let mut raw = [0u8; 8];
+    raw.copy_from_slice(&buf[offset..offset + 8]);
+    match i64::from_le_bytes(raw) {
+        0 => Ok(None),
+        ticks => {
+            FileTime::from_i64_checked(ticks)
+                .map(Some)
+                .map_err(|_| FileTimeError::Malformed {
+                    format: FORMAT,
+                    offset,
+                    reason: "negative FILETIME",
+                })
+        }
+    }
+}
+
+/// Parse the timestamps from the `ShellLinkHeader` at the start of `buf`
+///
+/// `buf` may hold the whole file, only the first 0x4C bytes are read.
+/// Errors are [`FileTimeError::Malformed`] with distinct offsets and reasons:
+/// 0x4C for a truncated header, 0 for a wrong header size and 4 for a wrong CLSID.
+pub fn parse_header_times(buf: &[u8]) -> Result<LnkTimes, FileTimeError> {
+    if buf.len() < HEADER_SIZE {
+        return Err(FileTimeError::Malformed {
+            format: FORMAT,
+            offset: HEADER_SIZE,
+            reason: "truncated header",
+        });
+    }
+    if buf[..4] != (HEADER_SIZE as u32).to_le_bytes() {
+        return Err(FileTimeError::Malformed {
+            format: FORMAT,
+            offset: 0,
+            reason: "header size is not 0x4C",
+        });
+    }
+    if buf[4..20] != LINK_CLSID {
+        return Err(FileTimeError::Malformed {
+            format: FORMAT,
+            offset: 4,
+            reason: "unexpected LinkCLSID",
+        });
+    }
+    Ok(LnkTimes {
+        created: read_time(buf, 0x1C)?,
+        accessed: read_time(buf, 0x24)?,
+        written: read_time(buf, 0x2C)?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    // Header of the example shortcut in [MS-SHLLINK] section 3.1
+    const HEADER: [u8; 0x4C] = [
+        0x4C, 0x00, 0x00, 0x00, 0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x46, 0x9B, 0x00, 0x08, 0x00, 0x20, 0x00, 0x00, 0x00, 0xD0, 0xE9,
+        0xEE, 0xF2, 0x15, 0x15, 0xC9, 0x01, 0xD0, 0xE9, 0xEE, 0xF2, 0x15, 0x15, 0xC9, 0x01, 0xD0,
+        0xE9, 0xEE, 0xF2, 0x15, 0x15, 0xC9, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00,
+    ];
+
+    #[test]
+    fn parse() {
+        let expected = FileTime::from_datetime(
+            Utc.with_ymd_and_hms(2008, 9, 12, 20, 27, 17).unwrap()
+                + chrono::Duration::milliseconds(101),
+        );
+        let times = parse_header_times(&HEADER).unwrap();
+        assert_eq!(
+            times,
+            LnkTimes {
+                created: Some(expected),
+                accessed: Some(expected),
+                written: Some(expected),
+            }
+        );
+    }
+
+    #[test]
+    fn not_set() {
+        let mut header = HEADER;
+        header[0x24..0x2C].copy_from_slice(&[0; 8]);
+        let times = parse_header_times(&header).unwrap();
+        assert!(times.created.is_some());
+        assert_eq!(times.accessed, None);
+        assert!(times.written.is_some());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse_header_times(&HEADER[..0x4B]),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 0x4C,
+                reason: "truncated header",
+            })
+        );
+
+        let mut header = HEADER;
+        header[0] = 0x50;
+        assert_eq!(
+            parse_header_times(&header),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 0,
+                reason: "header size is not 0x4C",
+            })
+        );
+
+        let mut header = HEADER;
+        header[19] = 0x47;
+        assert_eq!(
+            parse_header_times(&header),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 4,
+                reason: "unexpected LinkCLSID",
+            })
+        );
+
+        let mut header = HEADER;
+        header[0x33] = 0x80;
+        assert_eq!(
+            parse_header_times(&header),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 0x2C,
+                reason: "negative FILETIME",
+            })
+        );
+    }
+}
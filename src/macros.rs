@@ -0,0 +1,242 @@
+//! The `filetime!` macro for compile-time FileTime constants
+use crate::{FileTime, FileTimeError};
+
+/// FileTime constant from an RFC 3339 UTC timestamp or a raw tick count, checked at compile time
+///
+/// Timestamps take the form `YYYY-MM-DDTHH:MM:SS[.fffffff]Z` with up to 7
+/// fraction digits; offsets other than `Z` are rejected. Raw values are
+/// decimal integer literals up to `u64::MAX`, `_` separators are allowed.
+/// ```
+/// use filetime_type::{filetime, FileTime};
+///
+/// const INCIDENT_START: FileTime = filetime!("2023-05-17T09:00:00Z");
+/// assert_eq!(INCIDENT_START.to_datetime().to_rfc3339(), "2023-05-17T09:00:00+00:00");
+/// assert_eq!(filetime!(128930364000001000), FileTime::from_i64(128930364000001000));
+/// assert_eq!(filetime!("2009-07-25T23:00:00.0000010Z"), filetime!(128_930_364_000_000_010));
+/// ```
+///
+/// Malformed input fails the build, the error names the problem, e.g.
+/// `filetime!: invalid day, does not exist in the given month`:
+/// ```compile_fail
+/// # use filetime_type::filetime;
+/// let ft = filetime!("2023-02-29T00:00:00Z");
+/// ```
+/// ```compile_fail
+/// # use filetime_type::filetime;
+/// let ft = filetime!("2023-05-17T09:00:00+02:00");
+/// ```
+/// ```compile_fail
+/// # use filetime_type::filetime;
+/// let ft = filetime!("2023-05-17T09:00:00.12345678Z");
+/// ```
+/// ```compile_fail
+/// # use filetime_type::filetime;
+/// let ft = filetime!(-1);
+/// ```
+/// ```compile_fail
+/// # use filetime_type::filetime;
+/// let ft = filetime!(18446744073709551616);
+/// ```
+#[macro_export]
+macro_rules! filetime {
+    ($value:literal) => {{
+        const FILETIME: $crate::FileTime = $crate::macros::parse_literal(stringify!($value));
+        FILETIME
+    }};
+}
+
+/// Parse the stringified literal passed to `filetime!`, panicking on invalid input
+#[doc(hidden)]
+pub const fn parse_literal(literal: &str) -> FileTime {
+    let result = match literal.as_bytes() {
+        [b'"', ..] => parse_rfc3339(literal.as_bytes()),
+        _ => parse_raw(literal.as_bytes()),
+    };
+    match result {
+        Ok(ft) => ft,
+        Err(message) => panic!("{}", message),
+    }
+}
+
+const fn parse_raw(s: &[u8]) -> Result<FileTime, &'static str> {
+    if let [b'-', ..] = s {
+        return Err("filetime!: raw value lies before the FILETIME epoch");
+    }
+    let mut ticks: u64 = 0;
+    let mut digits = 0;
+    let mut i = 0;
+    while i < s.len() {
+        let b = s[i];
+        i += 1;
+        if b == b'_' {
+            continue;
+        }
+        if !b.is_ascii_digit() {
+            return Err("filetime!: expected a decimal tick count or an RFC 3339 string");
+        }
+        ticks = match ticks.checked_mul(10) {
+            Some(t) => match t.checked_add((b - b'0') as u64) {
+                Some(t) => t,
+                None => return Err("filetime!: raw value exceeds the maximum FILETIME"),
+            },
+            None => return Err("filetime!: raw value exceeds the maximum FILETIME"),
+        };
+        digits += 1;
+    }
+    if digits == 0 {
+        return Err("filetime!: expected a decimal tick count or an RFC 3339 string");
+    }
+    Ok(FileTime { ticks })
+}
+
+/// Value of the ASCII digits `s[start..start + len]`
+const fn digits(s: &[u8], start: usize, len: usize) -> Option<u32> {
+    let mut value = 0;
+    let mut i = start;
+    while i < start + len {
+        if !s[i].is_ascii_digit() {
+            return None;
+        }
+        value = value * 10 + (s[i] - b'0') as u32;
+        i += 1;
+    }
+    Some(value)
+}
+
+/// Parse a quoted `YYYY-MM-DDTHH:MM:SS[.fffffff]Z` timestamp
+const fn parse_rfc3339(s: &[u8]) -> Result<FileTime, &'static str> {
+    const INVALID: &str = "filetime!: expected an RFC 3339 timestamp like \"2023-05-17T09:00:00Z\"";
+    // Opening quote, at least `YYYY-MM-DDTHH:MM:SS`, closing quote
+    if s.len() < 21 || s[s.len() - 1] != b'"' {
+        return Err(INVALID);
+    }
+    if s[5] != b'-' || s[8] != b'-' || (s[11] != b'T' && s[11] != b't') {
+        return Err(INVALID);
+    }
+    if s[14] != b':' || s[17] != b':' {
+        return Err(INVALID);
+    }
+    let (year, month, day, hour, min, sec) = match (
+        digits(s, 1, 4),
+        digits(s, 6, 2),
+        digits(s, 9, 2),
+        digits(s, 12, 2),
+        digits(s, 15, 2),
+        digits(s, 18, 2),
+    ) {
+        (Some(y), Some(mo), Some(d), Some(h), Some(mi), Some(se)) => (y, mo, d, h, mi, se),
+        _ => return Err(INVALID),
+    };
+
+    let mut i = 20;
+    let mut nanos = 0;
+    if s[i] == b'.' {
+        i += 1;
+        let mut scale = 100_000_000;
+        while i < s.len() && s[i].is_ascii_digit() {
+            if scale < 100 {
+                return Err("filetime!: at most 7 fraction digits are supported");
+            }
+            nanos += (s[i] - b'0') as u32 * scale;
+            scale /= 10;
+            i += 1;
+        }
+        if scale == 100_000_000 {
+            return Err(INVALID);
+        }
+    }
+    match s[i] {
+        b'Z' | b'z' if i + 2 == s.len() => {}
+        b'+' | b'-' => return Err("filetime!: only the Z offset is supported"),
+        _ => return Err(INVALID),
+    }
+
+    match FileTime::from_ymd_hms_nano(year as i32, month, day, hour, min, sec, nanos) {
+        Ok(ft) => Ok(ft),
+        Err(FileTimeError::InvalidField { field, .. }) => Err(match field.as_bytes() {
+            b"month" => "filetime!: invalid month, must be within 1 to 12",
+            b"day" => "filetime!: invalid day, does not exist in the given month",
+            b"hour" => "filetime!: invalid hour, must be below 24",
+            b"minute" => "filetime!: invalid minute, must be below 60",
+            _ => "filetime!: invalid second, must be below 60",
+        }),
+        Err(FileTimeError::BeforeEpoch) => {
+            Err("filetime!: timestamp lies before the FILETIME epoch (1601-01-01)")
+        }
+        Err(_) => Err("filetime!: timestamp exceeds the maximum FILETIME"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(literal: &str) -> Result<FileTime, &'static str> {
+        match literal.as_bytes() {
+            [b'"', ..] => parse_rfc3339(literal.as_bytes()),
+            _ => parse_raw(literal.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn macro_forms() {
+        const RAW: FileTime = filetime!(128930364000001000);
+        const TEXT: FileTime = filetime!("2009-07-25T23:00:00.0001Z");
+        assert_eq!(RAW, FileTime::from_i64(128930364000001000));
+        assert_eq!(TEXT, RAW);
+        assert_eq!(filetime!(0), FileTime::MIN);
+        assert_eq!(filetime!(9_223_372_036_854_775_807), FileTime::MAX);
+        assert_eq!(filetime!(18446744073709551615), FileTime::MAX_RAW);
+        assert_eq!(filetime!("1601-01-01T00:00:00Z"), FileTime::MIN);
+        assert_eq!(
+            filetime!("2013-05-25t16:01:23.148283z"),
+            FileTime::from_i64(130139712831482830)
+        );
+    }
+
+    #[test]
+    fn matches_from_str() {
+        for s in &[
+            "1970-01-01T00:00:00Z",
+            "2016-12-31T23:59:59.9999999Z",
+            "2024-02-29T12:00:00.5Z",
+            "9999-12-31T23:59:59.1234567Z",
+        ] {
+            assert_eq!(
+                parse(&format!("\"{}\"", s)),
+                Ok(s.parse().unwrap()),
+                "{}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn rejects() {
+        for &(literal, message) in &[
+            ("\"2023-02-29T00:00:00Z\"", "invalid day"),
+            ("\"2023-13-01T00:00:00Z\"", "invalid month"),
+            ("\"2023-05-17T24:00:00Z\"", "invalid hour"),
+            ("\"2023-05-17T09:60:00Z\"", "invalid minute"),
+            ("\"2016-12-31T23:59:60Z\"", "invalid second"),
+            ("\"1600-12-31T23:59:59Z\"", "before the FILETIME epoch"),
+            ("\"2023-05-17T09:00:00+02:00\"", "only the Z offset"),
+            (
+                "\"2023-05-17T09:00:00.12345678Z\"",
+                "at most 7 fraction digits",
+            ),
+            ("\"2023-05-17T09:00:00.Z\"", "expected an RFC 3339"),
+            ("\"2023-05-17T09:00:00\"", "expected an RFC 3339"),
+            ("\"2023-05-17 09:00:00Z\"", "expected an RFC 3339"),
+            ("\"23-05-17T09:00:00Z\"", "expected an RFC 3339"),
+            ("\"\"", "expected an RFC 3339"),
+            ("-1", "before the FILETIME epoch"),
+            ("18446744073709551616", "exceeds the maximum"),
+            ("0x10", "expected a decimal tick count"),
+            ("1.5", "expected a decimal tick count"),
+        ] {
+            let err = parse(literal).unwrap_err();
+            assert!(err.contains(message), "{}: {}", literal, err);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! minicbor `Encode`/`Decode` support, no_std
+//!
+//! FileTime is encoded as a CBOR unsigned integer holding the raw ticks.
+//! Decoding also takes CBOR tag 1 (epoch-based date/time, Unix seconds as
+//! integer or float), as written by the [`epoch`] with-module and by CBOR
+//! libraries on other platforms.
+use crate::FileTime;
+use ::minicbor::data::{IanaTag, Type};
+use ::minicbor::decode::{self, Decoder};
+use ::minicbor::encode::{self, Encoder, Write};
+use ::minicbor::{Decode, Encode};
+
+impl<C> Encode<C> for FileTime {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _: &mut C,
+    ) -> Result<(), encode::Error<W::Error>> {
+        e.u64(self.as_u64())?;
+        Ok(())
+    }
+}
+
+impl<'b, C> Decode<'b, C> for FileTime {
+    fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, decode::Error> {
+        match d.datatype()? {
+            Type::Tag => epoch::decode(d, &mut ()),
+            _ => d.u64().map(FileTime::from_u64),
+        }
+    }
+}
+
+/// With-module for CBOR tag 1, Unix seconds, for `#[cbor(with = "filetime_type::minicbor::epoch")]`
+///
+/// Whole seconds are encoded as integer, others as `f64`. A double holds
+/// current dates to about 0.24µs, so the sub-second part comes back up to
+/// a tick or two off; use the raw ticks where they need to survive.
+/// Decoding takes tag 1 with an integer or float, rounded to the nearest
+/// tick with halves away from zero.
+/// ```
+/// use filetime_type::FileTime;
+///
+/// let ft = FileTime::from_unix_seconds(1363896240).unwrap();
+/// let mut buf = [0u8; 16];
+/// let mut e = minicbor::Encoder::new(&mut buf[..]);
+/// filetime_type::minicbor::epoch::encode(&ft, &mut e, &mut ()).unwrap();
+/// assert_eq!(&buf[..6], [0xC1, 0x1A, 0x51, 0x4B, 0x67, 0xB0]);
+/// ```
+pub mod epoch {
+    use super::*;
+
+    const TICKS_PER_SECOND: i128 = FileTime::HUNDREDS_OF_NANOSECONDS as i128;
+
+    /// Encode as tag 1 with integer or float Unix seconds
+    pub fn encode<C, W: Write>(
+        ft: &FileTime,
+        e: &mut Encoder<W>,
+        _: &mut C,
+    ) -> Result<(), encode::Error<W::Error>> {
+        e.tag(IanaTag::Timestamp)?;
+        let ticks = ft.unix_ticks();
+        let secs = ticks.div_euclid(TICKS_PER_SECOND);
+        let subsec = ticks.rem_euclid(TICKS_PER_SECOND);
+        // The whole seconds of every FILETIME fit into an i64
+        if subsec == 0 {
+            e.i64(secs as i64)?;
+        } else {
+            e.f64(secs as f64 + subsec as f64 / TICKS_PER_SECOND as f64)?;
+        }
+        Ok(())
+    }
+
+    /// Decode tag 1 with integer or float Unix seconds
+    pub fn decode<C>(d: &mut Decoder<'_>, _: &mut C) -> Result<FileTime, decode::Error> {
+        let pos = d.position();
+        let tag = d.tag()?;
+        if tag != IanaTag::Timestamp {
+            return Err(decode::Error::tag_mismatch(tag)
+                .at(pos)
+                .with_message("expected tag 1 (epoch-based date/time)"));
+        }
+        let pos = d.position();
+        let ticks = match d.datatype()? {
+            Type::F16 | Type::F32 | Type::F64 => float_ticks(d.f64()?),
+            _ => Some(i128::from(d.i64()?) * TICKS_PER_SECOND),
+        };
+        ticks
+            .and_then(|ticks| {
+                FileTime::from_ticks_i128(ticks + i128::from(FileTime::EPOCH_AS_FILETIME)).ok()
+            })
+            .ok_or_else(|| {
+                decode::Error::message("epoch time outside of the FILETIME range").at(pos)
+            })
+    }
+
+    /// Unix ticks nearest to `secs`, `None` if not finite or far out of range
+    ///
+    /// Whole and fractional part are scaled separately to keep the
+    /// precision of the fraction, and rounded without std's `f64::round`.
+    fn float_ticks(secs: f64) -> Option<i128> {
+        // Values this far beyond the FILETIME range would overflow the casts
+        if !secs.is_finite() || secs.abs() > 1e18 {
+            return None;
+        }
+        let whole = secs as i64;
+        let fraction = (secs - whole as f64) * TICKS_PER_SECOND as f64;
+        let rounded = if fraction < 0.0 {
+            (fraction - 0.5) as i128
+        } else {
+            (fraction + 0.5) as i128
+        };
+        Some(i128::from(whole) * TICKS_PER_SECOND + rounded)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Reading {
+        #[n(0)]
+        sensor: u8,
+        #[n(1)]
+        at: FileTime,
+        #[cbor(n(2), with = "epoch")]
+        at_epoch: FileTime,
+    }
+
+    fn encode<T: Encode<()>>(value: &T) -> Vec<u8> {
+        ::minicbor::to_vec(value).unwrap()
+    }
+
+    fn decode<'b, T: Decode<'b, ()>>(bytes: &'b [u8]) -> Result<T, decode::Error> {
+        ::minicbor::decode(bytes)
+    }
+
+    #[test]
+    fn raw_ticks() {
+        let ft = FileTime::from_i64(130139712831482830);
+        let bytes = encode(&ft);
+        assert_eq!(
+            bytes,
+            [0x1B, 0x01, 0xCE, 0x59, 0x61, 0x1A, 0x7D, 0xEB, 0xCE]
+        );
+        assert_eq!(decode::<FileTime>(&bytes).unwrap(), ft);
+        for ft in [FileTime::MIN, FileTime::MAX, FileTime::MAX_RAW] {
+            assert_eq!(decode::<FileTime>(&encode(&ft)).unwrap(), ft);
+        }
+        assert_eq!(encode(&FileTime::MIN), [0x00]);
+        assert!(decode::<FileTime>(&[0x20]).is_err());
+        assert!(decode::<FileTime>(&[0x60]).is_err());
+    }
+
+    #[test]
+    fn epoch_roundtrip() {
+        let whole = FileTime::from_ymd_hms(2023, 5, 17, 9, 30, 15).unwrap();
+        let reading = Reading {
+            sensor: 3,
+            at: whole,
+            at_epoch: whole,
+        };
+        let bytes = encode(&reading);
+        assert_eq!(decode::<Reading>(&bytes).unwrap(), reading);
+
+        // Sub-second values go through f64
+        let ft = FileTime::from_i64(133325856001234567);
+        let mut buf = Vec::new();
+        epoch::encode(&ft, &mut Encoder::new(&mut buf), &mut ()).unwrap();
+        assert_eq!(buf[..2], [0xC1, 0xFB]);
+        let back = epoch::decode(&mut Decoder::new(&buf), &mut ()).unwrap();
+        assert!(back.approx_eq(ft, 2), "{:?}", back);
+
+        // Before 1970 and at the FILETIME epoch
+        for ft in [
+            FileTime::MIN,
+            FileTime::from_ymd_hms(1900, 1, 1, 0, 0, 0).unwrap(),
+        ] {
+            let mut buf = Vec::new();
+            epoch::encode(&ft, &mut Encoder::new(&mut buf), &mut ()).unwrap();
+            assert_eq!(epoch::decode(&mut Decoder::new(&buf), &mut ()).unwrap(), ft);
+            assert_eq!(decode::<FileTime>(&buf).unwrap(), ft);
+        }
+    }
+
+    #[test]
+    fn foreign_tag1() {
+        // The tag 1 examples of RFC 8949 Appendix A
+        let int = [0xC1, 0x1A, 0x51, 0x4B, 0x67, 0xB0];
+        let float = [0xC1, 0xFB, 0x41, 0xD4, 0x52, 0xD9, 0xEC, 0x20, 0x00, 0x00];
+        let expected = FileTime::from_unix_seconds(1363896240).unwrap();
+        assert_eq!(decode::<FileTime>(&int).unwrap(), expected);
+        assert_eq!(
+            decode::<FileTime>(&float).unwrap(),
+            FileTime::from_i64(expected.filetime() + 5_000_000)
+        );
+        let mut d = Decoder::new(&float);
+        assert_eq!(
+            epoch::decode(&mut d, &mut ()).unwrap(),
+            FileTime::from_i64(expected.filetime() + 5_000_000)
+        );
+    }
+
+    #[test]
+    fn float_rounding() {
+        let at = |secs: f64| {
+            let mut buf = Vec::new();
+            Encoder::new(&mut buf)
+                .tag(IanaTag::Timestamp)
+                .unwrap()
+                .f64(secs)
+                .unwrap();
+            decode::<FileTime>(&buf).ok().map(|ft| ft.unix_ticks())
+        };
+        assert_eq!(at(0.00000004), Some(0));
+        assert_eq!(at(0.00000005), Some(1));
+        assert_eq!(at(0.00000016), Some(2));
+        assert_eq!(at(-0.00000004), Some(0));
+        assert_eq!(at(-0.00000016), Some(-2));
+        assert_eq!(at(-1.5), Some(-15_000_000));
+        assert!(at(f64::NAN).is_none());
+        assert!(at(f64::INFINITY).is_none());
+        assert!(at(-11_644_473_601.0).is_none());
+        assert!(at(1e300).is_none());
+    }
+
+    #[test]
+    fn wrong_tag() {
+        // Tag 0, an RFC 3339 string
+        let bytes = [0xC0, 0x61, 0x30];
+        let err = decode::<FileTime>(&bytes).unwrap_err();
+        assert!(err.to_string().contains("expected tag 1"), "{}", err);
+    }
+}
@@ -0,0 +1,185 @@
+//! Microsoft JSON dates, `"/Date(1688140800000)/"`
+//!
+//! Written by ASP.NET's `DataContractJsonSerializer` and `JavaScriptSerializer`
+//! and still found in SharePoint and WCF payloads: milliseconds since the
+//! Unix-Epoch, optionally followed by the `±HHMM` offset of the sender's time
+//! zone. The milliseconds are UTC either way, the offset only records the
+//! local time zone and must not be applied again.
+use crate::parse;
+use crate::{FileTime, FileTimeError};
+
+const MS_JSON: &str = "Microsoft JSON date";
+
+impl FileTime {
+    /// Parse a Microsoft JSON date, e.g. `/Date(1688140800000)/` or `/Date(1688140800000+0200)/`
+    ///
+    /// The JSON escaped form `\/Date(...)\/` is accepted too, as are negative
+    /// milliseconds for instants before 1970. A `±HHMM` offset is validated
+    /// but does not change the result, the milliseconds are UTC already.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let utc = FileTime::parse_ms_json_date("/Date(1688140800000)/").unwrap();
+    /// assert_eq!(utc.to_datetime().to_rfc3339(), "2023-06-30T16:00:00+00:00");
+    /// // Same instant, sent from a +02:00 time zone
+    /// assert_eq!(FileTime::parse_ms_json_date("/Date(1688140800000+0200)/"), Ok(utc));
+    /// assert_eq!(FileTime::parse_ms_json_date(r"\/Date(1688140800000)\/"), Ok(utc));
+    /// ```
+    pub fn parse_ms_json_date(s: &str) -> Result<Self, FileTimeError> {
+        let invalid = |reason| FileTimeError::InvalidFormat {
+            format: MS_JSON,
+            reason,
+        };
+        let inner = s
+            .strip_prefix("/Date(")
+            .and_then(|rest| rest.strip_suffix(")/"))
+            .or_else(|| {
+                s.strip_prefix("\\/Date(")
+                    .and_then(|rest| rest.strip_suffix(")\\/"))
+            })
+            .ok_or_else(|| invalid("expected /Date(...)/"))?;
+
+        let (sign, unsigned) = match inner.strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, inner),
+        };
+        let (millis, offset) = match unsigned.find(['+', '-']) {
+            Some(pos) => unsigned.split_at(pos),
+            None => (unsigned, ""),
+        };
+        if millis.is_empty() || !millis.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid("expected decimal milliseconds"));
+        }
+        if !offset.is_empty() {
+            let b = offset.as_bytes();
+            let minutes = (b.len() == 5)
+                .then(|| parse::digits(&b[3..5]))
+                .flatten()
+                .filter(|minutes| *minutes < 60);
+            if minutes.is_none() || parse::digits(&b[1..3]).is_none() {
+                return Err(invalid("expected offset as +HHMM or -HHMM"));
+            }
+        }
+        let millis: i64 = millis
+            .parse()
+            .map_err(|_| invalid("milliseconds out of range"))?;
+        Self::from_unix_millis(sign * millis)
+    }
+
+    /// Format as Microsoft JSON date without offset, e.g. `/Date(1688140800000)/`
+    ///
+    /// Sub-millisecond ticks are truncated towards the earlier instant. Put
+    /// the result into a JSON string as-is; serializers that escape `/` emit
+    /// the equivalent `\/Date(...)\/`.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_i64(133325856001234567);
+    /// assert_eq!(ft.to_ms_json_date(), "/Date(1688112000123)/");
+    /// ```
+    pub fn to_ms_json_date(&self) -> String {
+        format!("/Date({})/", self.to_unix_millis())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn invalid(reason: &'static str) -> Result<FileTime, FileTimeError> {
+        Err(FileTimeError::InvalidFormat {
+            format: MS_JSON,
+            reason,
+        })
+    }
+
+    #[test]
+    fn plain() {
+        let ft = FileTime::parse_ms_json_date("/Date(1688140800000)/").unwrap();
+        assert_eq!(ft, FileTime::from_ymd_hms(2023, 6, 30, 16, 0, 0).unwrap());
+        assert_eq!(ft.to_ms_json_date(), "/Date(1688140800000)/");
+        assert_eq!(
+            FileTime::parse_ms_json_date("/Date(0)/"),
+            FileTime::from_unix_seconds(0)
+        );
+    }
+
+    #[test]
+    fn offset() {
+        let utc = FileTime::parse_ms_json_date("/Date(1688140800000)/").unwrap();
+        for s in &[
+            "/Date(1688140800000+0200)/",
+            "/Date(1688140800000-0530)/",
+            "/Date(1688140800000+0000)/",
+        ] {
+            assert_eq!(FileTime::parse_ms_json_date(s), Ok(utc), "{}", s);
+        }
+        assert_eq!(
+            FileTime::parse_ms_json_date("/Date(1688140800000+02)/"),
+            invalid("expected offset as +HHMM or -HHMM")
+        );
+        assert_eq!(
+            FileTime::parse_ms_json_date("/Date(1688140800000+0260)/"),
+            invalid("expected offset as +HHMM or -HHMM")
+        );
+    }
+
+    #[test]
+    fn escaped() {
+        assert_eq!(
+            FileTime::parse_ms_json_date(r"\/Date(1688140800000+0200)\/"),
+            FileTime::from_ymd_hms(2023, 6, 30, 16, 0, 0)
+        );
+        // Decoded by a JSON parser first, the escapes are gone
+        let decoded: String = serde_json::from_str(r#""\/Date(1688140800000)\/""#).unwrap();
+        assert_eq!(
+            FileTime::parse_ms_json_date(&decoded),
+            FileTime::from_ymd_hms(2023, 6, 30, 16, 0, 0)
+        );
+        assert_eq!(
+            FileTime::parse_ms_json_date(r"\/Date(1688140800000)/"),
+            invalid("expected /Date(...)/")
+        );
+    }
+
+    #[test]
+    fn negative() {
+        // 1969-12-31T23:59:59.999Z
+        let ft = FileTime::parse_ms_json_date("/Date(-1)/").unwrap();
+        assert_eq!(ft.filetime(), 116444736000000000 - 10_000);
+        assert_eq!(ft.to_ms_json_date(), "/Date(-1)/");
+        let ft = FileTime::parse_ms_json_date("/Date(-2208988800000-0500)/").unwrap();
+        assert_eq!(ft, FileTime::from_ymd_hms(1900, 1, 1, 0, 0, 0).unwrap());
+        // Truncation before 1970 goes to the earlier millisecond
+        assert_eq!(
+            FileTime::from_i64(116444736000000000 - 1).to_ms_json_date(),
+            "/Date(-1)/"
+        );
+        assert_eq!(
+            FileTime::parse_ms_json_date("/Date(-11644473600001)/"),
+            Err(FileTimeError::BeforeEpoch)
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let cases = [
+            ("/Date()/", "expected decimal milliseconds"),
+            ("/Date(-)/", "expected decimal milliseconds"),
+            ("/Date(+0200)/", "expected decimal milliseconds"),
+            ("/Date(12a)/", "expected decimal milliseconds"),
+            ("/Date(--1)/", "expected decimal milliseconds"),
+            ("/Date(99999999999999999999)/", "milliseconds out of range"),
+            ("Date(0)", "expected /Date(...)/"),
+            ("/Date(0)", "expected /Date(...)/"),
+            ("2023-06-30T16:00:00Z", "expected /Date(...)/"),
+        ];
+        for (s, reason) in &cases {
+            assert_eq!(FileTime::parse_ms_json_date(s), invalid(reason), "{}", s);
+        }
+        assert_eq!(
+            FileTime::parse_ms_json_date("/Date(9223372036854775807)/"),
+            Err(FileTimeError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,521 @@
+//! NTFS `$STANDARD_INFORMATION` / `$FILE_NAME` timestamps
+//!
+//! Both attributes store four FILETIMEs back to back, in this order:
+//!
+//! ```text
+//! 0x00  FILETIME  created
+//! 0x08  FILETIME  modified
+//! 0x10  FILETIME  MFT record modified
+//! 0x18  FILETIME  accessed
+//! ```
+//!
+//! In `$STANDARD_INFORMATION` they start at offset 0 of the attribute content,
+//! in `$FILE_NAME` at offset 8, after the parent directory reference.
+//!
+//! Windows sets the `$FILE_NAME` timestamps from `$STANDARD_INFORMATION`
+//! when a file is created, renamed or moved, and only the kernel can write
+//! them. `SetFileTime` and timestomping tools built on it change only
+//! `$STANDARD_INFORMATION`, so comparing both sets with
+//! [`FileTimeSet::compare`] reveals many back-dated files.
+use crate::{FileTime, FileTimeDelta, FileTimeError};
+use core::fmt;
+
+const FORMAT: &str = "NTFS timestamps";
+
+/// (truncated reason, negative reason) per field in on-disk order
+const FIELDS: [(&str, &str); 4] = [
+    ("truncated created timestamp", "negative created timestamp"),
+    (
+        "truncated modified timestamp",
+        "negative modified timestamp",
+    ),
+    (
+        "truncated mft_modified timestamp",
+        "negative mft_modified timestamp",
+    ),
+    (
+        "truncated accessed timestamp",
+        "negative accessed timestamp",
+    ),
+];
+
+/// The four timestamps of an NTFS `$STANDARD_INFORMATION` or `$FILE_NAME` attribute
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct FileTimeSet {
+    /// File creation time (B)
+    pub created: FileTime,
+    /// Last modification of the file content (M)
+    pub modified: FileTime,
+    /// Last modification of the MFT record (C)
+    pub mft_modified: FileTime,
+    /// Last access time (A)
+    pub accessed: FileTime,
+}
+
+impl FileTimeSet {
+    /// Size of the on-disk encoding
+    pub const SIZE: usize = 32;
+
+    /// Parse four consecutive little-endian FILETIMEs in NTFS field order
+    ///
+    /// Only the first [`SIZE`](Self::SIZE) bytes are read. A short buffer or a
+    /// negative value fails with [`FileTimeError::Malformed`], naming the
+    /// offending field in its reason.
+    pub fn parse_le(buf: &[u8]) -> Result<Self, FileTimeError> {
+        let mut times = [FileTime::from_i64(0); 4];
+        for (i, (time, (truncated, negative))) in times.iter_mut().zip(&FIELDS).enumerate() {
+            let offset = i * 8;
+            let raw = buf
+                .get(offset..offset + 8)
+                .ok_or(FileTimeError::Malformed {
+                    format: FORMAT,
+                    offset,
+                    reason: truncated,
+                })?;
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(raw);
+            *time = FileTime::from_i64_checked(i64::from_le_bytes(bytes)).map_err(|_| {
+                FileTimeError::Malformed {
+                    format: FORMAT,
+                    offset,
+                    reason: negative,
+                }
+            })?;
+        }
+        let [created, modified, mft_modified, accessed] = times;
+        Ok(Self {
+            created,
+            modified,
+            mft_modified,
+            accessed,
+        })
+    }
+
+    /// Encode as four little-endian FILETIMEs in NTFS field order
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut buf = [0u8; 32];
+        let times = [
+            self.created,
+            self.modified,
+            self.mft_modified,
+            self.accessed,
+        ];
+        for (chunk, time) in buf.chunks_exact_mut(8).zip(&times) {
+            chunk.copy_from_slice(&time.as_u64().to_le_bytes());
+        }
+        buf
+    }
+
+    /// Timestamps in MACB order: modified, accessed, MFT modified (changed), created (born)
+    pub fn macb(&self) -> [FileTime; 4] {
+        [
+            self.modified,
+            self.accessed,
+            self.mft_modified,
+            self.created,
+        ]
+    }
+
+    /// Compare this `$STANDARD_INFORMATION` set against the `$FILE_NAME` set `file_name`
+    ///
+    /// Sets every [`Finding`] whose rule holds; the last two only look at
+    /// `self`. The accessed times are not compared across the sets, as
+    /// last-access updates are lazy and often disabled.
+    /// ```
+    /// use filetime_type::ntfs::{FileTimeSet, Finding};
+    /// use filetime_type::FileTime;
+    ///
+    /// let t = |ticks| FileTime::from_i64(ticks);
+    /// let file_name = FileTimeSet {
+    ///     created: t(133325856001234567),
+    ///     modified: t(133325856001234567),
+    ///     mft_modified: t(133325856001234567),
+    ///     accessed: t(133325856001234567),
+    /// };
+    /// // Created set back to a whole second a year earlier
+    /// let standard = FileTimeSet {
+    ///     created: t(133010496000000000),
+    ///     ..file_name
+    /// };
+    /// let report = standard.compare(&file_name);
+    /// assert!(report.has(Finding::CreatedBeforeFileName));
+    /// assert!(report.has(Finding::SubsecondsZeroed));
+    /// assert!(file_name.compare(&file_name).is_clean());
+    /// ```
+    pub fn compare(&self, file_name: &FileTimeSet) -> ComparisonReport {
+        let si = [
+            self.created,
+            self.modified,
+            self.mft_modified,
+            self.accessed,
+        ];
+        let fname = [
+            file_name.created,
+            file_name.modified,
+            file_name.mft_modified,
+            file_name.accessed,
+        ];
+        let zeroed = si.iter().zip(&fname).any(|(si, fname)| {
+            si.filetime() != 0 && si.is_second_aligned() && !fname.is_second_aligned()
+        });
+        let rules = [
+            (Finding::CreatedBeforeFileName, si[0] < fname[0]),
+            (Finding::ModifiedBeforeFileName, si[1] < fname[1]),
+            (Finding::MftModifiedBeforeFileName, si[2] < fname[2]),
+            (Finding::SubsecondsZeroed, zeroed),
+            (Finding::CreatedAfterModified, self.created > self.modified),
+            (
+                Finding::MftModifiedBeforeModified,
+                self.mft_modified < self.modified,
+            ),
+        ];
+        let findings = rules
+            .iter()
+            .filter(|(_, fired)| *fired)
+            .fold(0, |bits, (finding, _)| bits | finding.bit());
+        ComparisonReport {
+            findings,
+            created: self.created - file_name.created,
+            modified: self.modified - file_name.modified,
+            mft_modified: self.mft_modified - file_name.mft_modified,
+            accessed: self.accessed - file_name.accessed,
+        }
+    }
+}
+
+/// Timestomping indicator found by [`FileTimeSet::compare`]
+///
+/// None of them is proof on its own, the variant docs name innocent causes.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum Finding {
+    /// `$SI` created lies before `$FN` created
+    ///
+    /// `$FN` created is copied from `$SI` when the name is written, and `$SI`
+    /// can only move forward afterwards unless set explicitly: the classic
+    /// sign of a back-dated creation time.
+    CreatedBeforeFileName,
+    /// `$SI` modified lies before `$FN` modified
+    ///
+    /// Same reasoning as [`CreatedBeforeFileName`](Self::CreatedBeforeFileName)
+    /// for the content modification time. Archive extractors that restore
+    /// modification times after creating the file cause it too.
+    ModifiedBeforeFileName,
+    /// `$SI` MFT modified lies before `$FN` MFT modified
+    ///
+    /// The record changes whenever a name is written, and `SetFileTime`
+    /// cannot set this field, so only tools calling `NtSetInformationFile`
+    /// directly produce it.
+    MftModifiedBeforeFileName,
+    /// A second-aligned, non-zero `$SI` timestamp whose `$FN` counterpart is not
+    ///
+    /// Windows writes full 100ns precision; tools that take the new time as
+    /// text or Unix seconds leave the sub-seconds at zero, see
+    /// [`FileTime::is_second_aligned`]. Files copied from FAT or extracted
+    /// from ZIP archives can show it innocently.
+    SubsecondsZeroed,
+    /// `$SI` created lies after `$SI` modified
+    ///
+    /// Weak on its own: copying a file keeps the modification time but sets
+    /// a new creation time, so copies show this legitimately.
+    CreatedAfterModified,
+    /// `$SI` MFT modified lies before `$SI` modified
+    ///
+    /// Writing content also updates the record, and `SetFileTime` sets the
+    /// MFT modified time to now, so a modification time later than it was
+    /// set into the future.
+    MftModifiedBeforeModified,
+}
+
+impl Finding {
+    /// All findings in report order
+    pub const ALL: [Finding; 6] = [
+        Finding::CreatedBeforeFileName,
+        Finding::ModifiedBeforeFileName,
+        Finding::MftModifiedBeforeFileName,
+        Finding::SubsecondsZeroed,
+        Finding::CreatedAfterModified,
+        Finding::MftModifiedBeforeModified,
+    ];
+
+    /// One line summary for reports
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Finding::CreatedBeforeFileName => "$SI created before $FN created",
+            Finding::ModifiedBeforeFileName => "$SI modified before $FN modified",
+            Finding::MftModifiedBeforeFileName => "$SI MFT modified before $FN MFT modified",
+            Finding::SubsecondsZeroed => "$SI sub-seconds zeroed where $FN has them",
+            Finding::CreatedAfterModified => "$SI created after $SI modified",
+            Finding::MftModifiedBeforeModified => "$SI MFT modified before $SI modified",
+        }
+    }
+
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// Result of [`FileTimeSet::compare`]: the findings and `$SI` minus `$FN` per field
+///
+/// Negative deltas mean the `$SI` timestamp is the earlier one. Displays as
+/// one line per field and finding:
+/// ```text
+/// created       -365d 0h 0m 0.0000000s
+/// modified      0.0000000s
+/// mft_modified  0.0000000s
+/// accessed      0.0000000s
+/// finding       $SI created before $FN created
+/// ```
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct ComparisonReport {
+    findings: u8,
+    /// `$SI` created minus `$FN` created
+    pub created: FileTimeDelta,
+    /// `$SI` modified minus `$FN` modified
+    pub modified: FileTimeDelta,
+    /// `$SI` MFT modified minus `$FN` MFT modified
+    pub mft_modified: FileTimeDelta,
+    /// `$SI` accessed minus `$FN` accessed
+    pub accessed: FileTimeDelta,
+}
+
+impl ComparisonReport {
+    /// Whether `finding` fired
+    pub const fn has(&self, finding: Finding) -> bool {
+        self.findings & finding.bit() != 0
+    }
+
+    /// Whether no finding fired
+    pub const fn is_clean(&self) -> bool {
+        self.findings == 0
+    }
+
+    /// The findings that fired, in [`Finding::ALL`] order
+    pub fn findings(&self) -> impl Iterator<Item = Finding> + '_ {
+        Finding::ALL.iter().copied().filter(move |f| self.has(*f))
+    }
+}
+
+impl fmt::Display for ComparisonReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "created       {}", self.created)?;
+        writeln!(f, "modified      {}", self.modified)?;
+        writeln!(f, "mft_modified  {}", self.mft_modified)?;
+        write!(f, "accessed      {}", self.accessed)?;
+        if self.is_clean() {
+            return write!(f, "\nfinding       none");
+        }
+        for finding in self.findings() {
+            write!(f, "\nfinding       {}", finding)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    const SECOND: i64 = 10_000_000;
+    const DAY: i64 = 86_400 * SECOND;
+    // 2023-06-30T08:00:00.1234567Z and later, synthetic values with full precision
+    const T0: i64 = 133325856001234567;
+
+    // $STANDARD_INFORMATION of `empty-file` in `testdata/testfs1` of the ntfs
+    // crate 0.4.0 (sha256 e3612c18...ba814eb9), an image made with mkntfs and
+    // ntfs-3g by its `create-testfs1.sh`, which back-dates the modification
+    // time with `touch -m -t 202101011337`. MFT record 64 at image offset
+    // 0x14000, attribute content at 0x14050.
+    const BLOB: [u8; 32] = [
+        0xcd, 0x5b, 0xb8, 0x96, 0x6b, 0x2f, 0xd9, 0x01, 0x00, 0x7e, 0xa3, 0xcc, 0x3a, 0xe0, 0xd6,
+        0x01, 0x0f, 0x6d, 0xb8, 0x96, 0x6b, 0x2f, 0xd9, 0x01, 0xcd, 0x5b, 0xb8, 0x96, 0x6b, 0x2f,
+        0xd9, 0x01,
+    ];
+
+    #[test]
+    fn parse() {
+        let set = FileTimeSet::parse_le(&BLOB).unwrap();
+        let created = Utc.with_ymd_and_hms(2023, 1, 23, 20, 45, 12).unwrap()
+            + chrono::Duration::nanoseconds(81_095_700);
+        assert_eq!(set.created.to_datetime(), created);
+        // 13:37 local time, so the script ran in UTC+1
+        assert_eq!(
+            set.modified.to_datetime(),
+            Utc.with_ymd_and_hms(2021, 1, 1, 12, 37, 0).unwrap()
+        );
+        assert_eq!(
+            set.mft_modified.to_datetime(),
+            created + chrono::Duration::nanoseconds(441_800)
+        );
+        assert_eq!(set.accessed, set.created);
+        assert_eq!(
+            set.macb(),
+            [set.modified, set.accessed, set.mft_modified, set.created]
+        );
+        assert_eq!(set.to_bytes(), BLOB);
+    }
+
+    #[test]
+    fn trailing_bytes_ignored() {
+        // Followed by the file attributes, FILE_ATTRIBUTE_ARCHIVE here
+        let mut attr = BLOB.to_vec();
+        attr.extend_from_slice(&[0x20, 0, 0, 0]);
+        assert_eq!(FileTimeSet::parse_le(&attr), FileTimeSet::parse_le(&BLOB));
+    }
+
+    #[test]
+    fn truncated() {
+        assert_eq!(
+            FileTimeSet::parse_le(&BLOB[..30]),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 24,
+                reason: "truncated accessed timestamp",
+            })
+        );
+        assert_eq!(
+            FileTimeSet::parse_le(&[]),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 0,
+                reason: "truncated created timestamp",
+            })
+        );
+    }
+
+    #[test]
+    fn negative() {
+        let mut blob = BLOB;
+        blob[15] = 0x80;
+        assert_eq!(
+            FileTimeSet::parse_le(&blob),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 8,
+                reason: "negative modified timestamp",
+            })
+        );
+    }
+
+    fn set(created: i64, modified: i64, mft_modified: i64, accessed: i64) -> FileTimeSet {
+        FileTimeSet {
+            created: FileTime::from_i64(created),
+            modified: FileTime::from_i64(modified),
+            mft_modified: FileTime::from_i64(mft_modified),
+            accessed: FileTime::from_i64(accessed),
+        }
+    }
+
+    fn findings(report: &ComparisonReport) -> Vec<Finding> {
+        report.findings().collect()
+    }
+
+    #[test]
+    fn clean() {
+        // Created, then written to and read later; $FN kept the creation times
+        let file_name = set(T0, T0, T0, T0);
+        let standard = set(T0, T0 + DAY + 17, T0 + DAY + 4711, T0 + 2 * DAY + 3);
+        let report = standard.compare(&file_name);
+        assert!(report.is_clean());
+        assert_eq!(findings(&report), []);
+        assert_eq!(report.created, FileTimeDelta::from_ticks(0));
+        assert_eq!(report.modified, FileTimeDelta::from_ticks(DAY + 17));
+        assert_eq!(report.mft_modified, FileTimeDelta::from_ticks(DAY + 4711));
+        assert_eq!(report.accessed, FileTimeDelta::from_ticks(2 * DAY + 3));
+    }
+
+    #[test]
+    fn back_dated() {
+        // SetFileTime with full precision values a year back; the MFT
+        // modified time records when that happened
+        let file_name = set(T0, T0, T0, T0);
+        let standard = set(
+            T0 - 365 * DAY,
+            T0 - 300 * DAY + 99,
+            T0 + 5 * SECOND + 1,
+            T0 - 300 * DAY + 99,
+        );
+        let report = standard.compare(&file_name);
+        assert_eq!(
+            findings(&report),
+            [
+                Finding::CreatedBeforeFileName,
+                Finding::ModifiedBeforeFileName
+            ]
+        );
+        assert_eq!(report.created, FileTimeDelta::from_ticks(-365 * DAY));
+        assert!(report.accessed.is_negative());
+        assert!(!report.has(Finding::SubsecondsZeroed));
+    }
+
+    #[test]
+    fn precision_mismatch() {
+        // Set to whole seconds later than $FN, so only the precision gives it away
+        let file_name = set(T0, T0, T0, T0);
+        let whole = T0 - T0 % SECOND + 10 * SECOND;
+        let standard = set(whole, whole, T0 + 20 * SECOND + 1, T0);
+        let report = standard.compare(&file_name);
+        assert_eq!(findings(&report), [Finding::SubsecondsZeroed]);
+
+        // Both whole seconds, e.g. copied from FAT: not a mismatch
+        let fat = set(whole, whole, T0 + 20 * SECOND + 1, T0);
+        assert!(standard.compare(&fat).is_clean());
+        // Unset timestamps are not zeroed sub-seconds
+        assert!(set(T0, T0, T0, 0).compare(&file_name).is_clean());
+    }
+
+    #[test]
+    fn internal_rules() {
+        let file_name = set(T0, T0, T0, T0);
+        // Copied file: new creation time, old modification time kept
+        let copied = set(T0 + DAY, T0 + 7, T0 + DAY, T0 + DAY);
+        assert_eq!(
+            findings(&copied.compare(&set(T0 + DAY, T0 + 7, T0 + DAY, T0 + DAY))),
+            [Finding::CreatedAfterModified]
+        );
+        // Modified set into the future
+        let future = set(T0, T0 + 30 * DAY + 1, T0 + DAY + 1, T0);
+        assert_eq!(
+            findings(&future.compare(&file_name)),
+            [Finding::MftModifiedBeforeModified]
+        );
+        let rewound = set(T0, T0, T0 - 1, T0);
+        assert_eq!(
+            findings(&rewound.compare(&file_name)),
+            [
+                Finding::MftModifiedBeforeFileName,
+                Finding::MftModifiedBeforeModified
+            ]
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn display() {
+        let file_name = set(T0, T0, T0, T0);
+        let standard = set(T0 - 365 * DAY - T0 % SECOND, T0, T0, T0);
+        assert_eq!(
+            standard.compare(&file_name).to_string(),
+            "created       -365d 0h 0m 0.1234567s\n\
+             modified      0.0000000s\n\
+             mft_modified  0.0000000s\n\
+             accessed      0.0000000s\n\
+             finding       $SI created before $FN created\n\
+             finding       $SI sub-seconds zeroed where $FN has them"
+        );
+        assert!(file_name
+            .compare(&file_name)
+            .to_string()
+            .ends_with("\nfinding       none"));
+        assert_eq!(
+            Finding::CreatedAfterModified.to_string(),
+            "$SI created after $SI modified"
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Conversion from/to the ntfs crate's `NtfsTime`
+//!
+//! Both hold the unsigned 64-bit tick count since 1601 as NTFS stores it, so
+//! the conversions are infallible and keep every raw value. Values above
+//! `i64::MAX` (see [`FileTime::MAX_RAW`]) come through unchanged; they have
+//! no calendar date, [`FileTime::to_datetime_checked`] reports them.
+use crate::FileTime;
+use ::ntfs::NtfsTime;
+
+impl From<NtfsTime> for FileTime {
+    fn from(time: NtfsTime) -> Self {
+        FileTime::from_u64(time.nt_timestamp())
+    }
+}
+
+impl From<FileTime> for NtfsTime {
+    fn from(ft: FileTime) -> Self {
+        NtfsTime::from(ft.as_u64())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn known_values() {
+        let cases = [
+            (130139712831482830, "2013-05-25T16:01:23.148283Z"),
+            (116444736000000000, "1970-01-01T00:00:00Z"),
+            (0, "1601-01-01T00:00:00Z"),
+            (i64::MAX as u64, "+30828-09-14T02:48:05.477580700Z"),
+        ];
+        for &(raw, datetime) in &cases {
+            let ft = FileTime::from(NtfsTime::from(raw));
+            assert_eq!(ft.as_u64(), raw);
+            assert_eq!(
+                ft.to_datetime()
+                    .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+                datetime
+            );
+            assert_eq!(NtfsTime::from(ft).nt_timestamp(), raw);
+        }
+
+        // No date above i64::MAX, yet the raw value survives both ways
+        let ft = FileTime::from(NtfsTime::from(u64::MAX));
+        assert_eq!(ft, FileTime::MAX_RAW);
+        assert!(ft.to_datetime_checked().is_err());
+        assert_eq!(NtfsTime::from(ft).nt_timestamp(), u64::MAX);
+    }
+}
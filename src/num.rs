@@ -0,0 +1,133 @@
+//! num-traits conversions over the raw tick count
+//!
+//! An `f64` holds integers exactly only up to 2^53, which is about 28.5 years
+//! worth of ticks. Present day FILETIMEs lose their last few bits in the
+//! conversion (steps of 16 ticks, 1.6µs, in the 2020s). The bounds are
+//! those of the raw value, [`FileTime::MIN`] and [`FileTime::MAX_RAW`].
+use crate::FileTime;
+use num_traits::{Bounded, FromPrimitive, ToPrimitive};
+use std::convert::TryFrom;
+
+impl ToPrimitive for FileTime {
+    /// `None` for raw values above `i64::MAX`
+    fn to_i64(&self) -> Option<i64> {
+        i64::try_from(self.as_u64()).ok()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        Some(self.as_u64())
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.as_u64() as f64)
+    }
+}
+
+impl FromPrimitive for FileTime {
+    /// `None` for negative values
+    fn from_i64(n: i64) -> Option<Self> {
+        FileTime::from_i64_checked(n).ok()
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(FileTime::from_u64(n))
+    }
+
+    /// Rounds to the nearest tick, `None` for non-finite and out of range values
+    fn from_f64(n: f64) -> Option<Self> {
+        let n = n.round();
+        // u64::MAX isn't representable, 2^64 is the first value out of range
+        if (0.0..18_446_744_073_709_551_616.0).contains(&n) {
+            Some(FileTime::from_u64(n as u64))
+        } else {
+            None
+        }
+    }
+}
+
+impl Bounded for FileTime {
+    fn min_value() -> Self {
+        FileTime::MIN
+    }
+
+    fn max_value() -> Self {
+        FileTime::MAX_RAW
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ticks<T: ToPrimitive>(value: T) -> Option<u64> {
+        value.to_u64()
+    }
+
+    #[test]
+    fn to_primitive() {
+        let ft = FileTime::from_i64(128930364000000010);
+        assert_eq!(ft.to_i64(), Some(128930364000000010));
+        assert_eq!(ticks(ft), Some(128930364000000010));
+        assert_eq!(FileTime::MAX.to_i64(), Some(i64::MAX));
+        assert_eq!(FileTime::MAX_RAW.to_i64(), None);
+        assert_eq!(ticks(FileTime::MAX_RAW), Some(u64::MAX));
+        assert_eq!(ft.to_f64(), Some(128930364000000010.0));
+        // Not representable as i32
+        assert_eq!(ft.to_i32(), None);
+        assert_eq!(FileTime::MIN.to_u8(), Some(0));
+    }
+
+    #[test]
+    fn from_primitive() {
+        assert_eq!(
+            <FileTime as FromPrimitive>::from_i64(0),
+            Some(FileTime::MIN)
+        );
+        assert_eq!(<FileTime as FromPrimitive>::from_i64(-1), None);
+        assert_eq!(
+            <FileTime as FromPrimitive>::from_i64(i64::MAX),
+            Some(FileTime::MAX)
+        );
+        assert_eq!(
+            <FileTime as FromPrimitive>::from_u64(i64::MAX as u64),
+            Some(FileTime::MAX)
+        );
+        assert_eq!(
+            <FileTime as FromPrimitive>::from_u64(u64::MAX),
+            Some(FileTime::MAX_RAW)
+        );
+        assert_eq!(FileTime::from_i32(-5), None);
+    }
+
+    #[test]
+    fn from_f64() {
+        assert_eq!(FileTime::from_f64(1.5), Some(FileTime::from_i64(2)));
+        assert_eq!(FileTime::from_f64(-0.4), Some(FileTime::MIN));
+        assert_eq!(FileTime::from_f64(-0.5), None);
+        assert_eq!(FileTime::from_f64(f64::NAN), None);
+        assert_eq!(FileTime::from_f64(f64::INFINITY), None);
+        assert_eq!(FileTime::from_f64(18_446_744_073_709_551_616.0), None);
+        assert_eq!(
+            FileTime::from_f64(9_223_372_036_854_775_808.0),
+            Some(FileTime::from_u64(1 << 63))
+        );
+        assert_eq!(
+            FileTime::from_f64(18_446_744_073_709_549_568.0),
+            Some(FileTime::from_u64(18_446_744_073_709_549_568))
+        );
+    }
+
+    #[test]
+    fn generic_code() {
+        let lo = FileTime::from_i64(100);
+        let hi = FileTime::from_i64(200);
+        assert_eq!(num_traits::clamp(FileTime::from_i64(50), lo, hi), lo);
+        assert_eq!(num_traits::clamp(FileTime::MAX, lo, hi), hi);
+        assert_eq!(
+            num_traits::clamp(FileTime::from_i64(150), lo, hi),
+            FileTime::from_i64(150)
+        );
+        assert_eq!(FileTime::min_value(), FileTime::MIN);
+        assert_eq!(FileTime::max_value(), FileTime::MAX_RAW);
+    }
+}
@@ -0,0 +1,51 @@
+//! Helpers shared by the textual format parsers
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Parse a fixed-width run of ASCII digits
+pub(crate) fn digits(s: &[u8]) -> Option<u32> {
+    if s.is_empty() || s.len() > 9 {
+        return None;
+    }
+    s.iter().try_fold(0u32, |acc, &b| {
+        if b.is_ascii_digit() {
+            Some(acc * 10 + u32::from(b - b'0'))
+        } else {
+            None
+        }
+    })
+}
+
+/// Check that `b` follows `layout`, where `d` stands for a digit, `a` for a
+/// letter and every other byte must match exactly
+pub(crate) fn matches_layout(b: &[u8], layout: &str) -> bool {
+    b.len() == layout.len()
+        && b.iter().zip(layout.bytes()).all(|(&c, l)| match l {
+            b'd' => c.is_ascii_digit(),
+            b'a' => c.is_ascii_alphabetic(),
+            _ => c == l,
+        })
+}
+
+/// Combine calendar fields into a UTC datetime, `None` if they don't form a valid date and time
+pub(crate) fn datetime(
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    min: u32,
+    sec: u32,
+    nanos: u32,
+) -> Option<DateTime<Utc>> {
+    NaiveDate::from_ymd_opt(year, month, day)?
+        .and_hms_nano_opt(hour, min, sec, nanos)
+        .map(|dt| dt.and_utc())
+}
+
+/// Parse fractional second digits into nanoseconds, digits beyond nanosecond precision are truncated
+pub(crate) fn fraction_nanos(s: &[u8]) -> Option<u32> {
+    if s.is_empty() || !s.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    let significant = &s[..s.len().min(9)];
+    Some(digits(significant)? * 10u32.pow(9 - significant.len() as u32))
+}
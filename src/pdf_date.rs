@@ -0,0 +1,265 @@
+//! PDF date strings, as in `/CreationDate` and `/ModDate` (ISO 32000-1 7.9.4)
+//!
+//! `D:YYYYMMDDHHmmSSOHH'mm'`: everything after the year may be cut off from
+//! the right, month and day then default to 01 and the time fields to 0.
+//! `O` is `Z`, `+` or `-`. PDF 1.x writers end the offset with an
+//! apostrophe, PDF 2.0 writers leave it out; both are read. Without `O` the
+//! relation to UTC is unknown, see [`MissingTimezone`].
+use crate::parse;
+use crate::{FileTime, FileTimeError, MissingTimezone};
+use chrono::{Datelike, Duration};
+
+const PDF_DATE: &str = "PDF date";
+
+fn invalid(reason: &'static str) -> FileTimeError {
+    FileTimeError::InvalidFormat {
+        format: PDF_DATE,
+        reason,
+    }
+}
+
+/// Parse the offset after `O`, `HH['][mm[']]`, into minutes
+fn offset_minutes(b: &[u8]) -> Result<i64, FileTimeError> {
+    let expected = || invalid("expected HH'mm' after the timezone sign");
+    let (hours, rest) = match b {
+        [h1, h2, rest @ ..] => (parse::digits(&[*h1, *h2]).ok_or_else(expected)?, rest),
+        _ => return Err(expected()),
+    };
+    let rest = rest.strip_prefix(b"'").unwrap_or(rest);
+    let (minutes, rest) = match rest {
+        [] => (0, rest),
+        [m1, m2, rest @ ..] => (parse::digits(&[*m1, *m2]).ok_or_else(expected)?, rest),
+        _ => return Err(expected()),
+    };
+    if !matches!(rest, [] | [b'\'']) {
+        return Err(expected());
+    }
+    if hours > 23 || minutes > 59 {
+        return Err(invalid("timezone offset beyond 23'59'"));
+    }
+    Ok(i64::from(hours * 60 + minutes))
+}
+
+impl FileTime {
+    /// Parse a PDF date, rejecting values without timezone
+    ///
+    /// Same as [`parse_pdf_date_with`](Self::parse_pdf_date_with) with
+    /// [`MissingTimezone::Reject`].
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::parse_pdf_date("D:20230517093000+02'00'").unwrap();
+    /// assert_eq!(ft, FileTime::from_ymd_hms(2023, 5, 17, 7, 30, 0).unwrap());
+    /// assert!(FileTime::parse_pdf_date("D:20230517093000").is_err());
+    /// ```
+    pub fn parse_pdf_date(s: &str) -> Result<Self, FileTimeError> {
+        Self::parse_pdf_date_with(s, MissingTimezone::Reject)
+    }
+
+    /// Parse a PDF date, handling a missing timezone as `missing` says
+    ///
+    /// The `D:` prefix is optional, since older writers left it out.
+    /// `Z` may be followed by a zero offset (`Z00'00'`). Years before 1601 fail
+    /// with [`FileTimeError::BeforeEpoch`], malformed input with
+    /// [`FileTimeError::InvalidFormat`].
+    /// ```
+    /// use filetime_type::{FileTime, MissingTimezone};
+    ///
+    /// let ft = FileTime::parse_pdf_date_with("D:2023", MissingTimezone::AssumeUtc).unwrap();
+    /// assert_eq!(ft, FileTime::from_ymd_hms(2023, 1, 1, 0, 0, 0).unwrap());
+    /// ```
+    pub fn parse_pdf_date_with(s: &str, missing: MissingTimezone) -> Result<Self, FileTimeError> {
+        let b = s.as_bytes();
+        let b = b.strip_prefix(b"D:").unwrap_or(b);
+        let len = b
+            .iter()
+            .position(|c| !c.is_ascii_digit())
+            .unwrap_or(b.len());
+        if !(4..=14).contains(&len) || !len.is_multiple_of(2) {
+            return Err(invalid("expected YYYY[MM[DD[HH[mm[SS]]]]]"));
+        }
+        let (fields, tz) = b.split_at(len);
+        let field = |index: usize, default: u32| {
+            let start = 4 + 2 * index;
+            fields
+                .get(start..start + 2)
+                .map_or(default, |f| parse::digits(f).unwrap())
+        };
+        let offset_minutes = match tz {
+            [] if missing == MissingTimezone::AssumeUtc => 0,
+            [] => return Err(invalid("missing timezone")),
+            [b'Z'] => 0,
+            [b'Z', rest @ ..] => match offset_minutes(rest)? {
+                0 => 0,
+                _ => return Err(invalid("nonzero offset after Z")),
+            },
+            [b'+', rest @ ..] => offset_minutes(rest)?,
+            [b'-', rest @ ..] => -offset_minutes(rest)?,
+            _ => return Err(invalid("expected Z, + or - timezone")),
+        };
+        let year = parse::digits(&fields[..4]).unwrap() as i32;
+        let local = parse::datetime(
+            year,
+            field(0, 1),
+            field(1, 1),
+            field(2, 0),
+            field(3, 0),
+            field(4, 0),
+            0,
+        )
+        .ok_or_else(|| invalid("invalid date or time"))?;
+        Self::from_datetime_checked(local - Duration::minutes(offset_minutes))
+    }
+
+    /// Format as full PDF date in the local time `offset_minutes` east of UTC
+    ///
+    /// Always writes every field, `D:YYYYMMDDHHmmSS+HH'mm'` with the
+    /// trailing apostrophe of PDF 1.x, and `Z` for offset 0. Sub-second ticks
+    /// are truncated. Years after 9999 get five digits, which PDF readers
+    /// including [`parse_pdf_date`](Self::parse_pdf_date) reject. Fails with
+    /// [`FileTimeError::OutOfRange`] if the offset is beyond ±23:59 or the
+    /// value beyond [`FileTime::MAX`].
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_ymd_hms(2023, 5, 17, 7, 30, 0).unwrap();
+    /// assert_eq!(ft.to_pdf_date(120).unwrap(), "D:20230517093000+02'00'");
+    /// assert_eq!(ft.to_pdf_date(-330).unwrap(), "D:20230517020000-05'30'");
+    /// assert_eq!(ft.to_pdf_date(0).unwrap(), "D:20230517073000Z");
+    /// ```
+    pub fn to_pdf_date(&self, offset_minutes: i16) -> Result<String, FileTimeError> {
+        if offset_minutes.unsigned_abs() >= 24 * 60 {
+            return Err(FileTimeError::OutOfRange(PDF_DATE));
+        }
+        let local = self
+            .to_datetime_checked()
+            .map_err(|_| FileTimeError::OutOfRange(PDF_DATE))?
+            + Duration::minutes(i64::from(offset_minutes));
+        let mut s = format!("D:{:04}{}", local.year(), local.format("%m%d%H%M%S"));
+        if offset_minutes == 0 {
+            s.push('Z');
+        } else {
+            let sign = if offset_minutes < 0 { '-' } else { '+' };
+            let abs = offset_minutes.unsigned_abs();
+            s.push_str(&format!("{}{:02}'{:02}'", sign, abs / 60, abs % 60));
+        }
+        Ok(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn invalid(reason: &'static str) -> Result<FileTime, FileTimeError> {
+        Err(FileTimeError::InvalidFormat {
+            format: PDF_DATE,
+            reason,
+        })
+    }
+
+    #[test]
+    fn full() {
+        let utc = FileTime::from_ymd_hms(2023, 5, 17, 7, 30, 0).unwrap();
+        assert_eq!(FileTime::parse_pdf_date("D:20230517093000+02'00'"), Ok(utc));
+        assert_eq!(utc.to_pdf_date(120).unwrap(), "D:20230517093000+02'00'");
+        for offset in [0, 60, -60, 345, -570, 23 * 60 + 59, -(23 * 60 + 59)] {
+            let ft = FileTime::from_i64(130139712831482830);
+            let s = ft.to_pdf_date(offset).unwrap();
+            assert_eq!(
+                FileTime::parse_pdf_date(&s),
+                FileTime::from_ymd_hms(2013, 5, 25, 16, 1, 23),
+                "{}",
+                s
+            );
+        }
+        assert_eq!(FileTime::MIN.to_pdf_date(0).unwrap(), "D:16010101000000Z");
+        assert_eq!(
+            FileTime::MIN.to_pdf_date(-60).unwrap(),
+            "D:16001231230000-01'00'"
+        );
+        assert_eq!(
+            FileTime::parse_pdf_date("D:16001231230000-01'00'"),
+            Ok(FileTime::MIN)
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let parse = |s| FileTime::parse_pdf_date_with(s, MissingTimezone::AssumeUtc);
+        let ymd = |y, mo, d, h, mi, s| FileTime::from_ymd_hms(y, mo, d, h, mi, s);
+        assert_eq!(parse("D:2023"), ymd(2023, 1, 1, 0, 0, 0));
+        assert_eq!(parse("D:202305"), ymd(2023, 5, 1, 0, 0, 0));
+        assert_eq!(parse("D:20230517"), ymd(2023, 5, 17, 0, 0, 0));
+        assert_eq!(parse("D:2023051709"), ymd(2023, 5, 17, 9, 0, 0));
+        assert_eq!(parse("D:202305170930"), ymd(2023, 5, 17, 9, 30, 0));
+        assert_eq!(parse("20230517093015"), ymd(2023, 5, 17, 9, 30, 15));
+        assert_eq!(parse("D:2023Z"), ymd(2023, 1, 1, 0, 0, 0));
+        assert_eq!(parse("D:20230517-08"), ymd(2023, 5, 17, 8, 0, 0));
+
+        assert_eq!(
+            FileTime::parse_pdf_date("D:2023"),
+            invalid("missing timezone")
+        );
+        assert_eq!(MissingTimezone::default(), MissingTimezone::Reject);
+        let layout = invalid("expected YYYY[MM[DD[HH[mm[SS]]]]]");
+        for s in ["D:", "D:202", "D:20235", "D:2023051709301500", "D:+02'00'"] {
+            assert_eq!(parse(s), layout, "{}", s);
+        }
+        assert_eq!(parse("D:20231317"), invalid("invalid date or time"));
+        assert_eq!(parse("D:20230229"), invalid("invalid date or time"));
+        assert_eq!(parse("D:16001231235959Z"), Err(FileTimeError::BeforeEpoch));
+    }
+
+    #[test]
+    fn offsets() {
+        let utc = FileTime::from_ymd_hms(2023, 5, 17, 7, 30, 0).unwrap();
+        for s in [
+            "D:20230517073000Z",
+            "D:20230517073000Z00'00'",
+            "D:20230517073000Z00'00",
+            "D:20230517093000+02'00'",
+            "D:20230517093000+02'00",
+            "D:20230517093000+0200",
+            "D:20230517093000+02'",
+            "D:20230517093000+02",
+            "D:20230517020000-05'30'",
+            "D:20230517020000-05'30",
+            "D:20230517073000+00'00'",
+            "D:20230517073000-00'00'",
+        ] {
+            assert_eq!(FileTime::parse_pdf_date(s), Ok(utc), "{}", s);
+        }
+
+        let expected = invalid("expected HH'mm' after the timezone sign");
+        for s in [
+            "D:20230517093000+",
+            "D:20230517093000+2'00'",
+            "D:20230517093000+02:00",
+            "D:20230517093000+02'00''",
+            "D:20230517093000+02'0'",
+        ] {
+            assert_eq!(FileTime::parse_pdf_date(s), expected, "{}", s);
+        }
+        assert_eq!(
+            FileTime::parse_pdf_date("D:20230517093000+24'00'"),
+            invalid("timezone offset beyond 23'59'")
+        );
+        assert_eq!(
+            FileTime::parse_pdf_date("D:20230517093000Z02'00'"),
+            invalid("nonzero offset after Z")
+        );
+        assert_eq!(
+            FileTime::parse_pdf_date("D:20230517093000 GMT"),
+            invalid("expected Z, + or - timezone")
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        let out_of_range = Err(FileTimeError::OutOfRange(PDF_DATE));
+        assert_eq!(FileTime::MIN.to_pdf_date(24 * 60), out_of_range);
+        assert_eq!(FileTime::MIN.to_pdf_date(-24 * 60), out_of_range);
+        assert_eq!(FileTime::MAX_RAW.to_pdf_date(0), out_of_range);
+    }
+}
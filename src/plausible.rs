@@ -0,0 +1,202 @@
+//! Sanity windows for filtering bogus timestamps
+//!
+//! Carved or corrupted data easily yields valid FILETIMEs in 1601 or the
+//! year 30000. A [`PlausibilityWindow`] states which instants to believe.
+use crate::FileTime;
+
+/// 1970-01-01T00:00:00Z
+const UNIX_EPOCH: FileTime = FileTime {
+    ticks: 116444736000000000,
+};
+/// 1980-01-01T00:00:00Z, the FAT / DOS date epoch
+#[cfg(any(feature = "std", test))]
+const DOS_EPOCH: FileTime = FileTime {
+    ticks: 119600064000000000,
+};
+/// 2100-01-01T00:00:00Z
+const YEAR_2100: FileTime = FileTime {
+    ticks: 157469184000000000,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum End {
+    Fixed(FileTime),
+    /// This many months after the current time
+    #[cfg(feature = "std")]
+    FromNow(u32),
+}
+
+/// Range of instants considered plausible, both ends included
+///
+/// - `default()`: 1980-01-01T00:00:00Z (the FAT epoch)
+///   to 10 years after the current time, evaluated on every check
+/// - `forensic()`: 1970-01-01T00:00:00Z to
+///   2100-01-01T00:00:00Z, independent of the clock so results are reproducible
+/// - `custom()`: any fixed range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlausibilityWindow {
+    start: FileTime,
+    end: End,
+}
+
+impl PlausibilityWindow {
+    /// 1980-01-01T00:00:00Z to 10 years after the current time, same as `default()`
+    #[cfg(feature = "std")]
+    pub const fn recent() -> Self {
+        Self {
+            start: DOS_EPOCH,
+            end: End::FromNow(120),
+        }
+    }
+
+    /// 1970-01-01T00:00:00Z to 2100-01-01T00:00:00Z
+    pub const fn forensic() -> Self {
+        Self::custom(UNIX_EPOCH, YEAR_2100)
+    }
+
+    /// `start` to `end`, panics if `start` lies after `end`
+    pub const fn custom(start: FileTime, end: FileTime) -> Self {
+        assert!(start.ticks <= end.ticks, "window start lies after its end");
+        Self {
+            start,
+            end: End::Fixed(end),
+        }
+    }
+
+    /// Earliest plausible instant
+    pub const fn start(&self) -> FileTime {
+        self.start
+    }
+
+    /// Latest plausible instant, for `default()` relative to the current time
+    pub fn end(&self) -> FileTime {
+        match self.end {
+            End::Fixed(end) => end,
+            #[cfg(feature = "std")]
+            End::FromNow(months) => FileTime::now()
+                .to_datetime()
+                .checked_add_months(chrono::Months::new(months))
+                .and_then(|dt| FileTime::from_datetime_checked(dt).ok())
+                .unwrap_or(FileTime::MAX),
+        }
+    }
+
+    /// Whether `ft` lies within the window
+    pub fn contains(&self, ft: FileTime) -> bool {
+        ft.is_within(self.start, self.end())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for PlausibilityWindow {
+    fn default() -> Self {
+        Self::recent()
+    }
+}
+
+impl FileTime {
+    /// Whether the instant lies within the [default plausibility window](PlausibilityWindow),
+    /// 1980-01-01T00:00:00Z to 10 years from now
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// assert!(FileTime::now().is_plausible());
+    /// assert!(!FileTime::MIN.is_plausible());
+    /// assert!(!FileTime::MAX.is_plausible());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn is_plausible(&self) -> bool {
+        PlausibilityWindow::default().contains(*self)
+    }
+
+    /// Whether the instant lies within `window`
+    pub fn is_plausible_in(&self, window: &PlausibilityWindow) -> bool {
+        window.contains(*self)
+    }
+
+    /// Whether the instant lies between `start` and `end`, both included
+    pub const fn is_within(&self, start: FileTime, end: FileTime) -> bool {
+        start.ticks <= self.ticks && self.ticks <= end.ticks
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tick(ft: FileTime, ticks: i64) -> FileTime {
+        FileTime::from_i64(ft.filetime() + ticks)
+    }
+
+    #[test]
+    fn constants() {
+        for &(ft, year) in &[(UNIX_EPOCH, 1970), (DOS_EPOCH, 1980), (YEAR_2100, 2100)] {
+            assert_eq!(FileTime::from_ymd_hms(year, 1, 1, 0, 0, 0), Ok(ft));
+        }
+    }
+
+    #[test]
+    fn is_within() {
+        let (start, end) = (FileTime::from_i64(10), FileTime::from_i64(20));
+        assert!(start.is_within(start, end));
+        assert!(end.is_within(start, end));
+        assert!(!FileTime::from_i64(9).is_within(start, end));
+        assert!(!FileTime::from_i64(21).is_within(start, end));
+        assert!(!start.is_within(end, start));
+    }
+
+    #[test]
+    fn fixed_windows() {
+        let forensic = PlausibilityWindow::forensic();
+        assert_eq!(forensic.start(), UNIX_EPOCH);
+        assert_eq!(forensic.end(), YEAR_2100);
+        assert!(UNIX_EPOCH.is_plausible_in(&forensic));
+        assert!(!tick(UNIX_EPOCH, -1).is_plausible_in(&forensic));
+        assert!(YEAR_2100.is_plausible_in(&forensic));
+        assert!(!tick(YEAR_2100, 1).is_plausible_in(&forensic));
+
+        const WINDOW: PlausibilityWindow =
+            PlausibilityWindow::custom(FileTime { ticks: 10 }, FileTime { ticks: 20 });
+        assert!(WINDOW.contains(FileTime::from_i64(10)));
+        assert!(WINDOW.contains(FileTime::from_i64(20)));
+        assert!(!WINDOW.contains(FileTime::from_i64(9)));
+        assert!(!WINDOW.contains(FileTime::from_i64(21)));
+    }
+
+    #[test]
+    #[should_panic(expected = "window start lies after its end")]
+    fn custom_inverted() {
+        PlausibilityWindow::custom(FileTime::from_i64(20), FileTime::from_i64(10));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn default_window() {
+        let window = PlausibilityWindow::default();
+        assert_eq!(window, PlausibilityWindow::recent());
+        assert_eq!(window.start(), DOS_EPOCH);
+        assert!(DOS_EPOCH.is_plausible());
+        assert!(!tick(DOS_EPOCH, -1).is_plausible());
+
+        // The end moves with the clock
+        let before = FileTime::now();
+        let end = window.end();
+        let after = FileTime::now();
+        let ten_years = |ft: FileTime| {
+            FileTime::from_datetime(
+                ft.to_datetime()
+                    .checked_add_months(chrono::Months::new(120))
+                    .unwrap(),
+            )
+        };
+        assert!(ten_years(before) <= end && end <= ten_years(after));
+        let nine_years = FileTime::from_datetime(
+            FileTime::now()
+                .to_datetime()
+                .checked_add_months(chrono::Months::new(108))
+                .unwrap(),
+        );
+        assert!(nine_years.is_plausible());
+        assert!(!tick(ten_years(FileTime::now()), 10_000_000).is_plausible());
+    }
+}
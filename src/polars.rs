@@ -0,0 +1,221 @@
+//! Conversion from/to polars timestamp series
+//!
+//! Series are built with the `Datetime(Microseconds, Some("UTC"))` dtype,
+//! the finest polars unit that holds every FileTime: nanoseconds only cover
+//! 1677-09-21 to 2262-04-11. The last tick digit is truncated towards the
+//! earlier instant.
+//!
+//! Reading takes `Datetime` series of any unit, with or without a timezone
+//! (polars stores both as UTC instants), and `Int64` series of raw FILETIME
+//! ticks, as written by `FileTime::filetime`.
+use crate::{FileTime, FileTimeError};
+use ::polars::prelude::{
+    DataType, Int64Chunked, IntoSeries, NewChunkedArray, PlSmallStr, Series, TimeUnit, TimeZone,
+};
+
+const TICKS_PER_MICROSECOND: i128 = 10;
+
+fn micros(ft: FileTime) -> i64 {
+    // Every FILETIME fits, i64 microseconds span about ±292000 years
+    ft.unix_ticks().div_euclid(TICKS_PER_MICROSECOND) as i64
+}
+
+fn build(name: &str, values: impl Iterator<Item = Option<i64>>) -> Series {
+    Int64Chunked::from_iter_options(PlSmallStr::from_str(name), values)
+        .into_datetime(TimeUnit::Microseconds, Some(TimeZone::UTC))
+        .into_series()
+}
+
+/// Build a `Datetime(Microseconds, Some("UTC"))` series, truncated to the µs
+/// ```
+/// use filetime_type::{polars::to_series, FileTime};
+/// use polars::prelude::{DataType, TimeUnit, TimeZone};
+///
+/// let series = to_series("modified", &[FileTime::from_i64(130139712831482830)]);
+/// assert_eq!(
+///     series.dtype(),
+///     &DataType::Datetime(TimeUnit::Microseconds, Some(TimeZone::UTC))
+/// );
+/// assert_eq!(series.datetime().unwrap().physical().get(0), Some(1369497683148283));
+/// ```
+pub fn to_series(name: &str, times: &[FileTime]) -> Series {
+    build(name, times.iter().map(|&ft| Some(micros(ft))))
+}
+
+/// Like [`to_series`], `None` becomes null
+pub fn to_series_opt(name: &str, times: &[Option<FileTime>]) -> Series {
+    build(name, times.iter().map(|ft| ft.map(micros)))
+}
+
+/// Read a `Datetime` series of any unit or an `Int64` series of raw ticks
+///
+/// Nulls become `None`. Values before 1601 or after [`FileTime::MAX`] fail
+/// with [`FileTimeError::InvalidElement`], or become `None` with
+/// `out_of_range_as_null`. Nanoseconds are truncated to ticks towards the
+/// earlier instant. Other dtypes fail with [`FileTimeError::InvalidFormat`].
+pub fn from_series(
+    series: &Series,
+    out_of_range_as_null: bool,
+) -> Result<Vec<Option<FileTime>>, FileTimeError> {
+    // The unit of Datetime values, None for raw ticks
+    let (values, unit) = match series.dtype() {
+        DataType::Datetime(unit, _) => {
+            (series.datetime().ok().map(|ca| ca.physical()), Some(*unit))
+        }
+        DataType::Int64 => (series.i64().ok(), None),
+        _ => (None, None),
+    };
+    let values = values.ok_or(FileTimeError::InvalidFormat {
+        format: "polars Series",
+        reason: "neither a Datetime nor an Int64 series",
+    })?;
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let ticks = match value {
+                None => return Ok(None),
+                Some(value) => to_ticks(value, unit),
+            };
+            match FileTime::from_ticks_i128(ticks) {
+                Ok(ft) => Ok(Some(ft)),
+                Err(_) if out_of_range_as_null => Ok(None),
+                Err(err) => Err(FileTimeError::InvalidElement {
+                    index,
+                    reason: match err {
+                        FileTimeError::BeforeEpoch => "before the FILETIME epoch",
+                        _ => "beyond the maximum FILETIME",
+                    },
+                }),
+            }
+        })
+        .collect()
+}
+
+/// FILETIME ticks of a value in `unit` since the Unix-Epoch, or of raw ticks
+fn to_ticks(value: i64, unit: Option<TimeUnit>) -> i128 {
+    let value = i128::from(value);
+    let unix_ticks = match unit {
+        None => return value,
+        Some(TimeUnit::Milliseconds) => value * i128::from(FileTime::TICKS_PER_MILLISECOND),
+        Some(TimeUnit::Microseconds) => value * TICKS_PER_MICROSECOND,
+        Some(TimeUnit::Nanoseconds) => value.div_euclid(100),
+    };
+    unix_ticks + i128::from(FileTime::EPOCH_AS_FILETIME)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::polars::prelude::NamedFrom;
+
+    #[test]
+    fn roundtrip() {
+        let ft = FileTime::from_i64(130139712831482830);
+        let times = [
+            Some(ft),
+            None,
+            Some(FileTime::MIN),
+            Some(FileTime::MAX),
+            Some(FileTime::from_unix_seconds(-1).unwrap()),
+        ];
+        let series = to_series_opt("modified", &times);
+        assert_eq!(series.name().as_str(), "modified");
+        assert_eq!(
+            series.dtype(),
+            &DataType::Datetime(TimeUnit::Microseconds, Some(TimeZone::UTC))
+        );
+        assert_eq!(series.null_count(), 1);
+        assert_eq!(
+            from_series(&series, false),
+            Ok(vec![
+                Some(ft),
+                None,
+                Some(FileTime::MIN),
+                Some(FileTime::from_i64(i64::MAX - 7)),
+                Some(FileTime::from_unix_seconds(-1).unwrap()),
+            ])
+        );
+
+        // The last tick digit is truncated towards the earlier instant
+        let odd = FileTime::from_i64(130139712831482837);
+        let series = to_series("modified", &[odd, FileTime::from_i64(5)]);
+        assert_eq!(series.null_count(), 0);
+        assert_eq!(
+            from_series(&series, false),
+            Ok(vec![Some(ft), Some(FileTime::MIN)])
+        );
+    }
+
+    #[test]
+    fn other_units() {
+        let ticks = Series::new("ticks".into(), [Some(130139712831482830i64), None, Some(0)]);
+        assert_eq!(
+            from_series(&ticks, false),
+            Ok(vec![
+                Some(FileTime::from_i64(130139712831482830)),
+                None,
+                Some(FileTime::MIN)
+            ])
+        );
+
+        // Nanoseconds truncate to the tick, also before 1970
+        let nanos = Int64Chunked::new("ns".into(), &[1369497683148283099, -1])
+            .into_datetime(TimeUnit::Nanoseconds, None)
+            .into_series();
+        assert_eq!(
+            from_series(&nanos, false),
+            Ok(vec![
+                Some(FileTime::from_i64(130139712831482830)),
+                Some(FileTime::from_i64(FileTime::EPOCH_AS_FILETIME - 1)),
+            ])
+        );
+        let millis = Int64Chunked::new("ms".into(), &[1369497683148])
+            .into_datetime(TimeUnit::Milliseconds, Some(TimeZone::UTC))
+            .into_series();
+        assert_eq!(
+            from_series(&millis, false),
+            Ok(vec![Some(FileTime::from_i64(130139712831480000))])
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        // 1600-12-31T23:59:59.999Z, 1 ms before the FILETIME-Epoch
+        let before = -11644473600 * 1000 - 1;
+        let millis = Int64Chunked::new("ms".into(), &[0, before, i64::MAX])
+            .into_datetime(TimeUnit::Milliseconds, None)
+            .into_series();
+        assert_eq!(
+            from_series(&millis, false),
+            Err(FileTimeError::InvalidElement {
+                index: 1,
+                reason: "before the FILETIME epoch",
+            })
+        );
+        assert_eq!(
+            from_series(&millis.slice(2, 1), false),
+            Err(FileTimeError::InvalidElement {
+                index: 0,
+                reason: "beyond the maximum FILETIME",
+            })
+        );
+        assert_eq!(
+            from_series(&millis, true),
+            Ok(vec![FileTime::from_unix_seconds(0).ok(), None, None])
+        );
+
+        let ticks = Series::new("ticks".into(), [-1i64]);
+        assert_eq!(from_series(&ticks, true), Ok(vec![None]));
+        assert!(from_series(&ticks, false).is_err());
+
+        let floats = Series::new("f".into(), [1.5f64]);
+        assert_eq!(
+            from_series(&floats, false),
+            Err(FileTimeError::InvalidFormat {
+                format: "polars Series",
+                reason: "neither a Datetime nor an Int64 series",
+            })
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! PostgreSQL `timestamptz` / `timestamp` support via `postgres-types`
+//!
+//! Values are exchanged in the binary wire format: microseconds since
+//! 2000-01-01T00:00:00Z as big-endian i64. PostgreSQL only stores microsecond
+//! precision, the last tick digit (100ns) is truncated on write.
+//!
+//! The `infinity` / `-infinity` sentinels have no FILETIME equivalent and are
+//! rejected with [`FileTimeError::Overflow`] / [`FileTimeError::BeforeEpoch`].
+use crate::FileTime;
+use bytes::BytesMut;
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use std::error::Error;
+
+impl<'a> FromSql<'a> for FileTime {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let micros = i64::from_sql(&Type::INT8, raw)?;
+        Ok(Self::from_pg_micros(micros)?)
+    }
+
+    accepts!(TIMESTAMPTZ, TIMESTAMP);
+}
+
+impl ToSql for FileTime {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        out.extend_from_slice(&self.to_pg_micros().to_be_bytes());
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIMESTAMPTZ, TIMESTAMP);
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FileTimeError;
+
+    const PG_EPOCH_AS_FILETIME: i64 = 125911584000000000;
+
+    const RAW: [u8; 8] = [0x00, 0x01, 0x12, 0x8e, 0x72, 0xf4, 0x9c, 0x64];
+
+    #[test]
+    fn to_sql() {
+        // 2009-07-25T23:00:00.0001000Z, last tick digit got truncated
+        let ft = FileTime::from_i64(128930364000001009);
+        let mut buf = BytesMut::new();
+        assert!(matches!(
+            ft.to_sql(&Type::TIMESTAMPTZ, &mut buf).unwrap(),
+            IsNull::No
+        ));
+        assert_eq!(&buf[..], &RAW);
+    }
+
+    #[test]
+    fn from_sql() {
+        let ft = FileTime::from_sql(&Type::TIMESTAMPTZ, &RAW).unwrap();
+        assert_eq!(ft, FileTime::from_i64(128930364000001000));
+
+        let ft = FileTime::from_sql(&Type::TIMESTAMP, &(-1_i64).to_be_bytes()).unwrap();
+        assert_eq!(ft, FileTime::from_i64(PG_EPOCH_AS_FILETIME - 10));
+    }
+
+    #[test]
+    fn roundtrip() {
+        for ticks in [0, PG_EPOCH_AS_FILETIME, 128930364000001000, i64::MAX - 7] {
+            let ft = FileTime::from_i64(ticks);
+            let mut buf = BytesMut::new();
+            ft.to_sql(&Type::TIMESTAMPTZ, &mut buf).unwrap();
+            assert_eq!(FileTime::from_sql(&Type::TIMESTAMPTZ, &buf).unwrap(), ft);
+        }
+    }
+
+    #[test]
+    fn from_sql_invalid() {
+        let err = FileTime::from_sql(&Type::TIMESTAMPTZ, &i64::MAX.to_be_bytes()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<FileTimeError>(),
+            Some(&FileTimeError::Overflow)
+        );
+        let err = FileTime::from_sql(&Type::TIMESTAMPTZ, &i64::MIN.to_be_bytes()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<FileTimeError>(),
+            Some(&FileTimeError::BeforeEpoch)
+        );
+        // 1600-12-31T23:59:59.999999Z
+        let micros = -(PG_EPOCH_AS_FILETIME / 10) - 1;
+        let err = FileTime::from_sql(&Type::TIMESTAMPTZ, &micros.to_be_bytes()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<FileTimeError>(),
+            Some(&FileTimeError::BeforeEpoch)
+        );
+        assert!(FileTime::from_sql(&Type::TIMESTAMPTZ, &[0x00; 4]).is_err());
+    }
+
+    #[test]
+    fn accepts() {
+        assert!(<FileTime as ToSql>::accepts(&Type::TIMESTAMPTZ));
+        assert!(<FileTime as FromSql>::accepts(&Type::TIMESTAMP));
+        assert!(!<FileTime as FromSql>::accepts(&Type::INT8));
+    }
+}
@@ -0,0 +1,167 @@
+//! Sub-second precision analysis, a timestomping heuristic
+//!
+//! NTFS stores 100ns ticks and Windows fills all of them when it sets a
+//! timestamp itself. Tools that take the new time as text or as Unix
+//! seconds, like many timestomping tools and PowerShell scripts, leave the
+//! sub-second part at exactly zero. Naturally only 1 in 10^7 timestamps is
+//! second-aligned and 1 in 10^4 millisecond-aligned, so a cluster of them is
+//! a tell.
+//!
+//! It is not proof: files copied from FAT (2s resolution), out of ZIP
+//! archives (DOS dates) or from file systems with second resolution, and
+//! times set by `touch` or installers, are aligned for innocent reasons.
+use crate::FileTime;
+
+const TICKS_PER_MICROSECOND: u64 = 10;
+
+/// Finest unit a FileTime is not a whole multiple of, from [`FileTime::subsec_ticks_entropy_class`]
+///
+/// Each class excludes the coarser ones: a whole second is `SecondOnly`,
+/// not also `MilliOnly`.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+pub enum PrecisionClass {
+    /// The raw value 0, an unset timestamp rather than 1601-01-01
+    Zero,
+    /// Whole seconds, as written from text or Unix seconds
+    SecondOnly,
+    /// Whole milliseconds, as converted from JavaScript, .NET DateTime strings or Java
+    MilliOnly,
+    /// Whole microseconds, as converted from Unix `timeval` or Python
+    MicroOnly,
+    /// Ticks below the microsecond are set, as Windows itself writes
+    FullPrecision,
+}
+
+impl FileTime {
+    /// Whether the sub-second part is exactly zero
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// assert!(FileTime::from_ymd_hms(2023, 5, 17, 9, 30, 15).unwrap().is_second_aligned());
+    /// assert!(!FileTime::from_i64(130139712831482830).is_second_aligned());
+    /// ```
+    pub const fn is_second_aligned(&self) -> bool {
+        self.ticks
+            .is_multiple_of(Self::HUNDREDS_OF_NANOSECONDS as u64)
+    }
+
+    /// Whether the sub-millisecond part is exactly zero, also true for second-aligned values
+    pub const fn is_millisecond_aligned(&self) -> bool {
+        self.ticks
+            .is_multiple_of(Self::TICKS_PER_MILLISECOND as u64)
+    }
+
+    /// Classify by the finest unit that is used, see [`PrecisionClass`]
+    ///
+    /// A single classification says little, 1 in 10^7 FullPrecision
+    /// timestamps lands on a whole second by chance. Look at many
+    /// timestamps of a volume, e.g. with [`second_aligned_fraction`].
+    /// ```
+    /// use filetime_type::{FileTime, PrecisionClass};
+    ///
+    /// let ft = FileTime::from_i64(130139712831482830);
+    /// assert_eq!(ft.subsec_ticks_entropy_class(), PrecisionClass::MicroOnly);
+    /// assert_eq!(FileTime::MIN.subsec_ticks_entropy_class(), PrecisionClass::Zero);
+    /// ```
+    pub const fn subsec_ticks_entropy_class(&self) -> PrecisionClass {
+        if self.ticks == 0 {
+            PrecisionClass::Zero
+        } else if self.is_second_aligned() {
+            PrecisionClass::SecondOnly
+        } else if self.is_millisecond_aligned() {
+            PrecisionClass::MilliOnly
+        } else if self.ticks.is_multiple_of(TICKS_PER_MICROSECOND) {
+            PrecisionClass::MicroOnly
+        } else {
+            PrecisionClass::FullPrecision
+        }
+    }
+}
+
+/// Share of set timestamps that are second-aligned, between 0.0 and 1.0
+///
+/// Zero (unset) values are left out of both the count and the total.
+/// `None` if no value is set. Compare with the natural rate of 1 in 10^7;
+/// copies from FAT or out of ZIP archives raise it for innocent reasons.
+/// ```
+/// use filetime_type::{second_aligned_fraction, FileTime};
+///
+/// let times = [
+///     FileTime::from_i64(133325856001234567),
+///     FileTime::from_i64(133325856000000000),
+///     FileTime::MIN,
+/// ];
+/// assert_eq!(second_aligned_fraction(&times), Some(0.5));
+/// assert_eq!(second_aligned_fraction(&[FileTime::MIN]), None);
+/// ```
+pub fn second_aligned_fraction(times: &[FileTime]) -> Option<f64> {
+    let (aligned, set) = times
+        .iter()
+        .filter(|ft| ft.ticks != 0)
+        .fold((0u64, 0u64), |(aligned, set), ft| {
+            (aligned + u64::from(ft.is_second_aligned()), set + 1)
+        });
+    (set > 0).then(|| aligned as f64 / set as f64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn classes() {
+        let second = 133325856000000000;
+        let cases = [
+            (0, PrecisionClass::Zero),
+            (second, PrecisionClass::SecondOnly),
+            (
+                FileTime::HUNDREDS_OF_NANOSECONDS,
+                PrecisionClass::SecondOnly,
+            ),
+            (second + 1_230_000, PrecisionClass::MilliOnly),
+            (second + 1_234_560, PrecisionClass::MicroOnly),
+            (second + 1_234_567, PrecisionClass::FullPrecision),
+            (second + 1, PrecisionClass::FullPrecision),
+            (i64::MAX, PrecisionClass::FullPrecision),
+        ];
+        for &(ticks, class) in &cases {
+            let ft = FileTime::from_i64(ticks);
+            assert_eq!(ft.subsec_ticks_entropy_class(), class, "{}", ticks);
+            assert_eq!(
+                ft.is_second_aligned(),
+                class <= PrecisionClass::SecondOnly,
+                "{}",
+                ticks
+            );
+            assert_eq!(
+                ft.is_millisecond_aligned(),
+                class <= PrecisionClass::MilliOnly,
+                "{}",
+                ticks
+            );
+        }
+    }
+
+    #[test]
+    fn aligned_fraction() {
+        let second = 133325856000000000;
+        // Synthetic volume: six untouched files, two stomped to whole seconds, one unset
+        let times = [
+            second + 1_234_567,
+            second + 98_765_432,
+            second + 55_555_555,
+            second + 1_230_000,
+            second + 1_234_560,
+            second + 9_999_999,
+            second,
+            second + 3600 * FileTime::HUNDREDS_OF_NANOSECONDS,
+            0,
+        ]
+        .map(FileTime::from_i64);
+        assert_eq!(second_aligned_fraction(&times), Some(0.25));
+        assert_eq!(second_aligned_fraction(&times[..6]), Some(0.0));
+        assert_eq!(second_aligned_fraction(&times[6..]), Some(1.0));
+        assert_eq!(second_aligned_fraction(&[]), None);
+        assert_eq!(second_aligned_fraction(&times[8..]), None);
+    }
+}
@@ -0,0 +1,291 @@
+//! Windows Prefetch last-run timestamps
+//!
+//! The file information block follows the 84 byte file header of a
+//! (decompressed) `.pf` file and holds the last run times at a version
+//! dependent offset:
+//!
+//! ```text
+//! version  offset  count   Windows
+//! 17       0x24    1       XP, 2003
+//! 23       0x2C    1       Vista, 7
+//! 26       0x2C    8       8, 8.1
+//! 30       0x2C    8       10, 11
+//! ```
+//!
+//! Unused slots of the eight entry array are zero, the most recent run comes first.
+use crate::{FileTime, FileTimeError};
+use std::convert::TryFrom;
+
+const FORMAT: &str = "prefetch file information";
+
+/// Prefetch format version, stored as u32 at offset 0 of the file header
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum PrefetchVersion {
+    /// Windows XP and Server 2003
+    V17,
+    /// Windows Vista and 7
+    V23,
+    /// Windows 8 and 8.1
+    V26,
+    /// Windows 10 and 11
+    V30,
+}
+
+impl PrefetchVersion {
+    /// Offset of the last run times within the file information block, and their count
+    fn last_run_layout(self) -> (usize, usize) {
+        match self {
+            Self::V17 => (0x24, 1),
+            Self::V23 => (0x2C, 1),
+            Self::V26 | Self::V30 => (0x2C, 8),
+        }
+    }
+}
+
+impl TryFrom<u32> for PrefetchVersion {
+    type Error = FileTimeError;
+
+    fn try_from(version: u32) -> Result<Self, Self::Error> {
+        match version {
+            17 => Ok(Self::V17),
+            23 => Ok(Self::V23),
+            26 => Ok(Self::V26),
+            30 => Ok(Self::V30),
+            _ => Err(FileTimeError::InvalidFormat {
+                format: "prefetch version",
+                reason: "unsupported format version",
+            }),
+        }
+    }
+}
+
+/// Read every last run slot, `None` for unused (zero) entries
+///
+/// Fails with [`FileTimeError::Malformed`] if `file_info_block` ends before the
+/// last slot or a slot holds a negative value.
+pub fn parse_last_run_slots(
+    file_info_block: &[u8],
+    version: PrefetchVersion,
+) -> Result<Vec<Option<FileTime>>, FileTimeError> {
+    let (start, count) = version.last_run_layout();
+    if file_info_block.len() < start + count * 8 {
+        return Err(FileTimeError::Malformed {
+            format: FORMAT,
+            offset: file_info_block.len(),
+            reason: "truncated last run times",
+        });
+    }
+    (0..count)
+        .map(|i| {
+            let offset = start + i * 8;
+            let mut raw = [0u8; 8];
+            raw.copy_from_slice(&file_info_block[offset..offset + 8]);
+            match i64::from_le_bytes(raw) {
+                0 => Ok(None),
+                ticks => FileTime::from_i64_checked(ticks).map(Some).map_err(|_| {
+                    FileTimeError::Malformed {
+                        format: FORMAT,
+                        offset,
+                        reason: "negative FILETIME",
+                    }
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Read the populated last run times, most recent first
+///
+/// Zero entries are skipped, use [`parse_last_run_slots`] to keep them.
+/// ```
+/// use filetime_type::prefetch::{parse_last_run_times, PrefetchVersion};
+///
+/// let mut block = vec![0u8; 0x98];
+/// block[0x2C..0x34].copy_from_slice(&132601876135897932i64.to_le_bytes());
+/// let times = parse_last_run_times(&block, PrefetchVersion::V23).unwrap();
+/// assert_eq!(times[0].filetime(), 132601876135897932);
+/// ```
+pub fn parse_last_run_times(
+    file_info_block: &[u8],
+    version: PrefetchVersion,
+) -> Result<Vec<FileTime>, FileTimeError> {
+    Ok(parse_last_run_slots(file_info_block, version)?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // File information blocks (header offset 0x54 up to the file metrics) of
+    // real prefetch files shipped as test data by other crates. No Windows 8
+    // (version 26) file was at hand, its layout is covered by the version 30
+    // blocks.
+
+    // WUAUCLT.EXE-399A8E72.pf from libprefetch 0.1.1 `assets/`, Windows XP or 2003
+    // (sha256 9cbf3223...f390aff6)
+    const V17_WUAUCLT: [u8; 68] = [
+        0x98, 0x00, 0x00, 0x00, 0x5a, 0x00, 0x00, 0x00, 0xa0, 0x07, 0x00, 0x00, 0x7b, 0x17, 0x00,
+        0x00, 0x64, 0x21, 0x01, 0x00, 0xa8, 0x31, 0x00, 0x00, 0x10, 0x53, 0x01, 0x00, 0x01, 0x00,
+        0x00, 0x00, 0x78, 0x06, 0x00, 0x00, 0x60, 0x55, 0xf7, 0xa6, 0xd9, 0xe8, 0xcb, 0x01, 0x00,
+        0x8c, 0x86, 0x47, 0x00, 0x00, 0x00, 0x00, 0x00, 0x8c, 0x86, 0x47, 0x00, 0x00, 0x00, 0x00,
+        0x26, 0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00,
+    ];
+
+    // WMIADAP.EXE-F8DFDFA2.pf from dfir-toolkit 0.12.3 `tests/data/scca/`,
+    // Windows Vista or 7 (sha256 5fe1435b...1a2a0106)
+    const V23_WMIADAP: [u8; 156] = [
+        0xf0, 0x00, 0x00, 0x00, 0x23, 0x00, 0x00, 0x00, 0x50, 0x05, 0x00, 0x00, 0x25, 0x04, 0x00,
+        0x00, 0x0c, 0x37, 0x00, 0x00, 0x86, 0x0e, 0x00, 0x00, 0x98, 0x45, 0x00, 0x00, 0x01, 0x00,
+        0x00, 0x00, 0xe2, 0x03, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x10,
+        0x3d, 0x23, 0x5a, 0x49, 0x2e, 0xcf, 0x01, 0x00, 0x8c, 0x86, 0x47, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x8c, 0x86, 0x47, 0x00, 0x00, 0x00, 0x00, 0x30, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    // AUDIODG.EXE-AB22E9A6.pf and AM_DELTA.EXE-78CA83B0.pf from the Windows 10
+    // desktop image of the public "Stolen Szechuan Sauce" DFIR case, as
+    // decompressed by xpress-huffman 0.1.3 `tests/data/*.expected`
+    // (sha256 83e8be95...afb8237e and 36616a7f...ad2811cc). AUDIODG has all
+    // eight slots in use, AM_DELTA ran once.
+    const V30_AUDIODG: [u8; 212] = [
+        0x28, 0x01, 0x00, 0x00, 0x4f, 0x00, 0x00, 0x00, 0x08, 0x0b, 0x00, 0x00, 0x57, 0x0a, 0x00,
+        0x00, 0xc0, 0x5d, 0x00, 0x00, 0xcc, 0x28, 0x00, 0x00, 0xf8, 0x86, 0x00, 0x00, 0x01, 0x00,
+        0x00, 0x00, 0x7a, 0x05, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x4f,
+        0x4e, 0xed, 0x58, 0x44, 0x8e, 0xd6, 0x01, 0xb4, 0x85, 0x2f, 0xfb, 0x42, 0x8e, 0xd6, 0x01,
+        0x95, 0x8a, 0xa8, 0x86, 0x37, 0x8e, 0xd6, 0x01, 0xbc, 0xaf, 0x1a, 0x6e, 0x36, 0x8e, 0xd6,
+        0x01, 0x5c, 0x7c, 0x08, 0x5a, 0x21, 0x8e, 0xd6, 0x01, 0xce, 0xca, 0x0f, 0x83, 0x0f, 0x8e,
+        0xd6, 0x01, 0x1b, 0xdc, 0x2c, 0x7e, 0x04, 0x8e, 0xd6, 0x01, 0xa5, 0x3f, 0xd6, 0x33, 0x7f,
+        0x8d, 0xd6, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00,
+        0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x8c, 0x86, 0x00, 0x00, 0x6c, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00,
+    ];
+    const V30_AM_DELTA: [u8; 212] = [
+        0x28, 0x01, 0x00, 0x00, 0x0d, 0x00, 0x00, 0x00, 0xc8, 0x02, 0x00, 0x00, 0x73, 0x01, 0x00,
+        0x00, 0x60, 0x0e, 0x00, 0x00, 0x22, 0x07, 0x00, 0x00, 0x30, 0x16, 0x00, 0x00, 0x01, 0x00,
+        0x00, 0x00, 0xf4, 0x04, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x33,
+        0xa9, 0xad, 0x46, 0x0d, 0x8e, 0xd6, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x82, 0x15, 0x00, 0x00, 0xa8, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00,
+    ];
+
+    fn filetimes(ticks: &[i64]) -> Vec<FileTime> {
+        ticks.iter().map(|t| FileTime::from_i64(*t)).collect()
+    }
+
+    #[test]
+    fn version() {
+        assert_eq!(PrefetchVersion::try_from(30), Ok(PrefetchVersion::V30));
+        assert_eq!(
+            PrefetchVersion::try_from(31),
+            Err(FileTimeError::InvalidFormat {
+                format: "prefetch version",
+                reason: "unsupported format version",
+            })
+        );
+    }
+
+    #[test]
+    fn v17() {
+        // 2011-03-22T21:39:41.6965472Z
+        assert_eq!(
+            parse_last_run_times(&V17_WUAUCLT, PrefetchVersion::V17),
+            Ok(filetimes(&[129453035816965472]))
+        );
+    }
+
+    #[test]
+    fn v23() {
+        // 2014-02-20T14:37:59.6782864Z
+        assert_eq!(
+            parse_last_run_times(&V23_WMIADAP, PrefetchVersion::V23),
+            Ok(filetimes(&[130373806796782864]))
+        );
+    }
+
+    #[test]
+    fn v30() {
+        // 2020-09-19T05:18:45.4875727Z back to 2020-09-18T05:47:32.4050341Z
+        let runs = filetimes(&[
+            132449663254875727,
+            132449657387189684,
+            132449608187546261,
+            132449603480629180,
+            132449512949578844,
+            132449436328512206,
+            132449389001890843,
+            132448816524050341,
+        ]);
+        assert_eq!(
+            parse_last_run_times(&V30_AUDIODG, PrefetchVersion::V30),
+            Ok(runs.clone())
+        );
+        assert_eq!(
+            parse_last_run_times(&V30_AUDIODG, PrefetchVersion::V26),
+            Ok(runs)
+        );
+
+        // 2020-09-18T22:44:32.5513523Z, the other slots are zero
+        let once = FileTime::from_i64(132449426725513523);
+        assert_eq!(
+            parse_last_run_times(&V30_AM_DELTA, PrefetchVersion::V30),
+            Ok(vec![once])
+        );
+        let slots = parse_last_run_slots(&V30_AM_DELTA, PrefetchVersion::V30).unwrap();
+        assert_eq!(slots.len(), 8);
+        assert_eq!(slots[0], Some(once));
+        assert_eq!(slots[1..], [None; 7]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse_last_run_times(&V17_WUAUCLT[..0x2B], PrefetchVersion::V17),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 0x2B,
+                reason: "truncated last run times",
+            })
+        );
+        // A v17 sized block lacks the eight entry array
+        assert_eq!(
+            parse_last_run_times(&V17_WUAUCLT, PrefetchVersion::V30),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 68,
+                reason: "truncated last run times",
+            })
+        );
+        let mut block = V30_AM_DELTA;
+        block[0x34..0x3C].copy_from_slice(&(-1i64).to_le_bytes());
+        assert_eq!(
+            parse_last_run_times(&block, PrefetchVersion::V30),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 0x34,
+                reason: "negative FILETIME",
+            })
+        );
+    }
+}
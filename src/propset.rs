@@ -0,0 +1,284 @@
+//! FILETIME properties of OLE property sets ([MS-OLEPS])
+//!
+//! Document metadata such as the `\u{5}SummaryInformation` stream of compound
+//! files is stored as property set: a header naming its sections by FMTID,
+//! and per section a PID/offset table pointing at typed values.
+//!
+//! ```text
+//! 0x00  u16        ByteOrder, 0xFFFE
+//! 0x02  u16        Version
+//! 0x04  u32        SystemIdentifier
+//! 0x08  [u8; 16]   CLSID
+//! 0x18  u32        NumPropertySets
+//! 0x1C  [u8; 16]   FMTID of the first section
+//! 0x2C  u32        Offset of the first section
+//! ...
+//! section:
+//! 0x00  u32        Size
+//! 0x04  u32        NumProperties
+//! 0x08  (u32, u32) PID and offset of each property, relative to the section
+//! ```
+//!
+//! A `VT_FILETIME` value is the 4 byte type tag 0x0040 followed by the FILETIME.
+use crate::{FileTime, FileTimeError};
+
+const FORMAT: &str = "OLE property set";
+const VT_FILETIME: u32 = 0x0040;
+/// F29F85E0-4FF9-1068-AB91-08002B27B3D9 in on-disk byte order
+const FMTID_SUMMARY_INFORMATION: [u8; 16] = [
+    0xE0, 0x85, 0x9F, 0xF2, 0xF9, 0x4F, 0x68, 0x10, 0xAB, 0x91, 0x08, 0x00, 0x2B, 0x27, 0xB3, 0xD9,
+];
+const PIDSI_LASTPRINTED: u32 = 0x0B;
+const PIDSI_CREATE_DTM: u32 = 0x0C;
+const PIDSI_LASTSAVE_DTM: u32 = 0x0D;
+
+/// Timestamps of a SummaryInformation property set, `None` where absent or zero
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct SummaryTimes {
+    /// `PIDSI_CREATE_DTM`, creation of the document content
+    pub created: Option<FileTime>,
+    /// `PIDSI_LASTSAVE_DTM`
+    pub last_saved: Option<FileTime>,
+    /// `PIDSI_LASTPRINTED`
+    pub last_printed: Option<FileTime>,
+}
+
+fn malformed(offset: usize, reason: &'static str) -> FileTimeError {
+    FileTimeError::Malformed {
+        format: FORMAT,
+        offset,
+        reason,
+    }
+}
+
+fn read_u32(buf: &[u8], offset: usize, reason: &'static str) -> Result<u32, FileTimeError> {
+    let raw = buf
+        .get(offset..offset.saturating_add(4))
+        .ok_or_else(|| malformed(offset, reason))?;
+    Ok(u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]))
+}
+
+/// Read the `VT_FILETIME` property at `offset` within `section`
+///
+/// Fails with [`FileTimeError::Malformed`] if the value is truncated, its
+/// type tag is not `VT_FILETIME` or it is negative. Offsets in errors are
+/// relative to the section. A zero value is returned as [`FileTime::MIN`].
+/// ```
+/// use filetime_type::propset::read_filetime_property;
+///
+/// let mut section = vec![0x40, 0, 0, 0];
+/// section.extend_from_slice(&132601876135897932i64.to_le_bytes());
+/// let ft = read_filetime_property(&section, 0).unwrap();
+/// assert_eq!(ft.filetime(), 132601876135897932);
+/// ```
+pub fn read_filetime_property(section: &[u8], offset: u32) -> Result<FileTime, FileTimeError> {
+    let offset = offset as usize;
+    if read_u32(section, offset, "truncated property")? != VT_FILETIME {
+        return Err(malformed(offset, "property type is not VT_FILETIME"));
+    }
+    let value = offset + 4;
+    let raw = section
+        .get(value..value + 8)
+        .ok_or_else(|| malformed(value, "truncated property"))?;
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(raw);
+    FileTime::from_i64_checked(i64::from_le_bytes(bytes))
+        .map_err(|_| malformed(value, "negative FILETIME"))
+}
+
+/// Parse the timestamps of a `\u{5}SummaryInformation` property set stream
+///
+/// Properties missing from the section are `None`, as are zero values.
+/// Fails with [`FileTimeError::Malformed`] for a wrong byte order mark or
+/// FMTID, truncated tables, or timestamp properties of another type; offsets
+/// in errors are relative to the start of `stream`.
+pub fn parse_summary_information(stream: &[u8]) -> Result<SummaryTimes, FileTimeError> {
+    if stream.len() < 0x30 {
+        return Err(malformed(stream.len(), "truncated header"));
+    }
+    if stream[..2] != [0xFE, 0xFF] {
+        return Err(malformed(0, "byte order mark is not 0xFFFE"));
+    }
+    if read_u32(stream, 0x18, "truncated header")? == 0 {
+        return Err(malformed(0x18, "no property set"));
+    }
+    if stream[0x1C..0x2C] != FMTID_SUMMARY_INFORMATION {
+        return Err(malformed(0x1C, "FMTID is not SummaryInformation"));
+    }
+    let start = read_u32(stream, 0x2C, "truncated header")? as usize;
+    let section = stream
+        .get(start..)
+        .ok_or_else(|| malformed(0x2C, "section offset out of range"))?;
+    let in_stream = |err| match err {
+        FileTimeError::Malformed {
+            format,
+            offset,
+            reason,
+        } => FileTimeError::Malformed {
+            format,
+            offset: start + offset,
+            reason,
+        },
+        err => err,
+    };
+    let count = read_u32(section, 4, "truncated section header").map_err(in_stream)?;
+
+    let mut times = SummaryTimes {
+        created: None,
+        last_saved: None,
+        last_printed: None,
+    };
+    for i in 0..count as usize {
+        let entry = 8 + i * 8;
+        let pid = read_u32(section, entry, "truncated property table").map_err(in_stream)?;
+        let slot = match pid {
+            PIDSI_CREATE_DTM => &mut times.created,
+            PIDSI_LASTSAVE_DTM => &mut times.last_saved,
+            PIDSI_LASTPRINTED => &mut times.last_printed,
+            _ => continue,
+        };
+        let offset = read_u32(section, entry + 4, "truncated property table").map_err(in_stream)?;
+        let ft = read_filetime_property(section, offset).map_err(in_stream)?;
+        *slot = Some(ft).filter(|ft| *ft != FileTime::MIN);
+    }
+    Ok(times)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The `\u{5}SummaryInformation` stream of the Word document in
+    // `assets/sample.doc` from the ole crate 0.1.15 (sha256
+    // 2ebe9170...49e3b05a), directory entry 13. Its section at 0x30 holds 17
+    // properties; edit time, creation and last save are VT_FILETIME, there is
+    // no last printed time.
+    const STREAM: [u8; 432] = [
+        0xFE, 0xFF, 0x00, 0x00, 0x05, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0xE0, 0x85,
+        0x9F, 0xF2, 0xF9, 0x4F, 0x68, 0x10, 0xAB, 0x91, 0x08, 0x00, 0x2B, 0x27, 0xB3, 0xD9, 0x30,
+        0x00, 0x00, 0x00, 0x80, 0x01, 0x00, 0x00, 0x11, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0x90, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x98, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00,
+        0x00, 0xA4, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0xB0, 0x00, 0x00, 0x00, 0x05, 0x00,
+        0x00, 0x00, 0xCC, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0xD8, 0x00, 0x00, 0x00, 0x07,
+        0x00, 0x00, 0x00, 0xE4, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0xF8, 0x00, 0x00, 0x00,
+        0x09, 0x00, 0x00, 0x00, 0x14, 0x01, 0x00, 0x00, 0x12, 0x00, 0x00, 0x00, 0x20, 0x01, 0x00,
+        0x00, 0x0A, 0x00, 0x00, 0x00, 0x3C, 0x01, 0x00, 0x00, 0x0C, 0x00, 0x00, 0x00, 0x48, 0x01,
+        0x00, 0x00, 0x0D, 0x00, 0x00, 0x00, 0x54, 0x01, 0x00, 0x00, 0x0E, 0x00, 0x00, 0x00, 0x60,
+        0x01, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x00, 0x68, 0x01, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00,
+        0x70, 0x01, 0x00, 0x00, 0x13, 0x00, 0x00, 0x00, 0x78, 0x01, 0x00, 0x00, 0x02, 0x00, 0x00,
+        0x00, 0xE4, 0x04, 0x00, 0x00, 0x1E, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x73, 0x00, 0x1E, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x73, 0x00, 0x1E,
+        0x00, 0x00, 0x00, 0x12, 0x00, 0x00, 0x00, 0x4C, 0x65, 0x78, 0x69, 0x73, 0x20, 0x4E, 0x65,
+        0x78, 0x69, 0x73, 0x20, 0x47, 0x72, 0x6F, 0x75, 0x70, 0x00, 0x64, 0x00, 0x1E, 0x00, 0x00,
+        0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x65, 0x78, 0x69, 0x1E, 0x00, 0x00, 0x00, 0x01, 0x00,
+        0x00, 0x00, 0x00, 0x65, 0x78, 0x69, 0x1E, 0x00, 0x00, 0x00, 0x0B, 0x00, 0x00, 0x00, 0x4E,
+        0x6F, 0x72, 0x6D, 0x61, 0x6C, 0x2E, 0x64, 0x6F, 0x74, 0x00, 0x20, 0x1E, 0x00, 0x00, 0x00,
+        0x12, 0x00, 0x00, 0x00, 0x4C, 0x65, 0x78, 0x69, 0x73, 0x20, 0x4E, 0x65, 0x78, 0x69, 0x73,
+        0x20, 0x47, 0x72, 0x6F, 0x75, 0x70, 0x00, 0x64, 0x00, 0x1E, 0x00, 0x00, 0x00, 0x02, 0x00,
+        0x00, 0x00, 0x37, 0x00, 0x78, 0x69, 0x1E, 0x00, 0x00, 0x00, 0x13, 0x00, 0x00, 0x00, 0x4D,
+        0x69, 0x63, 0x72, 0x6F, 0x73, 0x6F, 0x66, 0x74, 0x20, 0x57, 0x6F, 0x72, 0x64, 0x20, 0x39,
+        0x2E, 0x30, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x36, 0x46, 0xBA, 0x05, 0x00, 0x00,
+        0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x80, 0x7D, 0xD1, 0x42, 0x3A, 0xC1, 0x01, 0x40, 0x00,
+        0x00, 0x00, 0x00, 0xF4, 0x1F, 0x6B, 0x45, 0xD1, 0xC2, 0x01, 0x03, 0x00, 0x00, 0x00, 0x01,
+        0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0xC9, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+        0x7B, 0x04, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+    const SECTION: usize = 0x30;
+    /// Offset of the CREATE_DTM entry in the PID/offset table of the section
+    const CREATE_DTM_ENTRY: usize = 0x60;
+
+    #[test]
+    fn summary_information() {
+        assert_eq!(
+            parse_summary_information(&STREAM),
+            Ok(SummaryTimes {
+                created: FileTime::from_ymd_hms(2001, 9, 10, 21, 52, 0).ok(),
+                last_saved: FileTime::from_ymd_hms(2003, 2, 10, 20, 46, 0).ok(),
+                last_printed: None,
+            })
+        );
+        // A zero value counts as absent
+        let mut stream = STREAM;
+        stream[SECTION + 0x14C..SECTION + 0x154].copy_from_slice(&[0; 8]);
+        assert_eq!(
+            parse_summary_information(&stream).map(|times| times.created),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn property() {
+        let section = &STREAM[SECTION..];
+        // PIDSI_EDITTIME, 41 minutes
+        assert_eq!(
+            read_filetime_property(section, 0x13C),
+            Ok(FileTime::from_i64(41 * 60 * 10_000_000))
+        );
+        // PIDSI_TITLE, an empty VT_LPSTR
+        assert_eq!(
+            read_filetime_property(section, 0x98),
+            Err(malformed(0x98, "property type is not VT_FILETIME"))
+        );
+        assert_eq!(
+            read_filetime_property(&section[..0x153], 0x148),
+            Err(malformed(0x14C, "truncated property"))
+        );
+        assert_eq!(
+            read_filetime_property(section, u32::MAX),
+            Err(malformed(u32::MAX as usize, "truncated property"))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut stream = STREAM;
+        stream[0] = 0xFF;
+        assert_eq!(
+            parse_summary_information(&stream),
+            Err(malformed(0, "byte order mark is not 0xFFFE"))
+        );
+
+        let mut stream = STREAM;
+        stream[0x1C] = 0x02;
+        assert_eq!(
+            parse_summary_information(&stream),
+            Err(malformed(0x1C, "FMTID is not SummaryInformation"))
+        );
+
+        let mut stream = STREAM;
+        stream[0x2D] = 0xFF;
+        assert_eq!(
+            parse_summary_information(&stream),
+            Err(malformed(0x2C, "section offset out of range"))
+        );
+
+        // Create time pointing at the title
+        let mut stream = STREAM;
+        stream[SECTION + CREATE_DTM_ENTRY + 4..SECTION + CREATE_DTM_ENTRY + 8]
+            .copy_from_slice(&0x98u32.to_le_bytes());
+        assert_eq!(
+            parse_summary_information(&stream),
+            Err(malformed(
+                SECTION + 0x98,
+                "property type is not VT_FILETIME"
+            ))
+        );
+
+        let mut stream = STREAM;
+        stream[SECTION + 0x153] = 0x80;
+        assert_eq!(
+            parse_summary_information(&stream),
+            Err(malformed(SECTION + 0x14C, "negative FILETIME"))
+        );
+
+        assert_eq!(
+            parse_summary_information(&STREAM[..SECTION + 0x10]),
+            Err(malformed(SECTION + 0x10, "truncated property table"))
+        );
+        assert_eq!(
+            parse_summary_information(&STREAM[..0x2F]),
+            Err(malformed(0x2F, "truncated header"))
+        );
+    }
+}
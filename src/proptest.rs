@@ -0,0 +1,122 @@
+//! proptest strategies for FileTime
+//!
+//! `any::<FileTime>()` mixes uniformly drawn values with
+//! [`near_boundaries`], so edge cases show up without filtering raw integers.
+//! All strategies shrink towards the FILETIME-Epoch.
+//! ```
+//! use filetime_type::FileTime;
+//! use proptest::prelude::*;
+//! use proptest::test_runner::TestRunner;
+//!
+//! TestRunner::default()
+//!     .run(&any::<FileTime>(), |ft| {
+//!         let raw: [u8; 8] = ft.into();
+//!         prop_assert_eq!(FileTime::from(raw), ft);
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+use crate::FileTime;
+use ::proptest::prelude::*;
+use core::ops::Range;
+
+const TICKS_PER_SECOND: i64 = FileTime::HUNDREDS_OF_NANOSECONDS;
+
+/// Uniformly distributed over the complete FILETIME range
+pub fn any_valid() -> impl Strategy<Value = FileTime> {
+    (0..=i64::MAX).prop_map(FileTime::from_i64)
+}
+
+/// Uniformly distributed within `range`, the end is excluded
+///
+/// Panics when the strategy is used on an empty range, like an integer range would.
+pub fn in_range(range: Range<FileTime>) -> impl Strategy<Value = FileTime> {
+    (range.start.filetime()..range.end.filetime()).prop_map(FileTime::from_i64)
+}
+
+/// Edge values only: the FILETIME-Epoch, the Unix-Epoch, [`FileTime::MAX`]
+/// and a few ticks around them, or a whole second give or take one tick
+pub fn near_boundaries() -> impl Strategy<Value = FileTime> {
+    let anchor = prop_oneof![Just(0), Just(FileTime::EPOCH_AS_FILETIME), Just(i64::MAX),];
+    let whole_second = (0..=i64::MAX / TICKS_PER_SECOND).prop_map(|secs| secs * TICKS_PER_SECOND);
+    (prop_oneof![anchor, whole_second], -3i64..=3)
+        .prop_map(|(ticks, offset)| FileTime::from_i64(ticks.saturating_add(offset).max(0)))
+}
+
+impl Arbitrary for FileTime {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<FileTime>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![any_valid(), near_boundaries()].boxed()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::proptest::strategy::ValueTree;
+    use ::proptest::test_runner::TestRunner;
+
+    proptest! {
+        #[test]
+        fn bytes_roundtrip(ft in any::<FileTime>()) {
+            let raw: [u8; 8] = ft.into();
+            prop_assert_eq!(FileTime::from(raw), ft);
+        }
+
+        #[test]
+        fn datetime_roundtrip(ft in any::<FileTime>()) {
+            prop_assert_eq!(FileTime::from_datetime(ft.to_datetime()), ft);
+        }
+
+        #[test]
+        fn in_range_bounds(
+            (start, end, ft) in (0..i64::MAX / 2, 1..=1_000_000_000i64).prop_flat_map(|(start, len)| {
+                let start = FileTime::from_i64(start);
+                let end = FileTime::from_i64(start.filetime() + len);
+                (Just(start), Just(end), in_range(start..end))
+            })
+        ) {
+            prop_assert!(start <= ft && ft < end);
+        }
+
+        #[test]
+        fn near_boundaries_are_edges(ft in near_boundaries()) {
+            let ticks = ft.filetime();
+            let near = |anchor: i64| (ticks - anchor).abs() <= 3;
+            let sub_second = ticks % TICKS_PER_SECOND;
+            prop_assert!(
+                near(0)
+                    || near(FileTime::EPOCH_AS_FILETIME)
+                    || i64::MAX - ticks <= 3
+                    || sub_second <= 3
+                    || sub_second >= TICKS_PER_SECOND - 3
+            );
+        }
+    }
+
+    #[test]
+    fn boundaries_are_generated() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = any::<FileTime>();
+        let samples: Vec<FileTime> = (0..2000)
+            .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+            .collect();
+        for anchor in [
+            FileTime::MIN,
+            FileTime::from_i64(FileTime::EPOCH_AS_FILETIME),
+            FileTime::MAX,
+        ] {
+            assert!(samples.contains(&anchor), "{:?}", anchor);
+        }
+    }
+
+    #[test]
+    fn shrinks_towards_epoch() {
+        let mut runner = TestRunner::deterministic();
+        let mut tree = any_valid().new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+        assert_eq!(tree.current(), FileTime::MIN);
+    }
+}
@@ -0,0 +1,137 @@
+//! Conversion from/to the protobuf well-known type `google.protobuf.Timestamp`
+//!
+//! Timestamp is valid from 0001-01-01T00:00:00Z to 9999-12-31T23:59:59.999999999Z.
+//! Timestamps before the FILETIME-Epoch fail with [`FileTimeError::BeforeEpoch`],
+//! FileTimes after 9999 with [`FileTimeError::OutOfRange`].
+//! Sub-tick nanoseconds (below 100ns) are truncated.
+use crate::{FileTime, FileTimeError};
+use prost_types::Timestamp;
+use std::convert::TryFrom;
+
+/// Seconds between the FILETIME-Epoch and the Unix-Epoch
+const EPOCH_AS_UNIX_SECONDS: i64 = -11644473600;
+/// 0001-01-01T00:00:00Z
+const MIN_TIMESTAMP_SECONDS: i64 = -62135596800;
+/// 9999-12-31T23:59:59Z
+const MAX_TIMESTAMP_SECONDS: i64 = 253402300799;
+
+impl TryFrom<Timestamp> for FileTime {
+    type Error = FileTimeError;
+
+    /// Denormal values (`nanos` outside of 0..1e9) are normalized first
+    fn try_from(mut ts: Timestamp) -> Result<Self, Self::Error> {
+        ts.normalize();
+        if ts.seconds < MIN_TIMESTAMP_SECONDS || ts.seconds > MAX_TIMESTAMP_SECONDS {
+            return Err(FileTimeError::OutOfRange("google.protobuf.Timestamp"));
+        }
+        FileTime::from_ticks_i128(
+            (i128::from(ts.seconds) - i128::from(EPOCH_AS_UNIX_SECONDS))
+                * i128::from(FileTime::HUNDREDS_OF_NANOSECONDS)
+                + i128::from(ts.nanos / 100),
+        )
+    }
+}
+
+impl TryFrom<FileTime> for Timestamp {
+    type Error = FileTimeError;
+
+    fn try_from(ft: FileTime) -> Result<Self, Self::Error> {
+        let seconds = ft.seconds() + EPOCH_AS_UNIX_SECONDS;
+        if seconds > MAX_TIMESTAMP_SECONDS {
+            return Err(FileTimeError::OutOfRange("google.protobuf.Timestamp"));
+        }
+        Ok(Timestamp {
+            seconds,
+            nanos: ft.nanoseconds() as i32,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let ft = FileTime::from_i64(128930364000001234);
+        let ts = Timestamp::try_from(ft).unwrap();
+        assert_eq!(
+            ts,
+            Timestamp {
+                seconds: 1248562800,
+                nanos: 123400
+            }
+        );
+        assert_eq!(FileTime::try_from(ts), Ok(ft));
+
+        let ts = Timestamp::try_from(FileTime::from_i64(0)).unwrap();
+        assert_eq!(ts.seconds, EPOCH_AS_UNIX_SECONDS);
+        assert_eq!(FileTime::try_from(ts), Ok(FileTime::from_i64(0)));
+    }
+
+    #[test]
+    fn truncate_nanos() {
+        let ts = Timestamp {
+            seconds: 1248562800,
+            nanos: 123499,
+        };
+        assert_eq!(
+            FileTime::try_from(ts),
+            Ok(FileTime::from_i64(128930364000001234))
+        );
+    }
+
+    #[test]
+    fn normalize() {
+        // 1 second - 100ns
+        let ts = Timestamp {
+            seconds: 1248562801,
+            nanos: -100,
+        };
+        assert_eq!(
+            FileTime::try_from(ts),
+            Ok(FileTime::from_i64(128930364009999999))
+        );
+
+        let ts = Timestamp {
+            seconds: 1248562799,
+            nanos: 1_000_000_100,
+        };
+        assert_eq!(
+            FileTime::try_from(ts),
+            Ok(FileTime::from_i64(128930364000000001))
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        let before_epoch = Timestamp {
+            seconds: EPOCH_AS_UNIX_SECONDS,
+            nanos: -1,
+        };
+        assert_eq!(
+            FileTime::try_from(before_epoch),
+            Err(FileTimeError::BeforeEpoch)
+        );
+
+        let invalid = Timestamp {
+            seconds: MAX_TIMESTAMP_SECONDS + 1,
+            nanos: 0,
+        };
+        assert_eq!(
+            FileTime::try_from(invalid),
+            Err(FileTimeError::OutOfRange("google.protobuf.Timestamp"))
+        );
+
+        // 9999-12-31T23:59:59.9999999Z is the last representable instant
+        let last = FileTime::from_i64(
+            (MAX_TIMESTAMP_SECONDS - EPOCH_AS_UNIX_SECONDS) * 10_000_000 + 9_999_999,
+        );
+        assert!(Timestamp::try_from(last).is_ok());
+        let after = FileTime::from_i64(last.filetime() + 1);
+        assert_eq!(
+            Timestamp::try_from(after),
+            Err(FileTimeError::OutOfRange("google.protobuf.Timestamp"))
+        );
+    }
+}
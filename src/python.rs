@@ -0,0 +1,161 @@
+//! PyO3 conversions from/to Python `datetime.datetime`
+//!
+//! A FileTime is handed to Python as timezone-aware `datetime.datetime` with
+//! `tzinfo=datetime.timezone.utc`. Python datetimes only carry microsecond
+//! precision, the last tick digit (100ns) is truncated. Pass
+//! [`FileTime::filetime()`] as plain `int` for lossless transfer. Raw values
+//! above [`FileTime::MAX`] have no date and raise `OverflowError`.
+//!
+//! On the way in, aware datetimes of any timezone and plain `int` tick values
+//! are accepted. Naive datetimes are rejected with `ValueError`, as there is
+//! no way to tell which timezone they were meant in.
+use crate::{FileTime, FileTimeError};
+use chrono::{DateTime, FixedOffset, Utc};
+use pyo3::exceptions::{PyOverflowError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDateTime, PyTzInfoAccess};
+
+impl From<FileTimeError> for PyErr {
+    fn from(err: FileTimeError) -> Self {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+impl<'py> IntoPyObject<'py> for FileTime {
+    type Target = PyDateTime;
+    type Output = Bound<'py, PyDateTime>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        self.to_datetime_checked()
+            .map_err(|err| PyOverflowError::new_err(err.to_string()))?
+            .into_pyobject(py)
+    }
+}
+
+impl<'py> IntoPyObject<'py> for &FileTime {
+    type Target = PyDateTime;
+    type Output = Bound<'py, PyDateTime>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        (*self).into_pyobject(py)
+    }
+}
+
+impl<'py> FromPyObject<'_, 'py> for FileTime {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, 'py, PyAny>) -> PyResult<Self> {
+        if let Ok(dt) = obj.cast::<PyDateTime>() {
+            if dt.get_tzinfo().is_none() {
+                return Err(PyValueError::new_err(
+                    "naive datetime is not supported, attach a tzinfo",
+                ));
+            }
+            let dt: DateTime<FixedOffset> = dt.extract()?;
+            return Ok(FileTime::from_datetime_checked(dt.with_timezone(&Utc))?);
+        }
+        if let Ok(ticks) = obj.extract::<i64>() {
+            return Ok(FileTime::from_i64_checked(ticks)?);
+        }
+        Err(PyTypeError::new_err(
+            "expected datetime.datetime or int (raw FILETIME ticks)",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pyo3::ffi::c_str;
+    use pyo3::types::PyDict;
+
+    fn eval<'py>(py: Python<'py>, code: &std::ffi::CStr) -> Bound<'py, PyAny> {
+        let globals = PyDict::new(py);
+        py.run(c_str!("import datetime"), Some(&globals), None)
+            .unwrap();
+        py.eval(code, Some(&globals), None).unwrap()
+    }
+
+    #[test]
+    fn datetime_roundtrip() {
+        Python::initialize();
+        Python::attach(|py| {
+            let ft = FileTime::from_i64(128930364000001001);
+            let obj = ft.into_pyobject(py).unwrap();
+            let iso: String = obj.call_method0("isoformat").unwrap().extract().unwrap();
+            assert_eq!(iso, "2009-07-25T23:00:00.000100+00:00");
+
+            // Last tick digit is truncated
+            let back: FileTime = obj.extract().unwrap();
+            assert_eq!(back, FileTime::from_i64(128930364000001000));
+        });
+    }
+
+    #[test]
+    fn max_raw() {
+        Python::initialize();
+        Python::attach(|py| {
+            let err = FileTime::MAX_RAW.into_pyobject(py).unwrap_err();
+            assert!(err.is_instance_of::<PyOverflowError>(py));
+        });
+    }
+
+    #[test]
+    fn from_aware_datetime() {
+        Python::initialize();
+        Python::attach(|py| {
+            let obj = eval(
+                py,
+                c_str!("datetime.datetime(2009, 7, 26, 1, 0, 0, 100, tzinfo=datetime.timezone(datetime.timedelta(hours=2)))"),
+            );
+            assert_eq!(
+                obj.extract::<FileTime>().unwrap(),
+                FileTime::from_i64(128930364000001000)
+            );
+        });
+    }
+
+    #[test]
+    fn from_int() {
+        Python::initialize();
+        Python::attach(|py| {
+            let obj = 128930364000001001_i64.into_pyobject(py).unwrap();
+            assert_eq!(
+                obj.extract::<FileTime>().unwrap(),
+                FileTime::from_i64(128930364000001001)
+            );
+
+            let err = (-1_i64).into_pyobject(py).unwrap().extract::<FileTime>();
+            assert!(err.unwrap_err().is_instance_of::<PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn from_invalid() {
+        Python::initialize();
+        Python::attach(|py| {
+            let naive = eval(py, c_str!("datetime.datetime(2009, 7, 25, 23, 0, 0)"));
+            assert!(naive
+                .extract::<FileTime>()
+                .unwrap_err()
+                .is_instance_of::<PyValueError>(py));
+
+            let before_epoch = eval(
+                py,
+                c_str!("datetime.datetime(1600, 12, 31, tzinfo=datetime.timezone.utc)"),
+            );
+            assert!(before_epoch
+                .extract::<FileTime>()
+                .unwrap_err()
+                .is_instance_of::<PyValueError>(py));
+
+            let text = eval(py, c_str!("'2009-07-25'"));
+            assert!(text
+                .extract::<FileTime>()
+                .unwrap_err()
+                .is_instance_of::<PyTypeError>(py));
+        });
+    }
+}
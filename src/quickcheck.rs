@@ -0,0 +1,84 @@
+//! quickcheck Arbitrary for FileTime
+//!
+//! Values are drawn uniformly from the complete FILETIME range, one in eight
+//! is taken from a fixed set of boundary values instead. Shrinking halves the
+//! tick count towards the FILETIME-Epoch.
+use crate::FileTime;
+use ::quickcheck::{Arbitrary, Gen};
+
+const BOUNDARIES: [i64; 8] = [
+    0,
+    1,
+    FileTime::HUNDREDS_OF_NANOSECONDS - 1,
+    FileTime::HUNDREDS_OF_NANOSECONDS,
+    FileTime::EPOCH_AS_FILETIME - 1,
+    FileTime::EPOCH_AS_FILETIME,
+    i64::MAX - 1,
+    i64::MAX,
+];
+
+impl Arbitrary for FileTime {
+    fn arbitrary(g: &mut Gen) -> Self {
+        if u8::arbitrary(g) % 8 == 0 {
+            // The slice is not empty
+            return FileTime::from_i64(*g.choose(&BOUNDARIES).unwrap());
+        }
+        FileTime::from_i64((u64::arbitrary(g) >> 1) as i64)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let ticks = self.filetime();
+        if ticks == 0 {
+            return ::quickcheck::empty_shrinker();
+        }
+        let halves = std::iter::successors(Some(ticks / 2), |t| (*t > 0).then_some(t / 2));
+        Box::new(halves.map(FileTime::from_i64))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::quickcheck::QuickCheck;
+
+    #[test]
+    fn bytes_roundtrip() {
+        fn prop(ft: FileTime) -> bool {
+            let raw: [u8; 8] = ft.into();
+            FileTime::from(raw) == ft
+        }
+        QuickCheck::new().quickcheck(prop as fn(FileTime) -> bool);
+    }
+
+    #[test]
+    fn ordering_matches_ticks() {
+        fn prop(a: FileTime, b: FileTime) -> bool {
+            a.cmp(&b) == a.filetime().cmp(&b.filetime())
+        }
+        QuickCheck::new().quickcheck(prop as fn(FileTime, FileTime) -> bool);
+    }
+
+    #[test]
+    fn boundaries_are_generated() {
+        let mut g = Gen::new(100);
+        let samples: Vec<i64> = (0..2000)
+            .map(|_| FileTime::arbitrary(&mut g).filetime())
+            .collect();
+        assert!(samples.contains(&0));
+        assert!(samples.contains(&i64::MAX));
+        // The uniform part reaches into the upper half of the range
+        assert!(samples
+            .iter()
+            .any(|t| *t > i64::MAX / 2 && *t < i64::MAX - 1));
+    }
+
+    #[test]
+    fn shrink_halves() {
+        let shrunk: Vec<i64> = FileTime::from_i64(100)
+            .shrink()
+            .map(|ft| ft.filetime())
+            .collect();
+        assert_eq!(shrunk, [50, 25, 12, 6, 3, 1, 0]);
+        assert_eq!(FileTime::MIN.shrink().count(), 0);
+    }
+}
@@ -0,0 +1,157 @@
+//! rand sampling: `rng.gen::<FileTime>()` and `rng.gen_range(start..end)`
+use crate::FileTime;
+use ::rand::distributions::uniform::{SampleBorrow, SampleUniform, UniformInt, UniformSampler};
+use ::rand::distributions::{Distribution, Standard};
+use ::rand::Rng;
+
+/// Uniform over the FILETIMEs with a date, `0..=i64::MAX` ticks
+///
+/// Use `gen_range(FileTime::MIN..=FileTime::MAX_RAW)` for every raw value.
+impl Distribution<FileTime> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> FileTime {
+        FileTime::from_u64(rng.gen::<u64>() >> 1)
+    }
+}
+
+/// Sampler behind `Uniform<FileTime>` and `gen_range`, uniform over the raw ticks
+///
+/// Empty and inverted ranges panic like they do for integers.
+#[derive(Debug, Clone, Copy)]
+pub struct UniformFileTime(UniformInt<u64>);
+
+impl UniformSampler for UniformFileTime {
+    type X = FileTime;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self(UniformInt::<u64>::new(
+            low.borrow().as_u64(),
+            high.borrow().as_u64(),
+        ))
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self(UniformInt::<u64>::new_inclusive(
+            low.borrow().as_u64(),
+            high.borrow().as_u64(),
+        ))
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        FileTime::from_u64(self.0.sample(rng))
+    }
+
+    fn sample_single<R: Rng + ?Sized, B1, B2>(low: B1, high: B2, rng: &mut R) -> Self::X
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        FileTime::from_u64(UniformInt::<u64>::sample_single(
+            low.borrow().as_u64(),
+            high.borrow().as_u64(),
+            rng,
+        ))
+    }
+
+    fn sample_single_inclusive<R: Rng + ?Sized, B1, B2>(low: B1, high: B2, rng: &mut R) -> Self::X
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        FileTime::from_u64(UniformInt::<u64>::sample_single_inclusive(
+            low.borrow().as_u64(),
+            high.borrow().as_u64(),
+            rng,
+        ))
+    }
+}
+
+impl SampleUniform for FileTime {
+    type Sampler = UniformFileTime;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::rand::distributions::Uniform;
+    use ::rand::rngs::StdRng;
+    use ::rand::SeedableRng;
+
+    fn rng() -> StdRng {
+        StdRng::seed_from_u64(0x0F11_E710)
+    }
+
+    #[test]
+    fn standard_covers_range() {
+        let mut rng = rng();
+        let samples: Vec<FileTime> = (0..1000).map(|_| rng.gen()).collect();
+        assert!(samples.iter().any(|ft| ft.filetime() < i64::MAX / 2));
+        assert!(samples.iter().any(|ft| ft.filetime() > i64::MAX / 2));
+    }
+
+    #[test]
+    fn gen_range_bounds() {
+        let mut rng = rng();
+        let start = FileTime::from_i64(128930364000000000);
+        let end = FileTime::from_i64(128930364000000010);
+        let mid = FileTime::from_i64(128930364000000005);
+        let (mut low, mut high) = (0, 0);
+        for _ in 0..2000 {
+            let ft = rng.gen_range(start..end);
+            assert!(start <= ft && ft < end);
+            if ft < mid {
+                low += 1;
+            } else {
+                high += 1;
+            }
+        }
+        // Each half holds five of the ten ticks
+        assert!((800..=1200).contains(&low), "{} / {}", low, high);
+
+        let inclusive: Vec<FileTime> = (0..200).map(|_| rng.gen_range(start..=end)).collect();
+        assert!(inclusive.iter().all(|ft| start <= *ft && *ft <= end));
+        assert!(inclusive.contains(&end));
+        assert_eq!(rng.gen_range(end..=end), end);
+    }
+
+    #[test]
+    fn uniform_bounds() {
+        let mut rng = rng();
+        let start = FileTime::MIN;
+        let end = FileTime::from_i64(3);
+        let uniform = Uniform::new(start, end);
+        let samples: Vec<FileTime> = (0..100).map(|_| uniform.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|ft| *ft < end));
+        assert!(samples.contains(&FileTime::from_i64(2)));
+        let uniform = Uniform::new_inclusive(start, FileTime::MAX);
+        assert!((0..100).all(|_| uniform.sample(&mut rng) <= FileTime::MAX));
+
+        // Raw values above FileTime::MAX
+        let uniform = Uniform::new_inclusive(FileTime::MAX, FileTime::MAX_RAW);
+        assert!((0..100).any(|_| uniform.sample(&mut rng) > FileTime::MAX));
+        assert_eq!(
+            rng.gen_range(FileTime::MAX_RAW..=FileTime::MAX_RAW),
+            FileTime::MAX_RAW
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_range() {
+        let ft = FileTime::from_i64(1);
+        rng().gen_range(ft..ft);
+    }
+
+    #[test]
+    #[should_panic]
+    fn inverted_uniform() {
+        Uniform::new(FileTime::from_i64(2), FileTime::from_i64(1));
+    }
+}
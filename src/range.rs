@@ -0,0 +1,423 @@
+//! Half-open ranges of FILETIME instants and stepping through them
+use crate::{FileTime, FileTimeDelta, FileTimeError};
+use core::convert::TryFrom;
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+/// Half-open range of instants, `start <= t < end`
+///
+/// The start is inclusive and the end is exclusive, so two ranges sharing an
+/// endpoint (`a.end() == b.start()`) are adjacent but do not overlap.
+/// A range with `start == end` is empty: it contains no instant and overlaps
+/// no other range, not even one that spans its endpoints.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct FileTimeRange {
+    start: FileTime,
+    end: FileTime,
+}
+
+impl FileTimeRange {
+    /// Construct the range `start..end`
+    ///
+    /// Fails with [`FileTimeError::InvalidRange`] if `start` lies after `end`.
+    /// ```
+    /// use filetime_type::{FileTime, FileTimeError, FileTimeRange};
+    ///
+    /// let boot = FileTime::from_i64(132000000000000000);
+    /// let shutdown = FileTime::from_i64(132000360000000000);
+    /// assert!(FileTimeRange::new(boot, shutdown).is_ok());
+    /// assert_eq!(FileTimeRange::new(shutdown, boot), Err(FileTimeError::InvalidRange));
+    /// ```
+    pub fn new(start: FileTime, end: FileTime) -> Result<Self, FileTimeError> {
+        if start > end {
+            return Err(FileTimeError::InvalidRange);
+        }
+        Ok(Self { start, end })
+    }
+
+    /// Inclusive start of the range
+    pub fn start(&self) -> FileTime {
+        self.start
+    }
+
+    /// Exclusive end of the range
+    pub fn end(&self) -> FileTime {
+        self.end
+    }
+
+    /// Whether the range contains no instant, i.e. `start == end`
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Length of the range, never negative
+    ///
+    /// `None` if it does not fit a [`FileTimeDelta`], which takes a range
+    /// reaching above [`FileTime::MAX`].
+    /// ```
+    /// use filetime_type::{FileTime, FileTimeDelta, FileTimeRange};
+    ///
+    /// let r = FileTimeRange::new(FileTime::from_i64(10), FileTime::from_i64(25)).unwrap();
+    /// assert_eq!(r.duration(), Some(FileTimeDelta::from_ticks(15)));
+    /// let all = FileTimeRange::new(FileTime::MIN, FileTime::MAX_RAW).unwrap();
+    /// assert_eq!(all.duration(), None);
+    /// ```
+    pub fn duration(&self) -> Option<FileTimeDelta> {
+        self.end.checked_delta_since(self.start)
+    }
+
+    /// Whether `start <= ft < end`
+    pub fn contains(&self, ft: FileTime) -> bool {
+        self.start <= ft && ft < self.end
+    }
+
+    /// Whether both ranges share at least one instant
+    ///
+    /// Adjacent ranges and empty ranges never overlap.
+    pub fn overlaps(&self, other: &FileTimeRange) -> bool {
+        self.start.max(other.start) < self.end.min(other.end)
+    }
+
+    /// Instants contained in both ranges, `None` unless they [overlap](Self::overlaps)
+    pub fn intersection(&self, other: &FileTimeRange) -> Option<FileTimeRange> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        if start < end {
+            Some(Self { start, end })
+        } else {
+            None
+        }
+    }
+}
+
+/// Instants at a fixed step, returned by [`FileTime::iter_range`],
+/// [`FileTime::iter_range_inclusive`] and [`FileTimeRange::step_by`]
+///
+/// The number of remaining instants is always known, so `size_hint` is exact
+/// whenever it fits a `usize`. Like `Range<u64>` this is no
+/// `ExactSizeIterator`, since a range can hold more than `usize::MAX`
+/// instants: on 32 bit targets a 1 tick step over about 7 minutes is enough.
+#[derive(Debug, Clone)]
+pub struct FileTimeSteps {
+    next: u64,
+    step: i64,
+    remaining: u128,
+}
+
+impl FileTimeSteps {
+    /// Steps from `start` up to `last`, both included
+    fn new(
+        start: FileTime,
+        last: Option<FileTime>,
+        step: chrono::Duration,
+    ) -> Result<Self, FileTimeError> {
+        let step = match FileTimeDelta::from_duration(step) {
+            Ok(delta) => delta.as_ticks(),
+            // Larger than any range, only the start is visited
+            Err(_) if step > chrono::Duration::zero() => i64::MAX,
+            Err(_) => i64::MIN,
+        };
+        if step <= 0 {
+            return Err(FileTimeError::InvalidField {
+                field: "step",
+                reason: "must be positive",
+            });
+        }
+        let remaining = match last {
+            Some(last) if last >= start => {
+                u128::from((last.as_u64() - start.as_u64()) / step as u64) + 1
+            }
+            _ => 0,
+        };
+        Ok(Self {
+            next: start.as_u64(),
+            step,
+            remaining,
+        })
+    }
+}
+
+impl Iterator for FileTimeSteps {
+    type Item = FileTime;
+
+    fn next(&mut self) -> Option<FileTime> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let ft = FileTime::from_u64(self.next);
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            // Stays within the range, so cannot overflow
+            self.next += self.step as u64;
+        }
+        Some(ft)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining) {
+            Ok(n) => (n, Some(n)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl FusedIterator for FileTimeSteps {}
+
+impl FileTimeRange {
+    /// Instants from the start at a fixed step, not including the end
+    ///
+    /// Fails with [`FileTimeError::InvalidField`] for zero or negative steps,
+    /// steps below the 100ns tick count as zero.
+    pub fn step_by(&self, step: chrono::Duration) -> Result<FileTimeSteps, FileTimeError> {
+        FileTime::iter_range(self.start, self.end, step)
+    }
+}
+
+impl FileTime {
+    /// Instants `start`, `start + step`, ... before `end`, which is not included
+    ///
+    /// Empty if `end` does not lie after `start`. Fails with
+    /// [`FileTimeError::InvalidField`] for zero or negative steps, steps
+    /// below the 100ns tick count as zero.
+    /// ```
+    /// use chrono::Duration;
+    /// use filetime_type::FileTime;
+    ///
+    /// let start = FileTime::from_ymd_hms(2023, 5, 17, 0, 0, 0).unwrap();
+    /// let end = FileTime::from_ymd_hms(2023, 5, 18, 0, 0, 0).unwrap();
+    /// let hours = FileTime::iter_range(start, end, Duration::hours(1)).unwrap();
+    /// assert_eq!(hours.count(), 24);
+    /// ```
+    pub fn iter_range(
+        start: FileTime,
+        end: FileTime,
+        step: chrono::Duration,
+    ) -> Result<FileTimeSteps, FileTimeError> {
+        let last = end.as_u64().checked_sub(1).map(FileTime::from_u64);
+        FileTimeSteps::new(start, last, step)
+    }
+
+    /// Like [`FileTime::iter_range`], including `end` if a step lands on it
+    pub fn iter_range_inclusive(
+        start: FileTime,
+        end: FileTime,
+        step: chrono::Duration,
+    ) -> Result<FileTimeSteps, FileTimeError> {
+        FileTimeSteps::new(start, Some(end), step)
+    }
+}
+
+/// Panics if `range.start` lies after `range.end`, use [`FileTimeRange::new`] to handle that case.
+impl From<Range<FileTime>> for FileTimeRange {
+    fn from(range: Range<FileTime>) -> Self {
+        Self::new(range.start, range.end).expect("range start lies after its end")
+    }
+}
+
+impl From<FileTimeRange> for Range<FileTime> {
+    fn from(range: FileTimeRange) -> Self {
+        range.start..range.end
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ticks(steps: FileTimeSteps) -> Vec<i64> {
+        steps.map(|ft| ft.filetime()).collect()
+    }
+
+    fn step(ticks: i64) -> chrono::Duration {
+        chrono::Duration::nanoseconds(ticks * 100)
+    }
+
+    #[test]
+    fn iter_range_uneven() {
+        let (start, end) = (FileTime::from_i64(10), FileTime::from_i64(20));
+        let steps = FileTime::iter_range(start, end, step(3)).unwrap();
+        assert_eq!(steps.size_hint(), (4, Some(4)));
+        assert_eq!(ticks(steps), [10, 13, 16, 19]);
+        assert_eq!(
+            ticks(FileTime::iter_range_inclusive(start, end, step(3)).unwrap()),
+            [10, 13, 16, 19]
+        );
+        assert_eq!(
+            ticks(FileTime::iter_range(start, end, step(5)).unwrap()),
+            [10, 15]
+        );
+        assert_eq!(
+            ticks(FileTime::iter_range_inclusive(start, end, step(5)).unwrap()),
+            [10, 15, 20]
+        );
+        assert_eq!(ticks(range(10, 20).step_by(step(5)).unwrap()), [10, 15]);
+    }
+
+    #[test]
+    fn iter_range_large_step() {
+        let (start, end) = (FileTime::from_i64(10), FileTime::from_i64(20));
+        assert_eq!(
+            ticks(FileTime::iter_range(start, end, step(100)).unwrap()),
+            [10]
+        );
+        assert_eq!(
+            ticks(FileTime::iter_range(start, end, chrono::Duration::MAX).unwrap()),
+            [10]
+        );
+        assert_eq!(
+            ticks(FileTime::iter_range(start, start, step(1)).unwrap()),
+            Vec::<i64>::new()
+        );
+        assert_eq!(
+            ticks(FileTime::iter_range(end, start, step(1)).unwrap()),
+            Vec::<i64>::new()
+        );
+        assert_eq!(
+            ticks(FileTime::iter_range_inclusive(start, start, step(1)).unwrap()),
+            [10]
+        );
+    }
+
+    #[test]
+    fn iter_range_invalid_step() {
+        let err = Err(FileTimeError::InvalidField {
+            field: "step",
+            reason: "must be positive",
+        });
+        let (start, end) = (FileTime::from_i64(10), FileTime::from_i64(20));
+        assert_eq!(FileTime::iter_range(start, end, step(0)).map(ticks), err);
+        assert_eq!(FileTime::iter_range(start, end, step(-1)).map(ticks), err);
+        assert_eq!(
+            FileTime::iter_range(start, end, chrono::Duration::nanoseconds(99)).map(ticks),
+            err
+        );
+        assert_eq!(
+            FileTime::iter_range(start, end, chrono::Duration::MIN).map(ticks),
+            err
+        );
+    }
+
+    #[test]
+    fn iter_range_near_max() {
+        let start = FileTime::from_i64(i64::MAX - 10);
+        let mut steps = FileTime::iter_range_inclusive(start, FileTime::MAX, step(4)).unwrap();
+        assert_eq!(steps.size_hint(), (3, Some(3)));
+        assert_eq!(
+            steps.by_ref().last(),
+            Some(FileTime::from_i64(i64::MAX - 2))
+        );
+        assert_eq!(steps.next(), None);
+        assert_eq!(
+            ticks(FileTime::iter_range_inclusive(start, FileTime::MAX, step(5)).unwrap()),
+            [i64::MAX - 10, i64::MAX - 5, i64::MAX]
+        );
+        let all = FileTime::iter_range_inclusive(FileTime::MIN, FileTime::MAX, step(1)).unwrap();
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(all.size_hint(), (1 << 63, Some(1 << 63)));
+        #[cfg(target_pointer_width = "32")]
+        assert_eq!(all.size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn iter_range_full_u64() {
+        // 2^64 instants, one more than fits a u64
+        let mut all =
+            FileTime::iter_range_inclusive(FileTime::MIN, FileTime::MAX_RAW, step(1)).unwrap();
+        assert_eq!(all.size_hint(), (usize::MAX, None));
+        assert_eq!(all.next(), Some(FileTime::MIN));
+        assert_eq!(all.next(), Some(FileTime::from_u64(1)));
+        let start = FileTime::from_u64(u64::MAX - 1);
+        let tail = FileTime::iter_range_inclusive(start, FileTime::MAX_RAW, step(1)).unwrap();
+        assert_eq!(tail.size_hint(), (2, Some(2)));
+        let last: Vec<u64> = tail.map(|ft| ft.as_u64()).collect();
+        assert_eq!(last, [u64::MAX - 1, u64::MAX]);
+    }
+
+    fn range(start: i64, end: i64) -> FileTimeRange {
+        FileTimeRange::from(FileTime::from_i64(start)..FileTime::from_i64(end))
+    }
+
+    #[test]
+    fn endpoints() {
+        let r = range(10, 20);
+        assert!(r.contains(FileTime::from_i64(10)));
+        assert!(r.contains(FileTime::from_i64(19)));
+        assert!(!r.contains(FileTime::from_i64(20)));
+        assert!(!r.contains(FileTime::from_i64(9)));
+        assert_eq!(r.duration(), Some(FileTimeDelta::from_ticks(10)));
+        assert_eq!(
+            Range::from(r),
+            FileTime::from_i64(10)..FileTime::from_i64(20)
+        );
+        let raw =
+            |start, end| FileTimeRange::new(FileTime::from_u64(start), FileTime::from_u64(end));
+        assert_eq!(
+            raw(1 << 63, u64::MAX).unwrap().duration(),
+            Some(FileTimeDelta::MAX)
+        );
+        assert_eq!(raw(0, u64::MAX).unwrap().duration(), None);
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            FileTimeRange::new(FileTime::from_i64(2), FileTime::from_i64(1)),
+            Err(FileTimeError::InvalidRange)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_from_range() {
+        let _ = range(2, 1);
+    }
+
+    #[test]
+    fn adjacent() {
+        let a = range(10, 20);
+        let b = range(20, 30);
+        assert!(!a.overlaps(&b));
+        assert!(!b.overlaps(&a));
+        assert_eq!(a.intersection(&b), None);
+    }
+
+    #[test]
+    fn nested() {
+        let outer = range(10, 40);
+        let inner = range(20, 30);
+        assert!(outer.overlaps(&inner));
+        assert!(inner.overlaps(&outer));
+        assert_eq!(outer.intersection(&inner), Some(inner));
+        assert_eq!(inner.intersection(&outer), Some(inner));
+        assert_eq!(outer.intersection(&outer), Some(outer));
+    }
+
+    #[test]
+    fn partial_overlap() {
+        let a = range(10, 25);
+        let b = range(20, 30);
+        assert!(a.overlaps(&b));
+        assert_eq!(a.intersection(&b), Some(range(20, 25)));
+        assert_eq!(b.intersection(&a), Some(range(20, 25)));
+    }
+
+    #[test]
+    fn disjoint() {
+        let a = range(10, 20);
+        let b = range(30, 40);
+        assert!(!a.overlaps(&b));
+        assert_eq!(a.intersection(&b), None);
+        assert_eq!(b.intersection(&a), None);
+    }
+
+    #[test]
+    fn empty() {
+        let empty = range(15, 15);
+        assert!(empty.is_empty());
+        assert_eq!(empty.duration(), Some(FileTimeDelta::ZERO));
+        assert!(!empty.contains(FileTime::from_i64(15)));
+        assert!(!empty.overlaps(&range(10, 20)));
+        assert!(!empty.overlaps(&empty));
+        assert_eq!(empty.intersection(&range(10, 20)), None);
+    }
+}
@@ -0,0 +1,242 @@
+//! Windows Recycle Bin `$I` metadata files
+//!
+//! Deleting a file to the Recycle Bin moves it to `$R<id>.<ext>` and writes
+//! its original name and deletion time to `$I<id>.<ext>` next to it:
+//!
+//! ```text
+//! 0x00  u64       version, 1 (Vista to 8.1) or 2 (10 and later)
+//! 0x08  u64       original file size
+//! 0x10  FILETIME  deletion time
+//! v1:
+//! 0x18  [u16; 260]  original path, UTF-16LE, NUL padded
+//! v2:
+//! 0x18  u32         path length in UTF-16 code units, with the terminating NUL
+//! 0x1C  [u16]       original path, UTF-16LE
+//! ```
+use crate::{FileTime, FileTimeError, PlausibilityWindow};
+
+const FORMAT: &str = "Recycle Bin $I file";
+const PATH_OFFSET_V1: usize = 0x18;
+const PATH_UNITS_V1: usize = 260;
+const PATH_OFFSET_V2: usize = 0x1C;
+
+/// Contents of a `$I` file
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct IFileRecord {
+    /// Format version, 1 or 2
+    pub version: u64,
+    /// Size of the deleted file in bytes
+    pub original_size: u64,
+    /// When the file was moved to the Recycle Bin
+    pub deleted_at: FileTime,
+    /// Full path the file was deleted from
+    pub original_path: String,
+}
+
+fn malformed(offset: usize, reason: &'static str) -> FileTimeError {
+    FileTimeError::Malformed {
+        format: FORMAT,
+        offset,
+        reason,
+    }
+}
+
+fn read_u64(buf: &[u8], offset: usize) -> u64 {
+    let mut raw = [0u8; 8];
+    raw.copy_from_slice(&buf[offset..offset + 8]);
+    u64::from_le_bytes(raw)
+}
+
+/// Decode UTF-16LE up to the first NUL, if any
+fn read_path(raw: &[u8], offset: usize) -> Result<String, FileTimeError> {
+    let units = raw
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .take_while(|&unit| unit != 0);
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map_err(|_| malformed(offset, "original path is not valid UTF-16"))
+}
+
+/// Parse a version 1 or 2 `$I` file
+///
+/// The deletion time must lie within [`PlausibilityWindow::forensic`],
+/// 1970 to 2100. Errors are [`FileTimeError::Malformed`] with distinct reasons:
+/// - `"truncated header"` / `"truncated original path"` for short input
+/// - `"unsupported version"` at offset 0
+/// - `"negative deletion time"` / `"implausible deletion time"` at offset 0x10
+/// - `"original path is not valid UTF-16"`
+/// ```
+/// use filetime_type::recyclebin::parse_i_file;
+///
+/// let mut buf = Vec::new();
+/// buf.extend_from_slice(&2u64.to_le_bytes());
+/// buf.extend_from_slice(&1234u64.to_le_bytes());
+/// buf.extend_from_slice(&133325856001234567i64.to_le_bytes());
+/// buf.extend_from_slice(&7u32.to_le_bytes());
+/// for unit in "C:\\a.t\0".encode_utf16() {
+///     buf.extend_from_slice(&unit.to_le_bytes());
+/// }
+/// let record = parse_i_file(&buf).unwrap();
+/// assert_eq!(record.original_path, "C:\\a.t");
+/// assert_eq!(record.deleted_at.filetime(), 133325856001234567);
+/// ```
+pub fn parse_i_file(buf: &[u8]) -> Result<IFileRecord, FileTimeError> {
+    if buf.len() < PATH_OFFSET_V1 {
+        return Err(malformed(buf.len(), "truncated header"));
+    }
+    let version = read_u64(buf, 0);
+    let original_size = read_u64(buf, 0x08);
+    let ticks = read_u64(buf, 0x10) as i64;
+    let deleted_at =
+        FileTime::from_i64_checked(ticks).map_err(|_| malformed(0x10, "negative deletion time"))?;
+    if !PlausibilityWindow::forensic().contains(deleted_at) {
+        return Err(malformed(0x10, "implausible deletion time"));
+    }
+
+    let original_path = match version {
+        1 => {
+            let end = PATH_OFFSET_V1 + PATH_UNITS_V1 * 2;
+            let raw = buf
+                .get(PATH_OFFSET_V1..end)
+                .ok_or_else(|| malformed(buf.len(), "truncated original path"))?;
+            read_path(raw, PATH_OFFSET_V1)?
+        }
+        2 => {
+            let mut len = [0u8; 4];
+            len.copy_from_slice(
+                buf.get(PATH_OFFSET_V1..PATH_OFFSET_V2)
+                    .ok_or_else(|| malformed(buf.len(), "truncated header"))?,
+            );
+            let end = u32::from_le_bytes(len) as usize * 2 + PATH_OFFSET_V2;
+            let raw = buf
+                .get(PATH_OFFSET_V2..end)
+                .ok_or_else(|| malformed(buf.len(), "truncated original path"))?;
+            read_path(raw, PATH_OFFSET_V2)?
+        }
+        _ => return Err(malformed(0, "unsupported version")),
+    };
+    Ok(IFileRecord {
+        version,
+        original_size,
+        deleted_at,
+        original_path,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DELETED: i64 = 133325856001234567;
+    const PATH: &str = r"C:\Users\alice\Documents\Quarterly report (final).docx";
+
+    // $I files built for these tests following the documented layouts, the
+    // values are made up rather than taken from a real Recycle Bin
+    fn v1_file(path: &str) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&1u64.to_le_bytes());
+        buf.extend_from_slice(&48_213u64.to_le_bytes());
+        buf.extend_from_slice(&DELETED.to_le_bytes());
+        for unit in path.encode_utf16() {
+            buf.extend_from_slice(&unit.to_le_bytes());
+        }
+        buf.resize(PATH_OFFSET_V1 + 520, 0);
+        buf
+    }
+
+    fn v2_file(path: &str) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&2u64.to_le_bytes());
+        buf.extend_from_slice(&48_213u64.to_le_bytes());
+        buf.extend_from_slice(&DELETED.to_le_bytes());
+        let units: Vec<u16> = path.encode_utf16().chain(Some(0)).collect();
+        buf.extend_from_slice(&(units.len() as u32).to_le_bytes());
+        for unit in units {
+            buf.extend_from_slice(&unit.to_le_bytes());
+        }
+        buf
+    }
+
+    #[test]
+    fn v1() {
+        let buf = v1_file(PATH);
+        assert_eq!(buf.len(), 544);
+        assert_eq!(
+            parse_i_file(&buf),
+            Ok(IFileRecord {
+                version: 1,
+                original_size: 48_213,
+                deleted_at: FileTime::from_i64(DELETED),
+                original_path: PATH.to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn v2() {
+        let buf = v2_file(PATH);
+        assert_eq!(buf.len(), 0x1C + (PATH.len() + 1) * 2);
+        let record = parse_i_file(&buf).unwrap();
+        assert_eq!(record.version, 2);
+        assert_eq!(record.original_size, 48_213);
+        assert_eq!(record.deleted_at, FileTime::from_i64(DELETED));
+        assert_eq!(record.original_path, PATH);
+
+        // Paths beyond MAX_PATH and outside of the BMP
+        let long = format!(r"\\?\D:\{}\🗑.txt", "sub\\".repeat(100));
+        assert_eq!(parse_i_file(&v2_file(&long)).unwrap().original_path, long);
+    }
+
+    #[test]
+    fn truncated() {
+        let v1 = v1_file(PATH);
+        assert_eq!(
+            parse_i_file(&v1[..0x17]),
+            Err(malformed(0x17, "truncated header"))
+        );
+        assert_eq!(
+            parse_i_file(&v1[..0x100]),
+            Err(malformed(0x100, "truncated original path"))
+        );
+        let v2 = v2_file(PATH);
+        assert_eq!(
+            parse_i_file(&v2[..0x1A]),
+            Err(malformed(0x1A, "truncated header"))
+        );
+        assert_eq!(
+            parse_i_file(&v2[..v2.len() - 1]),
+            Err(malformed(v2.len() - 1, "truncated original path"))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let mut buf = v2_file(PATH);
+        buf[0] = 3;
+        assert_eq!(parse_i_file(&buf), Err(malformed(0, "unsupported version")));
+
+        let mut buf = v2_file(PATH);
+        buf[0x17] = 0x80;
+        assert_eq!(
+            parse_i_file(&buf),
+            Err(malformed(0x10, "negative deletion time"))
+        );
+        for ticks in [0i64, 116444736000000000 - 1, 157469184000000000 + 1] {
+            let mut buf = v1_file(PATH);
+            buf[0x10..0x18].copy_from_slice(&ticks.to_le_bytes());
+            assert_eq!(
+                parse_i_file(&buf),
+                Err(malformed(0x10, "implausible deletion time"))
+            );
+        }
+
+        // Unpaired surrogate
+        let mut buf = v1_file(PATH);
+        buf[0x18..0x1A].copy_from_slice(&0xD800u16.to_le_bytes());
+        assert_eq!(
+            parse_i_file(&buf),
+            Err(malformed(0x18, "original path is not valid UTF-16"))
+        );
+    }
+}
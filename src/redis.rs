@@ -0,0 +1,117 @@
+//! redis `ToRedisArgs`/`FromRedisValue` support
+//!
+//! FileTime is written as the decimal string of its raw ticks, the way redis
+//! stores integers. Integer, bulk string and simple string replies holding
+//! such a number are accepted. For sorted set scores see
+//! [`FileTime::to_redis_score`].
+use crate::FileTime;
+use ::redis::{
+    FromRedisValue, NumericBehavior, ParsingError, RedisWrite, ToRedisArgs, ToSingleRedisArg, Value,
+};
+
+impl ToRedisArgs for FileTime {
+    fn write_redis_args<W: ?Sized + RedisWrite>(&self, out: &mut W) {
+        self.as_u64().write_redis_args(out)
+    }
+
+    fn describe_numeric_behavior(&self) -> NumericBehavior {
+        NumericBehavior::NumberIsInteger
+    }
+}
+
+impl ToSingleRedisArg for FileTime {}
+
+impl FromRedisValue for FileTime {
+    fn from_redis_value(v: Value) -> Result<Self, ParsingError> {
+        if let Value::Int(ticks) = v {
+            return FileTime::from_i64_checked(ticks)
+                .map_err(|_| format!("negative FILETIME ticks {}", ticks).into());
+        }
+        // Raw values above i64::MAX only come back as strings
+        u64::from_redis_value(v).map(FileTime::from_u64)
+    }
+}
+
+impl FileTime {
+    /// Raw ticks as f64, e.g. for `ZADD` scores
+    ///
+    /// Redis scores are doubles, which hold integers exactly only up to 2^53.
+    /// The result is the nearest f64: from 1829 to 2057 (ticks in 2^56..2^57)
+    /// it is at most 8 ticks (800ns) off, near [`FileTime::MAX`] at most 512
+    /// ticks (51.2µs). Ordering is preserved, but distinct instants may share
+    /// a score.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_i64(130139712831482830);
+    /// assert_eq!(ft.to_redis_score(), 130139712831482832.0);
+    /// ```
+    pub fn to_redis_score(&self) -> f64 {
+        self.as_u64() as f64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn to_args() {
+        let ft = FileTime::from_i64(130139712831482830);
+        assert_eq!(ft.to_redis_args(), vec![b"130139712831482830".to_vec()]);
+        assert_eq!(
+            ft.describe_numeric_behavior(),
+            NumericBehavior::NumberIsInteger
+        );
+    }
+
+    #[test]
+    fn from_value() {
+        let ft = FileTime::from_i64(130139712831482830);
+        assert_eq!(
+            FileTime::from_redis_value(Value::Int(130139712831482830)),
+            Ok(ft)
+        );
+        assert_eq!(
+            FileTime::from_redis_value(Value::BulkString(b"130139712831482830".to_vec())),
+            Ok(ft)
+        );
+        assert_eq!(
+            FileTime::from_redis_value(Value::SimpleString("130139712831482830".into())),
+            Ok(ft)
+        );
+        assert_eq!(Option::<FileTime>::from_redis_value(Value::Nil), Ok(None));
+        assert!(FileTime::from_redis_value(Value::Nil).is_err());
+
+        let never = FileTime::MAX_RAW;
+        assert_eq!(
+            never.to_redis_args(),
+            vec![b"18446744073709551615".to_vec()]
+        );
+        assert_eq!(
+            FileTime::from_redis_value(Value::BulkString(never.to_redis_args().remove(0))),
+            Ok(never)
+        );
+    }
+
+    #[test]
+    fn from_value_errors() {
+        let err = FileTime::from_redis_value(Value::Int(-1)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Incompatible type - negative FILETIME ticks -1"
+        );
+        assert!(FileTime::from_redis_value(Value::BulkString(b"yesterday".to_vec())).is_err());
+        assert!(FileTime::from_redis_value(Value::BulkString(b"1.5".to_vec())).is_err());
+    }
+
+    #[test]
+    fn score_precision() {
+        let ft = FileTime::from_i64(130139712831482830);
+        let error = ft.to_redis_score() as i64 - ft.filetime();
+        assert!(error.abs() <= 8);
+        assert_eq!(FileTime::MAX.to_redis_score(), 2f64.powi(63));
+        let later = FileTime::from_i64(ft.filetime() + 1);
+        assert!(later.to_redis_score() >= ft.to_redis_score());
+    }
+}
@@ -0,0 +1,256 @@
+//! Windows registry values holding FILETIMEs
+//!
+//! Timestamps are stored as 8 byte `REG_BINARY` or `REG_QWORD` values, or
+//! split into two `REG_DWORD` values holding the low and high halves.
+//!
+//! On Windows [`RegValue`] and [`RegType`] are re-exported from the `winreg`
+//! crate. Other platforms get stand-ins with the same shape, so code
+//! handling registry data (e.g. exported from hives) builds and can be
+//! tested anywhere.
+use crate::{FileTime, FileTimeError};
+use std::borrow::Cow;
+
+#[cfg(windows)]
+pub use ::winreg::{enums::RegType, RegValue};
+
+#[cfg(not(windows))]
+mod model {
+    use std::borrow::Cow;
+
+    /// Registry value types, mirroring `winreg::enums::RegType`
+    #[allow(non_camel_case_types, missing_docs)]
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum RegType {
+        REG_NONE = 0,
+        REG_SZ = 1,
+        REG_EXPAND_SZ = 2,
+        REG_BINARY = 3,
+        REG_DWORD = 4,
+        REG_DWORD_BIG_ENDIAN = 5,
+        REG_LINK = 6,
+        REG_MULTI_SZ = 7,
+        REG_RESOURCE_LIST = 8,
+        REG_FULL_RESOURCE_DESCRIPTOR = 9,
+        REG_RESOURCE_REQUIREMENTS_LIST = 10,
+        REG_QWORD = 11,
+    }
+
+    /// Raw registry value, mirroring `winreg::RegValue`
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct RegValue<'a> {
+        /// Value data as stored in the registry
+        pub bytes: Cow<'a, [u8]>,
+        /// Type of the value
+        pub vtype: RegType,
+    }
+}
+
+#[cfg(not(windows))]
+pub use model::{RegType, RegValue};
+
+const FORMAT: &str = "registry value";
+
+/// Value type used by [`FileTime::to_reg_value`]
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum RegFileTimeKind {
+    /// 8 bytes of `REG_BINARY` data
+    Binary,
+    /// `REG_QWORD`
+    Qword,
+}
+
+fn read_exact<const N: usize>(
+    value: &RegValue<'_>,
+    format: &'static str,
+) -> Result<[u8; N], FileTimeError> {
+    if value.bytes.len() != N {
+        return Err(FileTimeError::InvalidLength {
+            format,
+            expected: N,
+            actual: value.bytes.len(),
+        });
+    }
+    let mut raw = [0u8; N];
+    raw.copy_from_slice(&value.bytes);
+    Ok(raw)
+}
+
+fn read_dword(value: &RegValue<'_>) -> Result<u32, FileTimeError> {
+    match value.vtype {
+        RegType::REG_DWORD => read_exact(value, "REG_DWORD").map(u32::from_le_bytes),
+        _ => Err(FileTimeError::InvalidFormat {
+            format: FORMAT,
+            reason: "expected a REG_DWORD value",
+        }),
+    }
+}
+
+fn dword_value(dword: u32) -> RegValue<'static> {
+    RegValue {
+        bytes: Cow::Owned(dword.to_le_bytes().to_vec()),
+        vtype: RegType::REG_DWORD,
+    }
+}
+
+impl FileTime {
+    /// Read a FILETIME from a `REG_BINARY` or `REG_QWORD` value
+    ///
+    /// Both need exactly 8 bytes of little-endian data, other sizes fail with
+    /// [`FileTimeError::InvalidLength`] holding the observed length.
+    /// ```
+    /// use filetime_type::FileTime;
+    /// use filetime_type::registry::{RegType, RegValue};
+    ///
+    /// let value = RegValue {
+    ///     bytes: vec![0xCE, 0xEB, 0x7D, 0x1A, 0x61, 0x59, 0xCE, 0x01].into(),
+    ///     vtype: RegType::REG_BINARY,
+    /// };
+    /// assert_eq!(FileTime::from_reg_value(&value).unwrap().filetime(), 130139712831482830);
+    /// ```
+    pub fn from_reg_value(value: &RegValue<'_>) -> Result<FileTime, FileTimeError> {
+        let raw = match value.vtype {
+            RegType::REG_BINARY => read_exact(value, "REG_BINARY FILETIME")?,
+            RegType::REG_QWORD => read_exact(value, "REG_QWORD")?,
+            _ => {
+                return Err(FileTimeError::InvalidFormat {
+                    format: FORMAT,
+                    reason: "expected a REG_BINARY or REG_QWORD value",
+                })
+            }
+        };
+        Ok(FileTime::from_u64(u64::from_le_bytes(raw)))
+    }
+
+    /// Read a FILETIME split into two `REG_DWORD` values (`dwLowDateTime`, `dwHighDateTime`)
+    pub fn from_reg_dword_pair(
+        low: &RegValue<'_>,
+        high: &RegValue<'_>,
+    ) -> Result<FileTime, FileTimeError> {
+        Ok(FileTime::from_parts(read_dword(low)?, read_dword(high)?))
+    }
+
+    /// Encode as registry value of the given kind
+    pub fn to_reg_value(&self, kind: RegFileTimeKind) -> RegValue<'static> {
+        let vtype = match kind {
+            RegFileTimeKind::Binary => RegType::REG_BINARY,
+            RegFileTimeKind::Qword => RegType::REG_QWORD,
+        };
+        RegValue {
+            bytes: Cow::Owned(self.as_u64().to_le_bytes().to_vec()),
+            vtype,
+        }
+    }
+
+    /// Encode as two `REG_DWORD` values, low half first
+    pub fn to_reg_dword_pair(&self) -> (RegValue<'static>, RegValue<'static>) {
+        let (low, high) = self.to_parts();
+        (dword_value(low), dword_value(high))
+    }
+
+    /// Last write time of a registry key, as returned by `RegKey::query_info`
+    #[cfg(windows)]
+    pub fn from_key_last_write(
+        metadata: &::winreg::reg_key_metadata::RegKeyMetadata,
+    ) -> Result<FileTime, FileTimeError> {
+        let last_write = &metadata.last_write_time;
+        Ok(FileTime::from_parts(
+            last_write.dwLowDateTime,
+            last_write.dwHighDateTime,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TICKS: i64 = 130053136616000462;
+
+    fn value(bytes: &[u8], vtype: RegType) -> RegValue<'_> {
+        RegValue {
+            bytes: Cow::Borrowed(bytes),
+            vtype,
+        }
+    }
+
+    #[test]
+    fn binary_and_qword() {
+        let raw = TICKS.to_le_bytes();
+        for (kind, vtype) in &[
+            (RegFileTimeKind::Binary, RegType::REG_BINARY),
+            (RegFileTimeKind::Qword, RegType::REG_QWORD),
+        ] {
+            let ft = FileTime::from_reg_value(&value(&raw, vtype.clone())).unwrap();
+            assert_eq!(ft.filetime(), TICKS);
+            assert_eq!(ft.to_reg_value(*kind), value(&raw, vtype.clone()));
+        }
+    }
+
+    #[test]
+    fn wrong_length() {
+        assert_eq!(
+            FileTime::from_reg_value(&value(&[0; 12], RegType::REG_BINARY)),
+            Err(FileTimeError::InvalidLength {
+                format: "REG_BINARY FILETIME",
+                expected: 8,
+                actual: 12,
+            })
+        );
+        assert_eq!(
+            FileTime::from_reg_value(&value(&[0; 4], RegType::REG_QWORD)),
+            Err(FileTimeError::InvalidLength {
+                format: "REG_QWORD",
+                expected: 8,
+                actual: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn wrong_type() {
+        assert_eq!(
+            FileTime::from_reg_value(&value(&TICKS.to_le_bytes(), RegType::REG_SZ)),
+            Err(FileTimeError::InvalidFormat {
+                format: FORMAT,
+                reason: "expected a REG_BINARY or REG_QWORD value",
+            })
+        );
+    }
+
+    #[test]
+    fn above_max() {
+        let ft = FileTime::from_reg_value(&value(&[0xff; 8], RegType::REG_QWORD)).unwrap();
+        assert_eq!(ft, FileTime::MAX_RAW);
+        assert_eq!(
+            ft.to_reg_value(RegFileTimeKind::Qword),
+            value(&[0xff; 8], RegType::REG_QWORD)
+        );
+        let (low, high) = ft.to_reg_dword_pair();
+        assert_eq!(FileTime::from_reg_dword_pair(&low, &high), Ok(ft));
+    }
+
+    #[test]
+    fn dword_pair() {
+        let ft = FileTime::from_i64(TICKS);
+        let (low, high) = ft.to_reg_dword_pair();
+        assert_eq!(low.bytes.as_ref(), &TICKS.to_le_bytes()[..4]);
+        assert_eq!(high.bytes.as_ref(), &TICKS.to_le_bytes()[4..]);
+        assert_eq!(FileTime::from_reg_dword_pair(&low, &high), Ok(ft));
+
+        assert_eq!(
+            FileTime::from_reg_dword_pair(&low, &value(&[0; 8], RegType::REG_QWORD)),
+            Err(FileTimeError::InvalidFormat {
+                format: FORMAT,
+                reason: "expected a REG_DWORD value",
+            })
+        );
+        assert_eq!(
+            FileTime::from_reg_dword_pair(&value(&[0; 3], RegType::REG_DWORD), &high),
+            Err(FileTimeError::InvalidLength {
+                format: "REG_DWORD",
+                expected: 4,
+                actual: 3,
+            })
+        );
+    }
+}
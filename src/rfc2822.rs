@@ -0,0 +1,190 @@
+//! RFC 2822 / RFC 5322 date-time, as in the `Date:` header of email
+//!
+//! `[Tue, ]1 Jul 2003 10:52[:37] +0200`, parsed by chrono, plus the parts of
+//! the obsolete syntax (RFC 5322 4.3) that real mail still has: two and three
+//! digit years, the US zone names (`EST`, `PDT`, ...), `UT`/`GMT` and
+//! military letters are left to chrono, other alphabetic zones are taken as
+//! `-0000` (UTC, local offset unknown) as the RFC says. The weekday is
+//! advisory and ignored, even if it does not match the date.
+use crate::{FileTime, FileTimeError};
+use chrono::{DateTime, Datelike, Utc};
+
+const RFC_2822: &str = "RFC 2822 date-time";
+
+/// Drop a leading `day-of-week ","`, whatever day it names
+fn strip_weekday(s: &str) -> &str {
+    let s = s.trim_start();
+    match s.split_once(',') {
+        Some((day, rest)) if day.trim_end().bytes().all(|c| c.is_ascii_alphabetic()) => rest,
+        _ => s,
+    }
+}
+
+impl FileTime {
+    /// Parse an RFC 2822 date-time, e.g. from an email `Date:` header
+    ///
+    /// Seconds are optional. Comments after the zone, like `(CEST)`, are
+    /// ignored. Years before 1601 fail with [`FileTimeError::BeforeEpoch`],
+    /// malformed input with [`FileTimeError::InvalidFormat`].
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::parse_rfc2822("Tue, 1 Jul 2003 10:52:37 +0200").unwrap();
+    /// assert_eq!(ft, FileTime::from_ymd_hms(2003, 7, 1, 8, 52, 37).unwrap());
+    /// ```
+    pub fn parse_rfc2822(s: &str) -> Result<Self, FileTimeError> {
+        let invalid = FileTimeError::InvalidFormat {
+            format: RFC_2822,
+            reason: "expected [day, ]DD Mon YYYY hh:mm[:ss] zone",
+        };
+        let s = strip_weekday(s);
+        let dt = DateTime::parse_from_rfc2822(s).or_else(|err| {
+            // Retry with an unknown zone name replaced, it is the fifth token
+            let tokens: Vec<&str> = s.split_whitespace().collect();
+            match tokens.get(4) {
+                Some(zone) if zone.bytes().all(|c| c.is_ascii_alphabetic()) => {
+                    let mut tokens = tokens;
+                    tokens[4] = "-0000";
+                    DateTime::parse_from_rfc2822(&tokens.join(" "))
+                }
+                _ => Err(err),
+            }
+        });
+        let dt = dt.map_err(|_| invalid)?;
+        Self::from_datetime_checked(dt.with_timezone(&Utc))
+    }
+
+    /// Format as RFC 2822 date-time in UTC, e.g. `Tue, 1 Jul 2003 08:52:37 +0000`
+    ///
+    /// The format has whole seconds: sub-second ticks are truncated, so the
+    /// result parses back to the start of the second. Fails with
+    /// [`FileTimeError::OutOfRange`] after 9999, which the format cannot hold.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_i64(130139712831482830);
+    /// assert_eq!(ft.to_rfc2822().unwrap(), "Sat, 25 May 2013 16:01:23 +0000");
+    /// ```
+    pub fn to_rfc2822(&self) -> Result<String, FileTimeError> {
+        self.to_datetime_checked()
+            .ok()
+            .filter(|dt| dt.year() <= 9999)
+            .map(|dt| dt.to_rfc2822())
+            .ok_or(FileTimeError::OutOfRange(RFC_2822))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> FileTime {
+        FileTime::from_ymd_hms(year, month, day, hour, min, sec).unwrap()
+    }
+
+    #[test]
+    fn modern() {
+        let expected = utc(2003, 7, 1, 8, 52, 37);
+        for s in [
+            "Tue, 1 Jul 2003 10:52:37 +0200",
+            "Tue, 01 Jul 2003 10:52:37 +0200",
+            "1 Jul 2003 10:52:37 +0200",
+            "  Tue,  1 Jul 2003 10:52:37 +0200 (CEST)",
+            "Tue, 1 Jul 2003 03:52:37 -0500",
+        ] {
+            assert_eq!(FileTime::parse_rfc2822(s), Ok(expected), "{}", s);
+        }
+        assert_eq!(
+            expected.to_rfc2822().unwrap(),
+            "Tue, 1 Jul 2003 08:52:37 +0000"
+        );
+        assert_eq!(
+            FileTime::parse_rfc2822("Tue, 1 Jul 2003 10:52 +0200"),
+            Ok(utc(2003, 7, 1, 8, 52, 0))
+        );
+
+        // Sub-second ticks are lost on output
+        let ft = FileTime::from_i64(130139712831482830);
+        assert_eq!(
+            FileTime::parse_rfc2822(&ft.to_rfc2822().unwrap()),
+            Ok(utc(2013, 5, 25, 16, 1, 23))
+        );
+        assert_eq!(
+            FileTime::MIN.to_rfc2822().unwrap(),
+            "Mon, 1 Jan 1601 00:00:00 +0000"
+        );
+        for ft in [utc(10000, 1, 1, 0, 0, 0), FileTime::MAX, FileTime::MAX_RAW] {
+            assert_eq!(ft.to_rfc2822(), Err(FileTimeError::OutOfRange(RFC_2822)));
+        }
+    }
+
+    #[test]
+    fn obsolete() {
+        let parse = FileTime::parse_rfc2822;
+        assert_eq!(
+            parse("Tue, 1 Jul 2003 03:52:37 EST"),
+            Ok(utc(2003, 7, 1, 8, 52, 37))
+        );
+        assert_eq!(
+            parse("Tue, 1 Jul 2003 04:52:37 EDT"),
+            Ok(utc(2003, 7, 1, 8, 52, 37))
+        );
+        assert_eq!(
+            parse("Thu, 1 Jul 99 10:52:37 GMT"),
+            Ok(utc(1999, 7, 1, 10, 52, 37))
+        );
+        assert_eq!(
+            parse("1 Jul 03 10:52:37 UT"),
+            Ok(utc(2003, 7, 1, 10, 52, 37))
+        );
+        assert_eq!(
+            parse("1 Jul 103 10:52:37 Z"),
+            Ok(utc(2003, 7, 1, 10, 52, 37))
+        );
+        // Unknown zone names count as -0000
+        assert_eq!(
+            parse("Tue, 1 Jul 2003 10:52:37 CEST"),
+            Ok(utc(2003, 7, 1, 10, 52, 37))
+        );
+    }
+
+    #[test]
+    fn advisory_weekday() {
+        let expected = Ok(utc(2003, 7, 1, 8, 52, 37));
+        assert_eq!(
+            FileTime::parse_rfc2822("Wed, 1 Jul 2003 10:52:37 +0200"),
+            expected
+        );
+        assert_eq!(
+            FileTime::parse_rfc2822("Tues, 1 Jul 2003 10:52:37 +0200"),
+            expected
+        );
+        assert_eq!(
+            FileTime::parse_rfc2822("Tue , 1 Jul 2003 10:52:37 +0200"),
+            expected
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let invalid = Err(FileTimeError::InvalidFormat {
+            format: RFC_2822,
+            reason: "expected [day, ]DD Mon YYYY hh:mm[:ss] zone",
+        });
+        for s in [
+            "",
+            "Tue, 1 Jul 2003",
+            "Tue, 1 Jul 2003 10:52:37",
+            "Tue, 32 Jul 2003 10:52:37 +0200",
+            "Tue, 1 Jly 2003 10:52:37 +0200",
+            "Tue, 1 Jul 2003 10:52:37 +02:00",
+            "2003-07-01T10:52:37+02:00",
+        ] {
+            assert_eq!(FileTime::parse_rfc2822(s), invalid, "{}", s);
+        }
+        assert_eq!(
+            FileTime::parse_rfc2822("1 Jan 1600 00:00:00 +0000"),
+            Err(FileTimeError::BeforeEpoch)
+        );
+    }
+}
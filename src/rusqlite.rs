@@ -0,0 +1,108 @@
+//! rusqlite `ToSql`/`FromSql` support
+//!
+//! FileTime is stored as `INTEGER` holding the raw FILETIME ticks
+//! (100ns intervals since 1601-01-01). Raw values above [`FileTime::MAX`]
+//! do not fit and fail to convert.
+//!
+//! Note that this is *not* the format used by Chrome / Chromium databases,
+//! which store microseconds since 1601-01-01. Read those as `i64` and convert
+//! with [`FileTime::from_webkit_micros`].
+use crate::FileTime;
+use ::rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+impl FromSql for FileTime {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Integer(ticks) => {
+                FileTime::from_i64_checked(ticks).map_err(|_| FromSqlError::OutOfRange(ticks))
+            }
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+impl ToSql for FileTime {
+    fn to_sql(&self) -> ::rusqlite::Result<ToSqlOutput<'_>> {
+        let ticks = self
+            .to_i64_checked()
+            .map_err(|err| ::rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        Ok(ToSqlOutput::from(ticks))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::rusqlite::{params, Connection};
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE files (id INTEGER PRIMARY KEY, mtime INTEGER)")
+            .unwrap();
+        conn
+    }
+
+    #[test]
+    fn roundtrip() {
+        let conn = setup();
+        let values = [
+            FileTime::from_i64(0),
+            FileTime::from_i64(128930364000001000),
+            FileTime::from_i64(i64::MAX),
+        ];
+        for (id, ft) in values.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO files (id, mtime) VALUES (?1, ?2)",
+                params![id as i64, ft],
+            )
+            .unwrap();
+        }
+
+        let mut stmt = conn.prepare("SELECT mtime FROM files ORDER BY id").unwrap();
+        let rows: Vec<FileTime> = stmt
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(rows, values);
+
+        let raw: i64 = conn
+            .query_row("SELECT mtime FROM files WHERE id = 1", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(raw, 128930364000001000);
+    }
+
+    #[test]
+    fn null() {
+        let conn = setup();
+        conn.execute("INSERT INTO files (id, mtime) VALUES (1, NULL)", [])
+            .unwrap();
+
+        let ft: Option<FileTime> = conn
+            .query_row("SELECT mtime FROM files", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(ft, None);
+
+        let err = conn
+            .query_row("SELECT mtime FROM files", [], |row| {
+                row.get::<_, FileTime>(0)
+            })
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ::rusqlite::Error::InvalidColumnType(0, _, ::rusqlite::types::Type::Null)
+        ));
+    }
+
+    #[test]
+    fn invalid() {
+        let err = FileTime::column_result(ValueRef::Integer(-1)).unwrap_err();
+        assert!(matches!(err, FromSqlError::OutOfRange(-1)));
+
+        let err = FileTime::column_result(ValueRef::Real(1.5)).unwrap_err();
+        assert!(matches!(err, FromSqlError::InvalidType));
+
+        let err = FileTime::column_result(ValueRef::Text(b"2009-07-25")).unwrap_err();
+        assert!(matches!(err, FromSqlError::InvalidType));
+    }
+}
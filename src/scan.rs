@@ -0,0 +1,210 @@
+//! Carving FILETIMEs out of unstructured data
+//!
+//! Slides an 8 byte window over a buffer and reports every value that
+//! decodes to an instant within a given range. Narrow ranges keep random
+//! data from matching: a range of 30 years matches about 1 in 2000 random
+//! 8 byte values.
+use crate::{FileTime, FileTimeRange};
+use core::iter::FusedIterator;
+
+/// Byte order of the values looked for by [`FileTime::scan`]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub enum Endianness {
+    /// Least significant byte first, as on Windows
+    Little,
+    /// Most significant byte first, as in network protocols
+    Big,
+}
+
+/// Iterator over `(offset, FileTime)` matches, see [`FileTime::scan`]
+#[derive(Debug, Clone)]
+pub struct FileTimeScan<'a> {
+    buf: &'a [u8],
+    offset: usize,
+    alignment: usize,
+    start: u64,
+    end: u64,
+    endianness: Endianness,
+}
+
+impl Iterator for FileTimeScan<'_> {
+    type Item = (usize, FileTime);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let buf = self.buf;
+        while let Some(chunk) = self
+            .offset
+            .checked_add(8)
+            .and_then(|end| buf.get(self.offset..end))
+        {
+            let offset = self.offset;
+            self.offset = offset.saturating_add(self.alignment);
+            let mut raw = [0u8; 8];
+            raw.copy_from_slice(chunk);
+            let ticks = match self.endianness {
+                Endianness::Little => u64::from_le_bytes(raw),
+                Endianness::Big => u64::from_be_bytes(raw),
+            };
+            if self.start <= ticks && ticks < self.end {
+                return Some((offset, FileTime { ticks }));
+            }
+        }
+        self.offset = self.buf.len();
+        None
+    }
+}
+
+impl FusedIterator for FileTimeScan<'_> {}
+
+impl FileTime {
+    /// Find values in `buf` whose instant lies within `window`
+    ///
+    /// Looks at every offset that is a multiple of `alignment`, 1 for
+    /// unaligned data and 8 for arrays or structures of 8 byte fields.
+    /// Matches may overlap at alignments below 8. Panics if `alignment` is 0.
+    /// ```
+    /// use filetime_type::{Endianness, FileTime, FileTimeRange};
+    ///
+    /// let mut buf = [0xAAu8; 32];
+    /// buf[3..11].copy_from_slice(&133325856001234567i64.to_le_bytes());
+    /// let window = FileTimeRange::new(
+    ///     FileTime::from_ymd_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+    ///     FileTime::from_ymd_hms(2030, 1, 1, 0, 0, 0).unwrap(),
+    /// )
+    /// .unwrap();
+    /// let found: Vec<_> = FileTime::scan(&buf, window, 1, Endianness::Little).collect();
+    /// assert_eq!(found, [(3, FileTime::from_i64(133325856001234567))]);
+    /// assert_eq!(FileTime::scan(&buf, window, 8, Endianness::Little).count(), 0);
+    /// ```
+    pub fn scan(
+        buf: &[u8],
+        window: FileTimeRange,
+        alignment: usize,
+        endianness: Endianness,
+    ) -> FileTimeScan<'_> {
+        assert!(alignment > 0, "alignment must not be zero");
+        FileTimeScan {
+            buf,
+            offset: 0,
+            alignment,
+            start: window.start().ticks,
+            end: window.end().ticks,
+            endianness,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PLANTED: [(usize, u64); 5] = [
+        (13, 133325856001234567),
+        (64, 128930364000000010),
+        (1001, 132601876135897932),
+        (4096, 126227808000000000),
+        (65_527, 130139712831482830),
+    ];
+
+    fn window() -> FileTimeRange {
+        FileTimeRange::new(
+            FileTime::from_ymd_hms(2000, 1, 1, 0, 0, 0).unwrap(),
+            FileTime::from_ymd_hms(2030, 1, 1, 0, 0, 0).unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// 64 KiB of xorshift noise with the `PLANTED` values written in `endianness`
+    fn noise(endianness: Endianness) -> [u8; 65_536] {
+        let mut buf = [0u8; 65_536];
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        for chunk in buf.chunks_exact_mut(8) {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            chunk.copy_from_slice(&state.to_le_bytes());
+        }
+        for &(offset, ticks) in &PLANTED {
+            let bytes = match endianness {
+                Endianness::Little => ticks.to_le_bytes(),
+                Endianness::Big => ticks.to_be_bytes(),
+            };
+            buf[offset..offset + 8].copy_from_slice(&bytes);
+        }
+        buf
+    }
+
+    fn is_planted(found: &(usize, FileTime)) -> bool {
+        PLANTED.contains(&(found.0, found.1.ticks))
+    }
+
+    #[test]
+    fn unaligned() {
+        for endianness in [Endianness::Little, Endianness::Big] {
+            let buf = noise(endianness);
+            let (planted, false_positives): (Vec<_>, Vec<_>) =
+                FileTime::scan(&buf, window(), 1, endianness).partition(is_planted);
+            assert_eq!(planted.len(), PLANTED.len(), "{:?}", endianness);
+            // About 5 in 10000 random values lie within 30 years
+            assert!(false_positives.len() < 100, "{:?}", false_positives.len());
+            assert!(false_positives.iter().all(|(_, ft)| window().contains(*ft)));
+        }
+    }
+
+    #[test]
+    fn aligned() {
+        let buf = noise(Endianness::Little);
+        let found: Vec<_> = FileTime::scan(&buf, window(), 8, Endianness::Little).collect();
+        let planted: Vec<_> = found.iter().filter(|f| is_planted(f)).collect();
+        // Only 64 and 4096 are multiples of 8
+        assert_eq!(
+            planted
+                .iter()
+                .map(|(offset, _)| *offset)
+                .collect::<Vec<_>>(),
+            [64, 4096]
+        );
+        assert!(found.len() - planted.len() < 20);
+        assert!(found.iter().all(|(offset, _)| offset % 8 == 0));
+    }
+
+    #[test]
+    fn edges() {
+        let ft = FileTime::from_ymd_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let end = FileTime::from_ymd_hms(2030, 1, 1, 0, 0, 0).unwrap();
+        let mut buf = [0u8; 16];
+        buf[8..].copy_from_slice(&ft.filetime().to_le_bytes());
+        let mut scan = FileTime::scan(&buf, window(), 1, Endianness::Little);
+        assert_eq!(scan.next(), Some((8, ft)));
+        assert_eq!(scan.next(), None);
+        assert_eq!(scan.next(), None);
+
+        // The range end is exclusive
+        buf[8..].copy_from_slice(&end.filetime().to_le_bytes());
+        assert_eq!(
+            FileTime::scan(&buf, window(), 1, Endianness::Little).count(),
+            0
+        );
+        // Shorter than one value
+        assert_eq!(
+            FileTime::scan(&buf[..7], window(), 1, Endianness::Little).count(),
+            0
+        );
+        // All ones is FileTime::MAX_RAW, which the exclusive end keeps out of every range
+        let all = FileTimeRange::new(FileTime::MIN, FileTime::MAX).unwrap();
+        assert_eq!(
+            FileTime::scan(&[0xFF; 8], all, 1, Endianness::Big).count(),
+            0
+        );
+        assert_eq!(
+            FileTime::scan(&[0; 8], all, 1, Endianness::Big).next(),
+            Some((0, FileTime::MIN))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "alignment must not be zero")]
+    fn zero_alignment() {
+        FileTime::scan(&[], window(), 0, Endianness::Little);
+    }
+}
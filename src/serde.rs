@@ -0,0 +1,695 @@
+//! serde support
+//!
+//! FileTime serializes as its raw tick count, an `i64`, or a `u64` for raw
+//! values above [`FileTime::MAX`]. Deserialization accepts both and rejects
+//! negative values. Formats that are not self-describing, like bincode, read
+//! such a `u64` back as a negative `i64` and fail.
+//!
+//! For inputs of mixed shapes use the [`lenient`] with-module, for legacy
+//! ASP.NET `"/Date(...)/"` strings [`ms_json_date`], for Win32 style
+//! `{"dwLowDateTime": .., "dwHighDateTime": ..}` objects [`win32_parts`], for BSON datetime
+//! elements `bson_datetime` (`bson` feature), for TOML datetime literals
+//! `toml_datetime` (`toml` feature).
+use crate::FileTime;
+use ::serde::de::{self, Deserializer, Unexpected, Visitor};
+use ::serde::ser::Serializer;
+use ::serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+
+const EXPECTED_TICKS: &str = "a non-negative FILETIME tick count";
+
+impl Serialize for FileTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match i64::try_from(self.as_u64()) {
+            Ok(ticks) => serializer.serialize_i64(ticks),
+            Err(_) => serializer.serialize_u64(self.as_u64()),
+        }
+    }
+}
+
+struct TicksVisitor;
+
+impl<'de> Visitor<'de> for TicksVisitor {
+    type Value = FileTime;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(EXPECTED_TICKS)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<FileTime, E> {
+        FileTime::from_i64_checked(v)
+            .map_err(|_| E::invalid_value(Unexpected::Signed(v), &EXPECTED_TICKS))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<FileTime, E> {
+        Ok(FileTime::from_u64(v))
+    }
+}
+
+impl<'de> Deserialize<'de> for FileTime {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_i64(TicksVisitor)
+    }
+}
+
+/// With-module accepting a tick count, a decimal tick string or an RFC 3339 string
+///
+/// Serializes the raw tick count like the plain impl; [`lenient::string`]
+/// and [`lenient::rfc3339`] deserialize the same way but emit a decimal
+/// string (safe from JavaScript's 53-bit integers) or an RFC 3339 timestamp.
+/// ```
+/// use filetime_type::FileTime;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Event {
+///     #[serde(with = "filetime_type::serde::lenient")]
+///     time: FileTime,
+/// }
+///
+/// let ft = FileTime::from_i64(130139712831482830);
+/// for json in &[
+///     r#"{"time": 130139712831482830}"#,
+///     r#"{"time": "130139712831482830"}"#,
+///     r#"{"time": "2013-05-25T16:01:23.148283Z"}"#,
+/// ] {
+///     assert_eq!(serde_json::from_str::<Event>(json).unwrap().time, ft);
+/// }
+/// ```
+pub mod lenient {
+    use super::*;
+
+    struct LenientVisitor;
+
+    impl<'de> Visitor<'de> for LenientVisitor {
+        type Value = FileTime;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(
+                "a FILETIME tick count, as integer or decimal string, or an RFC 3339 string",
+            )
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<FileTime, E> {
+            TicksVisitor.visit_i64(v)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<FileTime, E> {
+            TicksVisitor.visit_u64(v)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<FileTime, E> {
+            v.parse()
+                .map_err(|err| E::custom(format_args!("invalid FILETIME string {:?}: {}", v, err)))
+        }
+    }
+
+    /// Serialize the raw tick count
+    pub fn serialize<S: Serializer>(ft: &FileTime, serializer: S) -> Result<S::Ok, S::Error> {
+        ft.serialize(serializer)
+    }
+
+    /// Deserialize an integer, a decimal integer string or an RFC 3339 string
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FileTime, D::Error> {
+        deserializer.deserialize_any(LenientVisitor)
+    }
+
+    /// Lenient deserialization, serializing the tick count as decimal string
+    pub mod string {
+        use super::*;
+
+        /// Serialize the tick count as decimal string
+        pub fn serialize<S: Serializer>(ft: &FileTime, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(&ft.as_u64())
+        }
+
+        pub use super::deserialize;
+    }
+
+    /// Lenient deserialization, serializing as RFC 3339 timestamp with 7 fraction digits
+    pub mod rfc3339 {
+        use super::*;
+        use ::serde::ser::Error;
+        use chrono::Datelike;
+
+        /// Serialize as e.g. `2013-05-25T16:01:23.1482830Z`
+        ///
+        /// Fails for FileTimes after 9999, RFC 3339 has no 5-digit years.
+        pub fn serialize<S: Serializer>(ft: &FileTime, serializer: S) -> Result<S::Ok, S::Error> {
+            if ft.to_datetime_checked().map_or(true, |dt| dt.year() > 9999) {
+                return Err(S::Error::custom(
+                    "FILETIME after 9999-12-31 has no RFC 3339 form",
+                ));
+            }
+            serializer.collect_str(&ft.display())
+        }
+
+        pub use super::deserialize;
+    }
+}
+
+/// With-module for Microsoft JSON date strings, `"/Date(1688140800000)/"`
+///
+/// Serializes without offset, truncated to milliseconds. Deserialization
+/// accepts the forms of [`FileTime::parse_ms_json_date`], any offset is
+/// ignored as the milliseconds are UTC already.
+/// ```
+/// use filetime_type::FileTime;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Item {
+///     #[serde(with = "filetime_type::serde::ms_json_date")]
+///     modified: FileTime,
+/// }
+///
+/// let item: Item = serde_json::from_str(r#"{"modified": "\/Date(1688140800000+0200)\/"}"#).unwrap();
+/// assert_eq!(item.modified.to_unix_millis(), 1688140800000);
+/// assert_eq!(
+///     serde_json::to_string(&item).unwrap(),
+///     r#"{"modified":"/Date(1688140800000)/"}"#
+/// );
+/// ```
+pub mod ms_json_date {
+    use super::*;
+
+    struct MsJsonVisitor;
+
+    impl<'de> Visitor<'de> for MsJsonVisitor {
+        type Value = FileTime;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a Microsoft JSON date string like \"/Date(1688140800000)/\"")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<FileTime, E> {
+            FileTime::parse_ms_json_date(v).map_err(|err| {
+                E::custom(format_args!("invalid Microsoft JSON date {:?}: {}", v, err))
+            })
+        }
+    }
+
+    /// Serialize as `/Date(<milliseconds>)/`
+    pub fn serialize<S: Serializer>(ft: &FileTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&ft.to_ms_json_date())
+    }
+
+    /// Deserialize a `/Date(...)/` string
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FileTime, D::Error> {
+        deserializer.deserialize_str(MsJsonVisitor)
+    }
+}
+
+/// With-module for the Win32 struct shape, `{"dwLowDateTime": .., "dwHighDateTime": ..}`
+///
+/// As written by PowerShell's `ConvertTo-Json` and several Windows REST
+/// APIs. Deserialization accepts `dwLowDateTime`/`dwHighDateTime` with the
+/// `dw` prefix in any case, or `LowPart`/`HighPart`, and ignores other
+/// members. The halves may be signed, as .NET's `ComTypes.FILETIME` has
+/// `int` members, or unsigned; values outside of 32 bits fail. They are
+/// combined like [`FileTime::from_parts`]. Serializes the unsigned `dw` form.
+/// ```
+/// use filetime_type::FileTime;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Item {
+///     #[serde(with = "filetime_type::serde::win32_parts")]
+///     created: FileTime,
+/// }
+///
+/// let json = r#"{"created": {"LowPart": 444459982, "HighPart": 30300513}}"#;
+/// let item: Item = serde_json::from_str(json).unwrap();
+/// assert_eq!(item.created, FileTime::from_i64(130139712831482830));
+/// assert_eq!(
+///     serde_json::to_string(&item).unwrap(),
+///     r#"{"created":{"dwLowDateTime":444459982,"dwHighDateTime":30300513}}"#
+/// );
+/// ```
+pub mod win32_parts {
+    use super::*;
+    use ::serde::de::{MapAccess, SeqAccess};
+    use ::serde::ser::SerializeStruct;
+
+    const FIELDS: &[&str] = &["dwLowDateTime", "dwHighDateTime"];
+
+    enum Field {
+        Low,
+        High,
+        Other,
+    }
+
+    impl<'de> Deserialize<'de> for Field {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct FieldVisitor;
+
+            impl Visitor<'_> for FieldVisitor {
+                type Value = Field;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str("a FILETIME member name")
+                }
+
+                fn visit_str<E: de::Error>(self, v: &str) -> Result<Field, E> {
+                    let unprefixed = match v.get(..2) {
+                        Some(prefix) if prefix.eq_ignore_ascii_case("dw") => &v[2..],
+                        _ => "",
+                    };
+                    Ok(match (unprefixed, v) {
+                        ("LowDateTime", _) | (_, "LowPart") => Field::Low,
+                        ("HighDateTime", _) | (_, "HighPart") => Field::High,
+                        _ => Field::Other,
+                    })
+                }
+            }
+
+            deserializer.deserialize_identifier(FieldVisitor)
+        }
+    }
+
+    /// One half, signed or unsigned 32 bits
+    struct Half(u32);
+
+    impl<'de> Deserialize<'de> for Half {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let v = i64::deserialize(deserializer)?;
+            if v < i64::from(i32::MIN) || v > i64::from(u32::MAX) {
+                return Err(de::Error::invalid_value(
+                    Unexpected::Signed(v),
+                    &"a signed or unsigned 32-bit FILETIME half",
+                ));
+            }
+            // Two's complement, so -1 and 0xFFFFFFFF are the same half
+            Ok(Half(v as u32))
+        }
+    }
+
+    struct PartsVisitor;
+
+    impl<'de> Visitor<'de> for PartsVisitor {
+        type Value = FileTime;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a FILETIME struct with dwLowDateTime and dwHighDateTime")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<FileTime, A::Error> {
+            let Half(low) = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let Half(high) = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            Ok(FileTime::from_parts(low, high))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<FileTime, A::Error> {
+            let (mut low, mut high) = (None, None);
+            while let Some(field) = map.next_key()? {
+                let (slot, name) = match field {
+                    Field::Low => (&mut low, FIELDS[0]),
+                    Field::High => (&mut high, FIELDS[1]),
+                    Field::Other => {
+                        map.next_value::<de::IgnoredAny>()?;
+                        continue;
+                    }
+                };
+                if slot.is_some() {
+                    return Err(de::Error::duplicate_field(name));
+                }
+                let Half(half) = map.next_value()?;
+                *slot = Some(half);
+            }
+            let low = low.ok_or_else(|| de::Error::missing_field(FIELDS[0]))?;
+            let high = high.ok_or_else(|| de::Error::missing_field(FIELDS[1]))?;
+            Ok(FileTime::from_parts(low, high))
+        }
+    }
+
+    /// Serialize as `{"dwLowDateTime": .., "dwHighDateTime": ..}`
+    pub fn serialize<S: Serializer>(ft: &FileTime, serializer: S) -> Result<S::Ok, S::Error> {
+        let (low, high) = ft.to_parts();
+        let mut parts = serializer.serialize_struct("FILETIME", 2)?;
+        parts.serialize_field(FIELDS[0], &low)?;
+        parts.serialize_field(FIELDS[1], &high)?;
+        parts.end()
+    }
+
+    /// Deserialize a struct with either member naming
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FileTime, D::Error> {
+        deserializer.deserialize_struct("FILETIME", FIELDS, PartsVisitor)
+    }
+}
+
+/// With-module storing FileTime as BSON datetime, truncated to milliseconds
+///
+/// Other serializers see `bson::DateTime`'s extended JSON form,
+/// `{"$date": {"$numberLong": "..."}}`.
+/// ```
+/// use filetime_type::FileTime;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Event {
+///     #[serde(with = "filetime_type::serde::bson_datetime")]
+///     time: FileTime,
+/// }
+///
+/// let event = Event { time: FileTime::from_i64(130139712831482830) };
+/// let doc = bson::to_document(&event).unwrap();
+/// assert_eq!(doc.get_datetime("time").unwrap().timestamp_millis(), 1369497683148);
+/// ```
+#[cfg(feature = "bson")]
+pub mod bson_datetime {
+    use super::*;
+
+    /// Serialize as BSON datetime
+    pub fn serialize<S: Serializer>(ft: &FileTime, serializer: S) -> Result<S::Ok, S::Error> {
+        ::bson::DateTime::from(*ft).serialize(serializer)
+    }
+
+    /// Deserialize a BSON datetime, failing before 1601 and after [`FileTime::MAX`]
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FileTime, D::Error> {
+        let dt = ::bson::DateTime::deserialize(deserializer)?;
+        FileTime::try_from(dt).map_err(de::Error::custom)
+    }
+}
+
+/// With-module storing FileTime as TOML offset datetime
+///
+/// Deserializes any TOML datetime [`FileTime`] converts from, see its
+/// `TryFrom<toml::value::Datetime>` for how local forms are handled.
+/// ```
+/// use filetime_type::FileTime;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(with = "filetime_type::serde::toml_datetime")]
+///     not_before: FileTime,
+/// }
+///
+/// let config: Config = toml::from_str("not_before = 2023-05-17T09:00:00Z").unwrap();
+/// assert_eq!(config.not_before, FileTime::from_ymd_hms(2023, 5, 17, 9, 0, 0).unwrap());
+/// ```
+#[cfg(feature = "toml")]
+pub mod toml_datetime {
+    use super::*;
+
+    /// Serialize as TOML offset datetime in UTC, failing above [`FileTime::MAX`]
+    pub fn serialize<S: Serializer>(ft: &FileTime, serializer: S) -> Result<S::Ok, S::Error> {
+        ::toml::value::Datetime::try_from(*ft)
+            .map_err(::serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    /// Deserialize a TOML datetime, failing for local times and outside of the FILETIME range
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<FileTime, D::Error> {
+        let dt = ::toml::value::Datetime::deserialize(deserializer)?;
+        FileTime::try_from(dt).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::Datelike;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Plain {
+        time: FileTime,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Lenient {
+        #[serde(with = "lenient")]
+        default: FileTime,
+        #[serde(with = "lenient::string")]
+        string: FileTime,
+        #[serde(with = "lenient::rfc3339")]
+        rfc3339: FileTime,
+    }
+
+    fn lenient(json: &str) -> Result<FileTime, String> {
+        #[derive(Deserialize)]
+        struct One(#[serde(with = "lenient")] FileTime);
+        serde_json::from_str::<One>(json)
+            .map(|one| one.0)
+            .map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn plain() {
+        let value = Plain {
+            time: FileTime::from_i64(130139712831482830),
+        };
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, r#"{"time":130139712831482830}"#);
+        assert_eq!(serde_json::from_str::<Plain>(&json).unwrap(), value);
+        let err = serde_json::from_str::<Plain>(r#"{"time":-1}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value: integer `-1`, expected a non-negative FILETIME tick count at line 1 column 10"
+        );
+        assert!(serde_json::from_str::<Plain>(r#"{"time":"1"}"#).is_err());
+
+        // Raw values above FileTime::MAX round-trip as unsigned integers
+        let value = Plain {
+            time: FileTime::MAX_RAW,
+        };
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, r#"{"time":18446744073709551615}"#);
+        assert_eq!(serde_json::from_str::<Plain>(&json).unwrap(), value);
+    }
+
+    #[test]
+    fn lenient_shapes() {
+        let ft = FileTime::from_i64(130139712831482830);
+        for json in &[
+            "130139712831482830",
+            r#""130139712831482830""#,
+            r#""2013-05-25T16:01:23.148283Z""#,
+            r#""2013-05-25T18:01:23.148283+02:00""#,
+        ] {
+            assert_eq!(lenient(json), Ok(ft), "{}", json);
+        }
+        assert_eq!(lenient("9223372036854775807"), Ok(FileTime::MAX));
+        assert_eq!(lenient("18446744073709551615"), Ok(FileTime::MAX_RAW));
+        assert_eq!(lenient(r#""18446744073709551615""#), Ok(FileTime::MAX_RAW));
+        assert_eq!(lenient("0"), Ok(FileTime::MIN));
+    }
+
+    #[test]
+    fn lenient_errors() {
+        assert_eq!(
+            lenient("-1"),
+            Err("invalid value: integer `-1`, expected a non-negative FILETIME tick count at line 1 column 2".into())
+        );
+        assert_eq!(
+            lenient(r#""18446744073709551616""#),
+            Err(r#"invalid FILETIME string "18446744073709551616": instant exceeds the maximum FILETIME value at line 1 column 22"#.into())
+        );
+        assert_eq!(
+            lenient(r#""yesterday""#),
+            Err(r#"invalid FILETIME string "yesterday": invalid FILETIME: expected decimal ticks, 0x-prefixed hex ticks or an RFC 3339 timestamp at line 1 column 11"#.into())
+        );
+        assert!(lenient("1.5")
+            .unwrap_err()
+            .starts_with("invalid type: floating point `1.5`"));
+        assert!(lenient("null")
+            .unwrap_err()
+            .starts_with("invalid type: null"));
+    }
+
+    #[test]
+    fn lenient_serialize() {
+        let ft = FileTime::from_i64(130139712831482830);
+        let value = Lenient {
+            default: ft,
+            string: ft,
+            rfc3339: ft,
+        };
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(
+            json,
+            r#"{"default":130139712831482830,"string":"130139712831482830","rfc3339":"2013-05-25T16:01:23.1482830Z"}"#
+        );
+        assert_eq!(serde_json::from_str::<Lenient>(&json).unwrap(), value);
+
+        assert_eq!(FileTime::MAX.to_datetime().year(), 30828);
+        for max in [FileTime::MAX, FileTime::MAX_RAW] {
+            let err = serde_json::to_string(&Lenient {
+                default: ft,
+                string: ft,
+                rfc3339: max,
+            })
+            .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "FILETIME after 9999-12-31 has no RFC 3339 form"
+            );
+        }
+    }
+
+    #[test]
+    fn ms_json_roundtrip() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Item {
+            #[serde(with = "ms_json_date")]
+            modified: FileTime,
+        }
+
+        let item = Item {
+            modified: FileTime::from_i64(133326144001234567),
+        };
+        let json = serde_json::to_string(&item).unwrap();
+        assert_eq!(json, r#"{"modified":"/Date(1688140800123)/"}"#);
+        let back: Item = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.modified, FileTime::from_i64(133326144001230000));
+
+        let err = serde_json::from_str::<Item>(r#"{"modified": "/Date(x)/"}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid Microsoft JSON date \"/Date(x)/\": invalid Microsoft JSON date: expected decimal milliseconds at line 1 column 24"
+        );
+        assert!(serde_json::from_str::<Item>(r#"{"modified": 1688140800000}"#).is_err());
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Parts(#[serde(with = "win32_parts")] FileTime);
+
+    #[test]
+    fn win32_parts_shapes() {
+        // Laid out like Windows PowerShell 5.1's ConvertTo-Json output for a
+        // ComTypes.FILETIME, which has signed int members; values made up
+        let powershell =
+            "{\r\n    \"dwLowDateTime\":  -564914553,\r\n    \"dwHighDateTime\":  31042344\r\n}";
+        let ft = FileTime::from_i64(133325856001234567);
+        assert_eq!(serde_json::from_str::<Parts>(powershell).unwrap().0, ft);
+
+        let json = serde_json::to_string(&Parts(ft)).unwrap();
+        assert_eq!(
+            json,
+            r#"{"dwLowDateTime":3730052743,"dwHighDateTime":31042344}"#
+        );
+        assert_eq!(serde_json::from_str::<Parts>(&json).unwrap(), Parts(ft));
+
+        for json in &[
+            r#"{"DwLowDateTime": 3730052743, "DWHighDateTime": 31042344}"#,
+            r#"{"HighPart": 31042344, "LowPart": -564914553}"#,
+            r#"{"dwLowDateTime": 3730052743, "dwHighDateTime": 31042344, "Extra": [1]}"#,
+            "[3730052743, 31042344]",
+        ] {
+            assert_eq!(
+                serde_json::from_str::<Parts>(json).unwrap().0,
+                ft,
+                "{}",
+                json
+            );
+        }
+
+        let never = r#"{"dwLowDateTime": -1, "dwHighDateTime": -1}"#;
+        assert_eq!(
+            serde_json::from_str::<Parts>(never).unwrap().0,
+            FileTime::MAX_RAW
+        );
+    }
+
+    #[test]
+    fn win32_parts_errors() {
+        let err = |json| serde_json::from_str::<Parts>(json).unwrap_err().to_string();
+        assert_eq!(
+            err(r#"{"dwLowDateTime": 4294967296, "dwHighDateTime": 0}"#),
+            "invalid value: integer `4294967296`, expected a signed or unsigned 32-bit FILETIME half at line 1 column 28"
+        );
+        assert!(
+            err(r#"{"dwLowDateTime": 0, "dwHighDateTime": -2147483649}"#)
+                .starts_with("invalid value: integer `-2147483649`")
+        );
+        assert_eq!(
+            err(r#"{"dwLowDateTime": 0}"#),
+            "missing field `dwHighDateTime` at line 1 column 20"
+        );
+        assert_eq!(
+            err(r#"{"dwLowDateTime": 0, "LowPart": 0, "dwHighDateTime": 0}"#),
+            "duplicate field `dwLowDateTime` at line 1 column 30"
+        );
+        // Only the prefix is case-insensitive
+        assert!(err(r#"{"dwlowdatetime": 0, "dwhighdatetime": 0}"#).starts_with("missing field"));
+        assert!(err("130139712831482830").starts_with("invalid type: integer"));
+    }
+
+    #[cfg(feature = "bson")]
+    #[test]
+    fn bson_roundtrip() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Event {
+            #[serde(with = "bson_datetime")]
+            time: FileTime,
+            raw: FileTime,
+        }
+
+        let ft = FileTime::from_i64(130139712831482830);
+        let event = Event { time: ft, raw: ft };
+        let bytes = ::bson::to_vec(&event).unwrap();
+        // Element types: 0x09 UTC datetime, 0x12 int64
+        assert_eq!(bytes[4], 0x09);
+        assert_eq!(bytes[4 + 1 + 5 + 8], 0x12);
+        let back: Event = ::bson::from_slice(&bytes).unwrap();
+        assert_eq!(
+            back,
+            Event {
+                time: FileTime::from_i64(130139712831480000),
+                raw: ft,
+            }
+        );
+
+        let doc =
+            ::bson::doc! { "time": ::bson::DateTime::from_millis(-11644473600001), "raw": 0i64 };
+        let err = ::bson::from_document::<Event>(doc).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("instant lies before the FILETIME epoch"));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_config() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Config {
+            #[serde(with = "toml_datetime")]
+            not_before: FileTime,
+            #[serde(with = "toml_datetime")]
+            local: FileTime,
+            raw: FileTime,
+        }
+
+        let config: Config = ::toml::from_str(
+            "not_before = 2023-05-17T11:00:00+02:00\nlocal = 2023-05-17\nraw = 0\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                not_before: FileTime::from_ymd_hms(2023, 5, 17, 9, 0, 0).unwrap(),
+                local: FileTime::from_ymd_hms(2023, 5, 17, 0, 0, 0).unwrap(),
+                raw: FileTime::MIN,
+            }
+        );
+        let text = ::toml::to_string(&config).unwrap();
+        assert_eq!(
+            text,
+            "not_before = 2023-05-17T09:00:00Z\nlocal = 2023-05-17T00:00:00Z\nraw = 0\n"
+        );
+        assert_eq!(::toml::from_str::<Config>(&text).unwrap(), config);
+
+        let err = ::toml::from_str::<Config>("not_before = 09:00:00\nlocal = 2023-05-17\nraw = 0")
+            .unwrap_err();
+        assert!(
+            err.message().contains("local time without a date"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Fixed-width keys whose string order is the chronological order
+//!
+//! The key is the raw tick count as 20 decimal digits, zero-padded: every
+//! `u64` has at most 20 digits, so all keys have the same width and byte-wise
+//! (lexicographic) comparison of two keys gives the same result as
+//! comparing the FileTimes. Unlike hex, the keys read as the tick counts
+//! known from other tools.
+use crate::{FileTime, FileTimeError};
+
+const SORTABLE_KEY: &str = "sortable key";
+const KEY_WIDTH: usize = 20;
+
+impl FileTime {
+    /// Encode as sortable key, the raw ticks as 20 zero-padded decimal digits
+    ///
+    /// For any two values `a.to_sortable_key().cmp(&b.to_sortable_key())`
+    /// equals `a.cmp(&b)`, including raw values above [`FileTime::MAX`].
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_i64(130139712831482830);
+    /// assert_eq!(ft.to_sortable_key(), "00130139712831482830");
+    /// assert_eq!(FileTime::from_sortable_key("00130139712831482830"), Ok(ft));
+    /// assert!(FileTime::MIN.to_sortable_key() < ft.to_sortable_key());
+    /// ```
+    pub fn to_sortable_key(&self) -> String {
+        format!("{:020}", self.ticks)
+    }
+
+    /// Decode a key written by [`to_sortable_key`](Self::to_sortable_key)
+    ///
+    /// Anything but exactly 20 ASCII digits fails with
+    /// [`FileTimeError::InvalidFormat`], keys above [`FileTime::MAX_RAW`]
+    /// with [`FileTimeError::Overflow`].
+    pub fn from_sortable_key(key: &str) -> Result<Self, FileTimeError> {
+        if key.len() != KEY_WIDTH || !key.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(FileTimeError::InvalidFormat {
+                format: SORTABLE_KEY,
+                reason: "expected exactly 20 decimal digits",
+            });
+        }
+        key.parse()
+            .map(FileTime::from_u64)
+            .map_err(|_| FileTimeError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sorted_sample() {
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut times: Vec<FileTime> = (0..1000)
+            .map(|i| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                // Mix small values in, their keys differ in the leading zeros
+                FileTime::from_u64(state >> (i % 64))
+            })
+            .chain([FileTime::MIN, FileTime::MAX, FileTime::MAX_RAW])
+            .collect();
+        let mut keys: Vec<String> = times.iter().map(FileTime::to_sortable_key).collect();
+        times.sort();
+        keys.sort();
+        assert!(keys.iter().all(|key| key.len() == 20));
+        let decoded: Vec<FileTime> = keys
+            .iter()
+            .map(|key| FileTime::from_sortable_key(key).unwrap())
+            .collect();
+        assert_eq!(decoded, times);
+        assert_eq!(keys[0], "00000000000000000000");
+        assert_eq!(keys[keys.len() - 1], "18446744073709551615");
+    }
+
+    #[test]
+    fn invalid_keys() {
+        let invalid = Err(FileTimeError::InvalidFormat {
+            format: SORTABLE_KEY,
+            reason: "expected exactly 20 decimal digits",
+        });
+        for key in [
+            "",
+            "130139712831482830",
+            "000130139712831482830",
+            "+0130139712831482830",
+            "0013013971283148283a",
+            "0013013971283148283 ",
+            "00130139712831482830\n",
+            "００130139712831482830",
+        ] {
+            assert_eq!(FileTime::from_sortable_key(key), invalid, "{:?}", key);
+        }
+        assert_eq!(
+            FileTime::from_sortable_key("18446744073709551616"),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!(
+            FileTime::from_sortable_key("99999999999999999999"),
+            Err(FileTimeError::Overflow)
+        );
+    }
+
+    mod proptests {
+        use super::*;
+        use ::proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn order_preserved(a: u64, b: u64) {
+                let (a, b) = (FileTime::from_u64(a), FileTime::from_u64(b));
+                prop_assert_eq!(a.to_sortable_key().cmp(&b.to_sortable_key()), a.cmp(&b));
+            }
+
+            #[test]
+            fn roundtrip(ticks: u64) {
+                let ft = FileTime::from_u64(ticks);
+                prop_assert_eq!(FileTime::from_sortable_key(&ft.to_sortable_key()), Ok(ft));
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! specta `Type` for TypeScript bindings
+//!
+//! FileTime is exported as `FileTime = number`, matching its serde
+//! representation, the raw tick count. JavaScript numbers hold integers
+//! exactly only up to 2^53, about 28.5 years worth of ticks, so `JSON.parse`
+//! rounds present day FILETIMEs to multiples of 16 ticks: 130139712831482830
+//! and 130139712831482831 become the same number. Where the frontend needs
+//! exact values, serialize the field with `serde::lenient::string` or
+//! `serde::lenient::rfc3339` (`serde` feature) and annotate it
+//! `#[specta(type = filetime_type::specta::FileTimeString)]`.
+use crate::FileTime;
+use ::specta::{DataType, DefOpts, ExportError, GenericType, NamedDataType, NamedType, Type};
+
+/// Windows FILETIME: number of 100-nanosecond intervals since 1601-01-01T00:00:00Z.
+/// Exact only up to 2^53, present day values are rounded to multiples of 16.
+#[derive(Type)]
+#[specta(rename = "FileTime")]
+#[allow(dead_code)]
+struct FileTimeNumber(f64);
+
+/// Windows FILETIME as string: decimal ticks since 1601-01-01T00:00:00Z or an RFC 3339 timestamp.
+///
+/// For the `#[specta(type = ..)]` override of fields serialized as string.
+#[derive(Type)]
+#[specta(rename = "FileTimeString")]
+#[allow(dead_code)]
+pub struct FileTimeString(String);
+
+impl Type for FileTime {
+    fn inline(opts: DefOpts, generics: &[DataType]) -> Result<DataType, ExportError> {
+        FileTimeNumber::inline(opts, generics)
+    }
+
+    fn definition_generics() -> Vec<GenericType> {
+        FileTimeNumber::definition_generics()
+    }
+
+    fn category_impl(
+        opts: DefOpts,
+        generics: &[DataType],
+    ) -> Result<::specta::TypeCategory, ExportError> {
+        FileTimeNumber::category_impl(opts, generics)
+    }
+}
+
+impl NamedType for FileTime {
+    fn named_data_type(opts: DefOpts, generics: &[DataType]) -> Result<NamedDataType, ExportError> {
+        FileTimeNumber::named_data_type(opts, generics)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::specta::ts::{self, ExportConfiguration};
+
+    #[allow(dead_code)]
+    #[derive(Type)]
+    struct FileInfo {
+        name: String,
+        modified: FileTime,
+        #[specta(type = FileTimeString)]
+        created: FileTime,
+    }
+
+    #[test]
+    fn typescript() {
+        let conf = ExportConfiguration::default();
+        assert_eq!(
+            ts::export::<FileTime>(&conf).unwrap(),
+            "/**\n * Windows FILETIME: number of 100-nanosecond intervals since 1601-01-01T00:00:00Z.\n * Exact only up to 2^53, present day values are rounded to multiples of 16.\n */\nexport type FileTime = number"
+        );
+        assert_eq!(
+            ts::export::<FileInfo>(&conf).unwrap(),
+            "export type FileInfo = { name: string; modified: FileTime; created: FileTimeString }"
+        );
+        assert_eq!(ts::inline::<FileTime>(&conf).unwrap(), "number");
+    }
+}
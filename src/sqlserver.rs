@@ -0,0 +1,280 @@
+//! SQL Server `datetime2` / `datetimeoffset` binary encodings
+//!
+//! As stored on data pages and sent over TDS ([MS-TDS] 2.2.5.5.1.8), all
+//! little-endian:
+//!
+//! ```text
+//! time    3 bytes (scale 0-2), 4 (3-4) or 5 (5-7), units of 10^-scale seconds since midnight
+//! date    3 bytes, days since 0001-01-01
+//! offset  2 bytes, datetimeoffset only, signed minutes east of UTC
+//! ```
+//!
+//! The time and date of a `datetimeoffset` are in UTC already, the offset
+//! only records the local time zone. Scale 7 counts 100ns ticks, like FILETIME.
+use crate::{FileTime, FileTimeError};
+
+const DATETIME2: &str = "SQL Server datetime2";
+const DATETIMEOFFSET: &str = "SQL Server datetimeoffset";
+/// Days from 0001-01-01 to 1601-01-01
+const DAYS_BEFORE_EPOCH: i64 = 584_388;
+/// Days from 0001-01-01 to 9999-12-31, the last day SQL Server supports
+const MAX_DAYS: i64 = 3_652_058;
+const TICKS_PER_DAY: i64 = 864_000_000_000;
+/// Largest offset SQL Server accepts, ±14:00
+const MAX_OFFSET_MINUTES: i16 = 14 * 60;
+
+/// Size of the time part and ticks per unit for `scale`
+fn time_layout(scale: u8) -> Result<(usize, i64), FileTimeError> {
+    let len = match scale {
+        0..=2 => 3,
+        3..=4 => 4,
+        5..=7 => 5,
+        _ => {
+            return Err(FileTimeError::InvalidField {
+                field: "scale",
+                reason: "must be 0 to 7",
+            })
+        }
+    };
+    Ok((len, 10i64.pow(7 - u32::from(scale))))
+}
+
+fn check_offset(offset_minutes: i16) -> Result<(), FileTimeError> {
+    if offset_minutes.abs() > MAX_OFFSET_MINUTES {
+        return Err(FileTimeError::InvalidField {
+            field: "offset",
+            reason: "must be within ±14:00",
+        });
+    }
+    Ok(())
+}
+
+fn decode(bytes: &[u8], scale: u8, format: &'static str) -> Result<FileTime, FileTimeError> {
+    let (len, ticks_per_unit) = time_layout(scale)?;
+    if bytes.len() != len + 3 {
+        return Err(FileTimeError::InvalidLength {
+            format,
+            expected: len + 3,
+            actual: bytes.len(),
+        });
+    }
+    let mut time = [0u8; 8];
+    time[..len].copy_from_slice(&bytes[..len]);
+    let time = i64::from_le_bytes(time);
+    if time >= TICKS_PER_DAY / ticks_per_unit {
+        return Err(FileTimeError::InvalidField {
+            field: "time",
+            reason: "exceeds one day",
+        });
+    }
+    let days = i64::from(u32::from_le_bytes([
+        bytes[len],
+        bytes[len + 1],
+        bytes[len + 2],
+        0,
+    ]));
+    if days > MAX_DAYS {
+        return Err(FileTimeError::OutOfRange(format));
+    }
+    let ticks = (days - DAYS_BEFORE_EPOCH) * TICKS_PER_DAY + time * ticks_per_unit;
+    FileTime::from_i64_checked(ticks)
+}
+
+fn encode(ft: &FileTime, scale: u8, format: &'static str) -> Result<Vec<u8>, FileTimeError> {
+    let (len, ticks_per_unit) = time_layout(scale)?;
+    let days = ft.filetime() / TICKS_PER_DAY + DAYS_BEFORE_EPOCH;
+    if days > MAX_DAYS {
+        return Err(FileTimeError::OutOfRange(format));
+    }
+    let time = ft.filetime() % TICKS_PER_DAY / ticks_per_unit;
+    let mut buf = Vec::with_capacity(len + 5);
+    buf.extend_from_slice(&time.to_le_bytes()[..len]);
+    buf.extend_from_slice(&days.to_le_bytes()[..3]);
+    Ok(buf)
+}
+
+impl FileTime {
+    /// Decode a `datetime2` of the given scale (fraction digits, 0 to 7)
+    ///
+    /// Fails with [`FileTimeError::InvalidLength`] unless `bytes` matches the
+    /// scale, [`FileTimeError::InvalidField`] for a scale above 7 or a time of
+    /// day of 24 hours or more, and [`FileTimeError::BeforeEpoch`] for dates
+    /// before 1601.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// // 2009-07-25T23:00:00.1234567
+    /// let bytes = [0x87, 0x2e, 0xb8, 0xc8, 0xc0, 0xac, 0x31, 0x0b];
+    /// let ft = FileTime::from_sql_datetime2(&bytes, 7).unwrap();
+    /// assert_eq!(ft.filetime(), 128930364001234567);
+    /// assert_eq!(ft.to_sql_datetime2(7).unwrap(), bytes);
+    /// ```
+    pub fn from_sql_datetime2(bytes: &[u8], scale: u8) -> Result<Self, FileTimeError> {
+        decode(bytes, scale, DATETIME2)
+    }
+
+    /// Encode as `datetime2` of the given scale, truncating to its precision
+    ///
+    /// Lossless at scale 7. Fails with [`FileTimeError::OutOfRange`] after
+    /// 9999-12-31 and [`FileTimeError::InvalidField`] for a scale above 7.
+    pub fn to_sql_datetime2(&self, scale: u8) -> Result<Vec<u8>, FileTimeError> {
+        encode(self, scale, DATETIME2)
+    }
+
+    /// Decode a `datetimeoffset`, returning the UTC instant and the offset in minutes
+    ///
+    /// Errors as [`from_sql_datetime2`](Self::from_sql_datetime2), plus
+    /// [`FileTimeError::InvalidField`] for an offset beyond ±14:00.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// // 2009-07-25T23:00:00.1234567Z, recorded at +02:00
+    /// let bytes = [0x87, 0x2e, 0xb8, 0xc8, 0xc0, 0xac, 0x31, 0x0b, 0x78, 0x00];
+    /// let (ft, offset) = FileTime::from_sql_datetimeoffset(&bytes, 7).unwrap();
+    /// assert_eq!((ft.filetime(), offset), (128930364001234567, 120));
+    /// ```
+    pub fn from_sql_datetimeoffset(bytes: &[u8], scale: u8) -> Result<(Self, i16), FileTimeError> {
+        let (len, _) = time_layout(scale)?;
+        if bytes.len() != len + 5 {
+            return Err(FileTimeError::InvalidLength {
+                format: DATETIMEOFFSET,
+                expected: len + 5,
+                actual: bytes.len(),
+            });
+        }
+        let offset = i16::from_le_bytes([bytes[len + 3], bytes[len + 4]]);
+        check_offset(offset)?;
+        Ok((decode(&bytes[..len + 3], scale, DATETIMEOFFSET)?, offset))
+    }
+
+    /// Encode as `datetimeoffset` of the given scale, recording `offset_minutes`
+    ///
+    /// The instant itself is stored in UTC. Errors as
+    /// [`to_sql_datetime2`](Self::to_sql_datetime2), plus
+    /// [`FileTimeError::InvalidField`] for an offset beyond ±14:00.
+    pub fn to_sql_datetimeoffset(
+        &self,
+        scale: u8,
+        offset_minutes: i16,
+    ) -> Result<Vec<u8>, FileTimeError> {
+        check_offset(offset_minutes)?;
+        let mut buf = encode(self, scale, DATETIMEOFFSET)?;
+        buf.extend_from_slice(&offset_minutes.to_le_bytes());
+        Ok(buf)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Encodings assembled from the MS-TDS layout for 2009-07-25T23:00:00.1234567
+    const SCALE_7: [u8; 8] = [0x87, 0x2e, 0xb8, 0xc8, 0xc0, 0xac, 0x31, 0x0b];
+    const FT: i64 = 128930364001234567;
+
+    #[test]
+    fn scales() {
+        let ft = FileTime::from_i64(FT);
+        assert_eq!(FileTime::from_sql_datetime2(&SCALE_7, 7), Ok(ft));
+        assert_eq!(ft.to_sql_datetime2(7).unwrap(), SCALE_7);
+
+        // 82800.1 s as 828001 tenths, 3 byte time
+        let scale_1 = [0x61, 0xa2, 0x0c, 0xac, 0x31, 0x0b];
+        assert_eq!(ft.to_sql_datetime2(1).unwrap(), scale_1);
+        assert_eq!(
+            FileTime::from_sql_datetime2(&scale_1, 1),
+            Ok(FileTime::from_i64(128930364001000000))
+        );
+        // 82800123 ms, 4 byte time
+        let scale_3 = [0xfb, 0x6d, 0xef, 0x04, 0xac, 0x31, 0x0b];
+        assert_eq!(ft.to_sql_datetime2(3).unwrap(), scale_3);
+        assert_eq!(
+            FileTime::from_sql_datetime2(&scale_3, 3),
+            Ok(FileTime::from_i64(128930364001230000))
+        );
+        for scale in 0..=7 {
+            let bytes = ft.to_sql_datetime2(scale).unwrap();
+            let decoded = FileTime::from_sql_datetime2(&bytes, scale).unwrap();
+            let unit = 10i64.pow(7 - u32::from(scale));
+            assert_eq!(decoded.filetime(), FT - FT % unit, "scale {}", scale);
+        }
+    }
+
+    #[test]
+    fn range() {
+        assert_eq!(
+            FileTime::MIN.to_sql_datetime2(7).unwrap(),
+            [0, 0, 0, 0, 0, 0xc4, 0xea, 0x08]
+        );
+        assert_eq!(
+            FileTime::from_sql_datetime2(&[0, 0, 0, 0, 0, 0xc3, 0xea, 0x08], 7),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        let last = [0xff, 0xbf, 0x69, 0x2a, 0xc9, 0xda, 0xb9, 0x37];
+        let last_ft = FileTime::from_sql_datetime2(&last, 7).unwrap();
+        assert_eq!(
+            last_ft,
+            FileTime::from_ymd_hms_nano(9999, 12, 31, 23, 59, 59, 999_999_900).unwrap()
+        );
+        assert_eq!(last_ft.to_sql_datetime2(7).unwrap(), last);
+        assert_eq!(
+            FileTime::from_i64(last_ft.filetime() + 1).to_sql_datetime2(7),
+            Err(FileTimeError::OutOfRange(DATETIME2))
+        );
+        assert_eq!(
+            FileTime::from_sql_datetime2(&[0, 0, 0, 0, 0, 0xdb, 0xb9, 0x37], 7),
+            Err(FileTimeError::OutOfRange(DATETIME2))
+        );
+    }
+
+    #[test]
+    fn offset() {
+        let ft = FileTime::from_i64(FT);
+        let bytes = ft.to_sql_datetimeoffset(7, -300).unwrap();
+        assert_eq!(bytes[..8], SCALE_7);
+        assert_eq!(bytes[8..], (-300i16).to_le_bytes());
+        assert_eq!(FileTime::from_sql_datetimeoffset(&bytes, 7), Ok((ft, -300)));
+        assert_eq!(
+            ft.to_sql_datetimeoffset(7, 841),
+            Err(FileTimeError::InvalidField {
+                field: "offset",
+                reason: "must be within ±14:00",
+            })
+        );
+        assert_eq!(
+            FileTime::from_sql_datetimeoffset(&SCALE_7, 7),
+            Err(FileTimeError::InvalidLength {
+                format: DATETIMEOFFSET,
+                expected: 10,
+                actual: 8,
+            })
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            FileTime::from_sql_datetime2(&SCALE_7, 8),
+            Err(FileTimeError::InvalidField {
+                field: "scale",
+                reason: "must be 0 to 7",
+            })
+        );
+        assert_eq!(
+            FileTime::from_sql_datetime2(&SCALE_7, 4),
+            Err(FileTimeError::InvalidLength {
+                format: DATETIME2,
+                expected: 7,
+                actual: 8,
+            })
+        );
+        // 86400 s at scale 0
+        assert_eq!(
+            FileTime::from_sql_datetime2(&[0x80, 0x51, 0x01, 0xac, 0x31, 0x0b], 0),
+            Err(FileTimeError::InvalidField {
+                field: "time",
+                reason: "exceeds one day",
+            })
+        );
+    }
+}
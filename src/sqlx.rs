@@ -0,0 +1,180 @@
+//! sqlx `Type`/`Encode`/`Decode` support
+//!
+//! - SQLite (`sqlx-sqlite`): stored as `INTEGER` holding the raw FILETIME ticks,
+//!   negative values fail to decode, raw values above [`FileTime::MAX`] to encode.
+//! - PostgreSQL (`sqlx-postgres`): stored as `timestamptz` (also decodes `timestamp`),
+//!   the last tick digit (100ns) is truncated as PostgreSQL keeps microseconds only.
+use crate::FileTime;
+use ::sqlx::encode::IsNull;
+use ::sqlx::error::BoxDynError;
+use ::sqlx::{Database, Decode, Encode, Type};
+
+#[cfg(feature = "sqlx-sqlite")]
+mod sqlite {
+    use super::*;
+    use ::sqlx::sqlite::{SqliteTypeInfo, SqliteValueRef};
+    use ::sqlx::Sqlite;
+
+    impl Type<Sqlite> for FileTime {
+        fn type_info() -> SqliteTypeInfo {
+            <i64 as Type<Sqlite>>::type_info()
+        }
+
+        fn compatible(ty: &SqliteTypeInfo) -> bool {
+            <i64 as Type<Sqlite>>::compatible(ty)
+        }
+    }
+
+    impl<'q> Encode<'q, Sqlite> for FileTime {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <Sqlite as Database>::ArgumentBuffer,
+        ) -> Result<IsNull, BoxDynError> {
+            <i64 as Encode<Sqlite>>::encode(self.to_i64_checked()?, buf)
+        }
+    }
+
+    impl<'r> Decode<'r, Sqlite> for FileTime {
+        fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+            let ticks = <i64 as Decode<Sqlite>>::decode(value)?;
+            Ok(FileTime::from_i64_checked(ticks)?)
+        }
+    }
+}
+
+#[cfg(feature = "sqlx-postgres")]
+mod postgres {
+    use super::*;
+    use ::sqlx::postgres::{PgTypeInfo, PgValueFormat, PgValueRef};
+    use ::sqlx::Postgres;
+    use chrono::{DateTime, NaiveDateTime, Utc};
+
+    impl Type<Postgres> for FileTime {
+        fn type_info() -> PgTypeInfo {
+            <DateTime<Utc> as Type<Postgres>>::type_info()
+        }
+
+        fn compatible(ty: &PgTypeInfo) -> bool {
+            <DateTime<Utc> as Type<Postgres>>::compatible(ty)
+                || <NaiveDateTime as Type<Postgres>>::compatible(ty)
+        }
+    }
+
+    impl<'q> Encode<'q, Postgres> for FileTime {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <Postgres as Database>::ArgumentBuffer,
+        ) -> Result<IsNull, BoxDynError> {
+            <i64 as Encode<Postgres>>::encode(self.to_pg_micros(), buf)
+        }
+
+        fn size_hint(&self) -> usize {
+            std::mem::size_of::<i64>()
+        }
+    }
+
+    impl<'r> Decode<'r, Postgres> for FileTime {
+        fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+            match value.format() {
+                PgValueFormat::Binary => {
+                    let micros = <i64 as Decode<Postgres>>::decode(value)?;
+                    Ok(FileTime::from_pg_micros(micros)?)
+                }
+                PgValueFormat::Text => {
+                    let s = value.as_str()?;
+                    let dt = match DateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f%#z") {
+                        Ok(dt) => dt.with_timezone(&Utc),
+                        Err(_) => {
+                            NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f")?.and_utc()
+                        }
+                    };
+                    Ok(FileTime::from_datetime_checked(dt)?)
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use ::sqlx::TypeInfo;
+
+        #[test]
+        fn type_info() {
+            assert_eq!(
+                <FileTime as Type<Postgres>>::type_info().name(),
+                "TIMESTAMPTZ"
+            );
+            assert!(<FileTime as Type<Postgres>>::compatible(
+                &PgTypeInfo::with_name("timestamp")
+            ));
+            assert!(!<FileTime as Type<Postgres>>::compatible(
+                &PgTypeInfo::with_name("int8")
+            ));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sqlx-sqlite"))]
+mod test {
+    use super::*;
+    use crate::FileTimeError;
+    use ::sqlx::{Connection, SqliteConnection};
+    use futures_executor::block_on;
+
+    #[test]
+    fn sqlite_roundtrip() {
+        block_on(async {
+            let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+            ::sqlx::query("CREATE TABLE events (id INTEGER PRIMARY KEY, at INTEGER NOT NULL)")
+                .execute(&mut conn)
+                .await
+                .unwrap();
+
+            let values = [
+                FileTime::from_i64(0),
+                FileTime::from_i64(133326144001230000),
+            ];
+            for (id, ft) in values.iter().enumerate() {
+                ::sqlx::query("INSERT INTO events (id, at) VALUES (?, ?)")
+                    .bind(id as i64)
+                    .bind(ft)
+                    .execute(&mut conn)
+                    .await
+                    .unwrap();
+            }
+
+            let rows: Vec<(i64, FileTime)> =
+                ::sqlx::query_as("SELECT id, at FROM events ORDER BY id")
+                    .fetch_all(&mut conn)
+                    .await
+                    .unwrap();
+            assert_eq!(rows, vec![(0, values[0]), (1, values[1])]);
+
+            // The raw ticks are stored as-is
+            let (raw,): (i64,) = ::sqlx::query_as("SELECT at FROM events WHERE id = 1")
+                .fetch_one(&mut conn)
+                .await
+                .unwrap();
+            assert_eq!(raw, 133326144001230000);
+        });
+    }
+
+    #[test]
+    fn sqlite_decode_negative() {
+        block_on(async {
+            let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+            let err = ::sqlx::query_as::<_, (FileTime,)>("SELECT -1")
+                .fetch_one(&mut conn)
+                .await
+                .unwrap_err();
+            match err {
+                ::sqlx::Error::ColumnDecode { source, .. } => assert_eq!(
+                    source.downcast_ref::<FileTimeError>(),
+                    Some(&FileTimeError::BeforeEpoch)
+                ),
+                other => panic!("unexpected error: {}", other),
+            }
+        });
+    }
+}
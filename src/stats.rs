@@ -0,0 +1,360 @@
+//! Summaries of FileTime collections
+use crate::FileTime;
+use core::iter::FromIterator;
+use core::time::Duration;
+
+impl FileTime {
+    /// Instant halfway between `a` and `b`, rounded towards the earlier one
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let (a, b) = (FileTime::from_i64(10), FileTime::from_i64(21));
+    /// assert_eq!(FileTime::midpoint(a, b), FileTime::from_i64(15));
+    /// assert_eq!(FileTime::midpoint(b, a), FileTime::from_i64(15));
+    /// ```
+    pub fn midpoint(a: FileTime, b: FileTime) -> FileTime {
+        let (earlier, later) = if a <= b { (a, b) } else { (b, a) };
+        FileTime::from_u64(earlier.ticks + (later.ticks - earlier.ticks) / 2)
+    }
+}
+
+/// Arithmetic mean of the instants, rounded towards the earlier instant
+///
+/// Accumulates in i128, so any number of values is safe from overflow.
+/// `None` for empty input.
+/// ```
+/// use filetime_type::{filetime_mean, FileTime};
+///
+/// let times = [1, 2, 4].iter().map(|&t| FileTime::from_i64(t));
+/// assert_eq!(filetime_mean(times), Some(FileTime::from_i64(2)));
+/// assert_eq!(filetime_mean(Vec::new()), None);
+/// ```
+pub fn filetime_mean<I: IntoIterator<Item = FileTime>>(iter: I) -> Option<FileTime> {
+    let (sum, count) = iter.into_iter().fold((0i128, 0u64), |(sum, count), ft| {
+        (sum + i128::from(ft.ticks), count + 1)
+    });
+    if count == 0 {
+        return None;
+    }
+    // sum and count are non-negative, so the quotient truncates towards the earlier instant
+    Some(FileTime::from_u64((sum / i128::from(count)) as u64))
+}
+
+/// Running summary of FileTimes: count, bounds, mean and an estimated median
+///
+/// Takes values one at a time with [`push`](Self::push), or collects them
+/// from an iterator, in constant memory. All accessors return `None` until
+/// the first value. The mean is exact, see [`filetime_mean`]. The median is
+/// an estimate, see [`median`](Self::median).
+/// ```
+/// use filetime_type::{FileTime, FileTimeStats};
+///
+/// let stats: FileTimeStats = [30, 10, 20].iter().map(|&t| FileTime::from_i64(t)).collect();
+/// assert_eq!(stats.count(), 3);
+/// assert_eq!(stats.min(), Some(FileTime::from_i64(10)));
+/// assert_eq!(stats.max(), Some(FileTime::from_i64(30)));
+/// assert_eq!(stats.mean(), Some(FileTime::from_i64(20)));
+/// assert_eq!(stats.median(), Some(FileTime::from_i64(20)));
+/// assert_eq!(FileTimeStats::new().span(), None);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FileTimeStats {
+    count: u64,
+    sum: u128,
+    min: Option<FileTime>,
+    max: Option<FileTime>,
+    /// P² marker heights, the first values sorted while `count` is below 5
+    heights: [u64; 5],
+    /// P² marker positions, 0-based ranks among the values seen
+    positions: [u64; 5],
+    /// P² desired marker positions
+    desired: [f64; 5],
+}
+
+/// Increments of the desired P² marker positions per value, for the median
+const DESIRED_STEP: [f64; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+impl FileTimeStats {
+    /// Empty summary
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a value
+    pub fn push(&mut self, ft: FileTime) {
+        self.count += 1;
+        self.sum += u128::from(ft.ticks);
+        self.min = Some(self.min.map_or(ft, |min| min.min(ft)));
+        self.max = Some(self.max.map_or(ft, |max| max.max(ft)));
+
+        let x = ft.ticks;
+        if self.count <= 5 {
+            let len = (self.count - 1) as usize;
+            let at = self.heights[..len].partition_point(|&h| h <= x);
+            self.heights.copy_within(at..len, at + 1);
+            self.heights[at] = x;
+            if self.count == 5 {
+                self.positions = [0, 1, 2, 3, 4];
+                self.desired = [0.0, 1.0, 2.0, 3.0, 4.0];
+            }
+            return;
+        }
+        self.update_markers(x);
+    }
+
+    /// One step of the P² algorithm (Jain & Chlamtac, 1985)
+    fn update_markers(&mut self, x: u64) {
+        let q = &mut self.heights;
+        let n = &mut self.positions;
+        let cell = if x < q[0] {
+            q[0] = x;
+            0
+        } else if x >= q[4] {
+            q[4] = x;
+            3
+        } else {
+            q[1..4].iter().position(|&h| x < h).unwrap_or(3)
+        };
+        for position in &mut n[cell + 1..] {
+            *position += 1;
+        }
+        for (desired, step) in self.desired.iter_mut().zip(&DESIRED_STEP) {
+            *desired += step;
+        }
+
+        for i in 1..4 {
+            let d = self.desired[i] - n[i] as f64;
+            let (below, above) = (n[i] - n[i - 1], n[i + 1] - n[i]);
+            let s: i64 = if d >= 1.0 && above > 1 {
+                1
+            } else if d <= -1.0 && below > 1 {
+                -1
+            } else {
+                continue;
+            };
+            // Heights stay integer, only the parabolic step goes through f64
+            let h = |k: usize| i128::from(q[k]);
+            let sf = s as f64;
+            let (below, above) = (below as f64, above as f64);
+            let step = sf / (below + above)
+                * ((below + sf) * (h(i + 1) - h(i)) as f64 / above
+                    + (above - sf) * (h(i) - h(i - 1)) as f64 / below);
+            let parabolic = h(i) + step as i128;
+            let height = if h(i - 1) < parabolic && parabolic < h(i + 1) {
+                parabolic
+            } else {
+                let j = (i as i64 + s) as usize;
+                h(i) + (h(j) - h(i)) / i128::from(n[j].abs_diff(n[i]))
+            };
+            q[i] = height as u64;
+            n[i] = (n[i] as i64 + s) as u64;
+        }
+    }
+
+    /// Number of values
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Earliest value
+    pub fn min(&self) -> Option<FileTime> {
+        self.min
+    }
+
+    /// Latest value
+    pub fn max(&self) -> Option<FileTime> {
+        self.max
+    }
+
+    /// Arithmetic mean, rounded towards the earlier instant
+    ///
+    /// Accumulates in u128, safe from overflow for any number of values.
+    pub fn mean(&self) -> Option<FileTime> {
+        (self.count > 0).then(|| FileTime::from_u64((self.sum / u128::from(self.count)) as u64))
+    }
+
+    /// Time between the earliest and the latest value
+    pub fn span(&self) -> Option<Duration> {
+        let ticks = self.max?.ticks - self.min?.ticks;
+        let per_second = FileTime::HUNDREDS_OF_NANOSECONDS as u64;
+        Some(Duration::new(
+            ticks / per_second,
+            (ticks % per_second) as u32 * 100,
+        ))
+    }
+
+    /// Estimated median
+    ///
+    /// Exact up to 4 values (the earlier-rounded midpoint for an even count).
+    /// From 5 values on it is the P² estimate, which tracks the median with
+    /// five markers instead of keeping the values. The estimate is usually
+    /// within a few percent of the values' rank from the true median, but is
+    /// no guaranteed bound; skewed or sorted input does worse than shuffled
+    /// input.
+    pub fn median(&self) -> Option<FileTime> {
+        let at = |i: usize| FileTime::from_u64(self.heights[i]);
+        match self.count {
+            0 => None,
+            1 => Some(at(0)),
+            2 => Some(FileTime::midpoint(at(0), at(1))),
+            3 => Some(at(1)),
+            4 => Some(FileTime::midpoint(at(1), at(2))),
+            _ => Some(at(2)),
+        }
+    }
+}
+
+impl Extend<FileTime> for FileTimeStats {
+    fn extend<I: IntoIterator<Item = FileTime>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|ft| self.push(ft));
+    }
+}
+
+impl FromIterator<FileTime> for FileTimeStats {
+    fn from_iter<I: IntoIterator<Item = FileTime>>(iter: I) -> Self {
+        let mut stats = Self::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn midpoint() {
+        let (a, b) = (FileTime::from_i64(41), FileTime::from_i64(42));
+        assert_eq!(FileTime::midpoint(a, b), a);
+        assert_eq!(FileTime::midpoint(b, a), a);
+        assert_eq!(FileTime::midpoint(a, a), a);
+        assert_eq!(
+            FileTime::midpoint(FileTime::MAX, FileTime::from_i64(i64::MAX - 1)),
+            FileTime::from_i64(i64::MAX - 1)
+        );
+        assert_eq!(
+            FileTime::midpoint(FileTime::MIN, FileTime::MAX),
+            FileTime::from_i64(i64::MAX / 2)
+        );
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(filetime_mean(core::iter::empty()), None);
+        assert_eq!(filetime_mean(Some(FileTime::MAX)), Some(FileTime::MAX));
+        assert_eq!(
+            filetime_mean(core::iter::repeat_n(FileTime::MAX, 1_000_000)),
+            Some(FileTime::MAX)
+        );
+        let ft = FileTime::from_i64(130139712831482830);
+        assert_eq!(filetime_mean(core::iter::repeat_n(ft, 1_000_000)), Some(ft));
+        let pair = [FileTime::from_i64(41), FileTime::from_i64(42)];
+        assert_eq!(
+            filetime_mean(pair.iter().copied()),
+            Some(FileTime::from_i64(41))
+        );
+        assert_eq!(
+            filetime_mean(
+                [FileTime::MAX, FileTime::MAX, FileTime::MIN]
+                    .iter()
+                    .copied()
+            ),
+            Some(FileTime::from_i64(i64::MAX / 3 * 2))
+        );
+    }
+
+    /// Xorshift values within 30 years from 2000, in a fixed order
+    fn random(len: usize) -> Vec<FileTime> {
+        let start = FileTime::from_ymd_hms(2000, 1, 1, 0, 0, 0)
+            .unwrap()
+            .as_u64();
+        let width = 30 * 365 * 86_400 * FileTime::HUNDREDS_OF_NANOSECONDS as u64;
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                FileTime::from_u64(start + state % width)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stats_brute_force() {
+        let values = random(5000);
+        let stats: FileTimeStats = values.iter().copied().collect();
+        let mut sorted = values.clone();
+        sorted.sort();
+        assert_eq!(stats.count(), 5000);
+        assert_eq!(stats.min(), sorted.first().copied());
+        assert_eq!(stats.max(), sorted.last().copied());
+        assert_eq!(stats.mean(), filetime_mean(values.iter().copied()));
+        let span = sorted[4999].as_u64() - sorted[0].as_u64();
+        assert_eq!(stats.span().unwrap().as_nanos(), u128::from(span) * 100);
+
+        // The estimate has a rank within 5% of the middle
+        let median = stats.median().unwrap();
+        let rank = sorted.partition_point(|&ft| ft < median);
+        assert!((2250..=2750).contains(&rank), "{}", rank);
+
+        // Pushing one by one gives the same summary
+        let mut pushed = FileTimeStats::new();
+        for &ft in &values[..3000] {
+            pushed.push(ft);
+        }
+        pushed.extend(values[3000..].iter().copied());
+        assert_eq!(pushed.median(), stats.median());
+        assert_eq!(pushed.mean(), stats.mean());
+    }
+
+    #[test]
+    fn stats_small() {
+        let empty = FileTimeStats::new();
+        assert_eq!(empty.count(), 0);
+        assert_eq!(empty.min(), None);
+        assert_eq!(empty.max(), None);
+        assert_eq!(empty.mean(), None);
+        assert_eq!(empty.span(), None);
+        assert_eq!(empty.median(), None);
+
+        let ft = |t: i64| FileTime::from_i64(t);
+        let median = |ticks: &[i64]| {
+            ticks
+                .iter()
+                .map(|&t| ft(t))
+                .collect::<FileTimeStats>()
+                .median()
+        };
+        assert_eq!(median(&[7]), Some(ft(7)));
+        assert_eq!(median(&[9, 4]), Some(ft(6)));
+        assert_eq!(median(&[9, 1, 4]), Some(ft(4)));
+        assert_eq!(median(&[9, 1, 4, 2]), Some(ft(3)));
+        assert_eq!(median(&[9, 1, 4, 2, 5]), Some(ft(4)));
+
+        let stats: FileTimeStats = [ft(20_000_005), ft(5)].iter().copied().collect();
+        assert_eq!(stats.span(), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn stats_overflow() {
+        let stats: FileTimeStats = core::iter::repeat_n(FileTime::MAX_RAW, 1_000_000).collect();
+        assert_eq!(stats.mean(), Some(FileTime::MAX_RAW));
+        assert_eq!(stats.median(), Some(FileTime::MAX_RAW));
+        assert_eq!(stats.span(), Some(Duration::ZERO));
+
+        let mut stats: FileTimeStats = core::iter::repeat_n(FileTime::MAX, 1000).collect();
+        stats.push(FileTime::MIN);
+        stats.push(FileTime::MAX_RAW);
+        assert_eq!(stats.min(), Some(FileTime::MIN));
+        assert_eq!(stats.max(), Some(FileTime::MAX_RAW));
+        assert_eq!(stats.span().unwrap().as_nanos(), u128::from(u64::MAX) * 100);
+        assert_eq!(
+            stats.mean(),
+            Some(FileTime::from_u64(
+                ((u128::from(i64::MAX as u64) * 1000 + u128::from(u64::MAX)) / 1002) as u64
+            ))
+        );
+        assert_eq!(stats.median(), Some(FileTime::MAX));
+    }
+}
@@ -0,0 +1,255 @@
+//! Reading runs of little-endian FILETIMEs from `io::Read`
+use crate::FileTime;
+use std::fmt;
+use std::io::{self, Read};
+
+/// What went wrong while reading a FILETIME record
+#[derive(Debug)]
+pub enum ParseManyErrorKind {
+    /// The reader failed
+    Io(io::Error),
+    /// The input ended inside of the record, after this many bytes
+    Truncated(usize),
+    /// The input ended cleanly before the requested number of records
+    Eof,
+}
+
+/// Error of [`FileTime::parse_many`] and [`FileTime::stream_from`]
+#[derive(Debug)]
+pub struct ParseManyError {
+    /// Zero-based index of the failing record
+    pub index: usize,
+    /// Byte offset of the failing record from where reading started
+    pub offset: u64,
+    /// Underlying cause
+    pub kind: ParseManyErrorKind,
+}
+
+impl fmt::Display for ParseManyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "FILETIME record {} at offset {}: ",
+            self.index, self.offset
+        )?;
+        match &self.kind {
+            ParseManyErrorKind::Io(err) => write!(f, "{}", err),
+            ParseManyErrorKind::Truncated(len) => {
+                write!(f, "truncated after {} of 8 bytes", len)
+            }
+            ParseManyErrorKind::Eof => write!(f, "unexpected end of input"),
+        }
+    }
+}
+
+impl std::error::Error for ParseManyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ParseManyErrorKind::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Iterator over the FILETIMEs of a reader, returned by [`FileTime::stream_from`]
+///
+/// Ends after clean EOF or the first error.
+#[derive(Debug)]
+pub struct FileTimeStream<R> {
+    reader: R,
+    index: usize,
+    done: bool,
+}
+
+/// Read one record, `Ok(None)` on EOF before its first byte
+fn read_record<R: Read>(reader: &mut R) -> Result<Option<FileTime>, ParseManyErrorKind> {
+    let mut raw = [0u8; 8];
+    let mut filled = 0;
+    while filled < raw.len() {
+        match reader.read(&mut raw[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(ParseManyErrorKind::Truncated(filled)),
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(ParseManyErrorKind::Io(err)),
+        }
+    }
+    Ok(Some(FileTime::from_u64(u64::from_le_bytes(raw))))
+}
+
+impl<R: Read> Iterator for FileTimeStream<R> {
+    type Item = Result<FileTime, ParseManyError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let index = self.index;
+        self.index += 1;
+        match read_record(&mut self.reader) {
+            Ok(Some(ft)) => Some(Ok(ft)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(kind) => {
+                self.done = true;
+                Some(Err(ParseManyError {
+                    index,
+                    offset: index as u64 * 8,
+                    kind,
+                }))
+            }
+        }
+    }
+}
+
+impl FileTime {
+    /// Read exactly `count` little-endian FILETIMEs
+    ///
+    /// The error names the failing record; input ending before `count`
+    /// records is [`ParseManyErrorKind::Eof`], or [`ParseManyErrorKind::Truncated`]
+    /// if it ends inside of a record.
+    pub fn parse_many<R: Read>(r: &mut R, count: usize) -> Result<Vec<FileTime>, ParseManyError> {
+        // `count` usually comes from an untrusted header, grow past this as records arrive
+        let mut times = Vec::with_capacity(count.min(4096));
+        let mut stream = Self::stream_from(r);
+        for index in 0..count {
+            match stream.next() {
+                Some(result) => times.push(result?),
+                None => {
+                    return Err(ParseManyError {
+                        index,
+                        offset: index as u64 * 8,
+                        kind: ParseManyErrorKind::Eof,
+                    })
+                }
+            }
+        }
+        Ok(times)
+    }
+
+    /// Lazily read little-endian FILETIMEs until EOF
+    /// ```
+    /// use filetime_type::FileTime;
+    /// use std::io::Cursor;
+    ///
+    /// let bytes: Vec<u8> = [1i64, 2].iter().flat_map(|v| v.to_le_bytes().to_vec()).collect();
+    /// let times: Result<Vec<_>, _> = FileTime::stream_from(Cursor::new(bytes)).collect();
+    /// assert_eq!(times.unwrap(), vec![FileTime::from_i64(1), FileTime::from_i64(2)]);
+    /// ```
+    pub fn stream_from<R: Read>(r: R) -> FileTimeStream<R> {
+        FileTimeStream {
+            reader: r,
+            index: 0,
+            done: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    fn bytes(values: &[i64]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|v| v.to_le_bytes().to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn good_values() {
+        let data = bytes(&[0, 128930364000000010, i64::MAX]);
+        let expected = vec![
+            FileTime::from_i64(0),
+            FileTime::from_i64(128930364000000010),
+            FileTime::MAX,
+        ];
+        assert_eq!(
+            FileTime::parse_many(&mut Cursor::new(&data), 3).unwrap(),
+            expected
+        );
+        // Records after `count` are left in the reader
+        let mut cursor = Cursor::new(&data);
+        assert_eq!(FileTime::parse_many(&mut cursor, 2).unwrap(), expected[..2]);
+        assert_eq!(cursor.position(), 16);
+
+        let streamed: Vec<_> = FileTime::stream_from(Cursor::new(&data))
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn above_max() {
+        // Records are unsigned, the high half is not an error
+        let data = bytes(&[1, -1, i64::MIN]);
+        let expected = vec![
+            FileTime::from_u64(1),
+            FileTime::MAX_RAW,
+            FileTime::from_u64(1 << 63),
+        ];
+        assert_eq!(
+            FileTime::parse_many(&mut Cursor::new(&data), 3).unwrap(),
+            expected
+        );
+        let streamed: Vec<_> = FileTime::stream_from(Cursor::new(&data))
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn truncated_tail() {
+        let mut data = bytes(&[1, 2]);
+        data.extend_from_slice(&[0xaa; 5]);
+        let err = FileTime::parse_many(&mut Cursor::new(&data), 3).unwrap_err();
+        assert_eq!((err.index, err.offset), (2, 16));
+        assert!(matches!(err.kind, ParseManyErrorKind::Truncated(5)));
+        assert_eq!(
+            err.to_string(),
+            "FILETIME record 2 at offset 16: truncated after 5 of 8 bytes"
+        );
+
+        let results: Vec<_> = FileTime::stream_from(Cursor::new(&data)).collect();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(
+            results[2],
+            Err(ParseManyError {
+                index: 2,
+                kind: ParseManyErrorKind::Truncated(5),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn clean_eof() {
+        let data = bytes(&[1, 2]);
+        let err = FileTime::parse_many(&mut Cursor::new(&data), 3).unwrap_err();
+        assert_eq!((err.index, err.offset), (2, 16));
+        assert!(matches!(err.kind, ParseManyErrorKind::Eof));
+        assert_eq!(FileTime::stream_from(Cursor::new(&data)).count(), 2);
+        assert_eq!(FileTime::stream_from(Cursor::new(&[])).count(), 0);
+
+        // A bogus count fails at the end of input rather than preallocating
+        let err = FileTime::parse_many(&mut Cursor::new(vec![]), usize::MAX / 8).unwrap_err();
+        assert_eq!((err.index, err.offset), (0, 0));
+        assert!(matches!(err.kind, ParseManyErrorKind::Eof));
+    }
+
+    #[test]
+    fn io_error() {
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("device gone"))
+            }
+        }
+        let err = FileTime::parse_many(&mut Failing, 1).unwrap_err();
+        assert!(matches!(err.kind, ParseManyErrorKind::Io(_)));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+}
@@ -0,0 +1,98 @@
+//! Comparison with `std::time::SystemTime`
+//!
+//! Instants are compared at nanosecond precision, so a SystemTime a few
+//! nanoseconds after a FileTime compares greater even though FILETIME cannot
+//! represent the difference. SystemTimes before 1601 compare less than every
+//! FileTime. Note that SystemTime itself is coarser on some platforms (e.g.
+//! 100ns on Windows, 1µs on some others), values read from the system clock
+//! may therefore compare equal where the true instants differ.
+use crate::FileTime;
+use std::cmp::Ordering;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Nanoseconds since the FILETIME-Epoch, negative before 1601
+fn nanos_since_epoch(t: &SystemTime) -> i128 {
+    let since_unix = match t.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_nanos() as i128,
+        Err(err) => -(err.duration().as_nanos() as i128),
+    };
+    since_unix + i128::from(FileTime::EPOCH_AS_FILETIME) * 100
+}
+
+impl FileTime {
+    fn cmp_system_time(&self, other: &SystemTime) -> Ordering {
+        (i128::from(self.as_u64()) * 100).cmp(&nanos_since_epoch(other))
+    }
+}
+
+impl PartialEq<SystemTime> for FileTime {
+    fn eq(&self, other: &SystemTime) -> bool {
+        self.cmp_system_time(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd<SystemTime> for FileTime {
+    fn partial_cmp(&self, other: &SystemTime) -> Option<Ordering> {
+        Some(self.cmp_system_time(other))
+    }
+}
+
+impl PartialEq<FileTime> for SystemTime {
+    fn eq(&self, other: &FileTime) -> bool {
+        other == self
+    }
+}
+
+impl PartialOrd<FileTime> for SystemTime {
+    fn partial_cmp(&self, other: &FileTime) -> Option<Ordering> {
+        other.partial_cmp(self).map(Ordering::reverse)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn unix_epoch_boundary() {
+        let epoch = FileTime::from_unix_seconds(0).unwrap();
+        assert!(epoch == UNIX_EPOCH);
+        assert!(UNIX_EPOCH == epoch);
+
+        let before = FileTime::from_i64(epoch.filetime() - 1);
+        assert!(before < UNIX_EPOCH);
+        assert!(UNIX_EPOCH > before);
+        assert!(before == UNIX_EPOCH - Duration::from_nanos(100));
+
+        let after = FileTime::from_i64(epoch.filetime() + 1);
+        assert!(after > UNIX_EPOCH);
+        assert!(UNIX_EPOCH < after);
+        assert!(after == UNIX_EPOCH + Duration::from_nanos(100));
+    }
+
+    #[test]
+    fn sub_tick() {
+        let ft = FileTime::from_i64(130139712831482830);
+        let st = UNIX_EPOCH + Duration::new(1369497683, 148283000);
+        assert!(ft == st);
+        let later = st + Duration::from_nanos(1);
+        assert!(ft != later);
+        assert!(ft < later);
+        assert!(later > ft);
+        assert_eq!(ft.partial_cmp(&later), Some(Ordering::Less));
+        let earlier = st - Duration::from_nanos(99);
+        assert!(ft > earlier);
+        assert!(earlier < ft);
+    }
+
+    #[test]
+    fn before_filetime_epoch() {
+        let epoch = UNIX_EPOCH - Duration::from_secs(11644473600);
+        assert!(FileTime::MIN == epoch);
+        if let Some(before) = epoch.checked_sub(Duration::from_nanos(1)) {
+            assert!(FileTime::MIN > before);
+            assert!(before < FileTime::MIN);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Forensic timeline output in the Sleuth Kit body file format
+//!
+//! Body files (TSK 3.x) are consumed by `mactime` and plaso, one record per line:
+//!
+//! ```text
+//! MD5|name|inode|mode_as_string|UID|GID|size|atime|mtime|ctime|crtime
+//! ```
+//!
+//! Timestamps are whole seconds since the Unix-Epoch, sub-second ticks are
+//! truncated (never rounded) and missing timestamps are written as `0`.
+use crate::FileTime;
+use std::io::{self, Write};
+
+/// A single body file record
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BodyRecord {
+    /// MD5 hash of the content, written as `0` if unknown
+    pub md5: Option<String>,
+    /// File name / path
+    pub name: String,
+    /// Inode or MFT entry, e.g. `1234-128-1`
+    pub inode: String,
+    /// Mode as string, e.g. `r/rrwxrwxrwx`
+    pub mode: String,
+    /// Owner user ID
+    pub uid: u32,
+    /// Owner group ID
+    pub gid: u32,
+    /// Size in bytes
+    pub size: u64,
+    /// Last access time
+    pub atime: Option<FileTime>,
+    /// Last modification time
+    pub mtime: Option<FileTime>,
+    /// Last metadata change time ($MFT entry modified on NTFS)
+    pub ctime: Option<FileTime>,
+    /// Creation time
+    pub crtime: Option<FileTime>,
+}
+
+/// Escape the field separator, so names containing `|` don't shift the columns
+fn escape(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    for c in field.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '|' => out.push_str("\\|"),
+            '\n' => out.push_str("\\n"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn unix_seconds(ft: Option<FileTime>) -> i64 {
+    ft.map_or(0, |ft| ft.to_unix_seconds())
+}
+
+impl BodyRecord {
+    /// Format as body file line, without trailing newline
+    ///
+    /// `|`, `\` and newlines in the name, inode and mode fields are escaped with a backslash.
+    /// ```
+    /// use filetime_type::timeline::BodyRecord;
+    /// use filetime_type::FileTime;
+    ///
+    /// let record = BodyRecord {
+    ///     name: "C:/Windows/notepad.exe".into(),
+    ///     mtime: Some(FileTime::from_i64(128930364000001000)),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(record.to_body_line(), "0|C:/Windows/notepad.exe|||0|0|0|0|1248562800|0|0");
+    /// ```
+    pub fn to_body_line(&self) -> String {
+        format!(
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
+            self.md5.as_deref().map_or_else(|| "0".to_string(), escape),
+            escape(&self.name),
+            escape(&self.inode),
+            escape(&self.mode),
+            self.uid,
+            self.gid,
+            self.size,
+            unix_seconds(self.atime),
+            unix_seconds(self.mtime),
+            unix_seconds(self.ctime),
+            unix_seconds(self.crtime),
+        )
+    }
+}
+
+/// Write `records` as body file, one line per record
+pub fn write_body<'a, I, W>(records: I, w: &mut W) -> io::Result<()>
+where
+    I: IntoIterator<Item = &'a BodyRecord>,
+    W: Write,
+{
+    for record in records {
+        writeln!(w, "{}", record.to_body_line())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn notepad() -> BodyRecord {
+        BodyRecord {
+            md5: None,
+            name: "C:/Windows/System32/notepad.exe".into(),
+            inode: "66291-128-3".into(),
+            mode: "r/rrwxrwxrwx".into(),
+            uid: 0,
+            gid: 0,
+            size: 201216,
+            atime: Some(FileTime::from_i64(133284683983436231)),
+            mtime: Some(FileTime::from_i64(133284683452815582)),
+            ctime: Some(FileTime::from_i64(133284683452815582)),
+            crtime: Some(FileTime::from_i64(132514526099529382)),
+        }
+    }
+
+    #[test]
+    fn body_line() {
+        assert_eq!(
+            notepad().to_body_line(),
+            "0|C:/Windows/System32/notepad.exe|66291-128-3|r/rrwxrwxrwx|0|0|201216|1683994798|1683994745|1683994745|1606979009"
+        );
+    }
+
+    #[test]
+    fn missing_timestamps() {
+        let record = BodyRecord {
+            md5: Some("d41d8cd98f00b204e9800998ecf8427e".into()),
+            name: "/etc/passwd".into(),
+            inode: "123".into(),
+            mode: "-/-rw-r--r--".into(),
+            size: 1234,
+            mtime: Some(FileTime::from_unix_seconds(1107714045).unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(
+            record.to_body_line(),
+            "d41d8cd98f00b204e9800998ecf8427e|/etc/passwd|123|-/-rw-r--r--|0|0|1234|0|1107714045|0|0"
+        );
+    }
+
+    #[test]
+    fn truncate_subsec() {
+        // One tick before the next full second is still truncated
+        let ft = FileTime::from_i64(128930364009999999);
+        let record = BodyRecord {
+            atime: Some(ft),
+            ..Default::default()
+        };
+        assert_eq!(record.to_body_line(), "0||||0|0|0|1248562800|0|0|0");
+
+        // Before 1970 towards the earlier second
+        let record = BodyRecord {
+            atime: Some(FileTime::from_i64(116444735999999999)),
+            ..Default::default()
+        };
+        assert_eq!(record.to_body_line(), "0||||0|0|0|-1|0|0|0");
+    }
+
+    #[test]
+    fn escape_name() {
+        let record = BodyRecord {
+            name: "a|b\\c".into(),
+            ..Default::default()
+        };
+        assert_eq!(record.to_body_line(), "0|a\\|b\\\\c|||0|0|0|0|0|0|0");
+    }
+
+    #[test]
+    fn write() {
+        let mut out = Vec::new();
+        write_body(&[notepad(), BodyRecord::default()], &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], notepad().to_body_line());
+        assert_eq!(lines[1], "0||||0|0|0|0|0|0|0");
+    }
+}
@@ -0,0 +1,152 @@
+//! Conversion from/to `toml::value::Datetime`
+//!
+//! TOML has four datetime forms. Offset datetimes are converted to UTC.
+//! Local datetimes and local dates carry no offset; TOML leaves their
+//! meaning to the application and they are taken as UTC here, a local date
+//! as midnight. Local times have no date and fail with
+//! [`FileTimeError::InvalidFormat`], as do leap seconds.
+//!
+//! FileTime itself serializes as integer; for TOML datetime literals in
+//! config structs use the `filetime_type::serde::toml_datetime`
+//! with-module (`serde` feature).
+use crate::parse;
+use crate::{FileTime, FileTimeError};
+use ::toml::value::{Date, Datetime, Offset, Time};
+use chrono::{Datelike, Duration, Timelike};
+use std::convert::TryFrom;
+
+const TOML: &str = "TOML datetime";
+
+impl TryFrom<Datetime> for FileTime {
+    type Error = FileTimeError;
+
+    fn try_from(dt: Datetime) -> Result<Self, Self::Error> {
+        let invalid = |reason| FileTimeError::InvalidFormat {
+            format: TOML,
+            reason,
+        };
+        let date = dt
+            .date
+            .ok_or_else(|| invalid("local time without a date"))?;
+        let (hour, minute, second, nanosecond) = match dt.time {
+            Some(time) => (
+                time.hour,
+                time.minute,
+                time.second.unwrap_or(0),
+                time.nanosecond.unwrap_or(0),
+            ),
+            None => (0, 0, 0, 0),
+        };
+        let local = parse::datetime(
+            date.year.into(),
+            date.month.into(),
+            date.day.into(),
+            hour.into(),
+            minute.into(),
+            second.into(),
+            nanosecond,
+        )
+        .ok_or_else(|| invalid("invalid date or time"))?;
+        let offset_minutes = match dt.offset {
+            None | Some(Offset::Z) => 0,
+            Some(Offset::Custom { minutes }) => minutes,
+        };
+        Self::from_datetime_checked(local - Duration::minutes(offset_minutes.into()))
+    }
+}
+
+/// Offset datetime in UTC (`Z`), with the fraction only if there is one
+///
+/// Years after 9999 print with five digits, which TOML parsers reject. Raw
+/// values above [`FileTime::MAX`] fail with [`FileTimeError::OutOfRange`].
+impl TryFrom<FileTime> for Datetime {
+    type Error = FileTimeError;
+
+    fn try_from(ft: FileTime) -> Result<Self, Self::Error> {
+        let dt = ft
+            .to_datetime_checked()
+            .map_err(|_| FileTimeError::OutOfRange(TOML))?;
+        Ok(Datetime {
+            date: Some(Date {
+                year: dt.year() as u16,
+                month: dt.month() as u8,
+                day: dt.day() as u8,
+            }),
+            time: Some(Time {
+                hour: dt.hour() as u8,
+                minute: dt.minute() as u8,
+                second: Some(dt.second() as u8),
+                nanosecond: Some(dt.nanosecond()).filter(|&nanos| nanos != 0),
+            }),
+            offset: Some(Offset::Z),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DOCUMENT: &str = r#"
+        offset = 2023-05-17T11:00:00.1234567+02:00
+        utc = 2023-05-17T09:00:00Z
+        local = 2023-05-17T09:00:00.5
+        date = 2023-05-17
+        time = 09:00:00
+        spaced = 2023-05-17 09:00:00Z
+        leap = 2016-12-31T23:59:60Z
+        before = 1600-12-31T23:59:59Z
+        shifted = 1601-01-01T00:30:00+01:00
+    "#;
+
+    fn convert(key: &str) -> Result<FileTime, FileTimeError> {
+        let table: ::toml::Table = ::toml::from_str(DOCUMENT).unwrap();
+        match &table[key] {
+            ::toml::Value::Datetime(dt) => FileTime::try_from(*dt),
+            other => panic!("{} is {:?}", key, other),
+        }
+    }
+
+    fn invalid(reason: &'static str) -> Result<FileTime, FileTimeError> {
+        Err(FileTimeError::InvalidFormat {
+            format: TOML,
+            reason,
+        })
+    }
+
+    #[test]
+    fn variants() {
+        let at = |h, mi, s, n| FileTime::from_ymd_hms_nano(2023, 5, 17, h, mi, s, n);
+        assert_eq!(convert("offset"), at(9, 0, 0, 123_456_700));
+        assert_eq!(convert("utc"), at(9, 0, 0, 0));
+        assert_eq!(convert("spaced"), at(9, 0, 0, 0));
+        assert_eq!(convert("local"), at(9, 0, 0, 500_000_000));
+        assert_eq!(convert("date"), at(0, 0, 0, 0));
+        assert_eq!(convert("time"), invalid("local time without a date"));
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(convert("leap"), invalid("invalid date or time"));
+        assert_eq!(convert("before"), Err(FileTimeError::BeforeEpoch));
+        assert_eq!(convert("shifted"), Err(FileTimeError::BeforeEpoch));
+    }
+
+    #[test]
+    fn to_toml() {
+        let ft = FileTime::from_i64(130139712831482830);
+        let dt = Datetime::try_from(ft).unwrap();
+        assert_eq!(dt.to_string(), "2013-05-25T16:01:23.148283Z");
+        assert_eq!(FileTime::try_from(dt), Ok(ft));
+        assert_eq!(
+            Datetime::try_from(FileTime::MIN).unwrap().to_string(),
+            "1601-01-01T00:00:00Z"
+        );
+        assert_eq!(
+            Datetime::try_from(FileTime::MAX_RAW),
+            Err(FileTimeError::OutOfRange(TOML))
+        );
+        let parsed: Datetime = "2013-05-25T16:01:23.148283Z".parse().unwrap();
+        assert_eq!(parsed, dt);
+    }
+}
@@ -0,0 +1,192 @@
+//! `touch -t` timestamps, `[[CC]YY]MMDDhhmm[.SS]` (POSIX touch)
+use crate::clock::{Clock, SystemClock};
+use crate::parse;
+use crate::{FileTime, FileTimeError};
+use chrono::{Datelike, Duration, FixedOffset};
+
+const TOUCH: &str = "touch -t timestamp";
+
+impl FileTime {
+    /// Parse a `touch -t` timestamp as UTC, e.g. `202305170930.15`
+    ///
+    /// Same as [`parse_touch_with`](Self::parse_touch_with) at offset zero
+    /// with the system clock supplying the year when it is omitted.
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::parse_touch("202305170930.15").unwrap();
+    /// assert_eq!(ft.to_datetime().to_rfc3339(), "2023-05-17T09:30:15+00:00");
+    /// assert_eq!(FileTime::parse_touch("2305170930.15"), Ok(ft));
+    /// ```
+    pub fn parse_touch(s: &str) -> Result<Self, FileTimeError> {
+        Self::parse_touch_with(s, FixedOffset::east_opt(0).unwrap(), &SystemClock)
+    }
+
+    /// Parse a `touch -t` timestamp as local time at `assume_offset`
+    ///
+    /// The digit count decides which fields are present: 8 for `MMDDhhmm`,
+    /// 10 for `YYMMDDhhmm`, 12 for `CCYYMMDDhhmm`. Without a year, the current
+    /// year at `assume_offset` according to `clock` is used. Two digit years
+    /// 69 to 99 are taken as 1969 to 1999, 00 to 68 as 2000 to 2068.
+    /// Seconds may be 60; as POSIX specifies for times that are not a leap
+    /// second, this is one second after `:59`, i.e. the next minute.
+    /// Fails with [`FileTimeError::InvalidFormat`] for other lengths,
+    /// non-digits or invalid dates and times.
+    /// ```
+    /// use chrono::FixedOffset;
+    /// use filetime_type::{FileTime, FixedClock};
+    ///
+    /// let clock = FixedClock(FileTime::from_ymd_hms(2024, 1, 1, 0, 0, 0).unwrap());
+    /// let cest = FixedOffset::east_opt(2 * 3600).unwrap();
+    /// let ft = FileTime::parse_touch_with("05170930", cest, &clock).unwrap();
+    /// assert_eq!(ft.to_datetime().to_rfc3339(), "2024-05-17T07:30:00+00:00");
+    /// ```
+    pub fn parse_touch_with<C: Clock + ?Sized>(
+        s: &str,
+        assume_offset: FixedOffset,
+        clock: &C,
+    ) -> Result<Self, FileTimeError> {
+        let invalid = |reason| FileTimeError::InvalidFormat {
+            format: TOUCH,
+            reason,
+        };
+        let (digits, secs) = match s.split_once('.') {
+            Some((digits, secs)) if secs.len() == 2 => (digits.as_bytes(), secs.as_bytes()),
+            Some(_) => return Err(invalid("expected two digit seconds after '.'")),
+            None => (s.as_bytes(), &b"00"[..]),
+        };
+        let field = |b: &[u8]| parse::digits(b).ok_or_else(|| invalid("expected digits"));
+        let secs = field(secs)?;
+
+        let (year, rest) = match digits.len() {
+            8 => {
+                let now = clock.now_filetime().to_datetime_checked()?;
+                (now.with_timezone(&assume_offset).year(), digits)
+            }
+            10 => {
+                let yy = field(&digits[..2])? as i32;
+                (if yy < 69 { 2000 + yy } else { 1900 + yy }, &digits[2..])
+            }
+            12 => (field(&digits[..4])? as i32, &digits[4..]),
+            _ => return Err(invalid("expected 8, 10 or 12 digits before the seconds")),
+        };
+        // :60 is validated as :59 and moved a second on afterwards
+        let local = parse::datetime(
+            year,
+            field(&rest[0..2])?,
+            field(&rest[2..4])?,
+            field(&rest[4..6])?,
+            field(&rest[6..8])?,
+            secs.min(59),
+            0,
+        )
+        .filter(|_| secs <= 60)
+        .ok_or_else(|| invalid("invalid date or time"))?;
+        let offset = Duration::seconds(assume_offset.local_minus_utc().into());
+        let leap = Duration::seconds(i64::from(secs / 60));
+        Self::from_datetime_checked(local - offset + leap)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FixedClock;
+
+    fn utc() -> FixedOffset {
+        FixedOffset::east_opt(0).unwrap()
+    }
+
+    fn parse(s: &str) -> Result<FileTime, FileTimeError> {
+        let clock = FixedClock(FileTime::from_ymd_hms(2024, 12, 31, 23, 0, 0).unwrap());
+        FileTime::parse_touch_with(s, utc(), &clock)
+    }
+
+    fn invalid(reason: &'static str) -> Result<FileTime, FileTimeError> {
+        Err(FileTimeError::InvalidFormat {
+            format: TOUCH,
+            reason,
+        })
+    }
+
+    #[test]
+    fn components() {
+        let expected = |y, mo, d, h, mi, s| FileTime::from_ymd_hms(y, mo, d, h, mi, s);
+        assert_eq!(parse("05170930"), expected(2024, 5, 17, 9, 30, 0));
+        assert_eq!(parse("05170930.15"), expected(2024, 5, 17, 9, 30, 15));
+        assert_eq!(parse("2305170930"), expected(2023, 5, 17, 9, 30, 0));
+        assert_eq!(parse("2305170930.15"), expected(2023, 5, 17, 9, 30, 15));
+        assert_eq!(parse("202305170930"), expected(2023, 5, 17, 9, 30, 0));
+        assert_eq!(parse("202305170930.15"), expected(2023, 5, 17, 9, 30, 15));
+        assert_eq!(parse("160101010000"), expected(1601, 1, 1, 0, 0, 0));
+    }
+
+    #[test]
+    fn two_digit_years() {
+        let year = |s| parse(s).unwrap().to_datetime().year();
+        assert_eq!(year("6801010000"), 2068);
+        assert_eq!(year("6901010000"), 1969);
+        assert_eq!(year("9912312359"), 1999);
+        assert_eq!(year("0001010000"), 2000);
+    }
+
+    #[test]
+    fn leap_second() {
+        assert_eq!(
+            parse("201612312359.60"),
+            FileTime::from_ymd_hms(2017, 1, 1, 0, 0, 0)
+        );
+        assert_eq!(parse("201612312359.61"), invalid("invalid date or time"));
+    }
+
+    #[test]
+    fn offset() {
+        let clock = FixedClock(FileTime::from_ymd_hms(2024, 12, 31, 23, 0, 0).unwrap());
+        // Already 2025 at +02:00
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        assert_eq!(
+            FileTime::parse_touch_with("01011200", offset, &clock),
+            FileTime::from_ymd_hms(2025, 1, 1, 10, 0, 0)
+        );
+        let offset = FixedOffset::west_opt(5 * 3600).unwrap();
+        assert_eq!(
+            FileTime::parse_touch_with("202305170930", offset, &clock),
+            FileTime::from_ymd_hms(2023, 5, 17, 14, 30, 0)
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse("202313170930"), invalid("invalid date or time"));
+        assert_eq!(parse("202302300930"), invalid("invalid date or time"));
+        assert_eq!(parse("202305172430"), invalid("invalid date or time"));
+        assert_eq!(
+            parse("0517093"),
+            invalid("expected 8, 10 or 12 digits before the seconds")
+        );
+        assert_eq!(
+            parse("20230517093015"),
+            invalid("expected 8, 10 or 12 digits before the seconds")
+        );
+        assert_eq!(
+            parse("202305170930.1"),
+            invalid("expected two digit seconds after '.'")
+        );
+        assert_eq!(parse("2023-5170930"), invalid("expected digits"));
+        assert_eq!(parse("202305170930.1a"), invalid("expected digits"));
+        assert_eq!(parse("160012312359"), Err(FileTimeError::BeforeEpoch));
+        // A clock beyond FileTime::MAX has no current year
+        assert_eq!(
+            FileTime::parse_touch_with("05170930", utc(), &FixedClock(FileTime::MAX_RAW)),
+            Err(FileTimeError::OutOfRange("chrono::DateTime"))
+        );
+    }
+
+    #[test]
+    fn system_clock() {
+        let year = FileTime::now().to_datetime().year();
+        let ft = FileTime::parse_touch("01010000").unwrap();
+        // Unless the year changed in between
+        assert!((year..=year + 1).contains(&ft.to_datetime().year()));
+    }
+}
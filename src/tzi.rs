@@ -0,0 +1,542 @@
+//! Windows time zone rules (`TIME_ZONE_INFORMATION` / `REG_TZI`)
+//!
+//! The `TZI` value below `HKLM\SOFTWARE\Microsoft\Windows NT\CurrentVersion\Time Zones`
+//! (and the `TimeZoneInformation` key of the machine's own settings) holds,
+//! little-endian:
+//!
+//! ```text
+//! 0x00  i32         Bias, UTC = local time + bias, in minutes
+//! 0x04  i32         StandardBias, added to Bias during standard time
+//! 0x08  i32         DaylightBias, added to Bias during daylight saving time
+//! 0x0c  SYSTEMTIME  StandardDate, transition to standard time
+//! 0x1c  SYSTEMTIME  DaylightDate, transition to daylight saving time
+//! ```
+use crate::{FileTime, FileTimeError};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+
+const FORMAT: &str = "REG_TZI";
+const MINUTES_PER_DAY: i32 = 24 * 60;
+
+/// Transition rule of a [`TimeZoneInformation`], laid out as a `SYSTEMTIME`
+///
+/// With `year == 0` the rule recurs every year: it fires on the `day`-th
+/// (1 to 4, or 5 for the last) `day_of_week` (0 is Sunday) of `month`.
+/// Otherwise it fires once, on the given date. The time of day is the local
+/// time in effect before the transition. A `month` of 0 means the zone has no
+/// daylight saving time.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct TziTransition {
+    /// `wYear`, 0 for a recurring rule
+    pub year: u16,
+    /// `wMonth`, 1 to 12, 0 if unused
+    pub month: u16,
+    /// `wDayOfWeek`, 0 (Sunday) to 6
+    pub day_of_week: u16,
+    /// `wDay`, the week of the month for recurring rules, else the day of the month
+    pub day: u16,
+    /// `wHour`
+    pub hour: u16,
+    /// `wMinute`
+    pub minute: u16,
+    /// `wSecond`
+    pub second: u16,
+    /// `wMilliseconds`
+    pub milliseconds: u16,
+}
+
+impl TziTransition {
+    fn parse(buf: &[u8], offset: usize) -> Result<Self, FileTimeError> {
+        let word = |i: usize| u16::from_le_bytes([buf[offset + i * 2], buf[offset + i * 2 + 1]]);
+        let rule = Self {
+            year: word(0),
+            month: word(1),
+            day_of_week: word(2),
+            day: word(3),
+            hour: word(4),
+            minute: word(5),
+            second: word(6),
+            milliseconds: word(7),
+        };
+        if rule.month == 0 {
+            return Ok(rule);
+        }
+        let malformed = |field: usize, reason| FileTimeError::Malformed {
+            format: FORMAT,
+            offset: offset + field * 2,
+            reason,
+        };
+        if rule.month > 12 {
+            return Err(malformed(1, "month out of range"));
+        }
+        if rule.day_of_week > 6 {
+            return Err(malformed(2, "day of week out of range"));
+        }
+        if rule.year == 0 && !(1..=5).contains(&rule.day) {
+            return Err(malformed(3, "week of month out of range"));
+        }
+        if rule.year != 0
+            && NaiveDate::from_ymd_opt(rule.year.into(), rule.month.into(), rule.day.into())
+                .is_none()
+        {
+            return Err(malformed(3, "day does not exist in the given month"));
+        }
+        if NaiveTime::from_hms_milli_opt(
+            rule.hour.into(),
+            rule.minute.into(),
+            rule.second.into(),
+            rule.milliseconds.into(),
+        )
+        .is_none()
+        {
+            return Err(malformed(4, "time of day out of range"));
+        }
+        Ok(rule)
+    }
+
+    /// Local date and time the rule fires at in `year`, if any
+    fn local_in(&self, year: i32) -> Option<NaiveDateTime> {
+        let date = if self.year == 0 {
+            let month = u32::from(self.month);
+            let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+            let weekday = u32::from(self.day_of_week);
+            let first_match = 1 + (weekday + 7 - first.weekday().num_days_from_sunday()) % 7;
+            // Week 5 is the last occurrence, which may be the 4th
+            (0..u32::from(self.day))
+                .rev()
+                .find_map(|week| NaiveDate::from_ymd_opt(year, month, first_match + week * 7))?
+        } else if i32::from(self.year) == year {
+            NaiveDate::from_ymd_opt(year, self.month.into(), self.day.into())?
+        } else {
+            return None;
+        };
+        date.and_hms_milli_opt(
+            self.hour.into(),
+            self.minute.into(),
+            self.second.into(),
+            self.milliseconds.into(),
+        )
+    }
+}
+
+/// A machine's time zone, as stored in a `REG_TZI` blob
+///
+/// See [`FileTime::to_machine_local`].
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct TimeZoneInformation {
+    bias: i32,
+    standard_bias: i32,
+    daylight_bias: i32,
+    standard_date: TziTransition,
+    daylight_date: TziTransition,
+}
+
+impl TimeZoneInformation {
+    /// Size of a `REG_TZI` blob
+    pub const SIZE: usize = 44;
+
+    /// Parse a `REG_TZI` blob
+    ///
+    /// Fails with [`FileTimeError::Malformed`] if a bias puts local time a day
+    /// or more away from UTC, or a transition rule names an invalid date or time.
+    pub fn parse_reg_tzi(bytes: &[u8; 44]) -> Result<Self, FileTimeError> {
+        let int = |offset: usize| {
+            let mut buf = [0u8; 4];
+            buf.copy_from_slice(&bytes[offset..offset + 4]);
+            i32::from_le_bytes(buf)
+        };
+        let (bias, standard_bias, daylight_bias) = (int(0), int(4), int(8));
+        for &(offset, total) in &[
+            (0, i64::from(bias)),
+            (4, i64::from(bias) + i64::from(standard_bias)),
+            (8, i64::from(bias) + i64::from(daylight_bias)),
+        ] {
+            if total.abs() >= i64::from(MINUTES_PER_DAY) {
+                return Err(FileTimeError::Malformed {
+                    format: FORMAT,
+                    offset,
+                    reason: "bias of a day or more",
+                });
+            }
+        }
+        Ok(Self {
+            bias,
+            standard_bias,
+            daylight_bias,
+            standard_date: TziTransition::parse(bytes, 0x0c)?,
+            daylight_date: TziTransition::parse(bytes, 0x1c)?,
+        })
+    }
+
+    /// Minutes to add to local time for UTC, outside of daylight saving time
+    pub const fn bias(&self) -> i32 {
+        self.bias
+    }
+
+    /// Added to [`bias`](Self::bias) during standard time
+    pub const fn standard_bias(&self) -> i32 {
+        self.standard_bias
+    }
+
+    /// Added to [`bias`](Self::bias) during daylight saving time
+    pub const fn daylight_bias(&self) -> i32 {
+        self.daylight_bias
+    }
+
+    /// Transition from daylight saving to standard time
+    pub const fn standard_date(&self) -> TziTransition {
+        self.standard_date
+    }
+
+    /// Transition from standard to daylight saving time
+    pub const fn daylight_date(&self) -> TziTransition {
+        self.daylight_date
+    }
+
+    /// Total bias in effect at the UTC instant `utc`
+    fn bias_at(&self, utc: NaiveDateTime) -> i32 {
+        let standard = self.bias + self.standard_bias;
+        let daylight = self.bias + self.daylight_bias;
+        if self.standard_date.month == 0 || self.daylight_date.month == 0 {
+            return standard;
+        }
+        let year = (utc - Duration::minutes(standard.into())).year();
+        // Each rule is given in the local time in effect before it fires
+        let to_daylight = self
+            .daylight_date
+            .local_in(year)
+            .map(|local| local + Duration::minutes(standard.into()));
+        let to_standard = self
+            .standard_date
+            .local_in(year)
+            .map(|local| local + Duration::minutes(daylight.into()));
+        let is_daylight = match (to_daylight, to_standard) {
+            (Some(start), Some(end)) if start < end => start <= utc && utc < end,
+            // Southern hemisphere, daylight saving time spans the new year
+            (Some(start), Some(end)) => utc < end || start <= utc,
+            _ => false,
+        };
+        if is_daylight {
+            daylight
+        } else {
+            standard
+        }
+    }
+}
+
+impl FileTime {
+    /// Convert to local time of the machine whose time zone is `tzi`
+    ///
+    /// The daylight saving rules are evaluated for the instant's year, so
+    /// the result carries the standard or daylight offset in effect at the
+    /// instant. Zones without daylight saving time always use the standard
+    /// offset. Raw values above [`FileTime::MAX`] fail with
+    /// [`FileTimeError::OutOfRange`].
+    /// ```
+    /// use filetime_type::{FileTime, TimeZoneInformation};
+    ///
+    /// // Tokyo Standard Time, UTC+9 without daylight saving time
+    /// let mut blob = [0u8; 44];
+    /// blob[..4].copy_from_slice(&(-540i32).to_le_bytes());
+    /// let tzi = TimeZoneInformation::parse_reg_tzi(&blob).unwrap();
+    /// let ft = FileTime::from_ymd_hms(2023, 7, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(ft.to_machine_local(&tzi).unwrap().to_rfc3339(), "2023-07-01T09:00:00+09:00");
+    /// ```
+    pub fn to_machine_local(
+        &self,
+        tzi: &TimeZoneInformation,
+    ) -> Result<DateTime<FixedOffset>, FileTimeError> {
+        let utc = self.to_datetime_checked()?;
+        let bias = tzi.bias_at(utc.naive_utc());
+        // parse_reg_tzi keeps every bias within a day
+        let offset = FixedOffset::west_opt(bias * 60).unwrap();
+        Ok(utc.with_timezone(&offset))
+    }
+
+    /// Shift a UTC FileTime to local time, like `FileTimeToLocalFileTime`
+    ///
+    /// The result is not the same instant: it holds the wall clock time of
+    /// `offset` as if it were UTC, the way FAT timestamps, ZIP entries and
+    /// some logs end up in FILETIME fields. To look at an instant in a time
+    /// zone, keep the value and use `to_datetime().with_timezone(&offset)`.
+    /// Unlike the Win32 call, which applies the machine's current bias, the
+    /// offset is given. Fails with [`FileTimeError::BeforeEpoch`] /
+    /// [`FileTimeError::Overflow`] if the shift leaves the FILETIME range.
+    /// ```
+    /// use chrono::FixedOffset;
+    /// use filetime_type::FileTime;
+    ///
+    /// let cest = FixedOffset::east_opt(2 * 3600).unwrap();
+    /// let ft = FileTime::from_ymd_hms(2023, 7, 1, 10, 0, 0).unwrap();
+    /// let local = ft.to_local_filetime(cest).unwrap();
+    /// assert_eq!(local, FileTime::from_ymd_hms(2023, 7, 1, 12, 0, 0).unwrap());
+    /// assert_eq!(local.from_local_filetime(cest), Ok(ft));
+    /// ```
+    pub fn to_local_filetime(&self, offset: FixedOffset) -> Result<FileTime, FileTimeError> {
+        self.shift_seconds(i64::from(offset.local_minus_utc()))
+    }
+
+    /// Shift a local time FileTime back to UTC, like `LocalFileTimeToFileTime`
+    ///
+    /// The inverse of [`to_local_filetime`](Self::to_local_filetime), for
+    /// values that hold the wall clock time of `offset`.
+    pub fn from_local_filetime(&self, offset: FixedOffset) -> Result<FileTime, FileTimeError> {
+        self.shift_seconds(-i64::from(offset.local_minus_utc()))
+    }
+
+    fn shift_seconds(&self, seconds: i64) -> Result<FileTime, FileTimeError> {
+        FileTime::from_ticks_i128(
+            i128::from(self.ticks)
+                + i128::from(seconds) * i128::from(FileTime::HUNDREDS_OF_NANOSECONDS),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// `TZI` of Pacific Standard Time as shipped with Windows: bias 480,
+    /// daylight bias -60, standard time from the 1st Sunday of November 2:00,
+    /// daylight saving time from the 2nd Sunday of March 2:00
+    const PACIFIC: [u8; 44] = [
+        0xe0, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc4, 0xff, 0xff, 0xff, 0x00, 0x00, 0x0b,
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x03, 0x00, 0x00, 0x00, 0x02, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    fn blob(bias: i32, daylight_bias: i32, standard: [u16; 8], daylight: [u16; 8]) -> [u8; 44] {
+        let mut buf = [0u8; 44];
+        buf[0..4].copy_from_slice(&bias.to_le_bytes());
+        buf[8..12].copy_from_slice(&daylight_bias.to_le_bytes());
+        for (i, word) in standard.iter().chain(&daylight).enumerate() {
+            buf[12 + i * 2..14 + i * 2].copy_from_slice(&word.to_le_bytes());
+        }
+        buf
+    }
+
+    fn local(
+        tzi: &TimeZoneInformation,
+        y: i32,
+        m: u32,
+        d: u32,
+        h: u32,
+        min: u32,
+        s: u32,
+    ) -> (NaiveDateTime, i32) {
+        let dt = FileTime::from_ymd_hms(y, m, d, h, min, s)
+            .unwrap()
+            .to_machine_local(tzi)
+            .unwrap();
+        (dt.naive_local(), dt.offset().local_minus_utc() / 3600)
+    }
+
+    fn naive(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, s)
+            .unwrap()
+    }
+
+    #[test]
+    fn pacific() {
+        let tzi = TimeZoneInformation::parse_reg_tzi(&PACIFIC).unwrap();
+        assert_eq!(tzi.bias(), 480);
+        assert_eq!(tzi.daylight_bias(), -60);
+        assert_eq!(tzi.standard_date().month, 11);
+        assert_eq!(tzi.daylight_date().day, 2);
+
+        // 2023-03-12 02:00 PST
+        assert_eq!(
+            local(&tzi, 2023, 3, 12, 9, 59, 59),
+            (naive(2023, 3, 12, 1, 59, 59), -8)
+        );
+        assert_eq!(
+            local(&tzi, 2023, 3, 12, 10, 0, 0),
+            (naive(2023, 3, 12, 3, 0, 0), -7)
+        );
+        // 2023-11-05 02:00 PDT
+        assert_eq!(
+            local(&tzi, 2023, 11, 5, 8, 59, 59),
+            (naive(2023, 11, 5, 1, 59, 59), -7)
+        );
+        assert_eq!(
+            local(&tzi, 2023, 11, 5, 9, 0, 0),
+            (naive(2023, 11, 5, 1, 0, 0), -8)
+        );
+        assert_eq!(
+            local(&tzi, 2024, 1, 1, 0, 0, 0),
+            (naive(2023, 12, 31, 16, 0, 0), -8)
+        );
+    }
+
+    #[test]
+    fn last_week_and_southern_hemisphere() {
+        // W. Europe Standard Time: last Sunday of October 3:00 / of March 2:00
+        let europe = TimeZoneInformation::parse_reg_tzi(&blob(
+            -60,
+            -60,
+            [0, 10, 0, 5, 3, 0, 0, 0],
+            [0, 3, 0, 5, 2, 0, 0, 0],
+        ))
+        .unwrap();
+        // March 2024 has five Sundays, the last is the 31st
+        assert_eq!(
+            local(&europe, 2024, 3, 31, 0, 59, 59),
+            (naive(2024, 3, 31, 1, 59, 59), 1)
+        );
+        assert_eq!(
+            local(&europe, 2024, 3, 31, 1, 0, 0),
+            (naive(2024, 3, 31, 3, 0, 0), 2)
+        );
+        // October 2024 has only four, week 5 falls back to the 27th
+        assert_eq!(
+            local(&europe, 2024, 10, 27, 0, 59, 59),
+            (naive(2024, 10, 27, 2, 59, 59), 2)
+        );
+        assert_eq!(
+            local(&europe, 2024, 10, 27, 1, 0, 0),
+            (naive(2024, 10, 27, 2, 0, 0), 1)
+        );
+
+        // AUS Eastern Standard Time: 1st Sunday of April 3:00 / of October 2:00
+        let sydney = TimeZoneInformation::parse_reg_tzi(&blob(
+            -600,
+            -60,
+            [0, 4, 0, 1, 3, 0, 0, 0],
+            [0, 10, 0, 1, 2, 0, 0, 0],
+        ))
+        .unwrap();
+        assert_eq!(
+            local(&sydney, 2024, 1, 1, 0, 0, 0),
+            (naive(2024, 1, 1, 11, 0, 0), 11)
+        );
+        assert_eq!(
+            local(&sydney, 2024, 4, 6, 16, 0, 0),
+            (naive(2024, 4, 7, 2, 0, 0), 10)
+        );
+        assert_eq!(
+            local(&sydney, 2024, 7, 1, 0, 0, 0),
+            (naive(2024, 7, 1, 10, 0, 0), 10)
+        );
+        assert_eq!(
+            local(&sydney, 2024, 10, 5, 16, 0, 0),
+            (naive(2024, 10, 6, 3, 0, 0), 11)
+        );
+    }
+
+    #[test]
+    fn without_daylight_saving() {
+        // US Mountain Standard Time (Arizona), all-zero rules
+        let arizona = TimeZoneInformation::parse_reg_tzi(&blob(420, -60, [0; 8], [0; 8])).unwrap();
+        assert_eq!(
+            local(&arizona, 2023, 7, 1, 12, 0, 0),
+            (naive(2023, 7, 1, 5, 0, 0), -7)
+        );
+        assert_eq!(
+            FileTime::MAX
+                .to_machine_local(&arizona)
+                .unwrap()
+                .naive_local(),
+            NaiveDate::from_ymd_opt(30828, 9, 13)
+                .unwrap()
+                .and_hms_nano_opt(19, 48, 5, 477_580_700)
+                .unwrap()
+        );
+        assert_eq!(
+            FileTime::MAX_RAW.to_machine_local(&arizona),
+            Err(FileTimeError::OutOfRange("chrono::DateTime"))
+        );
+
+        // One-off rules apply to their year only
+        let one_off = TimeZoneInformation::parse_reg_tzi(&blob(
+            0,
+            -60,
+            [2023, 10, 0, 1, 0, 0, 0, 0],
+            [2023, 4, 0, 1, 0, 0, 0, 0],
+        ))
+        .unwrap();
+        assert_eq!(
+            local(&one_off, 2023, 7, 1, 0, 0, 0),
+            (naive(2023, 7, 1, 1, 0, 0), 1)
+        );
+        assert_eq!(
+            local(&one_off, 2024, 7, 1, 0, 0, 0),
+            (naive(2024, 7, 1, 0, 0, 0), 0)
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let err = |offset, reason| {
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset,
+                reason,
+            })
+        };
+        assert_eq!(
+            TimeZoneInformation::parse_reg_tzi(&blob(1440, 0, [0; 8], [0; 8])),
+            err(0, "bias of a day or more")
+        );
+        assert_eq!(
+            TimeZoneInformation::parse_reg_tzi(&blob(-1380, -60, [0; 8], [0; 8])),
+            err(8, "bias of a day or more")
+        );
+        assert_eq!(
+            TimeZoneInformation::parse_reg_tzi(&blob(0, 0, [0, 13, 0, 1, 0, 0, 0, 0], [0; 8])),
+            err(0x0e, "month out of range")
+        );
+        assert_eq!(
+            TimeZoneInformation::parse_reg_tzi(&blob(0, 0, [0; 8], [0, 3, 0, 6, 2, 0, 0, 0])),
+            err(0x22, "week of month out of range")
+        );
+        assert_eq!(
+            TimeZoneInformation::parse_reg_tzi(&blob(0, 0, [0; 8], [2023, 2, 0, 29, 2, 0, 0, 0])),
+            err(0x22, "day does not exist in the given month")
+        );
+        assert_eq!(
+            TimeZoneInformation::parse_reg_tzi(&blob(0, 0, [0, 3, 0, 1, 24, 0, 0, 0], [0; 8])),
+            err(0x14, "time of day out of range")
+        );
+    }
+
+    #[test]
+    fn local_filetime() {
+        let hour = 3600 * FileTime::HUNDREDS_OF_NANOSECONDS;
+        let kiribati = FixedOffset::east_opt(14 * 3600).unwrap();
+        let baker = FixedOffset::west_opt(12 * 3600).unwrap();
+        let ft = FileTime::from_i64(130139712831482830);
+        let ahead = ft.to_local_filetime(kiribati).unwrap();
+        assert_eq!(ahead.filetime(), 130139712831482830 + 14 * hour);
+        assert_eq!(ahead.from_local_filetime(kiribati), Ok(ft));
+        let behind = ft.to_local_filetime(baker).unwrap();
+        assert_eq!(behind.filetime(), 130139712831482830 - 12 * hour);
+        assert_eq!(behind.from_local_filetime(baker), Ok(ft));
+
+        // The instant is kept by with_timezone, not by the shift
+        let utc = FixedOffset::east_opt(0).unwrap();
+        assert_eq!(ft.to_local_filetime(utc), Ok(ft));
+        assert_eq!(
+            ft.to_datetime().with_timezone(&kiribati).naive_local(),
+            ahead.to_datetime().naive_utc()
+        );
+
+        let near_epoch = FileTime::from_i64(11 * hour);
+        assert_eq!(
+            near_epoch.to_local_filetime(baker),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            near_epoch.from_local_filetime(kiribati),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::from_i64(12 * hour).to_local_filetime(baker),
+            Ok(FileTime::MIN)
+        );
+        assert_eq!(
+            FileTime::MAX.to_local_filetime(kiribati),
+            Err(FileTimeError::Overflow)
+        );
+    }
+}
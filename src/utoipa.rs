@@ -0,0 +1,54 @@
+//! utoipa OpenAPI schema for FileTime
+//!
+//! FileTime is documented as `int64` holding raw ticks, the representation
+//! FileTime is serialized as. Any other serialized shape must be reflected here.
+use crate::FileTime;
+use std::borrow::Cow;
+use utoipa::openapi::schema::{KnownFormat, ObjectBuilder, Schema, SchemaFormat, Type};
+use utoipa::openapi::RefOr;
+
+impl utoipa::PartialSchema for FileTime {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::Integer)
+            .format(Some(SchemaFormat::KnownFormat(KnownFormat::Int64)))
+            .minimum(Some(0))
+            .description(Some(
+                "Windows FILETIME: number of 100-nanosecond intervals since 1601-01-01T00:00:00Z",
+            ))
+            // 2009-07-25T23:00:00.0000010Z
+            .examples([128930364000000010i64])
+            .into()
+    }
+}
+
+impl utoipa::ToSchema for FileTime {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("FileTime")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ::utoipa::{OpenApi, ToSchema};
+
+    #[allow(dead_code)]
+    #[derive(ToSchema)]
+    struct FileInfo {
+        modified: FileTime,
+    }
+
+    #[derive(OpenApi)]
+    #[openapi(components(schemas(FileInfo)))]
+    struct ApiDoc;
+
+    #[test]
+    fn openapi_document() {
+        let doc = ApiDoc::openapi().to_json().unwrap();
+        assert!(doc.contains(r##""modified":{"$ref":"#/components/schemas/FileTime"}"##));
+        assert!(doc.contains(
+            r#""FileTime":{"type":"integer","format":"int64","description":"Windows FILETIME: number of 100-nanosecond intervals since 1601-01-01T00:00:00Z","examples":[128930364000000010],"minimum":0}"#
+        ), "{}", doc);
+    }
+}
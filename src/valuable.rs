@@ -0,0 +1,105 @@
+//! valuable `Valuable` for FileTime, for structured logging with `tracing`
+//!
+//! FileTime is a structable named `FileTime` with two fields, kept stable:
+//!
+//! - `ticks`: the raw tick count as `u64`
+//! - `rfc3339`: the instant as RFC 3339 string with 7 fraction digits, as
+//!   from [`FileTime::to_rfc3339`], unit for raw values above [`FileTime::MAX`]
+use crate::FileTime;
+use ::valuable::{Fields, NamedField, NamedValues, StructDef, Structable, Valuable, Value, Visit};
+
+const FIELDS: &[NamedField<'static>] = &[NamedField::new("ticks"), NamedField::new("rfc3339")];
+
+impl Valuable for FileTime {
+    fn as_value(&self) -> Value<'_> {
+        Value::Structable(self)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        let rfc3339 = self.to_rfc3339().ok();
+        let values = [
+            Value::U64(self.ticks),
+            rfc3339.as_deref().map_or(Value::Unit, Value::String),
+        ];
+        visit.visit_named_fields(&NamedValues::new(FIELDS, &values));
+    }
+}
+
+impl Structable for FileTime {
+    fn definition(&self) -> StructDef<'_> {
+        StructDef::new_static("FileTime", Fields::Named(FIELDS))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Default)]
+    struct Collect {
+        fields: Vec<(String, String)>,
+    }
+
+    impl Visit for Collect {
+        fn visit_value(&mut self, value: Value<'_>) {
+            if let Value::Structable(s) = value {
+                s.visit(self);
+            }
+        }
+
+        fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+            for (field, value) in named_values {
+                let value = match value {
+                    Value::U64(ticks) => ticks.to_string(),
+                    Value::String(s) => s.to_string(),
+                    Value::Unit => "()".to_owned(),
+                    other => panic!("unexpected value {:?}", other),
+                };
+                self.fields.push((field.name().to_owned(), value));
+            }
+        }
+    }
+
+    #[test]
+    fn fields() {
+        let ft = FileTime::from_i64(130139712831482830);
+        let mut collect = Collect::default();
+        ::valuable::visit(&ft, &mut collect);
+        assert_eq!(
+            collect.fields,
+            [
+                ("ticks".to_owned(), "130139712831482830".to_owned()),
+                (
+                    "rfc3339".to_owned(),
+                    "2013-05-25T16:01:23.1482830Z".to_owned()
+                ),
+            ]
+        );
+
+        let mut collect = Collect::default();
+        ::valuable::visit(&FileTime::MAX_RAW, &mut collect);
+        assert_eq!(
+            collect.fields,
+            [
+                ("ticks".to_owned(), "18446744073709551615".to_owned()),
+                ("rfc3339".to_owned(), "()".to_owned()),
+            ]
+        );
+        let def = ft.definition();
+        assert_eq!(def.name(), "FileTime");
+        assert!(matches!(def.fields(), Fields::Named(fields) if fields.len() == 2));
+    }
+
+    #[test]
+    fn nested() {
+        struct Event {
+            modified: FileTime,
+        }
+        let event = Event {
+            modified: FileTime::MIN,
+        };
+        let mut collect = Collect::default();
+        collect.visit_value(event.modified.as_value());
+        assert_eq!(collect.fields[1].1, "1601-01-01T00:00:00.0000000Z");
+    }
+}
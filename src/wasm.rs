@@ -0,0 +1,81 @@
+//! Interop with JavaScript `Date` objects via `js-sys`
+use crate::{FileTime, FileTimeError};
+
+impl FileTime {
+    /// Construct FileTime from a JavaScript `Date`
+    ///
+    /// Fails for invalid dates (`getTime()` returning `NaN`) and for
+    /// instants before the FILETIME-Epoch.
+    pub fn from_js_date(date: &js_sys::Date) -> Result<Self, FileTimeError> {
+        Self::from_js_millis(date.get_time())
+    }
+
+    /// Return FileTime as JavaScript `Date`
+    ///
+    /// `Date` only carries millisecond precision, sub-millisecond ticks are truncated.
+    pub fn to_js_date(&self) -> js_sys::Date {
+        let date = js_sys::Date::new_0();
+        date.set_time(self.to_js_millis());
+        date
+    }
+
+    /// Construct FileTime from a millisecond time value as returned by
+    /// `Date.prototype.getTime()` or `Date.now()`
+    ///
+    /// Fractional milliseconds are truncated.
+    pub fn from_js_millis(millis: f64) -> Result<Self, FileTimeError> {
+        if !millis.is_finite() {
+            return Err(FileTimeError::NotFinite);
+        }
+        Self::from_unix_millis(millis.floor() as i64)
+    }
+
+    /// Return milliseconds since the Unix-Epoch as f64, matching `Date.prototype.getTime()`
+    ///
+    /// Sub-millisecond ticks are truncated. The whole FILETIME range fits into
+    /// the 53 bit mantissa of f64 at millisecond resolution, so no further
+    /// precision is lost.
+    pub fn to_js_millis(&self) -> f64 {
+        self.to_unix_millis() as f64
+    }
+}
+
+/// Current time from the JavaScript host, `SystemTime` is not available on wasm32-unknown-unknown
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub(crate) fn now() -> FileTime {
+    FileTime::from_js_millis(js_sys::Date::now()).expect("JavaScript clock before 1601")
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod test {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn js_date_roundtrip() {
+        let ft = FileTime::from_i64(128930364000001000);
+        let date = ft.to_js_date();
+        assert_eq!(date.get_time(), 1248562800000.0);
+        assert_eq!(
+            date.to_iso_string().as_string().unwrap(),
+            "2009-07-25T23:00:00.000Z"
+        );
+        // Sub-millisecond ticks are lost
+        assert_eq!(
+            FileTime::from_js_date(&date),
+            Ok(FileTime::from_i64(128930364000000000))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn js_date_invalid() {
+        let date = js_sys::Date::new(&"garbage".into());
+        assert_eq!(FileTime::from_js_date(&date), Err(FileTimeError::NotFinite));
+    }
+
+    #[wasm_bindgen_test]
+    fn js_now() {
+        let ft = FileTime::now();
+        assert!((ft.to_js_millis() - js_sys::Date::now()).abs() < 1000.0);
+    }
+}
@@ -0,0 +1,54 @@
+//! Current time straight from Windows as FILETIME
+//!
+//! `GetSystemTimePreciseAsFileTime` (Windows 8 and later, like every
+//! current Rust Windows target requires) returns the system time with the
+//! full 100ns resolution, saving the conversions through `SystemTime` and
+//! chrono.
+use crate::FileTime;
+
+/// Win32 `FILETIME`
+#[repr(C)]
+struct RawFileTime {
+    low: u32,
+    high: u32,
+}
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn GetSystemTimePreciseAsFileTime(system_time: *mut RawFileTime);
+}
+
+pub(crate) fn now() -> FileTime {
+    let mut raw = RawFileTime { low: 0, high: 0 };
+    // SAFETY: the function has no preconditions and only writes the
+    // FILETIME behind the pointer, which is valid for writes
+    unsafe { GetSystemTimePreciseAsFileTime(&mut raw) };
+    FileTime::from_parts(raw.low, raw.high)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::SystemTime;
+
+    #[test]
+    fn matches_system_time() {
+        let before = SystemTime::now();
+        let ft = now();
+        let after = SystemTime::now();
+        assert!(ft >= before && ft <= after, "{:?}", ft);
+    }
+
+    #[test]
+    fn resolution() {
+        // Successive calls are well below a millisecond apart
+        let close = (0..100)
+            .filter(|_| {
+                let (a, b) = (now(), now());
+                b.filetime() - a.filetime() < 10_000
+            })
+            .count();
+        assert!(close > 50, "{}", close);
+        assert!(FileTime::now() <= now());
+    }
+}
@@ -0,0 +1,308 @@
+//! XML Schema `xs:dateTime`, as used by EWS, event log XML and scheduled tasks
+//!
+//! `[-]YYYY-MM-DDThh:mm:ss[.s+][Z|(+|-)hh:mm]`: years may have more than
+//! four digits and a sign, the fraction any number of digits and the
+//! timezone may be missing, all unlike RFC 3339.
+use crate::parse;
+use crate::{FileTime, FileTimeError};
+use chrono::{Datelike, Duration, Timelike};
+
+const XSD_DATETIME: &str = "xs:dateTime";
+
+/// How [`FileTime::parse_xsd_datetime_with`], [`FileTime::parse_pdf_date_with`]
+/// and [`FileTime::parse_exif`] treat values without timezone
+///
+/// XML Schema, PDF and EXIF leave their relation to UTC undefined, so by default they are rejected.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Default)]
+pub enum MissingTimezone {
+    /// Fail with [`FileTimeError::InvalidFormat`]
+    #[default]
+    Reject,
+    /// Take the value as UTC
+    AssumeUtc,
+}
+
+impl FileTime {
+    /// Parse an `xs:dateTime`, rejecting values without timezone
+    ///
+    /// Same as [`parse_xsd_datetime_with`](Self::parse_xsd_datetime_with) with
+    /// [`MissingTimezone::Reject`].
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::parse_xsd_datetime("2013-05-25T18:01:23.148283-07:00").unwrap();
+    /// assert_eq!(ft.to_xsd_datetime().unwrap(), "2013-05-26T01:01:23.148283Z");
+    /// assert!(FileTime::parse_xsd_datetime("2013-05-25T18:01:23").is_err());
+    /// ```
+    pub fn parse_xsd_datetime(s: &str) -> Result<Self, FileTimeError> {
+        Self::parse_xsd_datetime_with(s, MissingTimezone::Reject)
+    }
+
+    /// Parse an `xs:dateTime`, handling a missing timezone as `missing` says
+    ///
+    /// Fraction digits beyond tick precision are truncated. `24:00:00` is
+    /// the start of the next day. Negative years fail with
+    /// [`FileTimeError::BeforeEpoch`], years after 30828 with
+    /// [`FileTimeError::Overflow`], malformed input with
+    /// [`FileTimeError::InvalidFormat`].
+    pub fn parse_xsd_datetime_with(
+        s: &str,
+        missing: MissingTimezone,
+    ) -> Result<Self, FileTimeError> {
+        let invalid = |reason| FileTimeError::InvalidFormat {
+            format: XSD_DATETIME,
+            reason,
+        };
+        let (negative, b) = match s.as_bytes() {
+            [b'-', rest @ ..] => (true, rest),
+            b => (false, b),
+        };
+        let year_len = b
+            .iter()
+            .position(|c| !c.is_ascii_digit())
+            .unwrap_or(b.len());
+        if year_len < 4 || year_len > 4 && b[0] == b'0' {
+            return Err(invalid("expected a year of four or more digits"));
+        }
+        let (year, b) = b.split_at(year_len);
+        if b.len() < 15 || !parse::matches_layout(&b[..15], "-dd-ddTdd:dd:dd") {
+            return Err(invalid("expected -MM-DDThh:mm:ss after the year"));
+        }
+        let (fields, mut rest) = b.split_at(15);
+        let mut nanos = 0;
+        if let [b'.', fraction @ ..] = rest {
+            let len = fraction
+                .iter()
+                .position(|c| !c.is_ascii_digit())
+                .unwrap_or(fraction.len());
+            nanos = parse::fraction_nanos(&fraction[..len])
+                .ok_or_else(|| invalid("expected fraction digits after '.'"))?;
+            rest = &fraction[len..];
+        }
+        let offset_minutes = match rest {
+            [] if missing == MissingTimezone::AssumeUtc => 0,
+            [] => return Err(invalid("missing timezone")),
+            b"Z" => 0,
+            [sign @ (b'+' | b'-'), tz @ ..] if parse::matches_layout(tz, "dd:dd") => {
+                let hours = i64::from(parse::digits(&tz[0..2]).unwrap());
+                let minutes = i64::from(parse::digits(&tz[3..5]).unwrap());
+                if minutes > 59 || hours * 60 + minutes > 14 * 60 {
+                    return Err(invalid("timezone beyond ±14:00"));
+                }
+                if *sign == b'-' {
+                    -(hours * 60 + minutes)
+                } else {
+                    hours * 60 + minutes
+                }
+            }
+            _ => return Err(invalid("expected Z or ±hh:mm timezone")),
+        };
+
+        if negative {
+            return Err(FileTimeError::BeforeEpoch);
+        }
+        // chrono's year range ends at 262143, far beyond FILETIME's 30828
+        let year = match parse::digits(year) {
+            Some(year) if year <= 262_142 => year as i32,
+            _ => return Err(FileTimeError::Overflow),
+        };
+        let field = |range: core::ops::Range<usize>| parse::digits(&fields[range]).unwrap();
+        let (hour, end_of_day) = match (field(7..9), field(10..12), field(13..15), nanos) {
+            (24, 0, 0, 0) => (0, Duration::days(1)),
+            (hour, ..) => (hour, Duration::zero()),
+        };
+        let local = parse::datetime(
+            year,
+            field(1..3),
+            field(4..6),
+            hour,
+            field(10..12),
+            field(13..15),
+            nanos,
+        )
+        .ok_or_else(|| invalid("invalid date or time"))?;
+        let utc = local
+            .checked_add_signed(end_of_day)
+            .and_then(|dt| dt.checked_sub_signed(Duration::minutes(offset_minutes)))
+            .ok_or(FileTimeError::Overflow)?;
+        Self::from_datetime_checked(utc)
+    }
+
+    /// Format as canonical `xs:dateTime` in UTC
+    ///
+    /// The fraction has only as many digits as needed, down to 100ns, and is
+    /// left out for whole seconds. Years after 9999 get five digits, which
+    /// XML Schema allows. Raw values above [`FileTime::MAX`] fail with
+    /// [`FileTimeError::OutOfRange`].
+    /// ```
+    /// use filetime_type::FileTime;
+    ///
+    /// let ft = FileTime::from_i64(130139712831482830);
+    /// assert_eq!(ft.to_xsd_datetime().unwrap(), "2013-05-25T16:01:23.148283Z");
+    /// assert_eq!(FileTime::MIN.to_xsd_datetime().unwrap(), "1601-01-01T00:00:00Z");
+    /// ```
+    pub fn to_xsd_datetime(&self) -> Result<String, FileTimeError> {
+        let dt = self
+            .to_datetime_checked()
+            .map_err(|_| FileTimeError::OutOfRange(XSD_DATETIME))?;
+        // chrono's %Y would put a '+' before five digit years
+        let mut s = format!("{:04}{}", dt.year(), dt.format("-%m-%dT%H:%M:%S"));
+        let ticks = format!("{:07}", dt.nanosecond() / 100);
+        let fraction = ticks.trim_end_matches('0');
+        if !fraction.is_empty() {
+            s.push('.');
+            s.push_str(fraction);
+        }
+        s.push('Z');
+        Ok(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn invalid(reason: &'static str) -> Result<FileTime, FileTimeError> {
+        Err(FileTimeError::InvalidFormat {
+            format: XSD_DATETIME,
+            reason,
+        })
+    }
+
+    #[test]
+    fn fraction_digits() {
+        // EWS style, 12 digits are truncated to 7
+        let ft = FileTime::parse_xsd_datetime("2013-05-25T16:01:23.148283099999Z").unwrap();
+        assert_eq!(ft, FileTime::from_i64(130139712831482830));
+        assert_eq!(ft.to_xsd_datetime().unwrap(), "2013-05-25T16:01:23.148283Z");
+        assert_eq!(
+            FileTime::parse_xsd_datetime("2013-05-25T16:01:23.1Z"),
+            Ok(FileTime::from_i64(130139712831000000))
+        );
+        assert_eq!(
+            FileTime::parse_xsd_datetime("2013-05-25T16:01:23Z"),
+            Ok(FileTime::from_i64(130139712830000000))
+        );
+        assert_eq!(
+            FileTime::parse_xsd_datetime("2013-05-25T16:01:23.Z"),
+            invalid("expected fraction digits after '.'")
+        );
+    }
+
+    #[test]
+    fn missing_timezone() {
+        let s = "2023-04-01T10:30:00.5";
+        assert_eq!(FileTime::parse_xsd_datetime(s), invalid("missing timezone"));
+        assert_eq!(
+            FileTime::parse_xsd_datetime_with(s, MissingTimezone::AssumeUtc),
+            FileTime::from_ymd_hms_nano(2023, 4, 1, 10, 30, 0, 500_000_000)
+        );
+        assert_eq!(MissingTimezone::default(), MissingTimezone::Reject);
+    }
+
+    #[test]
+    fn timezones() {
+        let utc = FileTime::parse_xsd_datetime("2023-04-01T10:30:00Z").unwrap();
+        for s in &[
+            "2023-04-01T10:30:00+00:00",
+            "2023-04-01T12:30:00+02:00",
+            "2023-04-01T00:00:00-10:30",
+            "2023-04-02T00:30:00+14:00",
+        ] {
+            assert_eq!(FileTime::parse_xsd_datetime(s), Ok(utc), "{}", s);
+        }
+        assert_eq!(
+            FileTime::parse_xsd_datetime("2023-04-01T10:30:00+14:01"),
+            invalid("timezone beyond ±14:00")
+        );
+        assert_eq!(
+            FileTime::parse_xsd_datetime("2023-04-01T10:30:00+0200"),
+            invalid("expected Z or ±hh:mm timezone")
+        );
+    }
+
+    #[test]
+    fn years() {
+        // FILETIME reaches into the year 30828, so five digit years parse
+        let ft = FileTime::parse_xsd_datetime("20000-01-01T00:00:00Z").unwrap();
+        assert_eq!(ft, FileTime::from_ymd_hms(20000, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(ft.to_xsd_datetime().unwrap(), "20000-01-01T00:00:00Z");
+        assert_eq!(
+            FileTime::MAX.to_xsd_datetime().unwrap(),
+            "30828-09-14T02:48:05.4775807Z"
+        );
+        assert_eq!(
+            FileTime::MAX_RAW.to_xsd_datetime(),
+            Err(FileTimeError::OutOfRange(XSD_DATETIME))
+        );
+        assert_eq!(
+            FileTime::parse_xsd_datetime("30828-09-14T02:48:05.4775807Z"),
+            Ok(FileTime::MAX)
+        );
+        assert_eq!(
+            FileTime::parse_xsd_datetime("30829-01-01T00:00:00Z"),
+            Err(FileTimeError::Overflow)
+        );
+        assert_eq!(
+            FileTime::parse_xsd_datetime("2000000000-01-01T00:00:00Z"),
+            Err(FileTimeError::Overflow)
+        );
+        // The end of chrono's range, shifted past it by 24:00 or the offset
+        for s in &["262142-12-31T24:00:00Z", "262142-12-31T23:59:59-14:00"] {
+            assert_eq!(
+                FileTime::parse_xsd_datetime(s),
+                Err(FileTimeError::Overflow),
+                "{}",
+                s
+            );
+        }
+        assert_eq!(
+            FileTime::parse_xsd_datetime("-0044-03-15T12:00:00Z"),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::parse_xsd_datetime("1600-12-31T23:59:59Z"),
+            Err(FileTimeError::BeforeEpoch)
+        );
+        assert_eq!(
+            FileTime::parse_xsd_datetime("02023-04-01T10:30:00Z"),
+            invalid("expected a year of four or more digits")
+        );
+        assert_eq!(
+            FileTime::parse_xsd_datetime("923-04-01T10:30:00Z"),
+            invalid("expected a year of four or more digits")
+        );
+    }
+
+    #[test]
+    fn end_of_day() {
+        assert_eq!(
+            FileTime::parse_xsd_datetime("2023-12-31T24:00:00Z"),
+            FileTime::from_ymd_hms(2024, 1, 1, 0, 0, 0)
+        );
+        assert_eq!(
+            FileTime::parse_xsd_datetime("2023-12-31T24:00:01Z"),
+            invalid("invalid date or time")
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            FileTime::parse_xsd_datetime("2023-04-01 10:30:00Z"),
+            invalid("expected -MM-DDThh:mm:ss after the year")
+        );
+        assert_eq!(
+            FileTime::parse_xsd_datetime("2023-04-01"),
+            invalid("expected -MM-DDThh:mm:ss after the year")
+        );
+        assert_eq!(
+            FileTime::parse_xsd_datetime("2023-02-29T10:30:00Z"),
+            invalid("invalid date or time")
+        );
+        assert_eq!(
+            FileTime::parse_xsd_datetime("2023-04-01T10:30:00Zjunk"),
+            invalid("expected Z or ±hh:mm timezone")
+        );
+    }
+}
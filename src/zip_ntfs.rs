@@ -0,0 +1,256 @@
+//! ZIP "NTFS" extra field (header ID 0x000a)
+//!
+//! Archives created on Windows carry the modification, access and creation
+//! FILETIMEs of each entry in this extra field (PKWARE APPNOTE 4.5.5):
+//!
+//! ```text
+//! 0x000a  u16  header ID
+//!         u16  data size
+//!         u32  reserved
+//!         attributes: u16 tag, u16 size, data
+//!           tag 0x0001, size 24: mtime, atime, ctime as FILETIME
+//! ```
+use crate::{FileTime, FileTimeError};
+
+const FORMAT: &str = "ZIP NTFS extra field";
+const HEADER_ID: u16 = 0x000a;
+const TAG_TIMES: u16 = 0x0001;
+
+/// Timestamps stored in the ZIP NTFS extra field
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct NtfsExtraTimes {
+    /// Last modification time
+    pub modified: FileTime,
+    /// Last access time
+    pub accessed: FileTime,
+    /// Creation time
+    pub created: FileTime,
+}
+
+fn read_u16(buf: &[u8], offset: usize) -> Result<u16, FileTimeError> {
+    buf.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or(FileTimeError::Malformed {
+            format: FORMAT,
+            offset,
+            reason: "truncated header",
+        })
+}
+
+fn read_filetime(buf: &[u8], offset: usize) -> Result<FileTime, FileTimeError> {
+    let mut raw = [0u8; 8];
+    raw.copy_from_slice(&buf[offset..offset + 8]);
+    FileTime::from_i64_checked(i64::from_le_bytes(raw)).map_err(|_| FileTimeError::Malformed {
+        format: FORMAT,
+        offset,
+        reason: "negative FILETIME",
+    })
+}
+
+/// Parse the NTFS timestamps from a ZIP extra field
+///
+/// `extra` is the complete extra field of a local or central directory
+/// header; other extra field records before or after the NTFS record are
+/// skipped, as are attributes other than tag 1 inside of it.
+///
+/// Truncated records and attributes fail with [`FileTimeError::Malformed`]
+/// holding the offset into `extra`, a missing NTFS record or timestamp
+/// attribute with [`FileTimeError::InvalidFormat`].
+pub fn parse_ntfs_extra_field(extra: &[u8]) -> Result<NtfsExtraTimes, FileTimeError> {
+    let mut offset = 0;
+    while offset < extra.len() {
+        let id = read_u16(extra, offset)?;
+        let size = usize::from(read_u16(extra, offset + 2)?);
+        let data = offset + 4;
+        let end = data + size;
+        if end > extra.len() {
+            return Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: offset + 2,
+                reason: "record size exceeds extra field",
+            });
+        }
+        if id == HEADER_ID {
+            return parse_attributes(&extra[..end], data);
+        }
+        offset = end;
+    }
+    Err(FileTimeError::InvalidFormat {
+        format: FORMAT,
+        reason: "no NTFS extra field record",
+    })
+}
+
+/// Walk the attributes of the NTFS record, `record` ends with the record
+fn parse_attributes(record: &[u8], data: usize) -> Result<NtfsExtraTimes, FileTimeError> {
+    // Skip the reserved u32
+    let mut offset = data + 4;
+    if offset > record.len() {
+        return Err(FileTimeError::Malformed {
+            format: FORMAT,
+            offset: data,
+            reason: "truncated reserved field",
+        });
+    }
+    while offset < record.len() {
+        let tag = read_u16(record, offset)?;
+        let size = usize::from(read_u16(record, offset + 2)?);
+        let attr = offset + 4;
+        if attr + size > record.len() {
+            return Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: offset + 2,
+                reason: "attribute size exceeds record",
+            });
+        }
+        if tag == TAG_TIMES {
+            if size != 24 {
+                return Err(FileTimeError::Malformed {
+                    format: FORMAT,
+                    offset: offset + 2,
+                    reason: "timestamp attribute size is not 24",
+                });
+            }
+            return Ok(NtfsExtraTimes {
+                modified: read_filetime(record, attr)?,
+                accessed: read_filetime(record, attr + 8)?,
+                created: read_filetime(record, attr + 16)?,
+            });
+        }
+        offset = attr + size;
+    }
+    Err(FileTimeError::InvalidFormat {
+        format: FORMAT,
+        reason: "no timestamp attribute (tag 1)",
+    })
+}
+
+/// Build the canonical 36 byte NTFS extra field record holding `times`
+pub fn build_ntfs_extra_field(times: &NtfsExtraTimes) -> Vec<u8> {
+    let mut out = Vec::with_capacity(36);
+    out.extend_from_slice(&HEADER_ID.to_le_bytes());
+    out.extend_from_slice(&32u16.to_le_bytes());
+    out.extend_from_slice(&[0; 4]);
+    out.extend_from_slice(&TAG_TIMES.to_le_bytes());
+    out.extend_from_slice(&24u16.to_le_bytes());
+    for ft in [times.modified, times.accessed, times.created].iter() {
+        out.extend_from_slice(&<[u8; 8]>::from(*ft));
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Central directory extra field of `中文.txt` in
+    /// `tests/test_inputs/sample_data_utf8_extra.zip` of the async_zip 0.0.19
+    /// crate (SHA-256 64c1823f3e2b834ba0514db80ddac882f28155314ed0e499688ef172ac34dab8):
+    /// NTFS record followed by an Info-ZIP Unicode Path "up" (0x7075) record
+    const EXTRA: [u8; 55] = [
+        0x0A, 0x00, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x18, 0x00, // header
+        0xAA, 0x59, 0xED, 0x07, 0x1C, 0xCC, 0xD9, 0x01, // mtime
+        0xAA, 0x59, 0xED, 0x07, 0x1C, 0xCC, 0xD9, 0x01, // atime
+        0xAA, 0x59, 0xED, 0x07, 0x1C, 0xCC, 0xD9, 0x01, // ctime
+        0x75, 0x70, 0x0F, 0x00, 0x01, 0x32, 0xCC, 0x7F, 0x9A, // up: version, CRC
+        0xE4, 0xB8, 0xAD, 0xE6, 0x96, 0x87, 0x2E, 0x74, 0x78, 0x74, // up: UTF-8 name
+    ];
+
+    fn times() -> NtfsExtraTimes {
+        let ft = FileTime::from_i64(133362084748286378);
+        NtfsExtraTimes {
+            modified: ft,
+            accessed: ft,
+            created: ft,
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let parsed = parse_ntfs_extra_field(&EXTRA).unwrap();
+        assert_eq!(parsed, times());
+        assert_eq!(
+            parsed.modified.to_datetime().to_rfc3339(),
+            "2023-08-11T06:21:14.828637800+00:00"
+        );
+    }
+
+    #[test]
+    fn build() {
+        let built = build_ntfs_extra_field(&times());
+        assert_eq!(built.len(), 36);
+        assert_eq!(&built[..], &EXTRA[..36]);
+        assert_eq!(parse_ntfs_extra_field(&built).unwrap(), times());
+
+        // Field order, with made-up distinct times
+        let distinct = NtfsExtraTimes {
+            modified: FileTime::from_i64(3),
+            accessed: FileTime::from_i64(2),
+            created: FileTime::from_i64(1),
+        };
+        let built = build_ntfs_extra_field(&distinct);
+        assert_eq!(built[12..13], [3]);
+        assert_eq!(built[20..21], [2]);
+        assert_eq!(built[28..29], [1]);
+        assert_eq!(parse_ntfs_extra_field(&built).unwrap(), distinct);
+    }
+
+    #[test]
+    fn extra_attributes() {
+        let mut extra = vec![0x0A, 0x00, 0x26, 0x00, 0x00, 0x00, 0x00, 0x00];
+        // Unknown attribute before tag 1
+        extra.extend_from_slice(&[0x02, 0x00, 0x02, 0x00, 0xAA, 0xBB]);
+        extra.extend_from_slice(&EXTRA[8..36]);
+        // .. and a leading "up" record
+        let mut full = EXTRA[36..].to_vec();
+        full.extend_from_slice(&extra);
+        assert_eq!(parse_ntfs_extra_field(&full).unwrap(), times());
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            parse_ntfs_extra_field(&EXTRA[..30]),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 2,
+                reason: "record size exceeds extra field",
+            })
+        );
+        assert_eq!(
+            parse_ntfs_extra_field(&EXTRA[..3]),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 2,
+                reason: "truncated header",
+            })
+        );
+
+        let mut bad_attr = EXTRA[..36].to_vec();
+        bad_attr[10] = 0x20;
+        assert_eq!(
+            parse_ntfs_extra_field(&bad_attr),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 10,
+                reason: "attribute size exceeds record",
+            })
+        );
+
+        let mut negative = EXTRA[..36].to_vec();
+        negative[27] = 0x80;
+        assert_eq!(
+            parse_ntfs_extra_field(&negative),
+            Err(FileTimeError::Malformed {
+                format: FORMAT,
+                offset: 20,
+                reason: "negative FILETIME",
+            })
+        );
+
+        assert!(matches!(
+            parse_ntfs_extra_field(&EXTRA[36..]),
+            Err(FileTimeError::InvalidFormat { .. })
+        ));
+    }
+}
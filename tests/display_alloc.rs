@@ -0,0 +1,97 @@
+//! FileTime::display() and format_into() must not allocate, checked with a counting allocator
+//!
+//! Lives in its own test binary as the allocator is global.
+use filetime_type::{FileTime, FormatStyle};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Write;
+
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Fixed size buffer, writing to it never allocates
+struct Buf {
+    data: [u8; 128],
+    len: usize,
+}
+
+impl Write for Buf {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let end = self.len + s.len();
+        self.data
+            .get_mut(self.len..end)
+            .ok_or(std::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[test]
+fn display_does_not_allocate() {
+    let ft = FileTime::from_i64(128930364001234567);
+    let mut buf = Buf {
+        data: [0; 128],
+        len: 0,
+    };
+
+    let before = ALLOCATIONS.with(Cell::get);
+    let display = ft.display().precision(9).separator(' ').raw(true);
+    write!(buf, "{}", display).unwrap();
+    let after = ALLOCATIONS.with(Cell::get);
+
+    assert_eq!(after, before);
+    assert_eq!(
+        std::str::from_utf8(&buf.data[..buf.len]).unwrap(),
+        "2009-07-25 23:00:00.123456700Z (128930364001234567)"
+    );
+}
+
+#[test]
+fn format_into_does_not_allocate() {
+    let ft = FileTime::from_i64(128930364001234567);
+    let mut styles = Buf {
+        data: [0; 128],
+        len: 0,
+    };
+    let mut display = Buf {
+        data: [0; 128],
+        len: 0,
+    };
+
+    let before = ALLOCATIONS.with(Cell::get);
+    ft.format_into(&mut styles, FormatStyle::Rfc3339).unwrap();
+    ft.format_into(&mut styles, FormatStyle::CompactSortable)
+        .unwrap();
+    ft.format_into(&mut styles, FormatStyle::RawDecimal)
+        .unwrap();
+    write!(display, "{}", ft).unwrap();
+    let after = ALLOCATIONS.with(Cell::get);
+
+    assert_eq!(after, before);
+    assert_eq!(
+        std::str::from_utf8(&styles.data[..styles.len]).unwrap(),
+        "2009-07-25T23:00:00.1234567Z200907252300001234567128930364001234567"
+    );
+    assert_eq!(
+        std::str::from_utf8(&display.data[..display.len]).unwrap(),
+        "DateTime=2009-07-25 23:00:00.1234567 UTC secs=12893036400 nsecs=123456700"
+    );
+}
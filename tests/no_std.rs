@@ -0,0 +1,91 @@
+//! Exercises the core API from a `#![no_std]` crate
+//!
+//! Run with `cargo test --no-default-features --tests` to check that none of
+//! it depends on the `std` feature; formatting goes through a fixed buffer.
+#![no_std]
+
+use chrono::{TimeZone, Utc};
+use core::fmt::{self, Write};
+use filetime_type::{FileTime, FileTimeDelta, FileTimeError, FileTimeRange};
+
+struct Buf {
+    bytes: [u8; 128],
+    len: usize,
+}
+
+impl Buf {
+    fn new() -> Self {
+        Self {
+            bytes: [0; 128],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
+
+impl Write for Buf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.bytes.len() {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[test]
+fn i64_and_bytes() {
+    let ft = FileTime::from_i64(130139712831482830);
+    assert_eq!(ft.seconds(), 13013971283);
+    assert_eq!(ft.nanoseconds(), 148283000);
+    let raw: [u8; 8] = ft.into();
+    assert_eq!(raw, [0xCE, 0xEB, 0x7D, 0x1A, 0x61, 0x59, 0xCE, 0x01]);
+    assert_eq!(FileTime::from(raw), ft);
+    assert_eq!(
+        FileTime::from_i64_checked(-1),
+        Err(FileTimeError::BeforeEpoch)
+    );
+    assert_eq!(FileTime::MAX.filetime(), i64::MAX);
+}
+
+#[test]
+fn datetime() {
+    let dt = Utc.with_ymd_and_hms(2009, 7, 25, 23, 0, 0).unwrap();
+    let ft = FileTime::from_datetime(dt);
+    assert_eq!(ft.filetime(), 128930364000000000);
+    assert_eq!(ft.to_datetime(), dt);
+    assert_eq!(ft.to_unix_seconds(), 1248562800);
+    assert_eq!(filetime_type::filetime!("2009-07-25T23:00:00Z"), ft);
+}
+
+#[test]
+fn arithmetic() {
+    let start = FileTime::from_i64(128930364000000000);
+    let hour = FileTimeDelta::from_secs(3600).unwrap();
+    let end = start + hour;
+    assert_eq!(end - start, hour);
+    let range = FileTimeRange::new(start, end).unwrap();
+    assert!(range.contains(start));
+    assert!(!range.contains(end));
+    assert_eq!(FileTime::MAX_RAW.checked_add(hour), None);
+}
+
+#[test]
+fn display() {
+    let ft = FileTime::from_i64(128930364000000010);
+    let mut buf = Buf::new();
+    write!(buf, "{}", ft).unwrap();
+    assert_eq!(
+        buf.as_str(),
+        "DateTime=2009-07-25 23:00:00.0000010 UTC secs=12893036400 nsecs=1000"
+    );
+
+    let mut buf = Buf::new();
+    write!(buf, "{}", ft.display()).unwrap();
+    assert_eq!(buf.as_str(), "2009-07-25T23:00:00.0000010Z");
+}